@@ -0,0 +1,6 @@
+calendar-name = Prüfungen und Ablaufdaten
+calendar-inspection_due = Prüfung fällig
+calendar-expiry = Läuft ab
+calendar-kind = Art
+calendar-serial_number = Seriennummer
+calendar-location = Standort
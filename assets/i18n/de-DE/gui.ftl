@@ -1,10 +1,17 @@
 gui-nav-dashboard = Übersicht
 gui-nav-inventory = Inventar
+gui-nav-scan = Scannen
+gui-nav-downloads = Downloads
 gui-nav-manage = Verwalten
 gui-nav-login = Anmelden
 gui-nav-logout = Abmelden
 gui-nav-search_placeholder = Inventar durchsuchen
 gui-nav-search_aria = Inventargegenstände durchsuchen
+gui-nav-language = Sprache
+gui-language-name = Deutsch
+gui-language-flag = de
+gui-nav-preferences = Einstellungen
+gui-nav-tenant = Mandant
 
 gui-common-actions = Aktionen
 gui-common-edit = Bearbeiten
@@ -25,9 +32,14 @@ gui-manage-subtitle = Wähle einen Datenbestand zur Ansicht oder Bearbeitung.
 gui-manage-inventory_hint = Die Inventarübersicht findest du weiterhin unter
 
 gui-home-next_expiry = Nächster Ablauf
+gui-home-filter-all = Alle Artikel
+gui-home-filter-mine = Meine Artikel
+gui-home-mine_empty = Sie sind für keinen Artikel verantwortlich oder vertretend zuständig.
 
 gui-inventory-list-title = Liste der Inventargegenstände
 gui-inventory-list-add_item = Inventargegenstand hinzufügen
+gui-inventory-list-print_labels = Etiketten drucken
+gui-inventory-list-compliance_report = Prüfbericht
 gui-inventory-list-showing_results = Ergebnisse für
 gui-inventory-list-id = ID
 gui-inventory-list-name = Name
@@ -38,6 +50,28 @@ gui-inventory-list-expiry = Ablauf
 gui-inventory-list-remove_confirm = Diesen Inventargegenstand entfernen? Dies ist nur erlaubt, wenn keine Prüfungen existieren.
 gui-inventory-list-remove_error = Inventargegenstand konnte nicht entfernt werden.
 gui-inventory-list-remove_network_error = Netzwerkfehler beim Entfernen des Inventargegenstands.
+gui-inventory-list-filter = Filtern
+gui-inventory-list-filter_search = Suche
+gui-inventory-list-filter_any = Alle
+gui-inventory-list-filter_status = Status
+gui-inventory-list-filter_urgency = Dringlichkeit
+gui-inventory-list-filter_location = Standort
+gui-inventory-list-filter_field = Feld
+gui-inventory-list-filter_value = Wert
+gui-inventory-list-filter_reset = Zurücksetzen
+gui-inventory-list-status-expired = Abgelaufen
+gui-inventory-list-status-failed = Nicht bestanden
+gui-inventory-list-status-overdue = Überfällig
+gui-inventory-list-status-compliant = In Ordnung
+gui-inventory-list-urgency-critical = Überfällig
+gui-inventory-list-urgency-warning = Innerhalb einer Woche fällig
+gui-inventory-list-urgency-normal = Nicht bald fällig
+gui-inventory-list-page_size = Pro Seite
+gui-inventory-list-total = { $count } Gegenstände
+gui-inventory-list-page = Seite { $page } von { $pages }
+gui-inventory-list-previous = Zurück
+gui-inventory-list-next = Weiter
+gui-inventory-list-empty = Keine Gegenstände entsprechen dem Filter.
 
 gui-inventory-common-check = Prüfen
 gui-inventory-common-notes = Notizen:
@@ -48,6 +82,8 @@ gui-inventory-checks-by_prefix = von
 gui-inventory-checks-step_number = Nr.
 gui-inventory-checks-step = Schritt
 gui-inventory-checks-result = Ergebnis
+gui-inventory-checks-certificate = Prüfzertifikat
+gui-inventory-checks-total = { $count } Prüfungen
 
 gui-inventory-check_item-title_prefix = Prüfe
 gui-inventory-check_item-back_to_inventory = Zur Inventarliste
@@ -57,9 +93,13 @@ gui-inventory-check_item-optional_notes = Optionale Notizen
 gui-inventory-check_item-save = Prüfung speichern
 gui-inventory-check_item-store_error = Prüfung konnte nicht gespeichert werden.
 gui-inventory-check_item-network_error = Netzwerkfehler beim Speichern der Prüfung.
+gui-inventory-check_item-signature = Unterschrift des Prüfers
+gui-inventory-check_item-signature_clear = Löschen
+gui-inventory-check_item-signature_hint = Optional. Wird auf das Prüfzertifikat gedruckt.
 
 gui-inventory-detail-check_item = Prüfung starten
 gui-inventory-detail-edit_item = Bearbeiten
+gui-inventory-detail-print_label = Etikett drucken
 gui-inventory-detail-back_to_list = Zur Liste
 gui-inventory-detail-kind = Typ:
 gui-inventory-detail-kind_unknown = Unbekannter Typ
@@ -151,6 +191,121 @@ gui-translations-link = Übersetzungen
 gui-translations-code = Code
 gui-translations-lang = Sprache
 gui-translations-text = Text
+gui-translations-intro = Übersetzungen von Codes wie Intervallen, Ablaufzeiten und Ergebnissen. Sie haben Vorrang vor den mitgelieferten Übersetzungen und werden in der Oberfläche und in PDF-Berichten verwendet.
+gui-translations-add = Übersetzung hinzufügen
+gui-translations-save = Speichern
+gui-translations-remove = Entfernen
+gui-translations-remove_confirm = Diese Übersetzung entfernen?
+gui-preferences-title = Einstellungen
+gui-preferences-intro = Datumsangaben werden in dieser Zeitzone eingegeben und angezeigt. Wenn du angemeldet bist, wird sie mit deinem Konto gespeichert.
+gui-preferences-timezone = Zeitzone
+gui-preferences-site_timezone = Standardzeitzone dieser Seite:
+gui-preferences-save = Speichern
+gui-preferences-use_browser = Zeitzone des Browsers verwenden
+
+gui-api_keys-title = API-Schlüssel
+gui-api_keys-link = API-Schlüssel
+gui-api_keys-subtitle = Schlüssel für Scanner und Automatisierungsskripte erstellen. Schlüssel werden gehasht gespeichert und können jederzeit widerrufen werden.
+gui-api_keys-create = Neuen Schlüssel erstellen
+gui-api_keys-create_button = Schlüssel erstellen
+gui-api_keys-name = Name
+gui-api_keys-prefix = Schlüssel
+gui-api_keys-scopes = Berechtigungen
+gui-api_keys-scope-read = Nur lesen
+gui-api_keys-scope-submit_checks = Prüfungen erfassen
+gui-api_keys-scope-write = Vollzugriff
+gui-api_keys-expires = Läuft ab
+gui-api_keys-last_used = Zuletzt verwendet
+gui-api_keys-status = Status
+gui-api_keys-status-active = Aktiv
+gui-api_keys-status-inactive = Widerrufen oder abgelaufen
+gui-api_keys-revoke = Widerrufen
+gui-api_keys-revoke_confirm = Diesen Schlüssel widerrufen? Integrationen, die ihn verwenden, funktionieren dann nicht mehr.
+gui-api_keys-empty = Noch keine API-Schlüssel vorhanden.
+gui-api_keys-token_once = Kopieren Sie diesen Schlüssel jetzt. Er wird nicht erneut angezeigt.
+gui-api_keys-login_required = Bitte melden Sie sich an, um Ihre API-Schlüssel zu verwalten.
+gui-api_keys-load_error = API-Schlüssel konnten nicht geladen werden.
+gui-api_keys-save_error = API-Schlüssel konnte nicht gespeichert werden.
+gui-api_keys-network_error = Netzwerkfehler bei der Verwaltung der API-Schlüssel.
+
+gui-labels-title = Etiketten drucken
+gui-labels-subtitle = Lädt einen PDF-Etikettenbogen mit Code, Name und nächstem Prüftermin herunter.
+gui-labels-selection = Gegenstände
+gui-labels-all_items = Alle Gegenstände
+gui-labels-all_items_of_kind = Alle Gegenstände vom Typ
+gui-labels-layout = Bogenformat
+gui-labels-code = Codeart
+gui-labels-code-qr = QR-Code
+gui-labels-code-data_matrix = DataMatrix
+gui-labels-content = Code enthält
+gui-labels-content-url = Link zur Gegenstandsseite
+gui-labels-content-serial = Seriennummer
+gui-labels-skip = Etiketten überspringen
+gui-labels-skip_help = Anzahl bereits benutzter Etiketten am Anfang des Bogens.
+gui-labels-download = PDF herunterladen
+
+gui-scan-title = Gegenstand scannen
+gui-scan-hint = Kamera auf den Code auf dem Etikett richten.
+gui-scan-camera_unavailable = Der Kamera-Scanner ist in diesem Browser nicht verfügbar. Bitte Seriennummer oder Code eingeben.
+gui-scan-manual = Seriennummer oder Code
+gui-scan-open = Öffnen
+gui-scan-saved = Prüfung gespeichert für
+gui-scan-not_found = Kein Inventargegenstand gefunden für
+gui-scan-next_due = Fällig
+
+gui-reports-compliance-title = Prüfbericht
+gui-reports-compliance-subtitle = PDF-Übersicht über letzte Prüfung, nächsten Prüftermin sowie überfällige oder fehlgeschlagene Gegenstände, mit Zusammenfassung auf der ersten Seite.
+gui-reports-compliance-all_kinds = Alle Typen
+gui-reports-compliance-location = Standort
+gui-reports-compliance-location_help = Sucht im Metadatenfeld „Location“ der Gegenstände.
+gui-reports-compliance-from = Von
+gui-reports-compliance-to = Bis
+gui-reports-compliance-to_help = Der Status wird zum Ende dieses Tages ausgewertet. Leer lassen für heute.
+gui-reports-jobs-title = Downloads
+gui-reports-jobs-intro = Im Hintergrund erstellte Berichte stehen hier zum Herunterladen bereit. Die Seite aktualisiert sich selbst und benachrichtigt Sie, sobald ein Bericht fertig ist.
+gui-reports-jobs-empty = Es wurden noch keine Berichte erstellt.
+gui-reports-jobs-report = Bericht
+gui-reports-jobs-queued_at = Angefordert am
+gui-reports-jobs-status = Status
+gui-reports-jobs-status-queued = In Warteschlange
+gui-reports-jobs-status-running = Wird erstellt…
+gui-reports-jobs-status-completed = Fertig
+gui-reports-jobs-status-failed = Fehlgeschlagen
+gui-reports-jobs-ready = Bericht fertig
+gui-reports-jobs-queue = Im Hintergrund erstellen
+gui-report_templates-link = Berichtsvorlagen
+gui-report_templates-title = Berichtsvorlagen
+gui-report_templates-intro = Ersetzen Sie die Typst-Vorlage eines Berichtstyps, etwa um Ihr Logo und Ihre Firmenadresse einzufügen. Lässt sich eine eigene Vorlage nicht übersetzen, wird die mitgelieferte Vorlage verwendet.
+gui-report_templates-report = Bericht
+gui-report_templates-template = Vorlage
+gui-report_templates-custom = Eigene Vorlage
+gui-report_templates-builtin = Mitgelieferte Vorlage
+gui-report_templates-edit = Bearbeiten
+gui-report_templates-help = Die Berichtsdaten stehen als sys.inputs zur Verfügung. Das mitgelieferte Layout kann aus den Vorlagendateien daneben importiert werden, ein hochgeladenes Logo liegt unter
+gui-report_templates-errors = Die Vorlage konnte nicht übersetzt werden:
+gui-report_templates-hint = Hinweis
+gui-report_templates-source = Typst-Quelltext
+gui-report_templates-logo = Logo
+gui-report_templates-remove_logo = Aktuelles Logo entfernen
+gui-report_templates-preview = Vorschau
+gui-report_templates-save = Speichern
+gui-report_templates-back = Zurück zu den Vorlagen
+gui-report_templates-reset = Mitgelieferte Vorlage wiederherstellen
+gui-report_templates-reset_confirm = Eigene Vorlage löschen und wieder die mitgelieferte Vorlage verwenden?
+gui-verify-title = Bericht prüfen
+gui-verify-intro = Geben Sie die auf einem PDF-Bericht gedruckte Berichts-ID ein, um zu bestätigen, dass er von diesem System erstellt wurde.
+gui-verify-report_id = Berichts-ID
+gui-verify-submit = Prüfen
+gui-verify-not_found = Es wurde kein Bericht erstellt mit der ID
+gui-verify-authentic = Diese Berichts-ID wurde von diesem System vergeben.
+gui-verify-report_title = Bericht
+gui-verify-subject = Gegenstand
+gui-verify-generated_at = Erstellt am
+gui-verify-content_hash = SHA-256
+gui-verify-file_heading = PDF-Datei prüfen
+gui-verify-file_intro = Wählen Sie die PDF-Datei aus, um zu bestätigen, dass sie seit der Erstellung nicht verändert wurde.
+gui-verify-file_match = Die Datei ist identisch mit dem erstellten Bericht.
+gui-verify-file_mismatch = Die Datei stimmt nicht mit dem erstellten Bericht überein. Sie wurde möglicherweise verändert.
 
 gui-auth-login-title = Willkommen zurück
 gui-auth-login-subtitle = Melde dich an, um Inventar und Prüfungen zu verwalten.
@@ -184,3 +339,133 @@ gui-auth-logout-login_page = Anmeldeseite
 gui-auth-logout-switch_prompt = Konto wechseln?
 gui-auth-logout-return_to_login = Zur Anmeldung
 gui-auth-logout-success = Erfolgreich abgemeldet.
+
+gui-calendar-title = Kalender-Abonnement
+gui-calendar-link = Kalender-Abonnement
+gui-calendar-subtitle = Abonniere diesen Feed in deiner Kalender-App, um zu sehen, wann Gegenstände zur Prüfung fällig sind und wann sie ablaufen. Die Termine folgen neuen Prüfungen automatisch.
+gui-calendar-url = Feed-URL
+gui-calendar-kind = Gegenstandsart
+gui-calendar-location = Standort enthält
+gui-calendar-all_kinds = Alle Arten
+gui-calendar-create = Feed-URL erstellen
+gui-calendar-reset = Neue URL erstellen
+gui-calendar-reset_confirm = Neue URL erstellen? Kalender, die die bisherige URL abonniert haben, werden nicht mehr aktualisiert.
+gui-calendar-secret_hint = Jeder mit dieser URL kann die Termine lesen. Erstelle eine neue URL, wenn sie versehentlich weitergegeben wurde.
+gui-calendar-url_hidden = Die Feed-URL wird nur beim Erstellen angezeigt. Erstelle eine neue URL, um sie erneut zu kopieren.
+gui-calendar-login_required = Bitte melde dich an, um den Kalender zu abonnieren.
+gui-calendar-load_error = Der Kalender-Feed konnte nicht geladen werden.
+
+gui-responsibility-title = Verantwortung
+gui-responsibility-owner = Verantwortlich
+gui-responsibility-deputy = Vertretung
+gui-responsibility-from_kind = von der Art
+gui-responsibility-inherit = Wie bei der Art
+gui-responsibility-none = Niemand
+gui-responsibility-save = Speichern
+gui-responsibility-hint = Erinnerungen gehen an die verantwortliche Person und während ihrer Abwesenheit an die Vertretung. Angaben am Artikel ersetzen die der Art.
+gui-responsibility-users = Benutzer
+gui-responsibility-teams = Teams
+
+gui-teams-title = Teams
+gui-teams-link = Teams
+gui-teams-intro = Teams können für Artikel und Arten verantwortlich sein, gemeinsam benachrichtigt werden und einschränken, was ihre Mitglieder sehen.
+gui-teams-add = Team hinzufügen
+gui-teams-name = Name
+gui-teams-scoped = Mitglieder sehen nur die Artikel des Teams
+gui-teams-scoped_hint = Mitglieder eines solchen Teams sehen und prüfen über die API nur Artikel, für die sie oder ihre Teams verantwortlich oder Vertretung sind.
+gui-teams-save = Speichern
+gui-teams-remove = Entfernen
+gui-teams-remove_confirm = Dieses Team entfernen? Die Artikel behalten ihre übrigen Verantwortlichen.
+gui-teams-empty = Noch keine Teams.
+gui-teams-members = Mitglieder
+gui-teams-no_members = Noch keine Mitglieder.
+gui-teams-add_member = Mitglied hinzufügen
+gui-teams-remove_member = Entfernen
+gui-tenants-title = Mandanten
+gui-tenants-link = Mandanten
+gui-tenants-intro = Jeder Mandant hat eigene Ausrüstung, Checklisten, Teams, Webhooks und Benutzer. Intervalle, Ablauffristen und Prüfergebnisse teilen sich alle Mandanten und nur globale Administratoren ändern sie.
+gui-tenants-login_required = Melden Sie sich an, um Ihre Mandanten zu sehen.
+gui-tenants-load_error = Die Mandanten konnten nicht geladen werden.
+gui-tenants-save_error = Die Änderung konnte nicht gespeichert werden.
+gui-tenants-network_error = Der Server ist nicht erreichbar.
+gui-tenants-add = Mandant hinzufügen
+gui-tenants-name = Name
+gui-tenants-current = Aktuell
+gui-tenants-switch = Wechseln
+gui-tenants-members = Mitglieder
+gui-tenants-member_email = E-Mail eines registrierten Benutzers
+gui-tenants-add_member = Mitglied hinzufügen
+gui-webhooks-title = Webhooks
+gui-webhooks-link = Webhooks
+gui-webhooks-intro = Andere Systeme werden mit einer signierten POST-Anfrage über Prüfungen und Änderungen an Gegenständen benachrichtigt. Fehlgeschlagene Zustellungen werden in wachsenden Abständen wiederholt.
+gui-webhooks-add = Endpunkt hinzufügen
+gui-webhooks-name = Name
+gui-webhooks-url = URL
+gui-webhooks-events = Ereignisse
+gui-webhooks-secret = Signaturschlüssel
+gui-webhooks-secret_created = Kopieren Sie den Signaturschlüssel des neuen Endpunkts jetzt, er wird nicht noch einmal angezeigt:
+gui-webhooks-active = Aktiv
+gui-webhooks-save = Speichern
+gui-webhooks-remove = Entfernen
+gui-webhooks-remove_confirm = Diesen Endpunkt samt Zustellprotokoll entfernen?
+gui-webhooks-empty = Noch keine Endpunkte.
+gui-webhooks-deliveries = Letzte Zustellungen
+gui-webhooks-endpoint = Endpunkt
+gui-webhooks-event = Ereignis
+gui-webhooks-status = Status
+gui-webhooks-attempts = Versuche
+gui-webhooks-response = Antwort
+gui-webhooks-next_attempt = Nächster Versuch
+gui-webhooks-redeliver = Erneut senden
+gui-webhooks-no_deliveries = Noch nichts zugestellt.
+
+gui-notifications-title = Benachrichtigungen
+gui-notifications-link = Benachrichtigungen
+gui-notifications-subtitle = Erhalte Benachrichtigungen, wenn Gegenstände fällig oder überfällig werden. Jeder Gegenstand wird je Fälligkeitsdatum und Dringlichkeit nur einmal gemeldet, ein überfälliger Gegenstand wird also nicht bei jedem Lauf erneut gesendet.
+gui-notifications-add = Abonnieren
+gui-notifications-kind = Art
+gui-notifications-all_kinds = Alle Arten
+gui-notifications-location = Standort enthält
+gui-notifications-item = Gegenstand-ID
+gui-notifications-all_items = Alle Gegenstände
+gui-notifications-covers = Gegenstände
+gui-notifications-min_urgency = Benachrichtigen ab
+gui-notifications-level-warning = Bald fällig
+gui-notifications-level-critical = Überfällig
+gui-notifications-channel = Kanal
+gui-notifications-channel-email = E-Mail
+gui-notifications-channel-webhook = Webhook
+gui-notifications-endpoint = Webhook-Endpunkt
+gui-notifications-frequency = Häufigkeit
+gui-notifications-frequency-immediate = Sofort
+gui-notifications-frequency-daily = Täglich
+gui-notifications-frequency-weekly = Wöchentlich
+gui-notifications-quiet = Ruhezeit
+gui-notifications-quiet_start = Ruhe ab (Stunde)
+gui-notifications-quiet_end = Ruhe bis (Stunde)
+gui-notifications-quiet_hint = Stunden gelten in deiner Zeitzone. Während der Ruhezeit werden Benachrichtigungen zurückgehalten und danach gesendet.
+gui-notifications-last_sent = Zuletzt gesendet
+gui-notifications-remove = Entfernen
+gui-notifications-remove_confirm = Dieses Abonnement entfernen?
+gui-notifications-empty = Noch keine Abonnements.
+gui-notifications-login_required = Bitte melde dich an, um deine Benachrichtigungen zu verwalten.
+gui-notifications-load_error = Benachrichtigungen konnten nicht geladen werden.
+gui-notifications-save_error = Das Abonnement konnte nicht gespeichert werden.
+gui-notifications-responsible_only = Nur Artikel, für die ich verantwortlich bin, als Verantwortliche(r) oder als Vertretung während einer Abwesenheit
+gui-notifications-my_items = Meine Artikel
+gui-notifications-team = Team
+gui-notifications-team-none = Nur ich
+gui-notifications-absence = Abwesenheit
+gui-notifications-absence_hint = Während Ihrer Abwesenheit gehen Erinnerungen zu Ihren Artikeln an die Vertretung.
+gui-notifications-absent_until = Abwesend bis einschließlich
+gui-notifications-absence_save = Speichern
+gui-notifications-absence_clear = Ich bin zurück
+
+gui-search-title = Suche
+gui-search-query = Suchbegriffe
+gui-search-hint = Findet Gegenstände nach Name, Seriennummer, Art und Metadaten wie Hersteller oder Losnummer sowie Prüfungen nach ihren Notizen. Wörter dürfen abgekürzt werden.
+gui-search-submit = Suchen
+gui-search-items = Gegenstände
+gui-search-checks = Prüfungen
+gui-search-empty = Nichts gefunden.
+gui-search-inventory_list = Stattdessen die Inventarliste filtern
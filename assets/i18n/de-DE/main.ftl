@@ -1,4 +0,0 @@
-hello-world = Hallo Welt!
-greeting = Hallochen { $name }!
-        .placeholder = Hallo Freund!
-about = Uber
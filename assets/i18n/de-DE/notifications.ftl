@@ -0,0 +1,7 @@
+notification-subject = Fällige Prüfungen
+notification-greeting = Hallo
+notification-intro = Die folgenden abonnierten Gegenstände sind bald zur Prüfung fällig oder überfällig:
+notification-due = fällig am
+notification-warning = Bald fällig
+notification-critical = Überfällig
+notification-manage = Benachrichtigungen verwalten:
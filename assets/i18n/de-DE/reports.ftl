@@ -0,0 +1,62 @@
+format-date = %d.%m.%Y
+format-datetime = %d.%m.%Y %H:%M
+
+report-item_history-title = Prüfhistorie des Inventargegenstands
+report-compliance-title = Prüfstatusbericht
+report-check_certificate-title = Prüfzertifikat
+
+report-generated_at = Erstellt am
+report-report_id = Berichts-ID
+report-verify_at = Prüfen unter
+
+report-item_details = Details zum Gegenstand
+report-inventory_item = Inventargegenstand
+report-serial_number = Seriennummer
+report-item_kind = Art
+report-test_standard = Prüfnorm
+report-checklist = Checkliste
+report-interval = Intervall
+report-last_checked = Zuletzt geprüft
+report-created_at = Erstellt am
+report-updated_at = Aktualisiert am
+report-expiry = Ablaufdatum
+report-check_history = Prüfhistorie
+report-no_checks = Für diesen Inventargegenstand wurden noch keine Prüfungen erfasst.
+report-checked_at = Geprüft am
+report-checked_by = Geprüft von
+report-overall = Gesamt
+report-notes = Bemerkungen
+
+report-summary = Zusammenfassung
+report-by_item_kind = Nach Art
+report-items = Gegenstände
+report-no_items = Keine Inventargegenstände entsprechen den gewählten Filtern.
+report-item = Gegenstand
+report-location = Standort
+report-period = Zeitraum
+report-result = Ergebnis
+report-next_due = Nächste Prüfung
+report-status = Status
+report-total = Gegenstände
+report-compliant = In Ordnung
+report-overdue = Überfällig
+report-failed = Nicht bestanden
+report-expired = Abgelaufen
+report-compliance_rate = Prüfquote
+report-checks_in_period = Prüfungen im Zeitraum
+
+report-inspected_item = Geprüfter Gegenstand
+report-inspection = Prüfung
+report-inspector = Prüfer
+report-description = Beschreibung
+report-overall_result = Gesamtergebnis
+report-checklist_steps = Prüfschritte
+report-step = Schritt
+report-no_step_results = Für diese Prüfung wurden keine Ergebnisse zu Prüfschritten erfasst.
+report-date = Datum
+report-signature = Unterschrift
+report-verification_code = Prüfcode
+report-verification_note = Der Prüfcode wird aus der erfassten Prüfung berechnet und ändert sich, wenn die gespeicherten Prüfdaten von diesem Zertifikat abweichen.
+
+report-label_serial_number = S/N
+report-label_next_due = Fällig
@@ -0,0 +1,6 @@
+calendar-name = Inspections and expiries
+calendar-inspection_due = Inspection due
+calendar-expiry = Expires
+calendar-kind = Kind
+calendar-serial_number = Serial number
+calendar-location = Location
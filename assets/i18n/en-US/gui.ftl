@@ -1,10 +1,17 @@
 gui-nav-dashboard = Dashboard
 gui-nav-inventory = Inventory
+gui-nav-scan = Scan
+gui-nav-downloads = Downloads
 gui-nav-manage = Manage
 gui-nav-login = Log in
 gui-nav-logout = Log out
 gui-nav-search_placeholder = Search inventory
 gui-nav-search_aria = Search inventory items
+gui-nav-language = Language
+gui-language-name = English
+gui-language-flag = gb
+gui-nav-preferences = Preferences
+gui-nav-tenant = Tenant
 
 gui-common-actions = Actions
 gui-common-edit = Edit
@@ -25,9 +32,14 @@ gui-manage-subtitle = Choose a dataset to review or edit.
 gui-manage-inventory_hint = Inventory listings remain available under
 
 gui-home-next_expiry = Next expiry
+gui-home-filter-all = All items
+gui-home-filter-mine = My items
+gui-home-mine_empty = You are not the owner or deputy of any item.
 
 gui-inventory-list-title = List of Inventory Items
 gui-inventory-list-add_item = Add Inventory Item
+gui-inventory-list-print_labels = Print Labels
+gui-inventory-list-compliance_report = Compliance Report
 gui-inventory-list-showing_results = Showing results for
 gui-inventory-list-id = ID
 gui-inventory-list-name = Name
@@ -38,6 +50,28 @@ gui-inventory-list-expiry = Expiry
 gui-inventory-list-remove_confirm = Remove this inventory item? This is only allowed if it has no checks.
 gui-inventory-list-remove_error = Inventory item could not be removed.
 gui-inventory-list-remove_network_error = Network error while removing inventory item.
+gui-inventory-list-filter = Filter
+gui-inventory-list-filter_search = Search
+gui-inventory-list-filter_any = Any
+gui-inventory-list-filter_status = Status
+gui-inventory-list-filter_urgency = Urgency
+gui-inventory-list-filter_location = Location
+gui-inventory-list-filter_field = Field
+gui-inventory-list-filter_value = Value
+gui-inventory-list-filter_reset = Reset
+gui-inventory-list-status-expired = Expired
+gui-inventory-list-status-failed = Failed
+gui-inventory-list-status-overdue = Overdue
+gui-inventory-list-status-compliant = Compliant
+gui-inventory-list-urgency-critical = Overdue
+gui-inventory-list-urgency-warning = Due within a week
+gui-inventory-list-urgency-normal = Not due soon
+gui-inventory-list-page_size = Per page
+gui-inventory-list-total = { $count } items
+gui-inventory-list-page = Page { $page } of { $pages }
+gui-inventory-list-previous = Previous
+gui-inventory-list-next = Next
+gui-inventory-list-empty = No items match the filter.
 
 gui-inventory-common-check = Check
 gui-inventory-common-notes = Notes:
@@ -48,6 +82,8 @@ gui-inventory-checks-by_prefix = by
 gui-inventory-checks-step_number = #
 gui-inventory-checks-step = Step
 gui-inventory-checks-result = Result
+gui-inventory-checks-certificate = Certificate
+gui-inventory-checks-total = { $count } checks
 
 gui-inventory-check_item-title_prefix = Check
 gui-inventory-check_item-back_to_inventory = Back to inventory
@@ -57,9 +93,13 @@ gui-inventory-check_item-optional_notes = Optional notes
 gui-inventory-check_item-save = Save Check
 gui-inventory-check_item-store_error = Failed to store check result.
 gui-inventory-check_item-network_error = Network error while storing check.
+gui-inventory-check_item-signature = Inspector signature
+gui-inventory-check_item-signature_clear = Clear
+gui-inventory-check_item-signature_hint = Optional. Printed on the inspection certificate.
 
 gui-inventory-detail-check_item = Check Item
 gui-inventory-detail-edit_item = Edit Item
+gui-inventory-detail-print_label = Print Label
 gui-inventory-detail-back_to_list = Back to list
 gui-inventory-detail-kind = Kind:
 gui-inventory-detail-kind_unknown = Unknown kind
@@ -151,6 +191,121 @@ gui-translations-link = Translations
 gui-translations-code = Code
 gui-translations-lang = Language
 gui-translations-text = Text
+gui-translations-intro = Translations of lookup codes such as intervals, expiries and results. They take precedence over the built-in translations and are used in the UI and in PDF reports.
+gui-translations-add = Add translation
+gui-translations-save = Save
+gui-translations-remove = Remove
+gui-translations-remove_confirm = Remove this translation?
+gui-preferences-title = Preferences
+gui-preferences-intro = Dates are entered and shown in this time zone. When you are logged in, it is stored with your account.
+gui-preferences-timezone = Time zone
+gui-preferences-site_timezone = Default time zone of this site:
+gui-preferences-save = Save
+gui-preferences-use_browser = Use browser time zone
+
+gui-api_keys-title = API Keys
+gui-api_keys-link = API Keys
+gui-api_keys-subtitle = Create keys for scanners and automation scripts. Keys are stored hashed and can be revoked at any time.
+gui-api_keys-create = Create a new key
+gui-api_keys-create_button = Create key
+gui-api_keys-name = Name
+gui-api_keys-prefix = Key
+gui-api_keys-scopes = Scopes
+gui-api_keys-scope-read = Read only
+gui-api_keys-scope-submit_checks = Submit checks
+gui-api_keys-scope-write = Full access
+gui-api_keys-expires = Expires
+gui-api_keys-last_used = Last used
+gui-api_keys-status = Status
+gui-api_keys-status-active = Active
+gui-api_keys-status-inactive = Revoked or expired
+gui-api_keys-revoke = Revoke
+gui-api_keys-revoke_confirm = Revoke this key? Integrations using it will stop working.
+gui-api_keys-empty = No API keys yet.
+gui-api_keys-token_once = Copy this key now. It will not be shown again.
+gui-api_keys-login_required = Please log in to manage your API keys.
+gui-api_keys-load_error = Failed to load API keys.
+gui-api_keys-save_error = Failed to save API key.
+gui-api_keys-network_error = Network error while managing API keys.
+
+gui-labels-title = Print Labels
+gui-labels-subtitle = Download a PDF sheet of labels with a code, the item name and its next due date.
+gui-labels-selection = Items
+gui-labels-all_items = All items
+gui-labels-all_items_of_kind = All items of kind
+gui-labels-layout = Sheet layout
+gui-labels-code = Code type
+gui-labels-code-qr = QR code
+gui-labels-code-data_matrix = DataMatrix
+gui-labels-content = Code contains
+gui-labels-content-url = Link to the item page
+gui-labels-content-serial = Serial number
+gui-labels-skip = Skip labels
+gui-labels-skip_help = Number of already used labels at the start of the sheet.
+gui-labels-download = Download PDF
+
+gui-scan-title = Scan Item
+gui-scan-hint = Point the camera at the code on the item label.
+gui-scan-camera_unavailable = The camera scanner is not available in this browser. Enter the serial number or code instead.
+gui-scan-manual = Serial number or code
+gui-scan-open = Open
+gui-scan-saved = Check saved for
+gui-scan-not_found = No inventory item found for
+gui-scan-next_due = Due
+
+gui-reports-compliance-title = Compliance Report
+gui-reports-compliance-subtitle = PDF overview of every item's last check, next due date and overdue or failed status, with a summary on the first page.
+gui-reports-compliance-all_kinds = All kinds
+gui-reports-compliance-location = Location
+gui-reports-compliance-location_help = Matches the "Location" metadata field of the items.
+gui-reports-compliance-from = From
+gui-reports-compliance-to = To
+gui-reports-compliance-to_help = The status of each item is evaluated at the end of this day. Leave empty for today.
+gui-reports-jobs-title = Downloads
+gui-reports-jobs-intro = Reports generated in the background are kept here for download. This page updates itself and notifies you when a report is ready.
+gui-reports-jobs-empty = No reports have been generated yet.
+gui-reports-jobs-report = Report
+gui-reports-jobs-queued_at = Requested at
+gui-reports-jobs-status = Status
+gui-reports-jobs-status-queued = Queued
+gui-reports-jobs-status-running = Generating…
+gui-reports-jobs-status-completed = Ready
+gui-reports-jobs-status-failed = Failed
+gui-reports-jobs-ready = Report ready
+gui-reports-jobs-queue = Generate in background
+gui-report_templates-link = Report templates
+gui-report_templates-title = Report Templates
+gui-report_templates-intro = Replace the Typst template of a report type, for example to add your logo and company address. Reports fall back to the built-in template if a custom template fails to compile.
+gui-report_templates-report = Report
+gui-report_templates-template = Template
+gui-report_templates-custom = Custom template
+gui-report_templates-builtin = Built-in template
+gui-report_templates-edit = Edit
+gui-report_templates-help = The report data is available as sys.inputs. The built-in layout can be imported from the template files next to it, an uploaded logo is available at
+gui-report_templates-errors = The template could not be compiled:
+gui-report_templates-hint = Hint
+gui-report_templates-source = Typst source
+gui-report_templates-logo = Logo
+gui-report_templates-remove_logo = Remove the current logo
+gui-report_templates-preview = Preview
+gui-report_templates-save = Save
+gui-report_templates-back = Back to templates
+gui-report_templates-reset = Restore built-in template
+gui-report_templates-reset_confirm = Delete the custom template and use the built-in template again?
+gui-verify-title = Verify Report
+gui-verify-intro = Enter the report ID printed on a PDF report to confirm that it was issued by this system.
+gui-verify-report_id = Report ID
+gui-verify-submit = Verify
+gui-verify-not_found = No report has been issued with the ID
+gui-verify-authentic = This report ID was issued by this system.
+gui-verify-report_title = Report
+gui-verify-subject = Subject
+gui-verify-generated_at = Generated at
+gui-verify-content_hash = SHA-256
+gui-verify-file_heading = Check a PDF file
+gui-verify-file_intro = Select the PDF file to confirm that it has not been modified since it was generated.
+gui-verify-file_match = The file is identical to the generated report.
+gui-verify-file_mismatch = The file does not match the generated report. It may have been modified.
 
 gui-auth-login-title = Welcome back
 gui-auth-login-subtitle = Sign in to manage your inventories and scheduled checks.
@@ -184,3 +339,133 @@ gui-auth-logout-login_page = login page
 gui-auth-logout-switch_prompt = Need to switch accounts?
 gui-auth-logout-return_to_login = Return to login
 gui-auth-logout-success = Logged out successfully.
+
+gui-calendar-title = Calendar subscription
+gui-calendar-link = Calendar subscription
+gui-calendar-subtitle = Subscribe to this feed in your calendar app to see when items are due for inspection and when they expire. Dates follow new checks automatically.
+gui-calendar-url = Feed URL
+gui-calendar-kind = Item kind
+gui-calendar-location = Location contains
+gui-calendar-all_kinds = All kinds
+gui-calendar-create = Create feed URL
+gui-calendar-reset = Create new URL
+gui-calendar-reset_confirm = Create a new URL? Calendars subscribed to the current one will stop updating.
+gui-calendar-secret_hint = Anyone with this URL can read the due dates. Create a new URL if it was shared by mistake.
+gui-calendar-url_hidden = The feed URL is only shown when it is created. Create a new URL to copy it again.
+gui-calendar-login_required = Please log in to subscribe to the calendar.
+gui-calendar-load_error = Failed to load the calendar feed.
+
+gui-responsibility-title = Responsibility
+gui-responsibility-owner = Owner
+gui-responsibility-deputy = Deputy
+gui-responsibility-from_kind = from kind
+gui-responsibility-inherit = Same as kind
+gui-responsibility-none = Nobody
+gui-responsibility-save = Save
+gui-responsibility-hint = Reminders go to the owner, and to the deputy while the owner is absent. Owner and deputy of an item replace the ones of its kind.
+gui-responsibility-users = Users
+gui-responsibility-teams = Teams
+
+gui-teams-title = Teams
+gui-teams-link = Teams
+gui-teams-intro = Teams can own items and item kinds, receive notifications together and limit what their members see.
+gui-teams-add = Add team
+gui-teams-name = Name
+gui-teams-scoped = Members only see the team's items
+gui-teams-scoped_hint = Members of such a team only see and check the items owned or deputized by them or their teams through the API.
+gui-teams-save = Save
+gui-teams-remove = Remove
+gui-teams-remove_confirm = Remove this team? Its items keep their other owners.
+gui-teams-empty = No teams yet.
+gui-teams-members = Members
+gui-teams-no_members = No members yet.
+gui-teams-add_member = Add member
+gui-teams-remove_member = Remove
+gui-tenants-title = Tenants
+gui-tenants-link = Tenants
+gui-tenants-intro = Each tenant has its own equipment, checklists, teams, webhooks and users. Intervals, expiries and test results are shared by all tenants and only global admins change them.
+gui-tenants-login_required = Log in to see your tenants.
+gui-tenants-load_error = The tenants could not be loaded.
+gui-tenants-save_error = The change could not be saved.
+gui-tenants-network_error = The server could not be reached.
+gui-tenants-add = Add tenant
+gui-tenants-name = Name
+gui-tenants-current = Current
+gui-tenants-switch = Switch
+gui-tenants-members = Members
+gui-tenants-member_email = Email of a registered user
+gui-tenants-add_member = Add member
+gui-webhooks-title = Webhooks
+gui-webhooks-link = Webhooks
+gui-webhooks-intro = Other systems are notified about checks and item changes with a signed POST request. Failed deliveries are retried with increasing delays.
+gui-webhooks-add = Add endpoint
+gui-webhooks-name = Name
+gui-webhooks-url = URL
+gui-webhooks-events = Events
+gui-webhooks-secret = Signing secret
+gui-webhooks-secret_created = Copy the signing secret of the new endpoint now, it is not shown again:
+gui-webhooks-active = Active
+gui-webhooks-save = Save
+gui-webhooks-remove = Remove
+gui-webhooks-remove_confirm = Remove this endpoint and its delivery log?
+gui-webhooks-empty = No endpoints yet.
+gui-webhooks-deliveries = Recent deliveries
+gui-webhooks-endpoint = Endpoint
+gui-webhooks-event = Event
+gui-webhooks-status = Status
+gui-webhooks-attempts = Attempts
+gui-webhooks-response = Response
+gui-webhooks-next_attempt = Next attempt
+gui-webhooks-redeliver = Redeliver
+gui-webhooks-no_deliveries = Nothing delivered yet.
+
+gui-notifications-title = Notifications
+gui-notifications-link = Notifications
+gui-notifications-subtitle = Get notified when items become due or overdue. Each item is reported once per due date and urgency, so an overdue item is not sent again on every run.
+gui-notifications-add = Subscribe
+gui-notifications-kind = Kind
+gui-notifications-all_kinds = All kinds
+gui-notifications-location = Location contains
+gui-notifications-item = Item ID
+gui-notifications-all_items = All items
+gui-notifications-covers = Items
+gui-notifications-min_urgency = Notify from
+gui-notifications-level-warning = Due soon
+gui-notifications-level-critical = Overdue
+gui-notifications-channel = Channel
+gui-notifications-channel-email = Email
+gui-notifications-channel-webhook = Webhook
+gui-notifications-endpoint = Webhook endpoint
+gui-notifications-frequency = Frequency
+gui-notifications-frequency-immediate = Immediately
+gui-notifications-frequency-daily = Daily
+gui-notifications-frequency-weekly = Weekly
+gui-notifications-quiet = Quiet period
+gui-notifications-quiet_start = Quiet from (hour)
+gui-notifications-quiet_end = Quiet until (hour)
+gui-notifications-quiet_hint = Hours are in your time zone. Notices are held back during the quiet period and sent afterwards.
+gui-notifications-last_sent = Last sent
+gui-notifications-remove = Remove
+gui-notifications-remove_confirm = Remove this subscription?
+gui-notifications-empty = No subscriptions yet.
+gui-notifications-login_required = Please log in to manage your notifications.
+gui-notifications-load_error = Failed to load notifications.
+gui-notifications-save_error = Failed to save the subscription.
+gui-notifications-responsible_only = Only items I am responsible for, as owner or as deputy while the owner is absent
+gui-notifications-my_items = My items
+gui-notifications-team = Team
+gui-notifications-team-none = Only me
+gui-notifications-absence = Absence
+gui-notifications-absence_hint = While you are away, reminders for the items you own go to their deputies.
+gui-notifications-absent_until = Away until (inclusive)
+gui-notifications-absence_save = Save
+gui-notifications-absence_clear = I am back
+
+gui-search-title = Search
+gui-search-query = Search terms
+gui-search-hint = Finds items by name, serial number, kind and metadata such as manufacturer or lot number, and checks by their notes. Words may be abbreviated.
+gui-search-submit = Search
+gui-search-items = Items
+gui-search-checks = Checks
+gui-search-empty = Nothing found.
+gui-search-inventory_list = Filter the inventory list instead
@@ -0,0 +1,7 @@
+notification-subject = Inspections due
+notification-greeting = Hello
+notification-intro = The following items you subscribed to are due for inspection soon or overdue:
+notification-due = due on
+notification-warning = Due soon
+notification-critical = Overdue
+notification-manage = Manage your notifications:
@@ -0,0 +1,62 @@
+format-date = %m/%d/%Y
+format-datetime = %m/%d/%Y %H:%M
+
+report-item_history-title = Inventory Item Check History
+report-compliance-title = Compliance Report
+report-check_certificate-title = Inspection Certificate
+
+report-generated_at = Generated at
+report-report_id = Report ID
+report-verify_at = Verify at
+
+report-item_details = Item Details
+report-inventory_item = Inventory Item
+report-serial_number = Serial Number
+report-item_kind = Item Kind
+report-test_standard = Test Standard
+report-checklist = Checklist
+report-interval = Interval
+report-last_checked = Last Checked
+report-created_at = Created At
+report-updated_at = Updated At
+report-expiry = Expiry
+report-check_history = Check History
+report-no_checks = No checks have been recorded for this inventory item.
+report-checked_at = Checked At
+report-checked_by = Checked By
+report-overall = Overall
+report-notes = Notes
+
+report-summary = Summary
+report-by_item_kind = By Item Kind
+report-items = Items
+report-no_items = No inventory items match the selected filters.
+report-item = Item
+report-location = Location
+report-period = Period
+report-result = Result
+report-next_due = Next Due
+report-status = Status
+report-total = Items
+report-compliant = Compliant
+report-overdue = Overdue
+report-failed = Failed
+report-expired = Expired
+report-compliance_rate = Compliance
+report-checks_in_period = Checks in Period
+
+report-inspected_item = Inspected Item
+report-inspection = Inspection
+report-inspector = Inspector
+report-description = Description
+report-overall_result = Overall Result
+report-checklist_steps = Checklist Steps
+report-step = Step
+report-no_step_results = No step results have been recorded for this inspection.
+report-date = Date
+report-signature = Signature
+report-verification_code = Verification code
+report-verification_note = The verification code is derived from the recorded inspection and changes when the stored inspection data differs from this certificate.
+
+report-label_serial_number = S/N
+report-label_next_due = Due
@@ -0,0 +1,163 @@
+// Reusable Typst template for the certificate of a single inspection.
+//
+// Expected data shape:
+// #let certificate = (
+//   title: "Inspection Certificate",
+//   lang: "en",
+//   labels: (generated_at: "Generated at", inspector: "Inspector", ...),
+//   generated_at: "08/16/2025 09:45 UTC",
+//   report_id: "RPT-3F9A-01BC-77D2-90E4",
+//   verify_url: "https://example.com/verify/RPT-3F9A-01BC-77D2-90E4",
+//   verification_code: "3F9A-01BC-77D2-90E4-5A1B",
+//   item: (
+//     (label: "Inventory Item", value: "Harness A"),
+//     (label: "Test Standard", value: "EN 361"),
+//   ),
+//   checklist: "Annual PPE Inspection - Harness A",
+//   checklist_description: "Visual and functional inspection.",
+//   checked_at: "08/16/2025 08:30",
+//   inspector: "Inspector Name",
+//   overall_result: "OK",
+//   passed: true,
+//   notes: "General condition acceptable.",
+//   steps: (
+//     (
+//       position: 1,
+//       name: "Labels present",
+//       description: "Verify all labels are readable.",
+//       result: "OK",
+//       notes: none,
+//     ),
+//   ),
+//   signature: none, // PNG image bytes
+// )
+
+#set page(paper: "a4", margin: (x: 15mm, y: 15mm))
+#set text(font: "Liberation Sans", size: 9pt)
+#set par(justify: false)
+
+#let placeholder = "—"
+
+#let display(value) = if value == none or value == "" {
+  placeholder
+} else {
+  value
+}
+
+#let render_details_table(fields) = table(
+  columns: (48mm, 1fr),
+  stroke: 0.5pt,
+  inset: 4pt,
+  ..fields
+    .map(field => (
+      [*#display(field.label)*],
+      [#display(field.value)],
+    ))
+    .flatten(),
+)
+
+#let render_steps_table(steps, labels) = table(
+  columns: (10mm, 1fr, 28mm, 50mm),
+  stroke: 0.5pt,
+  inset: 4pt,
+  table.header([*\#*], [*#labels.step*], [*#labels.result*], [*#labels.notes*]),
+  ..steps
+    .map(step => (
+      [#step.position],
+      [
+        #display(step.name)
+        #if step.description != none and step.description != "" [
+          \ #text(size: 8pt, fill: luma(90))[#step.description]
+        ]
+      ],
+      [#display(step.result)],
+      [#display(step.notes)],
+    ))
+    .flatten(),
+)
+
+#let render_signature(certificate, labels) = grid(
+  columns: (1fr, 1fr),
+  column-gutter: 20mm,
+  [
+    #box(height: 22mm, width: 100%)[
+      #align(bottom)[#display(certificate.checked_at)]
+    ]
+    #line(length: 100%, stroke: 0.5pt)
+    #labels.date
+  ],
+  [
+    #box(height: 22mm, width: 100%)[
+      #align(bottom)[
+        #if certificate.signature != none {
+          image(certificate.signature, format: "png", height: 20mm)
+        }
+      ]
+    ]
+    #line(length: 100%, stroke: 0.5pt)
+    #labels.signature #display(certificate.inspector)
+  ],
+)
+
+#let render_check_certificate(certificate) = [
+  #let labels = certificate.labels
+  #set text(lang: certificate.lang)
+
+  #align(center)[#text(size: 18pt, weight: "bold")[#display(certificate.title)]]
+  #v(4pt)
+
+  #align(center)[
+    #labels.report_id: #display(certificate.report_id) \
+    #labels.generated_at: #display(certificate.generated_at) \
+    #labels.verify_at: #link(certificate.verify_url)[#certificate.verify_url]
+  ]
+
+  #v(10pt)
+  #text(weight: "bold", size: 12pt)[#labels.inspected_item]
+  #v(4pt)
+
+  #render_details_table(certificate.item)
+
+  #v(10pt)
+  #text(weight: "bold", size: 12pt)[#labels.inspection]
+  #v(4pt)
+
+  #render_details_table((
+    (label: labels.checked_at, value: certificate.checked_at),
+    (label: labels.inspector, value: certificate.inspector),
+    (label: labels.checklist, value: certificate.checklist),
+    (label: labels.description, value: certificate.checklist_description),
+    (label: labels.notes, value: certificate.notes),
+  ))
+
+  #v(6pt)
+  #align(center)[
+    #box(
+      stroke: 1pt + if certificate.passed { rgb("#198754") } else { rgb("#dc3545") },
+      inset: 8pt,
+    )[
+      #text(size: 12pt, weight: "bold")[#labels.overall_result: #display(certificate.overall_result)]
+    ]
+  ]
+
+  #v(10pt)
+  #text(weight: "bold", size: 12pt)[#labels.checklist_steps]
+  #v(4pt)
+
+  #if certificate.steps.len() == 0 [
+    #labels.no_step_results
+  ] else [
+    #render_steps_table(certificate.steps, labels)
+  ]
+
+  #v(16pt)
+  #block(breakable: false)[
+    #render_signature(certificate, labels)
+
+    #v(10pt)
+    #text(size: 8pt)[
+      #labels.verification_code: *#display(certificate.verification_code)* \
+      #labels.verification_note
+    ]
+  ]
+]
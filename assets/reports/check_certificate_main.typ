@@ -0,0 +1,4 @@
+#import sys: inputs
+#import "check_certificate.typ": render_check_certificate
+
+#render_check_certificate(inputs)
@@ -0,0 +1,180 @@
+// Reusable Typst template for the fleet-wide compliance report.
+//
+// Expected data shape:
+// #let report = (
+//   title: "Compliance Report",
+//   lang: "en",
+//   labels: (generated_at: "Generated at", compliant: "Compliant", ...),
+//   generated_at: "08/16/2025 09:45 UTC",
+//   report_id: "RPT-3F9A-01BC-77D2-90E4",
+//   verify_url: "https://example.com/verify/RPT-3F9A-01BC-77D2-90E4",
+//   filters: (
+//     (label: "Item Kind", value: "Safety Harness"),
+//     (label: "Period", value: "2025-01-01 – 2025-06-30"),
+//   ),
+//   summary: (
+//     total: "12",
+//     compliant: "9",
+//     overdue: "2",
+//     failed: "1",
+//     expired: "0",
+//     checks_in_period: "15",
+//     compliance_rate: "75.0 %",
+//   ),
+//   kinds: (
+//     (item_kind: "Safety Harness", total: "12", compliant: "9", ...),
+//   ),
+//   items: (
+//     (
+//       name: "Harness A",
+//       serial_number: "SN-12345",
+//       item_kind: "Safety Harness",
+//       location: "Station 1",
+//       last_checked_at: "06/16/2025 08:30",
+//       last_result: "Ok",
+//       checks_in_period: "1",
+//       next_due: "06/16/2026",
+//       status: "compliant",
+//     ),
+//   ),
+// )
+
+#set page(paper: "a4", flipped: true, margin: (x: 10mm, y: 10mm))
+#set text(font: "Liberation Sans", size: 9pt)
+#set par(justify: false)
+
+#let placeholder = "—"
+
+#let display(value) = if value == none or value == "" {
+  placeholder
+} else {
+  value
+}
+
+#let status_colors = (
+  compliant: rgb("#198754"),
+  overdue: rgb("#fd7e14"),
+  failed: rgb("#dc3545"),
+  expired: rgb("#6c757d"),
+)
+
+#let status_badge(item, labels) = text(
+  fill: status_colors.at(item.status, default: black),
+  weight: "bold",
+)[#labels.at(item.status)]
+
+#let count_keys = (
+  "total",
+  "compliant",
+  "overdue",
+  "failed",
+  "expired",
+  "compliance_rate",
+  "checks_in_period",
+)
+
+#let count_columns(labels) = count_keys.map(key => (key: key, label: labels.at(key)))
+
+#let render_summary(summary, labels) = grid(
+  columns: count_keys.len(),
+  column-gutter: 4pt,
+  ..count_columns(labels).map(column => box(
+    width: 100%,
+    stroke: 0.5pt,
+    inset: 6pt,
+  )[
+    #align(center)[
+      #text(size: 16pt, weight: "bold")[#display(summary.at(column.key))] \
+      #column.label
+    ]
+  ]),
+)
+
+#let render_kind_table(kinds, labels) = table(
+  columns: (1fr,) + (auto,) * count_keys.len(),
+  stroke: 0.5pt,
+  inset: 4pt,
+  table.header(
+    [*#labels.item_kind*],
+    ..count_columns(labels).map(column => [*#column.label*]),
+  ),
+  ..kinds
+    .map(kind => (
+      [#display(kind.item_kind)],
+      ..count_keys.map(key => [#display(kind.at(key))]),
+    ))
+    .flatten(),
+)
+
+#let render_item_table(items, labels) = table(
+  columns: (1fr, auto, auto, auto, auto, auto, auto, auto, auto),
+  stroke: 0.5pt,
+  inset: 3pt,
+  table.header(
+    [*#labels.item*],
+    [*#labels.serial_number*],
+    [*#labels.item_kind*],
+    [*#labels.location*],
+    [*#labels.last_checked*],
+    [*#labels.result*],
+    [*#labels.checks_in_period*],
+    [*#labels.next_due*],
+    [*#labels.status*],
+  ),
+  ..items
+    .map(item => (
+      [#display(item.name)],
+      [#display(item.serial_number)],
+      [#display(item.item_kind)],
+      [#display(item.location)],
+      [#display(item.last_checked_at)],
+      [#display(item.last_result)],
+      [#display(item.checks_in_period)],
+      [#display(item.next_due)],
+      [#status_badge(item, labels)],
+    ))
+    .flatten(),
+)
+
+#let render_compliance_report(report) = [
+  #let labels = report.labels
+  #set text(lang: report.lang)
+
+  #align(center)[#text(size: 16pt, weight: "bold")[#display(report.title)]]
+  #v(4pt)
+
+  #align(center)[
+    #labels.generated_at: #display(report.generated_at) \
+    #labels.report_id: #display(report.report_id) \
+    #labels.verify_at: #link(report.verify_url)[#report.verify_url]
+  ]
+
+  #v(6pt)
+  #if report.filters.len() > 0 [
+    #align(center)[
+      #report.filters.map(field => [*#field.label:* #display(field.value)]).join([ #h(8pt) ])
+    ]
+  ]
+
+  #v(10pt)
+  #text(weight: "bold", size: 12pt)[#labels.summary]
+  #v(4pt)
+  #render_summary(report.summary, labels)
+
+  #if report.kinds.len() > 0 [
+    #v(10pt)
+    #text(weight: "bold", size: 12pt)[#labels.by_item_kind]
+    #v(4pt)
+    #render_kind_table(report.kinds, labels)
+  ]
+
+  #pagebreak()
+  #text(weight: "bold", size: 12pt)[#labels.items]
+  #v(4pt)
+
+  #if report.items.len() == 0 [
+    #labels.no_items
+  ] else [
+    #render_item_table(report.items, labels)
+  ]
+]
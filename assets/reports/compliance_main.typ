@@ -0,0 +1,4 @@
+#import sys: inputs
+#import "compliance.typ": render_compliance_report
+
+#render_compliance_report(inputs)
@@ -0,0 +1,82 @@
+// Reusable Typst template for printable inventory item label sheets.
+//
+// Expected data shape (lengths in millimetres):
+// #let sheet = (
+//   layout: (
+//     columns: 3,
+//     rows: 7,
+//     width: 63.5,
+//     height: 38.1,
+//     margin_left: 7.25,
+//     margin_top: 15.15,
+//     pitch_x: 66.04,
+//     pitch_y: 38.1,
+//   ),
+//   skip: 0,
+//   lang: "en",
+//   captions: (label_serial_number: "S/N", label_next_due: "Due"),
+//   labels: (
+//     (
+//       name: "Harness A",
+//       serial_number: "SN-12345",
+//       next_due: "08/16/2026",
+//       code: bytes("<svg …>"),
+//     ),
+//   ),
+// )
+
+#set page(paper: "a4", margin: 0mm)
+#set text(font: "Liberation Sans")
+#set par(justify: false, leading: 0.4em)
+
+#let present(value) = value != none and value != ""
+
+#let render_label(label, layout, captions) = {
+  let padding = calc.min(2.5, layout.height * 0.08)
+  let code_size = calc.min(layout.height - 2 * padding, layout.width * 0.45) * 1mm
+  let font_size = if layout.height < 25 { 5.5pt } else { 8pt }
+
+  box(
+    width: layout.width * 1mm,
+    height: layout.height * 1mm,
+    inset: padding * 1mm,
+    clip: true,
+    grid(
+      columns: (code_size, 1fr),
+      column-gutter: padding * 1mm,
+      align: (left + horizon, left + horizon),
+      image(label.code, format: "svg", width: code_size),
+      text(size: font_size)[
+        #text(weight: "bold", size: font_size * 1.2)[#label.name]
+        #if present(label.serial_number) [ \ #captions.label_serial_number: #label.serial_number ]
+        #if present(label.next_due) [ \ #captions.label_next_due: #label.next_due ]
+      ],
+    ),
+  )
+}
+
+#let render_label_sheet(sheet) = {
+  set text(lang: sheet.lang)
+  let layout = sheet.layout
+  let per_sheet = layout.columns * layout.rows
+  let slots = (none,) * sheet.skip + sheet.labels
+
+  for (sheet_index, page_slots) in slots.chunks(per_sheet).enumerate() {
+    if sheet_index > 0 {
+      pagebreak()
+    }
+
+    for (index, label) in page_slots.enumerate() {
+      if label != none {
+        let column = calc.rem(index, layout.columns)
+        let row = calc.quo(index, layout.columns)
+        place(
+          top + left,
+          dx: (layout.margin_left + column * layout.pitch_x) * 1mm,
+          dy: (layout.margin_top + row * layout.pitch_y) * 1mm,
+          render_label(label, layout, sheet.captions),
+        )
+      }
+    }
+  }
+}
@@ -0,0 +1,4 @@
+#import sys: inputs
+#import "labels.typ": render_label_sheet
+
+#render_label_sheet(inputs)
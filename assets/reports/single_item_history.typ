@@ -3,14 +3,17 @@
 // Expected data shape:
 // #let report = (
 //   title: "Inventory Item Check History",
-//   generated_at: "2025-08-16 09:45",
-//   report_id: "REP-2025-0001",
+//   lang: "en",
+//   labels: (generated_at: "Generated at", report_id: "Report ID", ...),
+//   generated_at: "08/16/2025 09:45 UTC",
+//   report_id: "RPT-3F9A-01BC-77D2-90E4",
+//   verify_url: "https://example.com/verify/RPT-3F9A-01BC-77D2-90E4",
 //   item: (
 //     name: "Harness A",
 //     serial_number: "SN-12345",
 //     item_kind: "Safety Harness",
 //     checklist: "Annual PPE Inspection",
-//     interval: "INTERVAL_12_MONTHS",
+//     interval: "Yearly",
 //     created_at: "2024-04-04 08:00",
 //     updated_at: "2025-08-16 09:45",
 //     last_checked_at: "2025-08-16 08:30",
@@ -24,14 +27,14 @@
 //     (
 //       checked_at: "2025-08-16 08:30",
 //       checked_by: "Inspector Name",
-//       overall_result: "PASS",
+//       overall_result: "OK",
 //       notes: "General condition acceptable.",
 //       steps: (
 //         (
 //           position: 1,
 //           name: "Labels present",
 //           description: "Verify all labels are readable.",
-//           result: "PASS",
+//           result: "OK",
 //           notes: "",
 //         ),
 //       ),
@@ -88,7 +91,7 @@
   }
 }
 
-#let render_check_history_table(checks) = {
+#let render_check_history_table(checks, labels) = {
   let step_columns = if checks.len() == 0 { () } else { checks.at(0).steps }
 
   table(
@@ -96,11 +99,11 @@
     stroke: 0.5pt,
     inset: 3pt,
     table.header(
-      [*Checked At*],
-      [*Checked By*],
-      [*Overall*],
+      [*#labels.checked_at*],
+      [*#labels.checked_by*],
+      [*#labels.overall*],
       ..step_columns.map(step => [*#step_header(step)*]),
-      [*Notes*],
+      [*#labels.notes*],
     ),
     ..checks
       .map(check => (
@@ -125,38 +128,42 @@
 }
 
 #let render_single_item_history_report(report) = [
+  #let labels = report.labels
+  #set text(lang: report.lang)
+
   #align(center)[#text(size: 16pt, weight: "bold")[#display(report.title)]]
   #v(4pt)
 
   #align(center)[
-    Generated at: #display(report.generated_at) \
-    Report ID: #display(report.report_id)
+    #labels.generated_at: #display(report.generated_at) \
+    #labels.report_id: #display(report.report_id) \
+    #labels.verify_at: #link(report.verify_url)[#report.verify_url]
   ]
 
   #v(10pt)
-  #text(weight: "bold", size: 12pt)[Item Details]
+  #text(weight: "bold", size: 12pt)[#labels.item_details]
   #v(4pt)
 
   #render_details_table((
-    (label: "Inventory Item", value: report.item.name),
-    (label: "Serial Number", value: report.item.serial_number),
-    (label: "Item Kind", value: report.item.item_kind),
-    (label: "Checklist", value: report.item.checklist),
-    (label: "Interval", value: report.item.interval),
-    (label: "Last Checked", value: report.item.last_checked_at),
-    (label: "Created At", value: report.item.created_at),
-    (label: "Updated At", value: report.item.updated_at),
-    (label: "Expiry", value: report.item.expiry),
+    (label: labels.inventory_item, value: report.item.name),
+    (label: labels.serial_number, value: report.item.serial_number),
+    (label: labels.item_kind, value: report.item.item_kind),
+    (label: labels.checklist, value: report.item.checklist),
+    (label: labels.interval, value: report.item.interval),
+    (label: labels.last_checked, value: report.item.last_checked_at),
+    (label: labels.created_at, value: report.item.created_at),
+    (label: labels.updated_at, value: report.item.updated_at),
+    (label: labels.expiry, value: report.item.expiry),
     ..report.metadata.map(field => (label: field.label, value: field.value)),
   ))
 
   #v(10pt)
-  #text(weight: "bold", size: 12pt)[Check History]
+  #text(weight: "bold", size: 12pt)[#labels.check_history]
   #v(4pt)
 
   #if report.checks.len() == 0 [
-    No checks have been recorded for this inventory item.
+    #labels.no_checks
   ] else [
-    #render_check_history_table(report.checks)
+    #render_check_history_table(report.checks, labels)
   ]
 ]
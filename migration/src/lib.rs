@@ -6,6 +6,21 @@ mod m20220101_000001_users;
 mod m20251020_000001_tables;
 mod m20260616_180702_add_inventory_item_kind_metadata;
 mod m20260616_183925_add_item_kind_test_standard;
+mod m20261019_000001_api_keys;
+mod m20261019_000002_add_check_signature;
+mod m20261019_000003_generated_reports;
+mod m20261019_000004_report_jobs;
+mod m20261019_000005_report_templates;
+mod m20261019_000006_add_user_locale;
+mod m20261019_000007_add_timezones;
+mod m20261019_000008_add_calendar_token;
+mod m20261019_000009_webhooks;
+mod m20261019_000010_notifications;
+mod m20261019_000011_responsibilities;
+mod m20261019_000012_teams;
+mod m20261019_000013_tenants;
+mod m20261019_000014_search_index;
+mod m20261019_000015_item_next_due;
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -16,6 +31,21 @@ impl MigratorTrait for Migrator {
             Box::new(m20251020_000001_tables::Migration),
             Box::new(m20260616_180702_add_inventory_item_kind_metadata::Migration),
             Box::new(m20260616_183925_add_item_kind_test_standard::Migration),
+            Box::new(m20261019_000001_api_keys::Migration),
+            Box::new(m20261019_000002_add_check_signature::Migration),
+            Box::new(m20261019_000003_generated_reports::Migration),
+            Box::new(m20261019_000004_report_jobs::Migration),
+            Box::new(m20261019_000005_report_templates::Migration),
+            Box::new(m20261019_000006_add_user_locale::Migration),
+            Box::new(m20261019_000007_add_timezones::Migration),
+            Box::new(m20261019_000008_add_calendar_token::Migration),
+            Box::new(m20261019_000009_webhooks::Migration),
+            Box::new(m20261019_000010_notifications::Migration),
+            Box::new(m20261019_000011_responsibilities::Migration),
+            Box::new(m20261019_000012_teams::Migration),
+            Box::new(m20261019_000013_tenants::Migration),
+            Box::new(m20261019_000014_search_index::Migration),
+            Box::new(m20261019_000015_item_next_due::Migration),
             // inject-above (do not remove this comment)
         ]
     }
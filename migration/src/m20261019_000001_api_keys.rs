@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Alias::new("api_keys"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(string(Alias::new("name")))
+                .col(string(Alias::new("key_prefix")))
+                .col(string_uniq(Alias::new("key_hash")))
+                .col(string(Alias::new("scopes")))
+                .col(timestamp_with_time_zone_null(Alias::new("expires_at")))
+                .col(timestamp_with_time_zone_null(Alias::new("last_used_at")))
+                .col(timestamp_with_time_zone_null(Alias::new("revoked_at")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer(Alias::new("user_id")))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-api-keys-user")
+                        .from(Alias::new("api_keys"), Alias::new("user_id"))
+                        .to(Alias::new("users"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Alias::new("api_keys")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
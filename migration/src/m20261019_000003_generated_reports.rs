@@ -0,0 +1,45 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Alias::new("generated_reports"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(string_uniq(Alias::new("report_id")))
+                .col(string(Alias::new("kind")))
+                .col(string(Alias::new("title")))
+                .col(string_null(Alias::new("subject")))
+                .col(string(Alias::new("content_hash")))
+                .col(big_integer(Alias::new("byte_size")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("generated_reports"))
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Alias::new("report_jobs"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(uuid_uniq(Alias::new("pid")))
+                .col(string(Alias::new("kind")))
+                .col(text(Alias::new("params")))
+                .col(string(Alias::new("lang")))
+                .col(string(Alias::new("status")))
+                .col(text_null(Alias::new("error")))
+                .col(string_null(Alias::new("title")))
+                .col(string_null(Alias::new("filename")))
+                .col(string_null(Alias::new("report_id")))
+                .col(blob_null(Alias::new("content")))
+                .col(timestamp_with_time_zone_null(Alias::new("started_at")))
+                .col(timestamp_with_time_zone_null(Alias::new("finished_at")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer(Alias::new("user_id")))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-report-jobs-user")
+                        .from(Alias::new("report_jobs"), Alias::new("user_id"))
+                        .to(Alias::new("users"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Alias::new("report_jobs")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
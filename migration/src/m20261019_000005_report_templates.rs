@@ -0,0 +1,43 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Alias::new("report_templates"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                // unique per tenant, see the tenants migration
+                .col(string(Alias::new("kind")))
+                .col(text(Alias::new("source")))
+                .col(blob_null(Alias::new("logo")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("report_templates"))
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
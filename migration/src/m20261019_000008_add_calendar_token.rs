@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const INDEX: &str = "idx-users-calendar_token_hash";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // only a hash of the secret feed token is stored, like API keys
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("users"))
+                .add_column(string_null(Alias::new("calendar_token_hash")))
+                .to_owned(),
+        )
+        .await?;
+        // SQLite cannot add a unique column, the index enforces it instead
+        m.create_index(
+            Index::create()
+                .name(INDEX)
+                .table(Alias::new("users"))
+                .col(Alias::new("calendar_token_hash"))
+                .unique()
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_index(
+            Index::drop()
+                .name(INDEX)
+                .table(Alias::new("users"))
+                .to_owned(),
+        )
+        .await?;
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("users"))
+                .drop_column(Alias::new("calendar_token_hash"))
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Alias::new("webhook_endpoints"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(string(Alias::new("name")))
+                .col(string(Alias::new("url")))
+                .col(string(Alias::new("secret")))
+                .col(string(Alias::new("events")))
+                .col(boolean(Alias::new("active")).default(true))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_table(
+            Table::create()
+                .table(Alias::new("webhook_deliveries"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(uuid_uniq(Alias::new("pid")))
+                .col(string(Alias::new("event")))
+                .col(text(Alias::new("payload")))
+                .col(string(Alias::new("status")))
+                .col(integer(Alias::new("attempts")).default(0))
+                .col(integer_null(Alias::new("response_status")))
+                .col(text_null(Alias::new("error")))
+                .col(timestamp_with_time_zone_null(Alias::new("next_attempt_at")))
+                .col(timestamp_with_time_zone_null(Alias::new("delivered_at")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer(Alias::new("webhook_endpoint_id")))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-webhook-deliveries-endpoint")
+                        .from(
+                            Alias::new("webhook_deliveries"),
+                            Alias::new("webhook_endpoint_id"),
+                        )
+                        .to(Alias::new("webhook_endpoints"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        // last due state announced per item, so warnings are sent once
+        m.create_table(
+            Table::create()
+                .table(Alias::new("webhook_item_states"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(string(Alias::new("urgency")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer_uniq(Alias::new("inventory_item_id")))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-webhook-item-states-item")
+                        .from(
+                            Alias::new("webhook_item_states"),
+                            Alias::new("inventory_item_id"),
+                        )
+                        .to(Alias::new("inventory_items"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("webhook_item_states"))
+                .to_owned(),
+        )
+        .await?;
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("webhook_deliveries"))
+                .to_owned(),
+        )
+        .await?;
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("webhook_endpoints"))
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
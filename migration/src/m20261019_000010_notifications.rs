@@ -0,0 +1,155 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Alias::new("notification_subscriptions"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(string_null(Alias::new("location")))
+                .col(string(Alias::new("min_urgency")))
+                .col(string(Alias::new("channel")))
+                .col(string(Alias::new("frequency")))
+                .col(small_integer_null(Alias::new("quiet_start_hour")))
+                .col(small_integer_null(Alias::new("quiet_end_hour")))
+                .col(timestamp_with_time_zone_null(Alias::new("last_sent_at")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer(Alias::new("user_id")))
+                .col(integer_null(Alias::new("inventory_item_kind_id")))
+                .col(integer_null(Alias::new("inventory_item_id")))
+                .col(integer_null(Alias::new("webhook_endpoint_id")))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-notification-subscriptions-user")
+                        .from(
+                            Alias::new("notification_subscriptions"),
+                            Alias::new("user_id"),
+                        )
+                        .to(Alias::new("users"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-notification-subscriptions-kind")
+                        .from(
+                            Alias::new("notification_subscriptions"),
+                            Alias::new("inventory_item_kind_id"),
+                        )
+                        .to(Alias::new("inventory_item_kinds"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-notification-subscriptions-item")
+                        .from(
+                            Alias::new("notification_subscriptions"),
+                            Alias::new("inventory_item_id"),
+                        )
+                        .to(Alias::new("inventory_items"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-notification-subscriptions-endpoint")
+                        .from(
+                            Alias::new("notification_subscriptions"),
+                            Alias::new("webhook_endpoint_id"),
+                        )
+                        .to(Alias::new("webhook_endpoints"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        // one row per item and due date a subscription, or the reminder of an
+        // item's assignee, has reported, so the same overdue item is not sent
+        // again on every run
+        m.create_table(
+            Table::create()
+                .table(Alias::new("sent_notifications"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(string(Alias::new("urgency")))
+                .col(timestamp_with_time_zone(Alias::new("due_at")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer_null(Alias::new("notification_subscription_id")))
+                .col(integer_null(Alias::new("user_id")))
+                .col(integer(Alias::new("inventory_item_id")))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-sent-notifications-subscription")
+                        .from(
+                            Alias::new("sent_notifications"),
+                            Alias::new("notification_subscription_id"),
+                        )
+                        .to(Alias::new("notification_subscriptions"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-sent-notifications-user")
+                        .from(Alias::new("sent_notifications"), Alias::new("user_id"))
+                        .to(Alias::new("users"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-sent-notifications-item")
+                        .from(
+                            Alias::new("sent_notifications"),
+                            Alias::new("inventory_item_id"),
+                        )
+                        .to(Alias::new("inventory_items"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("sent_notifications"))
+                .to_owned(),
+        )
+        .await?;
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("notification_subscriptions"))
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
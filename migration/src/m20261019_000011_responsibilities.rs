@@ -0,0 +1,118 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        // one row per kind or item; an item's owner and deputy override the
+        // ones of its kind separately
+        m.create_table(
+            Table::create()
+                .table(Alias::new("responsibilities"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer_null(Alias::new("inventory_item_kind_id")).unique_key())
+                .col(integer_null(Alias::new("inventory_item_id")).unique_key())
+                .col(integer_null(Alias::new("owner_user_id")))
+                .col(integer_null(Alias::new("deputy_user_id")))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-responsibilities-kind")
+                        .from(
+                            Alias::new("responsibilities"),
+                            Alias::new("inventory_item_kind_id"),
+                        )
+                        .to(Alias::new("inventory_item_kinds"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-responsibilities-item")
+                        .from(
+                            Alias::new("responsibilities"),
+                            Alias::new("inventory_item_id"),
+                        )
+                        .to(Alias::new("inventory_items"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-responsibilities-owner")
+                        .from(Alias::new("responsibilities"), Alias::new("owner_user_id"))
+                        .to(Alias::new("users"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::SetNull)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-responsibilities-deputy")
+                        .from(Alias::new("responsibilities"), Alias::new("deputy_user_id"))
+                        .to(Alias::new("users"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::SetNull)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        // last day of an absence, in the user's time zone
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("users"))
+                .add_column(date_null(Alias::new("absent_until")))
+                .to_owned(),
+        )
+        .await?;
+
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("notification_subscriptions"))
+                .add_column(
+                    boolean(Alias::new("responsible_only"))
+                        .not_null()
+                        .default(false),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("notification_subscriptions"))
+                .drop_column(Alias::new("responsible_only"))
+                .to_owned(),
+        )
+        .await?;
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("users"))
+                .drop_column(Alias::new("absent_until"))
+                .to_owned(),
+        )
+        .await?;
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("responsibilities"))
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
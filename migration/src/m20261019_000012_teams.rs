@@ -0,0 +1,117 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Alias::new("teams"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                // unique per tenant, see the tenants migration
+                .col(string(Alias::new("name")))
+                // members of a scoped team only see the items of their teams
+                .col(boolean(Alias::new("scoped")).not_null().default(false))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_table(
+            Table::create()
+                .table(Alias::new("team_memberships"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer(Alias::new("team_id")))
+                .col(integer(Alias::new("user_id")))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-team-memberships-team")
+                        .from(Alias::new("team_memberships"), Alias::new("team_id"))
+                        .to(Alias::new("teams"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-team-memberships-user")
+                        .from(Alias::new("team_memberships"), Alias::new("user_id"))
+                        .to(Alias::new("users"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+        m.create_index(
+            Index::create()
+                .name("idx-team-memberships-team-user")
+                .table(Alias::new("team_memberships"))
+                .col(Alias::new("team_id"))
+                .col(Alias::new("user_id"))
+                .unique()
+                .to_owned(),
+        )
+        .await?;
+
+        // SQLite cannot add foreign keys with ALTER TABLE ... ADD CONSTRAINT,
+        // but accepts them inline on new columns
+        let db = m.get_connection();
+        for statement in [
+            "ALTER TABLE responsibilities ADD COLUMN owner_team_id integer NULL \
+             REFERENCES teams (id) ON DELETE SET NULL ON UPDATE CASCADE",
+            "ALTER TABLE responsibilities ADD COLUMN deputy_team_id integer NULL \
+             REFERENCES teams (id) ON DELETE SET NULL ON UPDATE CASCADE",
+            "ALTER TABLE notification_subscriptions ADD COLUMN team_id integer NULL \
+             REFERENCES teams (id) ON DELETE CASCADE ON UPDATE CASCADE",
+        ] {
+            db.execute_unprepared(statement).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        for (table, column) in [
+            ("notification_subscriptions", "team_id"),
+            ("responsibilities", "deputy_team_id"),
+            ("responsibilities", "owner_team_id"),
+        ] {
+            m.alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .drop_column(Alias::new(column))
+                    .to_owned(),
+            )
+            .await?;
+        }
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("team_memberships"))
+                .to_owned(),
+        )
+        .await?;
+        m.drop_table(Table::drop().table(Alias::new("teams")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
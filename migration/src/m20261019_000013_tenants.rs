@@ -0,0 +1,195 @@
+use sea_orm_migration::{prelude::*, schema::*, sea_orm::Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables whose rows belong to exactly one tenant.
+const TENANT_TABLES: [&str; 9] = [
+    "inventory_item_kinds",
+    "inventory_items",
+    "checklists",
+    "report_jobs",
+    "generated_reports",
+    "report_templates",
+    "teams",
+    "webhook_endpoints",
+    "webhook_deliveries",
+];
+
+/// Columns that are unique within a tenant.
+const TENANT_UNIQUE: [(&str, &str); 2] = [("report_templates", "kind"), ("teams", "name")];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Alias::new("tenants"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(string_uniq(Alias::new("name")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_table(
+            Table::create()
+                .table(Alias::new("tenant_memberships"))
+                .if_not_exists()
+                .col(pk_auto(Alias::new("id")))
+                .col(
+                    timestamp_with_time_zone(Alias::new("created_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(
+                    timestamp_with_time_zone(Alias::new("updated_at"))
+                        .default(Expr::current_timestamp()),
+                )
+                .col(integer(Alias::new("tenant_id")))
+                .col(integer(Alias::new("user_id")))
+                .col(boolean(Alias::new("admin")).default(false))
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-tenant-memberships-tenant")
+                        .from(Alias::new("tenant_memberships"), Alias::new("tenant_id"))
+                        .to(Alias::new("tenants"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .foreign_key(
+                    ForeignKey::create()
+                        .name("fk-tenant-memberships-user")
+                        .from(Alias::new("tenant_memberships"), Alias::new("user_id"))
+                        .to(Alias::new("users"), Alias::new("id"))
+                        .on_delete(ForeignKeyAction::Cascade)
+                        .on_update(ForeignKeyAction::Cascade),
+                )
+                .to_owned(),
+        )
+        .await?;
+        m.create_index(
+            Index::create()
+                .name("idx-tenant-memberships-tenant-user")
+                .table(Alias::new("tenant_memberships"))
+                .col(Alias::new("tenant_id"))
+                .col(Alias::new("user_id"))
+                .unique()
+                .to_owned(),
+        )
+        .await?;
+
+        // everything recorded so far belongs to the default tenant; the id
+        // comes from the table's own sequence so later tenants don't collide
+        // with it, and the oldest user administers it as its first member
+        let db = m.get_connection();
+        db.execute_unprepared("INSERT INTO tenants (name) VALUES ('Default')")
+            .await?;
+        let tenant_id: i32 = db
+            .query_one(Statement::from_string(
+                m.get_database_backend(),
+                "SELECT id FROM tenants WHERE name = 'Default'",
+            ))
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("default tenant".to_string()))?
+            .try_get("", "id")?;
+        db.execute_unprepared(&format!(
+            "INSERT INTO tenant_memberships (tenant_id, user_id, admin) \
+             SELECT {tenant_id}, id, id = (SELECT MIN(id) FROM users) FROM users"
+        ))
+        .await?;
+        // SQLite only accepts inline foreign keys on new nullable columns
+        db.execute_unprepared(
+            "ALTER TABLE users ADD COLUMN current_tenant_id integer NULL \
+             REFERENCES tenants (id) ON DELETE SET NULL ON UPDATE CASCADE",
+        )
+        .await?;
+        for table in TENANT_TABLES {
+            m.alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .add_column(
+                        integer(Alias::new("tenant_id"))
+                            .not_null()
+                            .default(tenant_id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+            m.create_index(
+                Index::create()
+                    .name(format!("idx-{table}-tenant"))
+                    .table(Alias::new(table))
+                    .col(Alias::new("tenant_id"))
+                    .to_owned(),
+            )
+            .await?;
+        }
+        for (table, column) in TENANT_UNIQUE {
+            m.create_index(
+                Index::create()
+                    .name(format!("idx-{table}-tenant-{column}"))
+                    .table(Alias::new(table))
+                    .col(Alias::new("tenant_id"))
+                    .col(Alias::new(column))
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        for (table, column) in TENANT_UNIQUE {
+            m.drop_index(
+                Index::drop()
+                    .name(format!("idx-{table}-tenant-{column}"))
+                    .table(Alias::new(table))
+                    .to_owned(),
+            )
+            .await?;
+        }
+        for table in TENANT_TABLES {
+            m.drop_index(
+                Index::drop()
+                    .name(format!("idx-{table}-tenant"))
+                    .table(Alias::new(table))
+                    .to_owned(),
+            )
+            .await?;
+            m.alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .drop_column(Alias::new("tenant_id"))
+                    .to_owned(),
+            )
+            .await?;
+        }
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("users"))
+                .drop_column(Alias::new("current_tenant_id"))
+                .to_owned(),
+        )
+        .await?;
+        m.drop_table(
+            Table::drop()
+                .table(Alias::new("tenant_memberships"))
+                .to_owned(),
+        )
+        .await?;
+        m.drop_table(Table::drop().table(Alias::new("tenants")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
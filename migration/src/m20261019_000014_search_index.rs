@@ -0,0 +1,58 @@
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Documents keyed by `rowid`, see `bestbefors::search`. SQLite keeps them
+/// in an FTS5 table whose filter columns are not tokenized.
+const SQLITE_UP: &str = "CREATE VIRTUAL TABLE search_index USING fts5(\
+     entity UNINDEXED, entity_id UNINDEXED, tenant_id UNINDEXED, item_id UNINDEXED, \
+     title, body, tokenize = 'unicode61 remove_diacritics 2')";
+
+/// Postgres ranks a weighted `tsvector` generated from the title and body.
+const POSTGRES_UP: [&str; 3] = [
+    "CREATE TABLE search_index (\
+     rowid bigint PRIMARY KEY, \
+     entity varchar NOT NULL, \
+     entity_id integer NOT NULL, \
+     tenant_id integer NOT NULL, \
+     item_id integer NOT NULL, \
+     title text NOT NULL, \
+     body text NOT NULL, \
+     document tsvector GENERATED ALWAYS AS (\
+     setweight(to_tsvector('simple', title), 'A') || \
+     setweight(to_tsvector('simple', body), 'B')) STORED)",
+    "CREATE INDEX \"idx-search-index-document\" ON search_index USING GIN (document)",
+    "CREATE INDEX \"idx-search-index-tenant\" ON search_index (tenant_id, entity)",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        let db = m.get_connection();
+        match m.get_database_backend() {
+            DbBackend::Sqlite => {
+                db.execute_unprepared(SQLITE_UP).await?;
+            }
+            DbBackend::Postgres => {
+                for statement in POSTGRES_UP {
+                    db.execute_unprepared(statement).await?;
+                }
+            }
+            DbBackend::MySql => {
+                return Err(DbErr::Migration(
+                    "full-text search needs SQLite or Postgres".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Alias::new("search_index")).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
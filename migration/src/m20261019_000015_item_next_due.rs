@@ -0,0 +1,141 @@
+use chrono::{Datelike, Days, Months};
+use sea_orm_migration::{
+    prelude::*,
+    schema::*,
+    sea_orm::{prelude::DateTimeWithTimeZone, ConnectionTrait, Statement},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Next check after `start` for an interval of `count` `modifier`s, like
+/// `intervals::Model::next_interval_expiry`. `None` for intervals without a
+/// period.
+fn next_check(
+    start: DateTimeWithTimeZone,
+    modifier: &str,
+    count: i32,
+) -> Option<DateTimeWithTimeZone> {
+    let end = match modifier {
+        "days" => {
+            let days = Days::new(count.unsigned_abs().into());
+            if count > 0 {
+                start.checked_add_days(days)
+            } else {
+                start.checked_sub_days(days)
+            }
+        }
+        "months" => {
+            let months = Months::new(count.unsigned_abs());
+            if count > 0 {
+                start.checked_add_months(months)
+            } else {
+                start.checked_sub_months(months)
+            }
+        }
+        "years" => start.with_year(start.year() + count),
+        _ => return None,
+    };
+    Some(end.unwrap_or_default())
+}
+
+/// Stores the due date of the items recorded so far, computed like
+/// `inventory_items::Model::compute_next_due_at`.
+async fn backfill_next_due_at(m: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let db = m.get_connection();
+    let rows = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT inventory_items.id, inventory_items.created_at, \
+             inventory_items.last_checked_at, inventory_items.expiry, \
+             intervals.sqlite_modifier, intervals.sqlite_num_of_modifier \
+             FROM inventory_items LEFT JOIN intervals ON intervals.id = inventory_items.interval_id",
+        ))
+        .await?;
+    for row in rows {
+        let created_at: DateTimeWithTimeZone = row.try_get("", "created_at")?;
+        let last_checked_at: Option<DateTimeWithTimeZone> = row.try_get("", "last_checked_at")?;
+        let expiry: Option<DateTimeWithTimeZone> = row.try_get("", "expiry")?;
+        let modifier: Option<String> = row.try_get("", "sqlite_modifier")?;
+        let count: Option<i32> = row.try_get("", "sqlite_num_of_modifier")?;
+        let next_check = modifier.zip(count).and_then(|(modifier, count)| {
+            next_check(last_checked_at.unwrap_or(created_at), &modifier, count)
+        });
+        let next_due_at = match (next_check, expiry) {
+            (Some(next_check), Some(expiry)) => Some(next_check.min(expiry)),
+            (next_check, expiry) => next_check.or(expiry),
+        };
+        if let Some(next_due_at) = next_due_at {
+            let id: i32 = row.try_get("", "id")?;
+            m.exec_stmt(
+                Query::update()
+                    .table(Alias::new("inventory_items"))
+                    .value(Alias::new("next_due_at"), next_due_at)
+                    .and_where(Expr::col(Alias::new("id")).eq(id))
+                    .to_owned(),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("inventory_items"))
+                .add_column(timestamp_with_time_zone_null(Alias::new("next_due_at")))
+                .to_owned(),
+        )
+        .await?;
+        backfill_next_due_at(m).await?;
+        m.create_index(
+            Index::create()
+                .name("idx-inventory_items-tenant-next-due")
+                .table(Alias::new("inventory_items"))
+                .col(Alias::new("tenant_id"))
+                .col(Alias::new("next_due_at"))
+                .to_owned(),
+        )
+        .await?;
+        m.create_index(
+            Index::create()
+                .name("idx-inventory_item_checks-item-checked")
+                .table(Alias::new("inventory_item_checks"))
+                .col(Alias::new("inventory_item_id"))
+                .col(Alias::new("checked_at"))
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_index(
+            Index::drop()
+                .name("idx-inventory_item_checks-item-checked")
+                .table(Alias::new("inventory_item_checks"))
+                .to_owned(),
+        )
+        .await?;
+        m.drop_index(
+            Index::drop()
+                .name("idx-inventory_items-tenant-next-due")
+                .table(Alias::new("inventory_items"))
+                .to_owned(),
+        )
+        .await?;
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("inventory_items"))
+                .drop_column(Alias::new("next_due_at"))
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -17,10 +17,11 @@ use std::path::Path;
 use crate::{
     controllers, initializers,
     models::_entities::{
-        checklist_steps, checklists, expiries, intervals, results, translations, users,
+        checklist_steps, checklists, expiries, intervals, results, tenant_memberships,
+        translations, users,
     },
     tasks,
-    workers::downloader::DownloadWorker,
+    workers::{downloader::DownloadWorker, report::ReportWorker, webhook::WebhookWorker},
 };
 
 pub struct App;
@@ -52,27 +53,49 @@ impl Hooks for App {
         Ok(vec![
             Box::new(initializers::view_engine::ViewEngineInitializer),
             Box::new(initializers::app_cache::AppCacheInitializer),
+            Box::new(initializers::locale::LocaleInitializer),
+            Box::new(initializers::search_index::SearchIndexInitializer),
         ])
     }
 
     fn routes(_ctx: &AppContext) -> AppRoutes {
         AppRoutes::with_default_routes() // controller routes below
+            .add_route(controllers::api_keys::routes())
             .add_route(controllers::auth::routes())
+            .add_route(controllers::calendar::routes())
             .add_route(controllers::checklists::routes())
             .add_route(controllers::expiries::routes())
             .add_route(controllers::home::routes())
             .add_route(controllers::intervals::routes())
             .add_route(controllers::inventory::routes())
+            .add_route(controllers::labels::routes())
+            .add_route(controllers::locale::routes())
+            .add_route(controllers::notifications::routes())
+            .add_route(controllers::report_templates::routes())
+            .add_route(controllers::reports::routes())
+            .add_route(controllers::responsibilities::routes())
+            .add_route(controllers::scan::routes())
+            .add_route(controllers::search::routes())
+            .add_route(controllers::teams::routes())
+            .add_route(controllers::tenants::routes())
             .add_route(controllers::users::routes())
             .add_route(controllers::translations::routes())
+            .add_route(controllers::verify::routes())
+            .add_route(controllers::webhooks::routes())
+            .nest_routes(controllers::api::PREFIX, controllers::api::routes())
     }
     async fn connect_workers(ctx: &AppContext, queue: &Queue) -> Result<()> {
         queue.register(DownloadWorker::build(ctx)).await?;
+        queue.register(ReportWorker::build(ctx)).await?;
+        queue.register(WebhookWorker::build(ctx)).await?;
         Ok(())
     }
 
-    #[allow(unused_variables)]
     fn register_tasks(tasks: &mut Tasks) {
+        tasks.register(tasks::i18n_check::I18nCheck);
+        tasks.register(tasks::notifications::Notifications);
+        tasks.register(tasks::search_reindex::SearchReindex);
+        tasks.register(tasks::webhooks::Webhooks);
         // tasks-inject (do not remove)
     }
     async fn truncate(ctx: &AppContext) -> Result<()> {
@@ -82,6 +105,11 @@ impl Hooks for App {
     async fn seed(ctx: &AppContext, base: &Path) -> Result<()> {
         db::seed::<users::ActiveModel>(&ctx.db, &base.join("users.yaml").display().to_string())
             .await?;
+        db::seed::<tenant_memberships::ActiveModel>(
+            &ctx.db,
+            &base.join("tenant_memberships.yaml").display().to_string(),
+        )
+        .await?;
         db::seed::<expiries::ActiveModel>(
             &ctx.db,
             &base.join("expiries.yaml").display().to_string(),
@@ -0,0 +1,190 @@
+//! Minimal iCalendar (RFC 5545) writer for the calendar feed of due dates.
+use chrono::{DateTime, Days, NaiveDate, Utc};
+
+/// Product identifier announced in every feed.
+const PRODUCT_ID: &str = "-//bestbefors//Inspection calendar//EN";
+/// Domain part of the event UIDs, keeping them unique across feeds.
+const UID_DOMAIN: &str = "bestbefors";
+/// How often subscribed clients are asked to reload the feed.
+const REFRESH_INTERVAL: &str = "PT1H";
+/// Content lines longer than this many octets are folded.
+const MAX_LINE_OCTETS: usize = 75;
+/// File extension calendar clients expect at the end of a feed URL.
+pub(crate) const FEED_EXTENSION: &str = ".ics";
+
+/// Path of the calendar feed with the secret `token`, relative to the site.
+#[must_use]
+pub fn feed_path(token: &str) -> String {
+    format!("/calendar/{token}{FEED_EXTENSION}")
+}
+
+/// An all-day event, e.g. the day an item is due for inspection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    /// Identifies the event across reloads, so clients move it instead of
+    /// adding a new one when the date changes.
+    pub uid: String,
+    pub date: NaiveDate,
+    pub summary: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub last_modified: DateTime<Utc>,
+}
+
+impl CalendarEvent {
+    /// UID of the event of `kind` (e.g. `inspection`) for item `item_id`.
+    #[must_use]
+    pub fn item_uid(item_id: i32, kind: &str) -> String {
+        format!("item-{item_id}-{kind}@{UID_DOMAIN}")
+    }
+}
+
+/// A feed of [`CalendarEvent`]s.
+#[derive(Debug, Clone, Default)]
+pub struct Calendar {
+    pub name: String,
+    /// IANA name of the zone the event dates are in.
+    pub timezone: String,
+    pub events: Vec<CalendarEvent>,
+}
+
+impl Calendar {
+    /// The feed as `text/calendar`, with `generated_at` as time stamp of
+    /// every event.
+    #[must_use]
+    pub fn render(&self, generated_at: DateTime<Utc>) -> String {
+        let mut out = String::new();
+        let stamp = format_utc(generated_at);
+        line(&mut out, "BEGIN:VCALENDAR");
+        line(&mut out, "VERSION:2.0");
+        line(&mut out, &format!("PRODID:{PRODUCT_ID}"));
+        line(&mut out, "CALSCALE:GREGORIAN");
+        line(&mut out, "METHOD:PUBLISH");
+        line(&mut out, &format!("X-WR-CALNAME:{}", escape(&self.name)));
+        line(
+            &mut out,
+            &format!("X-WR-TIMEZONE:{}", escape(&self.timezone)),
+        );
+        line(
+            &mut out,
+            &format!("REFRESH-INTERVAL;VALUE=DURATION:{REFRESH_INTERVAL}"),
+        );
+        line(&mut out, &format!("X-PUBLISHED-TTL:{REFRESH_INTERVAL}"));
+        for event in &self.events {
+            line(&mut out, "BEGIN:VEVENT");
+            line(&mut out, &format!("UID:{}", escape(&event.uid)));
+            line(&mut out, &format!("DTSTAMP:{stamp}"));
+            line(
+                &mut out,
+                &format!("LAST-MODIFIED:{}", format_utc(event.last_modified)),
+            );
+            line(
+                &mut out,
+                &format!("DTSTART;VALUE=DATE:{}", format_date(event.date)),
+            );
+            let end = event
+                .date
+                .checked_add_days(Days::new(1))
+                .unwrap_or(event.date);
+            line(&mut out, &format!("DTEND;VALUE=DATE:{}", format_date(end)));
+            line(&mut out, &format!("SUMMARY:{}", escape(&event.summary)));
+            if let Some(description) = &event.description {
+                line(&mut out, &format!("DESCRIPTION:{}", escape(description)));
+            }
+            if let Some(url) = &event.url {
+                line(&mut out, &format!("URL:{url}"));
+            }
+            line(&mut out, "TRANSP:TRANSPARENT");
+            line(&mut out, "END:VEVENT");
+        }
+        line(&mut out, "END:VCALENDAR");
+        out
+    }
+}
+
+fn format_utc(value: DateTime<Utc>) -> String {
+    value.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn format_date(value: NaiveDate) -> String {
+    value.format("%Y%m%d").to_string()
+}
+
+/// Escapes a TEXT value.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Appends a content line, folded after [`MAX_LINE_OCTETS`] octets without
+/// splitting characters.
+fn line(out: &mut String, content: &str) {
+    let mut octets = 0;
+    for c in content.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // the leading space of the continuation counts towards the limit
+            octets = 1;
+        }
+        octets += c.len_utf8();
+        out.push(c);
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn renders_all_day_events() {
+        let calendar = Calendar {
+            name: "Inspections".to_string(),
+            timezone: "Europe/Berlin".to_string(),
+            events: vec![CalendarEvent {
+                uid: CalendarEvent::item_uid(7, "expiry"),
+                date: NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(),
+                summary: "Expires: Rope, 60m; red".to_string(),
+                description: Some("Kind: Rope\nSN-1".to_string()),
+                url: Some("http://localhost:5150/inventory/item/7".to_string()),
+                last_modified: Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap(),
+            }],
+        };
+        let ics = calendar.render(Utc.with_ymd_and_hms(2026, 10, 19, 8, 0, 0).unwrap());
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains("UID:item-7-expiry@bestbefors\r\n"));
+        assert!(ics.contains("DTSTAMP:20261019T080000Z\r\n"));
+        assert!(ics.contains("LAST-MODIFIED:20260102T030405Z\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261231\r\nDTEND;VALUE=DATE:20270101\r\n"));
+        assert!(ics.contains("SUMMARY:Expires: Rope\\, 60m\\; red\r\n"));
+        assert!(ics.contains("DESCRIPTION:Kind: Rope\\nSN-1\r\n"));
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let mut out = String::new();
+        line(&mut out, &format!("SUMMARY:{}", "ä".repeat(60)));
+        let lines = out.split("\r\n").collect::<Vec<_>>();
+        assert!(lines.len() > 2);
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(
+            out.replace("\r\n ", ""),
+            format!("SUMMARY:{}\r\n", "ä".repeat(60))
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use loco_rs::prelude::*;
+use sea_orm::QueryOrder;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{pagination, ApiQuery, ApiUser};
+use crate::{
+    exts::StringExt,
+    models::api_keys::{self, ApiScope, Column as ApiKeysColumn},
+    views::api::{ApiKeyResponse, CreatedApiKeyResponse, ErrorResponse, PageView},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListApiKeysQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyParams {
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    /// The key stops working at this point in time. Keys without an expiry
+    /// stay valid until revoked.
+    pub expires_at: Option<DateTime<FixedOffset>>,
+}
+
+async fn load_own_key(ctx: &AppContext, auth: &ApiUser, id: i32) -> Result<api_keys::Model> {
+    api_keys::Entity::find_by_id(id)
+        .filter(ApiKeysColumn::UserId.eq(auth.user.id))
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api_keys",
+    tag = "api_keys",
+    params(ListApiKeysQuery),
+    responses(
+        (status = 200, body = PageView<ApiKeyResponse>),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(params): ApiQuery<ListApiKeysQuery>,
+) -> Result<Response> {
+    auth.require_session()?;
+
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        api_keys::Entity::find().order_by_desc(ApiKeysColumn::Id),
+        Some(
+            query::condition()
+                .eq(ApiKeysColumn::UserId, auth.user.id)
+                .build(),
+        ),
+        &pagination,
+    )
+    .await?;
+
+    format::json(PageView::new(page, &pagination, ApiKeyResponse::from))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api_keys",
+    tag = "api_keys",
+    request_body = CreateApiKeyParams,
+    responses(
+        (status = 200, body = CreatedApiKeyResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateApiKeyParams>,
+) -> Result<Response> {
+    auth.require_session()?;
+
+    let Some(name) = params.name.clean() else {
+        return Err(Error::BadRequest("Key name must not be empty".to_string()));
+    };
+    let mut scopes = params.scopes;
+    scopes.sort();
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(Error::BadRequest(
+            "At least one scope must be granted".to_string(),
+        ));
+    }
+    if params
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err(Error::BadRequest(
+            "Expiry must be in the future".to_string(),
+        ));
+    }
+
+    let (key, token) =
+        api_keys::ActiveModel::generate(auth.user.id, name, &scopes, params.expires_at);
+    let key = key.insert(&ctx.db).await?;
+
+    format::json(CreatedApiKeyResponse {
+        key: key.into(),
+        token,
+    })
+}
+
+/// Revokes the key. Revoked keys stay listed so their usage can be audited.
+#[utoipa::path(
+    delete,
+    path = "/api_keys/{id}",
+    tag = "api_keys",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = ApiKeyResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn revoke(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require_session()?;
+
+    let key = load_own_key(&ctx, &auth, id).await?;
+    let key = if key.revoked_at.is_some() {
+        key
+    } else {
+        let mut key = key.into_active_model();
+        key.revoked_at = ActiveValue::set(Some(Utc::now().into()));
+        key.update(&ctx.db).await?
+    };
+
+    format::json(ApiKeyResponse::from(key))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/api_keys")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", delete(revoke))
+}
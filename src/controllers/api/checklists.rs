@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use loco_rs::prelude::*;
+use sea_orm::{Condition, QueryOrder};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use super::{pagination, ApiQuery, ApiUser};
+use crate::{
+    controllers::checklists::{
+        delete_checklist, load_checklist, prepare_checklist_steps, AddChecklistParams,
+        PreparedChecklistStep,
+    },
+    exts::{OptionStringExt, StringExt},
+    initializers::app_cache::refresh_checklists_cache,
+    models::{
+        _entities::{
+            checklist_steps::Column as ChecklistStepsColumn, checklists::Column as ChecklistsColumn,
+        },
+        api_keys::ApiScope,
+        checklist_steps, checklists,
+    },
+    views::api::{ChecklistResponse, ErrorResponse, PageView},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListChecklistsQuery {
+    /// Matches the checklist name.
+    pub q: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+async fn load_steps_by_checklist_id<C>(
+    db: &C,
+    checklist_ids: Vec<i32>,
+) -> Result<HashMap<i32, Vec<checklist_steps::Model>>>
+where
+    C: ConnectionTrait,
+{
+    let steps = checklist_steps::Entity::find()
+        .filter(ChecklistStepsColumn::ChecklistId.is_in(checklist_ids))
+        .order_by_asc(ChecklistStepsColumn::Position)
+        .all(db)
+        .await?;
+
+    let mut by_checklist_id = HashMap::<i32, Vec<checklist_steps::Model>>::new();
+    for step in steps {
+        by_checklist_id
+            .entry(step.checklist_id)
+            .or_default()
+            .push(step);
+    }
+
+    Ok(by_checklist_id)
+}
+
+async fn checklist_response(
+    ctx: &AppContext,
+    checklist: checklists::Model,
+) -> Result<ChecklistResponse> {
+    let mut steps = load_steps_by_checklist_id(&ctx.db, vec![checklist.id]).await?;
+    let steps = steps.remove(&checklist.id).unwrap_or_default();
+    Ok(ChecklistResponse::new(checklist, steps))
+}
+
+async fn insert_steps<C>(db: &C, checklist_id: i32, steps: Vec<PreparedChecklistStep>) -> Result<()>
+where
+    C: ConnectionTrait,
+{
+    for prepared in steps {
+        checklist_steps::ActiveModel {
+            checklist_id: ActiveValue::set(checklist_id),
+            position: ActiveValue::set(prepared.position),
+            name: ActiveValue::set(prepared.name),
+            description: ActiveValue::set(prepared.description),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/checklists",
+    tag = "checklists",
+    params(ListChecklistsQuery),
+    responses(
+        (status = 200, body = PageView<ChecklistResponse>),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(params): ApiQuery<ListChecklistsQuery>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let mut condition = Condition::all();
+    if let Some(q) = params.q.clean() {
+        condition = condition.add(ChecklistsColumn::Name.contains(&q));
+    }
+
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        checklists::Entity::find_in_tenant(auth.tenant_id()?).order_by_asc(ChecklistsColumn::Id),
+        Some(condition),
+        &pagination,
+    )
+    .await?;
+    let checklist_ids = page
+        .page
+        .iter()
+        .map(|checklist| checklist.id)
+        .collect::<Vec<_>>();
+    let mut steps_by_checklist_id = load_steps_by_checklist_id(&ctx.db, checklist_ids).await?;
+
+    format::json(PageView::new(page, &pagination, |checklist| {
+        let steps = steps_by_checklist_id
+            .remove(&checklist.id)
+            .unwrap_or_default();
+        ChecklistResponse::new(checklist, steps)
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/checklists/{id}",
+    tag = "checklists",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = ChecklistResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let checklist = load_checklist(&ctx, auth.tenant_id()?, id).await?;
+    format::json(checklist_response(&ctx, checklist).await?)
+}
+
+#[utoipa::path(
+    post,
+    path = "/checklists",
+    tag = "checklists",
+    request_body = AddChecklistParams,
+    responses(
+        (status = 200, body = ChecklistResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<AddChecklistParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let AddChecklistParams {
+        name,
+        description,
+        steps,
+    } = params;
+    let name = name
+        .clean()
+        .ok_or_else(|| Error::BadRequest("Checklist name must not be empty".to_string()))?;
+    let steps = prepare_checklist_steps(steps)?;
+
+    let trx = ctx.db.begin().await?;
+    let checklist = checklists::ActiveModel {
+        tenant_id: ActiveValue::set(auth.tenant_id()?),
+        name: ActiveValue::set(name),
+        description: ActiveValue::set(description.clean()),
+        ..Default::default()
+    }
+    .insert(&trx)
+    .await?;
+    insert_steps(&trx, checklist.id, steps).await?;
+    trx.commit().await?;
+    refresh_checklists_cache(&ctx).await?;
+
+    format::json(checklist_response(&ctx, checklist).await?)
+}
+
+/// Replaces the checklist's name, description and steps. Checks that were
+/// already performed keep their own snapshot in `executed_checklist_steps`.
+#[utoipa::path(
+    put,
+    path = "/checklists/{id}",
+    tag = "checklists",
+    params(("id" = i32, Path)),
+    request_body = AddChecklistParams,
+    responses(
+        (status = 200, body = ChecklistResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn update(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(params): Json<AddChecklistParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let existing = load_checklist(&ctx, auth.tenant_id()?, id).await?;
+    let AddChecklistParams {
+        name,
+        description,
+        steps,
+    } = params;
+    let name = name
+        .clean()
+        .ok_or_else(|| Error::BadRequest("Checklist name must not be empty".to_string()))?;
+    let steps = prepare_checklist_steps(steps)?;
+
+    let trx = ctx.db.begin().await?;
+    let mut checklist: checklists::ActiveModel = existing.into();
+    checklist.name = ActiveValue::set(name);
+    checklist.description = ActiveValue::set(description.clean());
+    let checklist = checklist.update(&trx).await?;
+    checklist_steps::Entity::delete_many()
+        .filter(ChecklistStepsColumn::ChecklistId.eq(id))
+        .exec(&trx)
+        .await?;
+    insert_steps(&trx, id, steps).await?;
+    trx.commit().await?;
+    refresh_checklists_cache(&ctx).await?;
+
+    format::json(checklist_response(&ctx, checklist).await?)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/checklists/{id}",
+    tag = "checklists",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = Object),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    delete_checklist(&ctx, auth.tenant_id()?, id).await?;
+    refresh_checklists_cache(&ctx).await?;
+    format::empty_json()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/checklists")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", get(get_one))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+
+use loco_rs::prelude::*;
+use sea_orm::{Condition, QueryOrder, QuerySelect, QueryTrait};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{pagination, ApiQuery, ApiUser};
+use crate::{
+    controllers::inventory::{parse_expiry_date, record_item_check, PerformCheckPayload},
+    exts::OptionStringExt,
+    initializers::app_cache::AppData,
+    models::{
+        _entities::{
+            executed_checklist_steps::Column as ExecutedChecklistStepsColumn,
+            inventory_item_check_steps::Column as InventoryItemCheckStepsColumn,
+            inventory_item_checks::Column as InventoryItemChecksColumn,
+        },
+        api_keys::ApiScope,
+        executed_checklist_steps, executed_checklists, inventory_item_check_steps,
+        inventory_item_checks, inventory_items,
+    },
+    search::{self, SearchEntity},
+    timezone::RequestTimezone,
+    views::api::{CheckResponse, CheckStepResponse, ErrorResponse, PageView},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListChecksQuery {
+    pub item_id: Option<i32>,
+    pub result_id: Option<i32>,
+    pub checked_by: Option<i32>,
+    /// Only checks performed on or after this date (`YYYY-MM-DD`).
+    pub checked_after: Option<String>,
+    /// Only checks performed before this date (`YYYY-MM-DD`).
+    pub checked_before: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateCheckParams {
+    pub inventory_item_id: i32,
+    #[serde(flatten)]
+    pub check: PerformCheckPayload,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateCheckParams {
+    pub result_id: i32,
+    pub notes: Option<String>,
+}
+
+async fn load_check(
+    ctx: &AppContext,
+    auth: &ApiUser,
+    id: i32,
+) -> Result<inventory_item_checks::Model> {
+    let check = inventory_item_checks::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    inventory_items::Entity::find_by_id_in_tenant(auth.tenant_id()?, check.inventory_item_id)
+        .filter(
+            auth.item_scope(ctx)
+                .await?
+                .condition(inventory_items::Column::Id),
+        )
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    Ok(check)
+}
+
+async fn check_responses(
+    ctx: &AppContext,
+    checks: Vec<inventory_item_checks::Model>,
+) -> Result<Vec<CheckResponse>> {
+    let check_ids = checks.iter().map(|check| check.id).collect::<Vec<_>>();
+    let executed_checklist_ids = checks
+        .iter()
+        .map(|check| check.executed_checklist_id)
+        .collect::<Vec<_>>();
+
+    let executed_steps = executed_checklist_steps::Entity::find()
+        .filter(ExecutedChecklistStepsColumn::ExecutedChecklistId.is_in(executed_checklist_ids))
+        .order_by_asc(ExecutedChecklistStepsColumn::Position)
+        .all(&ctx.db)
+        .await?;
+    let step_results = inventory_item_check_steps::Entity::find()
+        .filter(InventoryItemCheckStepsColumn::InventoryItemCheckId.is_in(check_ids))
+        .all(&ctx.db)
+        .await?
+        .into_iter()
+        .map(|step| (step.executed_checklist_step_id, step))
+        .collect::<HashMap<_, _>>();
+
+    let mut steps_by_executed_checklist_id = HashMap::<i32, Vec<CheckStepResponse>>::new();
+    for executed_step in executed_steps {
+        let result = step_results.get(&executed_step.id);
+        steps_by_executed_checklist_id
+            .entry(executed_step.executed_checklist_id)
+            .or_default()
+            .push(CheckStepResponse::new(executed_step, result));
+    }
+
+    Ok(checks
+        .into_iter()
+        .map(|check| {
+            let steps = steps_by_executed_checklist_id
+                .remove(&check.executed_checklist_id)
+                .unwrap_or_default();
+            CheckResponse::new(check, steps)
+        })
+        .collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/checks",
+    tag = "checks",
+    params(ListChecksQuery),
+    responses(
+        (status = 200, body = PageView<CheckResponse>),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    RequestTimezone(tz): RequestTimezone,
+    ApiQuery(params): ApiQuery<ListChecksQuery>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let mut condition = Condition::all();
+    if let Some(item_id) = params.item_id {
+        condition = condition.add(InventoryItemChecksColumn::InventoryItemId.eq(item_id));
+    }
+    if let Some(result_id) = params.result_id {
+        condition = condition.add(InventoryItemChecksColumn::ResultId.eq(result_id));
+    }
+    if let Some(checked_by) = params.checked_by {
+        condition = condition.add(InventoryItemChecksColumn::CheckedBy.eq(checked_by));
+    }
+    condition = condition.add(
+        auth.item_scope(&ctx)
+            .await?
+            .condition(InventoryItemChecksColumn::InventoryItemId),
+    );
+    condition = condition.add(
+        InventoryItemChecksColumn::InventoryItemId.in_subquery(
+            inventory_items::Entity::find_in_tenant(auth.tenant_id()?)
+                .select_only()
+                .column(inventory_items::Column::Id)
+                .into_query(),
+        ),
+    );
+    if let Some(checked_after) = params.checked_after.clean() {
+        condition = condition
+            .add(InventoryItemChecksColumn::CheckedAt.gte(parse_expiry_date(&checked_after, tz)?));
+    }
+    if let Some(checked_before) = params.checked_before.clean() {
+        condition = condition
+            .add(InventoryItemChecksColumn::CheckedAt.lt(parse_expiry_date(&checked_before, tz)?));
+    }
+
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        inventory_item_checks::Entity::find()
+            .order_by_desc(InventoryItemChecksColumn::CheckedAt)
+            .order_by_desc(InventoryItemChecksColumn::Id),
+        Some(condition),
+        &pagination,
+    )
+    .await?;
+    let total_pages = page.total_pages;
+    let total_items = page.total_items;
+    let items = check_responses(&ctx, page.page).await?;
+
+    format::json(PageView {
+        items,
+        page: pagination.page,
+        page_size: pagination.page_size,
+        total_pages,
+        total_items,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/checks/{id}",
+    tag = "checks",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = CheckResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let check = load_check(&ctx, &auth, id).await?;
+    let mut responses = check_responses(&ctx, vec![check]).await?;
+    format::json(responses.remove(0))
+}
+
+#[utoipa::path(
+    post,
+    path = "/checks",
+    tag = "checks",
+    request_body = CreateCheckParams,
+    responses(
+        (status = 200, body = CheckResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateCheckParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::SubmitChecks)?;
+    let scope = auth.item_scope(&ctx).await?;
+    let Some(item) =
+        inventory_items::Entity::find_by_id_in_tenant(auth.tenant_id()?, params.inventory_item_id)
+            .filter(scope.condition(inventory_items::Column::Id))
+            .one(&ctx.db)
+            .await?
+    else {
+        return Err(Error::BadRequest("Unknown inventory item".to_string()));
+    };
+
+    let check = record_item_check(&ctx, item, params.check).await?;
+    let mut responses = check_responses(&ctx, vec![check]).await?;
+    format::json(responses.remove(0))
+}
+
+/// Corrects the overall result and notes of a recorded check. Step results
+/// are left untouched.
+#[utoipa::path(
+    put,
+    path = "/checks/{id}",
+    tag = "checks",
+    params(("id" = i32, Path)),
+    request_body = UpdateCheckParams,
+    responses(
+        (status = 200, body = CheckResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn update(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(params): Json<UpdateCheckParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let existing = load_check(&ctx, &auth, id).await?;
+    if !ctx.get_results()?.contains_key(&params.result_id) {
+        return Err(Error::BadRequest("Unknown result".to_string()));
+    }
+
+    let mut check: inventory_item_checks::ActiveModel = existing.into();
+    check.result_id = ActiveValue::set(params.result_id);
+    check.notes = ActiveValue::set(params.notes.clean());
+    let check = check.update(&ctx.db).await?;
+    search::index_check(&ctx.db, check.id).await?;
+
+    let mut responses = check_responses(&ctx, vec![check]).await?;
+    format::json(responses.remove(0))
+}
+
+/// Deletes a check together with its executed checklist and step results,
+/// then recomputes the item's `last_checked_at` from the remaining checks.
+#[utoipa::path(
+    delete,
+    path = "/checks/{id}",
+    tag = "checks",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = Object),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let check = load_check(&ctx, &auth, id).await?;
+
+    let trx = ctx.db.begin().await?;
+    inventory_item_check_steps::Entity::delete_many()
+        .filter(InventoryItemCheckStepsColumn::InventoryItemCheckId.eq(check.id))
+        .exec(&trx)
+        .await?;
+    inventory_item_checks::Entity::delete_by_id(check.id)
+        .exec(&trx)
+        .await?;
+    executed_checklist_steps::Entity::delete_many()
+        .filter(ExecutedChecklistStepsColumn::ExecutedChecklistId.eq(check.executed_checklist_id))
+        .exec(&trx)
+        .await?;
+    executed_checklists::Entity::delete_by_id(check.executed_checklist_id)
+        .exec(&trx)
+        .await?;
+
+    let latest_check = inventory_item_checks::Entity::find()
+        .filter(InventoryItemChecksColumn::InventoryItemId.eq(check.inventory_item_id))
+        .order_by_desc(InventoryItemChecksColumn::CheckedAt)
+        .one(&trx)
+        .await?;
+    if let Some(item) = inventory_items::Entity::find_by_id(check.inventory_item_id)
+        .one(&trx)
+        .await?
+    {
+        let mut item = item.into_active_model();
+        item.last_checked_at = ActiveValue::set(latest_check.map(|latest| latest.checked_at));
+        item.update(&trx).await?;
+    }
+    trx.commit().await?;
+    search::remove(&ctx.db, SearchEntity::Check, check.id).await?;
+
+    format::empty_json()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/checks")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", get(get_one))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
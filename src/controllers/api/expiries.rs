@@ -0,0 +1,197 @@
+use loco_rs::prelude::*;
+use sea_orm::{PaginatorTrait, QueryOrder};
+
+use super::{
+    intervals::{ListPeriodsQuery, PeriodParams},
+    pagination, ApiQuery, ApiUser,
+};
+use crate::{
+    initializers::app_cache::refresh_expiries_cache,
+    models::{
+        _entities::{
+            expiries::Column as ExpiriesColumn,
+            inventory_item_kinds::Column as InventoryItemKindsColumn,
+        },
+        api_keys::ApiScope,
+        expiries, inventory_item_kinds,
+    },
+    views::api::{ErrorResponse, PageView, PeriodResponse},
+};
+
+async fn load_expiry(ctx: &AppContext, id: i32) -> Result<expiries::Model> {
+    expiries::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)
+}
+
+#[utoipa::path(
+    get,
+    path = "/expiries",
+    tag = "expiries",
+    params(ListPeriodsQuery),
+    responses(
+        (status = 200, body = PageView<PeriodResponse>),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(params): ApiQuery<ListPeriodsQuery>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        expiries::Entity::find().order_by_asc(ExpiriesColumn::Id),
+        None,
+        &pagination,
+    )
+    .await?;
+
+    format::json(PageView::new(page, &pagination, PeriodResponse::from))
+}
+
+#[utoipa::path(
+    get,
+    path = "/expiries/{id}",
+    tag = "expiries",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = PeriodResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    format::json(PeriodResponse::from(load_expiry(&ctx, id).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/expiries",
+    tag = "expiries",
+    request_body = PeriodParams,
+    responses(
+        (status = 200, body = PeriodResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<PeriodParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let input = params.validate()?;
+    let expiry = expiries::ActiveModel {
+        code: ActiveValue::set(input.code),
+        sqlite_modifier: ActiveValue::set(input.sqlite_modifier),
+        sqlite_num_of_modifier: ActiveValue::set(input.sqlite_num_of_modifier),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await?;
+    refresh_expiries_cache(&ctx).await?;
+
+    format::json(PeriodResponse::from(expiry))
+}
+
+#[utoipa::path(
+    put,
+    path = "/expiries/{id}",
+    tag = "expiries",
+    params(("id" = i32, Path)),
+    request_body = PeriodParams,
+    responses(
+        (status = 200, body = PeriodResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn update(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(params): Json<PeriodParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let existing = load_expiry(&ctx, id).await?;
+    let input = params.validate()?;
+
+    let mut expiry: expiries::ActiveModel = existing.into();
+    expiry.code = ActiveValue::set(input.code);
+    expiry.sqlite_modifier = ActiveValue::set(input.sqlite_modifier);
+    expiry.sqlite_num_of_modifier = ActiveValue::set(input.sqlite_num_of_modifier);
+    let expiry = expiry.update(&ctx.db).await?;
+    refresh_expiries_cache(&ctx).await?;
+
+    format::json(PeriodResponse::from(expiry))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/expiries/{id}",
+    tag = "expiries",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = Object),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let expiry = load_expiry(&ctx, id).await?;
+
+    let kinds_using = inventory_item_kinds::Entity::find()
+        .filter(InventoryItemKindsColumn::DefaultExpiryId.eq(id))
+        .count(&ctx.db)
+        .await?;
+    if kinds_using > 0 {
+        return Err(Error::BadRequest(
+            "Expiry is still used by item kinds".to_string(),
+        ));
+    }
+
+    expiry.delete(&ctx.db).await?;
+    refresh_expiries_cache(&ctx).await?;
+    format::empty_json()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/expiries")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", get(get_one))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
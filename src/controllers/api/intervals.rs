@@ -0,0 +1,250 @@
+use loco_rs::prelude::*;
+use sea_orm::{PaginatorTrait, QueryOrder};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{pagination, ApiQuery, ApiUser};
+use crate::{
+    exts::StringExt,
+    initializers::app_cache::refresh_intervals_cache,
+    models::{
+        _entities::{
+            intervals::Column as IntervalsColumn,
+            inventory_item_kinds::Column as InventoryItemKindsColumn,
+            inventory_items::Column as InventoryItemsColumn,
+        },
+        api_keys::ApiScope,
+        intervals, inventory_item_kinds, inventory_items,
+    },
+    views::api::{ErrorResponse, PageView, PeriodResponse},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListPeriodsQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+/// Request body shared by intervals and expiries, which both describe a
+/// period as a number of `days`, `months` or `years` (or `never`).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PeriodParams {
+    pub code: String,
+    /// One of `days`, `months`, `years` or `never`.
+    pub sqlite_modifier: String,
+    pub sqlite_num_of_modifier: i32,
+}
+
+pub(crate) struct PeriodInput {
+    pub(crate) code: String,
+    pub(crate) sqlite_modifier: String,
+    pub(crate) sqlite_num_of_modifier: i32,
+}
+
+impl PeriodParams {
+    pub(crate) fn validate(self) -> Result<PeriodInput> {
+        let Some(code) = self.code.clean() else {
+            return Err(Error::BadRequest("Code must not be empty".to_string()));
+        };
+        let sqlite_modifier = self.sqlite_modifier.trim().to_lowercase();
+        if !matches!(
+            sqlite_modifier.as_str(),
+            "days" | "months" | "years" | "never"
+        ) {
+            return Err(Error::BadRequest(
+                "Modifier must be one of days, months, years or never".to_string(),
+            ));
+        }
+
+        Ok(PeriodInput {
+            code,
+            sqlite_modifier,
+            sqlite_num_of_modifier: self.sqlite_num_of_modifier,
+        })
+    }
+}
+
+async fn load_interval(ctx: &AppContext, id: i32) -> Result<intervals::Model> {
+    intervals::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)
+}
+
+#[utoipa::path(
+    get,
+    path = "/intervals",
+    tag = "intervals",
+    params(ListPeriodsQuery),
+    responses(
+        (status = 200, body = PageView<PeriodResponse>),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(params): ApiQuery<ListPeriodsQuery>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        intervals::Entity::find().order_by_asc(IntervalsColumn::Id),
+        None,
+        &pagination,
+    )
+    .await?;
+
+    format::json(PageView::new(page, &pagination, PeriodResponse::from))
+}
+
+#[utoipa::path(
+    get,
+    path = "/intervals/{id}",
+    tag = "intervals",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = PeriodResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    format::json(PeriodResponse::from(load_interval(&ctx, id).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/intervals",
+    tag = "intervals",
+    request_body = PeriodParams,
+    responses(
+        (status = 200, body = PeriodResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<PeriodParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let input = params.validate()?;
+    let interval = intervals::ActiveModel {
+        code: ActiveValue::set(input.code),
+        sqlite_modifier: ActiveValue::set(input.sqlite_modifier),
+        sqlite_num_of_modifier: ActiveValue::set(input.sqlite_num_of_modifier),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await?;
+    refresh_intervals_cache(&ctx).await?;
+
+    format::json(PeriodResponse::from(interval))
+}
+
+#[utoipa::path(
+    put,
+    path = "/intervals/{id}",
+    tag = "intervals",
+    params(("id" = i32, Path)),
+    request_body = PeriodParams,
+    responses(
+        (status = 200, body = PeriodResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn update(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(params): Json<PeriodParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let existing = load_interval(&ctx, id).await?;
+    let input = params.validate()?;
+
+    let mut interval: intervals::ActiveModel = existing.into();
+    interval.code = ActiveValue::set(input.code);
+    interval.sqlite_modifier = ActiveValue::set(input.sqlite_modifier);
+    interval.sqlite_num_of_modifier = ActiveValue::set(input.sqlite_num_of_modifier);
+    let interval = interval.update(&ctx.db).await?;
+    inventory_items::Entity::refresh_next_due_at(&ctx.db, InventoryItemsColumn::IntervalId.eq(id))
+        .await?;
+    refresh_intervals_cache(&ctx).await?;
+
+    format::json(PeriodResponse::from(interval))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/intervals/{id}",
+    tag = "intervals",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = Object),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let interval = load_interval(&ctx, id).await?;
+
+    let items_using = inventory_items::Entity::find()
+        .filter(InventoryItemsColumn::IntervalId.eq(id))
+        .count(&ctx.db)
+        .await?;
+    let kinds_using = inventory_item_kinds::Entity::find()
+        .filter(InventoryItemKindsColumn::DefaultIntervalId.eq(id))
+        .count(&ctx.db)
+        .await?;
+    if items_using > 0 || kinds_using > 0 {
+        return Err(Error::BadRequest(
+            "Interval is still used by items or item kinds".to_string(),
+        ));
+    }
+
+    interval.delete(&ctx.db).await?;
+    refresh_intervals_cache(&ctx).await?;
+    format::empty_json()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/intervals")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", get(get_one))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
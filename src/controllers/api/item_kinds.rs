@@ -0,0 +1,230 @@
+use loco_rs::prelude::*;
+use sea_orm::{Condition, QueryOrder};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{pagination, ApiQuery, ApiUser};
+use crate::{
+    controllers::inventory::{
+        create_item_kind, delete_item_kind, load_item_kind, load_metadata_field_names_by_kind_ids,
+        update_item_kind, ItemKindInput,
+    },
+    exts::{OptionStringExt, StringExt},
+    initializers::app_cache::AppData,
+    models::{
+        _entities::inventory_item_kinds::Column as InventoryItemKindsColumn, api_keys::ApiScope,
+        inventory_item_kinds,
+    },
+    views::api::{ErrorResponse, ItemKindResponse, PageView},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListItemKindsQuery {
+    /// Matches the item kind name.
+    pub q: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ItemKindParams {
+    pub name: String,
+    #[serde(default)]
+    pub test_standard: String,
+    pub default_checklist_id: i32,
+    pub default_interval_id: i32,
+    pub default_expiry_id: i32,
+    #[serde(default)]
+    pub metadata_fields: Vec<String>,
+}
+
+impl ItemKindParams {
+    /// The default checklist is checked against the tenant when the kind is
+    /// saved.
+    fn into_input(self, ctx: &AppContext) -> Result<ItemKindInput> {
+        let Some(name) = self.name.clean() else {
+            return Err(Error::BadRequest(
+                "Item kind name must not be empty".to_string(),
+            ));
+        };
+        if !ctx.get_intervals()?.contains_key(&self.default_interval_id) {
+            return Err(Error::BadRequest("Unknown interval".to_string()));
+        }
+        if !ctx.get_expiries()?.contains_key(&self.default_expiry_id) {
+            return Err(Error::BadRequest("Unknown expiry".to_string()));
+        }
+
+        Ok(ItemKindInput {
+            name,
+            test_standard: self.test_standard,
+            default_checklist_id: self.default_checklist_id,
+            default_interval_id: self.default_interval_id,
+            default_expiry_id: self.default_expiry_id,
+            metadata_field_names: self.metadata_fields,
+        })
+    }
+}
+
+async fn item_kind_response(
+    ctx: &AppContext,
+    kind: inventory_item_kinds::Model,
+) -> Result<ItemKindResponse> {
+    let mut metadata_fields = load_metadata_field_names_by_kind_ids(&ctx.db, vec![kind.id]).await?;
+    let metadata_fields = metadata_fields.remove(&kind.id).unwrap_or_default();
+    Ok(ItemKindResponse::new(kind, metadata_fields))
+}
+
+#[utoipa::path(
+    get,
+    path = "/item_kinds",
+    tag = "item_kinds",
+    params(ListItemKindsQuery),
+    responses(
+        (status = 200, body = PageView<ItemKindResponse>),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(params): ApiQuery<ListItemKindsQuery>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let mut condition = Condition::all();
+    if let Some(q) = params.q.clean() {
+        condition = condition.add(InventoryItemKindsColumn::Name.contains(&q));
+    }
+
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        inventory_item_kinds::Entity::find_in_tenant(auth.tenant_id()?)
+            .order_by_asc(InventoryItemKindsColumn::Id),
+        Some(condition),
+        &pagination,
+    )
+    .await?;
+    let kind_ids = page.page.iter().map(|kind| kind.id).collect::<Vec<_>>();
+    let mut metadata_fields_by_kind_id =
+        load_metadata_field_names_by_kind_ids(&ctx.db, kind_ids).await?;
+
+    format::json(PageView::new(page, &pagination, |kind| {
+        let metadata_fields = metadata_fields_by_kind_id
+            .remove(&kind.id)
+            .unwrap_or_default();
+        ItemKindResponse::new(kind, metadata_fields)
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/item_kinds/{id}",
+    tag = "item_kinds",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = ItemKindResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let kind = load_item_kind(&ctx, auth.tenant_id()?, id).await?;
+    format::json(item_kind_response(&ctx, kind).await?)
+}
+
+#[utoipa::path(
+    post,
+    path = "/item_kinds",
+    tag = "item_kinds",
+    request_body = ItemKindParams,
+    responses(
+        (status = 200, body = ItemKindResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ItemKindParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let input = params.into_input(&ctx)?;
+    let kind = create_item_kind(&ctx, auth.tenant_id()?, input).await?;
+    format::json(item_kind_response(&ctx, kind).await?)
+}
+
+#[utoipa::path(
+    put,
+    path = "/item_kinds/{id}",
+    tag = "item_kinds",
+    params(("id" = i32, Path)),
+    request_body = ItemKindParams,
+    responses(
+        (status = 200, body = ItemKindResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn update(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(params): Json<ItemKindParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let existing = load_item_kind(&ctx, auth.tenant_id()?, id).await?;
+    let input = params.into_input(&ctx)?;
+    let kind = update_item_kind(&ctx, existing, input).await?;
+    format::json(item_kind_response(&ctx, kind).await?)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/item_kinds/{id}",
+    tag = "item_kinds",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = Object),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    delete_item_kind(&ctx, auth.tenant_id()?, id).await?;
+    format::empty_json()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/item_kinds")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", get(get_one))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
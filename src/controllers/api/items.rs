@@ -0,0 +1,321 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono_tz::Tz;
+use loco_rs::prelude::*;
+use sea_orm::{Condition, QueryOrder};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{pagination, ApiQuery, ApiUser};
+use crate::{
+    controllers::inventory::{
+        create_item, delete_item, load_metadata_fields_by_kind_ids,
+        load_metadata_value_maps_for_items, parse_expiry_date, update_item, ItemInput,
+    },
+    exts::{OptionStringExt, StringExt},
+    initializers::app_cache::AppData,
+    models::{
+        _entities::inventory_items::Column as InventoryItemsColumn, api_keys::ApiScope,
+        inventory_items,
+    },
+    timezone::RequestTimezone,
+    views::api::{ErrorResponse, ItemResponse, PageView},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListItemsQuery {
+    /// Matches name or serial number.
+    pub q: Option<String>,
+    pub serial_number: Option<String>,
+    pub kind_id: Option<i32>,
+    pub checklist_id: Option<i32>,
+    pub interval_id: Option<i32>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ItemParams {
+    pub name: String,
+    pub serial_number: Option<String>,
+    pub inventory_item_kind_id: i32,
+    /// Defaults to the item kind's default checklist.
+    pub checklist_id: Option<i32>,
+    /// Defaults to the item kind's default interval.
+    pub interval_id: Option<i32>,
+    /// Expiry date formatted as `YYYY-MM-DD`.
+    pub expiry: Option<String>,
+    /// Metadata values keyed by the item kind's metadata field names.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+impl ItemParams {
+    async fn into_input(self, ctx: &AppContext, tenant: i32, tz: Tz) -> Result<ItemInput> {
+        let Some(name) = self.name.clean() else {
+            return Err(Error::BadRequest("Item name must not be empty".to_string()));
+        };
+
+        let item_kinds = ctx.get_item_kinds(tenant)?;
+        let Some(kind) = item_kinds.get(&self.inventory_item_kind_id) else {
+            return Err(Error::BadRequest("Unknown item kind".to_string()));
+        };
+
+        let checklist_id = self.checklist_id.unwrap_or(kind.default_checklist_id);
+        if !ctx.get_checklists(tenant)?.contains_key(&checklist_id) {
+            return Err(Error::BadRequest("Unknown checklist".to_string()));
+        }
+
+        let interval_id = self.interval_id.unwrap_or(kind.default_interval_id);
+        if !ctx.get_intervals()?.contains_key(&interval_id) {
+            return Err(Error::BadRequest("Unknown interval".to_string()));
+        }
+
+        let metadata_fields = load_metadata_fields_by_kind_ids(&ctx.db, vec![kind.id])
+            .await?
+            .remove(&kind.id)
+            .unwrap_or_default();
+        let mut metadata_values_by_field_id = HashMap::new();
+        for (field_name, value) in self.metadata {
+            let Some(field) = metadata_fields
+                .iter()
+                .find(|field| field.name.eq_ignore_ascii_case(field_name.trim()))
+            else {
+                return Err(Error::BadRequest(format!(
+                    "Unknown metadata field {field_name} for item kind"
+                )));
+            };
+            metadata_values_by_field_id.insert(field.id, value);
+        }
+
+        Ok(ItemInput {
+            name,
+            serial_number: self.serial_number.clean(),
+            item_kind_id: kind.id,
+            checklist_id,
+            interval_id,
+            expiry: self
+                .expiry
+                .clean()
+                .map(|expiry| parse_expiry_date(&expiry, tz))
+                .transpose()?,
+            metadata_values_by_field_id,
+        })
+    }
+}
+
+async fn load_item(ctx: &AppContext, auth: &ApiUser, id: i32) -> Result<inventory_items::Model> {
+    inventory_items::Entity::find_by_id_in_tenant(auth.tenant_id()?, id)
+        .filter(
+            auth.item_scope(ctx)
+                .await?
+                .condition(inventory_items::Column::Id),
+        )
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)
+}
+
+async fn item_responses(
+    ctx: &AppContext,
+    items: Vec<inventory_items::Model>,
+) -> Result<Vec<ItemResponse>> {
+    let item_ids = items.iter().map(|item| item.id).collect::<Vec<_>>();
+    let mut metadata_by_item_id = load_metadata_value_maps_for_items(&ctx.db, &item_ids).await?;
+
+    Ok(items
+        .into_iter()
+        .map(|item| {
+            let metadata = metadata_by_item_id
+                .remove(&item.id)
+                .unwrap_or_default()
+                .into_iter()
+                .collect::<BTreeMap<_, _>>();
+            ItemResponse::new(item, metadata)
+        })
+        .collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/items",
+    tag = "items",
+    params(ListItemsQuery),
+    responses(
+        (status = 200, body = PageView<ItemResponse>),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(params): ApiQuery<ListItemsQuery>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let mut condition = Condition::all();
+    if let Some(q) = params.q.clean() {
+        condition = condition.add(
+            Condition::any()
+                .add(InventoryItemsColumn::Name.contains(&q))
+                .add(InventoryItemsColumn::SerialNumber.contains(&q)),
+        );
+    }
+    if let Some(serial_number) = params.serial_number.clean() {
+        condition = condition.add(InventoryItemsColumn::SerialNumber.eq(serial_number));
+    }
+    if let Some(kind_id) = params.kind_id {
+        condition = condition.add(InventoryItemsColumn::InventoryItemKindId.eq(kind_id));
+    }
+    if let Some(checklist_id) = params.checklist_id {
+        condition = condition.add(InventoryItemsColumn::ChecklistId.eq(checklist_id));
+    }
+    if let Some(interval_id) = params.interval_id {
+        condition = condition.add(InventoryItemsColumn::IntervalId.eq(interval_id));
+    }
+    condition = condition.add(
+        auth.item_scope(&ctx)
+            .await?
+            .condition(InventoryItemsColumn::Id),
+    );
+
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        inventory_items::Entity::find_in_tenant(auth.tenant_id()?)
+            .order_by_asc(InventoryItemsColumn::Id),
+        Some(condition),
+        &pagination,
+    )
+    .await?;
+    let total_pages = page.total_pages;
+    let total_items = page.total_items;
+    let items = item_responses(&ctx, page.page).await?;
+
+    format::json(PageView {
+        items,
+        page: pagination.page,
+        page_size: pagination.page_size,
+        total_pages,
+        total_items,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/items/{id}",
+    tag = "items",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = ItemResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let item = load_item(&ctx, &auth, id).await?;
+    let mut responses = item_responses(&ctx, vec![item]).await?;
+    format::json(responses.remove(0))
+}
+
+#[utoipa::path(
+    post,
+    path = "/items",
+    tag = "items",
+    request_body = ItemParams,
+    responses(
+        (status = 200, body = ItemResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    RequestTimezone(tz): RequestTimezone,
+    Json(params): Json<ItemParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let tenant = auth.tenant_id()?;
+    let input = params.into_input(&ctx, tenant, tz).await?;
+    let item = create_item(&ctx, tenant, input).await?;
+    let mut responses = item_responses(&ctx, vec![item]).await?;
+    format::json(responses.remove(0))
+}
+
+#[utoipa::path(
+    put,
+    path = "/items/{id}",
+    tag = "items",
+    params(("id" = i32, Path)),
+    request_body = ItemParams,
+    responses(
+        (status = 200, body = ItemResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn update(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    RequestTimezone(tz): RequestTimezone,
+    Path(id): Path<i32>,
+    Json(params): Json<ItemParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let existing = load_item(&ctx, &auth, id).await?;
+    let input = params.into_input(&ctx, existing.tenant_id, tz).await?;
+    let item = update_item(&ctx, existing, input).await?;
+    let mut responses = item_responses(&ctx, vec![item]).await?;
+    format::json(responses.remove(0))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/items/{id}",
+    tag = "items",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = Object),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    let item = load_item(&ctx, &auth, id).await?;
+    delete_item(&ctx, item).await?;
+    format::empty_json()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/items")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", get(get_one))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
@@ -0,0 +1,203 @@
+//! Versioned JSON API, mounted under [`PREFIX`].
+//!
+//! Every handler requires an [`ApiUser`], which accepts a JWT issued by
+//! `/api/auth/login` or a scoped key managed via [`api_keys`] as bearer
+//! token. The plain text `users.api_key` is not accepted. Requests work on
+//! the data of the user's current tenant, see [`tenants`]. Errors use the
+//! same `{"error": .., "description": ..}` body as the rest of the app. The
+//! `OpenAPI` document is served unauthenticated at [`openapi::SPEC_PATH`].
+
+use axum::{
+    extract::{FromRequestParts, Query as AxumQuery},
+    http::{request::Parts, StatusCode},
+};
+use loco_rs::{auth::jwt, controller::ErrorDetail, prelude::*};
+use serde::de::DeserializeOwned;
+
+use crate::models::{
+    api_keys::{ApiScope, Model as ApiKey, API_TOKEN_PREFIX},
+    teams::{self, ItemScope},
+    tenant_memberships,
+    tenants::{Entity as Tenants, Model as Tenant},
+    users,
+};
+
+pub mod api_keys;
+pub mod checklists;
+pub mod checks;
+pub mod expiries;
+pub mod intervals;
+pub mod item_kinds;
+pub mod items;
+pub mod notifications;
+pub mod openapi;
+pub mod responsibilities;
+pub mod results;
+pub mod search;
+pub mod tenants;
+
+pub const PREFIX: &str = "/api/v1";
+pub const DEFAULT_PAGE_SIZE: u64 = 25;
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+#[must_use]
+pub fn routes() -> Vec<Routes> {
+    vec![
+        api_keys::routes(),
+        checklists::routes(),
+        checks::routes(),
+        expiries::routes(),
+        intervals::routes(),
+        item_kinds::routes(),
+        items::routes(),
+        notifications::routes(),
+        openapi::routes(),
+        responsibilities::routes(),
+        results::routes(),
+        search::routes(),
+        tenants::routes(),
+    ]
+}
+
+/// The user authenticated for an API request.
+pub struct ApiUser {
+    pub user: users::Model,
+    /// The scoped key used for the request. JWTs carry no key and are
+    /// granted every scope.
+    pub api_key: Option<ApiKey>,
+    /// The tenant the user currently works in, `None` when they belong to
+    /// none.
+    pub tenant: Option<Tenant>,
+}
+
+impl ApiUser {
+    /// Rejects the request with `403 Forbidden` unless the credentials grant
+    /// `scope`.
+    pub fn require(&self, scope: ApiScope) -> Result<()> {
+        match &self.api_key {
+            Some(key) if !key.has_scope(scope) => Err(forbidden(&format!(
+                "API key lacks the {} scope",
+                scope.as_str()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// The items of the current tenant the user may see and change: only
+    /// those of their teams if they are in a scoped team of the tenant.
+    /// Others are answered with `404 Not Found`.
+    pub async fn item_scope(&self, ctx: &AppContext) -> Result<ItemScope> {
+        Ok(teams::Entity::item_scope_for_user(&ctx.db, self.tenant_id()?, self.user.id).await?)
+    }
+
+    /// Id of the tenant whose data the request reads and changes. Users
+    /// without a tenant are answered with `403 Forbidden`.
+    pub fn tenant_id(&self) -> Result<i32> {
+        self.tenant
+            .as_ref()
+            .map(|tenant| tenant.id)
+            .ok_or_else(|| forbidden("user belongs to no tenant"))
+    }
+
+    /// Rejects the request with `403 Forbidden` unless the user is a global
+    /// admin, e.g. for the lookup tables shared by all tenants.
+    pub async fn require_global_admin(&self, ctx: &AppContext) -> Result<()> {
+        if tenant_memberships::Entity::is_global_admin(&ctx.db, self.user.id).await? {
+            Ok(())
+        } else {
+            Err(forbidden("only global admins may do this"))
+        }
+    }
+
+    /// Rejects requests made with a scoped API key, e.g. for managing keys.
+    pub fn require_session(&self) -> Result<()> {
+        if self.api_key.is_some() {
+            return Err(forbidden("API keys cannot be used for this endpoint"));
+        }
+        Ok(())
+    }
+}
+
+fn forbidden(description: &str) -> Error {
+    Error::CustomError(
+        StatusCode::FORBIDDEN,
+        ErrorDetail::new("forbidden", description),
+    )
+}
+
+impl FromRequestParts<AppContext> for ApiUser {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, ctx: &AppContext) -> Result<Self> {
+        let token = auth::extract_token_from_header(&parts.headers)?;
+
+        let found = if token.starts_with(API_TOKEN_PREFIX) {
+            find_by_scoped_key(&ctx.db, &token)
+                .await
+                .map(|(user, key)| (user, Some(key)))
+        } else {
+            let jwt_config = ctx.config.get_jwt_config()?;
+            let claims = jwt::JWT::new(&jwt_config.secret)
+                .validate(&token)
+                .map_err(|_| Error::Unauthorized("token is not valid".to_string()))?;
+            users::Model::find_by_pid(&ctx.db, &claims.claims.pid)
+                .await
+                .map(|user| (user, None))
+        };
+
+        let (user, api_key) = found.map_err(|err| match err {
+            ModelError::DbErr(db_err) => Error::DB(db_err),
+            _ => Error::Unauthorized("could not authorize".to_string()),
+        })?;
+
+        let tenant = Tenants::current_for_user(&ctx.db, &user).await?;
+        Ok(Self {
+            user,
+            api_key,
+            tenant,
+        })
+    }
+}
+
+async fn find_by_scoped_key(
+    db: &DatabaseConnection,
+    token: &str,
+) -> ModelResult<(users::Model, ApiKey)> {
+    let key = ApiKey::find_active_by_token(db, token).await?;
+    let user = users::Entity::find_by_id(key.user_id)
+        .one(db)
+        .await?
+        .ok_or(ModelError::EntityNotFound)?;
+    key.touch_last_used(db).await?;
+    Ok((user, key))
+}
+
+/// Query string extractor that reports malformed parameters as a JSON
+/// `400 Bad Request` instead of axum's plain-text rejection.
+pub struct ApiQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self> {
+        let AxumQuery(value) = AxumQuery::<T>::try_from_uri(&parts.uri)
+            .map_err(|rejection| Error::BadRequest(rejection.body_text()))?;
+        Ok(Self(value))
+    }
+}
+
+/// Builds a [`query::PaginationQuery`] from optional `page` and `page_size`
+/// parameters, clamping the page size to [`MAX_PAGE_SIZE`].
+#[must_use]
+pub fn pagination(page: Option<u64>, page_size: Option<u64>) -> query::PaginationQuery {
+    query::PaginationQuery {
+        page: page.unwrap_or(1).max(1),
+        page_size: page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE),
+    }
+}
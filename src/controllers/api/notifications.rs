@@ -0,0 +1,278 @@
+use loco_rs::prelude::*;
+use sea_orm::QueryOrder;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{pagination, ApiQuery, ApiUser};
+use crate::{
+    exts::OptionStringExt,
+    models::{
+        inventory_item_kinds, inventory_items,
+        notification_subscriptions::{
+            self, Column as NotificationSubscriptionsColumn, NotificationChannel,
+            NotificationFrequency, NotificationLevel,
+        },
+        team_memberships, teams, webhook_endpoints,
+    },
+    views::api::{ErrorResponse, NotificationSubscriptionResponse, PageView},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListSubscriptionsQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+/// What to be notified about and how. Without kind, location and item, the
+/// subscription covers every item.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubscriptionParams {
+    pub inventory_item_kind_id: Option<i32>,
+    /// Matches the `Location` metadata case-insensitively by substring.
+    pub location: Option<String>,
+    pub inventory_item_id: Option<i32>,
+    pub min_urgency: NotificationLevel,
+    pub channel: NotificationChannel,
+    /// Required for the webhook channel.
+    pub webhook_endpoint_id: Option<i32>,
+    pub frequency: NotificationFrequency,
+    /// Start of the quiet period, an hour from 0 to 23 in the user's time
+    /// zone. Notices are held back until it ends.
+    pub quiet_start_hour: Option<i16>,
+    /// End of the quiet period, exclusive.
+    pub quiet_end_hour: Option<i16>,
+    /// Only items the user is responsible for: as owner, or as deputy while
+    /// the owner is absent.
+    #[serde(default)]
+    pub responsible_only: bool,
+    /// Sends the notices to every present member of this team of the user,
+    /// and limits `responsible_only` to the items of the team.
+    pub team_id: Option<i32>,
+}
+
+impl SubscriptionParams {
+    /// The item kind and item must be in the user's current tenant.
+    async fn into_active_model(
+        self,
+        ctx: &AppContext,
+        auth: &ApiUser,
+        mut subscription: notification_subscriptions::ActiveModel,
+    ) -> Result<notification_subscriptions::ActiveModel> {
+        let user_id = auth.user.id;
+        let tenant_id = auth.tenant_id()?;
+        if let Some(kind_id) = self.inventory_item_kind_id {
+            inventory_item_kinds::Entity::find_by_id_in_tenant(tenant_id, kind_id)
+                .one(&ctx.db)
+                .await?
+                .ok_or_else(|| Error::BadRequest("Unknown item kind".to_string()))?;
+        }
+        if let Some(item_id) = self.inventory_item_id {
+            inventory_items::Entity::find_by_id_in_tenant(tenant_id, item_id)
+                .one(&ctx.db)
+                .await?
+                .ok_or_else(|| Error::BadRequest("Unknown item".to_string()))?;
+        }
+        if let Some(team_id) = self.team_id {
+            teams::Entity::find_by_id_in_tenant(tenant_id, team_id)
+                .one(&ctx.db)
+                .await?
+                .ok_or_else(|| Error::BadRequest("Unknown team".to_string()))?;
+            team_memberships::Entity::find()
+                .filter(team_memberships::Column::TeamId.eq(team_id))
+                .filter(team_memberships::Column::UserId.eq(user_id))
+                .one(&ctx.db)
+                .await?
+                .ok_or_else(|| Error::BadRequest("Not a member of this team".to_string()))?;
+        }
+        let webhook_endpoint_id = match self.channel {
+            NotificationChannel::Email => None,
+            NotificationChannel::Webhook => {
+                let endpoint_id = self.webhook_endpoint_id.ok_or_else(|| {
+                    Error::BadRequest("The webhook channel needs an endpoint".to_string())
+                })?;
+                webhook_endpoints::Entity::find_by_id_in_tenant(tenant_id, endpoint_id)
+                    .one(&ctx.db)
+                    .await?
+                    .ok_or_else(|| Error::BadRequest("Unknown webhook endpoint".to_string()))?;
+                Some(endpoint_id)
+            }
+        };
+        let quiet_hours = match (self.quiet_start_hour, self.quiet_end_hour) {
+            (None, None) => (None, None),
+            (Some(start), Some(end)) if (0..24).contains(&start) && (0..24).contains(&end) => {
+                (Some(start), Some(end))
+            }
+            _ => {
+                return Err(Error::BadRequest(
+                    "A quiet period needs a start and an end hour from 0 to 23".to_string(),
+                ))
+            }
+        };
+
+        subscription.inventory_item_kind_id = ActiveValue::set(self.inventory_item_kind_id);
+        subscription.location = ActiveValue::set(self.location.clean());
+        subscription.inventory_item_id = ActiveValue::set(self.inventory_item_id);
+        subscription.min_urgency = ActiveValue::set(self.min_urgency.as_str().to_string());
+        subscription.channel = ActiveValue::set(self.channel.as_str().to_string());
+        subscription.webhook_endpoint_id = ActiveValue::set(webhook_endpoint_id);
+        subscription.frequency = ActiveValue::set(self.frequency.as_str().to_string());
+        subscription.quiet_start_hour = ActiveValue::set(quiet_hours.0);
+        subscription.quiet_end_hour = ActiveValue::set(quiet_hours.1);
+        subscription.responsible_only = ActiveValue::set(self.responsible_only);
+        subscription.team_id = ActiveValue::set(self.team_id);
+        Ok(subscription)
+    }
+}
+
+async fn load_own_subscription(
+    ctx: &AppContext,
+    auth: &ApiUser,
+    id: i32,
+) -> Result<notification_subscriptions::Model> {
+    notification_subscriptions::Entity::find_by_id(id)
+        .filter(NotificationSubscriptionsColumn::UserId.eq(auth.user.id))
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)
+}
+
+#[utoipa::path(
+    get,
+    path = "/notifications/subscriptions",
+    tag = "notifications",
+    params(ListSubscriptionsQuery),
+    responses(
+        (status = 200, body = PageView<NotificationSubscriptionResponse>),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(params): ApiQuery<ListSubscriptionsQuery>,
+) -> Result<Response> {
+    auth.require_session()?;
+
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        notification_subscriptions::Entity::find()
+            .order_by_asc(NotificationSubscriptionsColumn::Id),
+        Some(
+            query::condition()
+                .eq(NotificationSubscriptionsColumn::UserId, auth.user.id)
+                .build(),
+        ),
+        &pagination,
+    )
+    .await?;
+
+    format::json(PageView::new(
+        page,
+        &pagination,
+        NotificationSubscriptionResponse::from,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/notifications/subscriptions",
+    tag = "notifications",
+    request_body = SubscriptionParams,
+    responses(
+        (status = 200, body = NotificationSubscriptionResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<SubscriptionParams>,
+) -> Result<Response> {
+    auth.require_session()?;
+
+    let subscription = notification_subscriptions::ActiveModel {
+        user_id: ActiveValue::set(auth.user.id),
+        ..Default::default()
+    };
+    let subscription = params
+        .into_active_model(&ctx, &auth, subscription)
+        .await?
+        .insert(&ctx.db)
+        .await?;
+    format::json(NotificationSubscriptionResponse::from(subscription))
+}
+
+#[utoipa::path(
+    put,
+    path = "/notifications/subscriptions/{id}",
+    tag = "notifications",
+    params(("id" = i32, Path)),
+    request_body = SubscriptionParams,
+    responses(
+        (status = 200, body = NotificationSubscriptionResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn update(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(params): Json<SubscriptionParams>,
+) -> Result<Response> {
+    auth.require_session()?;
+
+    let subscription = load_own_subscription(&ctx, &auth, id)
+        .await?
+        .into_active_model();
+    let subscription = params
+        .into_active_model(&ctx, &auth, subscription)
+        .await?
+        .update(&ctx.db)
+        .await?;
+    format::json(NotificationSubscriptionResponse::from(subscription))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/notifications/subscriptions/{id}",
+    tag = "notifications",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = Object),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require_session()?;
+
+    let subscription = load_own_subscription(&ctx, &auth, id).await?;
+    subscription.delete(&ctx.db).await?;
+    format::empty_json()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/notifications/subscriptions")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
@@ -0,0 +1,109 @@
+use loco_rs::prelude::*;
+use utoipa::{
+    openapi::security::{Http, HttpAuthScheme, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use super::{
+    api_keys, checklists, checks, expiries, intervals, item_kinds, items, notifications,
+    responsibilities, results, search, tenants,
+};
+
+/// Path of the generated document, relative to [`super::PREFIX`].
+pub const SPEC_PATH: &str = "/openapi.json";
+
+/// `OpenAPI` 3 description of the JSON API, generated from the request and
+/// response types and the `#[utoipa::path]` annotations on each handler.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Bestbefors API", version = "1"),
+    servers((url = "/api/v1")),
+    security(("bearer" = [])),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "api_keys"),
+        (name = "checklists"),
+        (name = "checks"),
+        (name = "expiries"),
+        (name = "intervals"),
+        (name = "item_kinds"),
+        (name = "items"),
+        (name = "notifications"),
+        (name = "responsibilities"),
+        (name = "results"),
+        (name = "search"),
+        (name = "tenants"),
+    ),
+    paths(
+        api_keys::list,
+        api_keys::add,
+        api_keys::revoke,
+        checklists::list,
+        checklists::get_one,
+        checklists::add,
+        checklists::update,
+        checklists::remove,
+        checks::list,
+        checks::get_one,
+        checks::add,
+        checks::update,
+        checks::remove,
+        expiries::list,
+        expiries::get_one,
+        expiries::add,
+        expiries::update,
+        expiries::remove,
+        intervals::list,
+        intervals::get_one,
+        intervals::add,
+        intervals::update,
+        intervals::remove,
+        item_kinds::list,
+        item_kinds::get_one,
+        item_kinds::add,
+        item_kinds::update,
+        item_kinds::remove,
+        items::list,
+        items::get_one,
+        items::add,
+        items::update,
+        items::remove,
+        notifications::list,
+        notifications::add,
+        notifications::update,
+        notifications::remove,
+        responsibilities::mine,
+        results::list,
+        results::get_one,
+        results::add,
+        results::update,
+        results::remove,
+        search::find,
+        tenants::list,
+        tenants::add,
+        tenants::add_member,
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let mut scheme = Http::new(HttpAuthScheme::Bearer);
+        scheme.description = Some("A JWT from `/api/auth/login` or an API key".to_string());
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme("bearer", SecurityScheme::Http(scheme));
+    }
+}
+
+#[debug_handler]
+pub async fn spec() -> Result<Response> {
+    format::json(ApiDoc::openapi())
+}
+
+pub fn routes() -> Routes {
+    Routes::new().add(SPEC_PATH, get(spec))
+}
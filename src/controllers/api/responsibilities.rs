@@ -0,0 +1,52 @@
+use loco_rs::prelude::*;
+use sea_orm::{QueryOrder, QuerySelect};
+
+use super::ApiUser;
+use crate::{
+    models::{
+        api_keys::ApiScope,
+        inventory_items,
+        responsibilities::{self, Role},
+    },
+    views::api::{ErrorResponse, MyItemsResponse},
+};
+
+/// Items of the current tenant the user or one of their teams owns or
+/// deputizes, directly or through the item's kind.
+#[utoipa::path(
+    get,
+    path = "/responsibilities/mine",
+    tag = "responsibilities",
+    responses(
+        (status = 200, body = MyItemsResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn mine(auth: ApiUser, State(ctx): State<AppContext>) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let tenant_id = auth.tenant_id()?;
+    let items_in_role = |role: Role| {
+        inventory_items::Entity::find_in_tenant(tenant_id)
+            .select_only()
+            .column(inventory_items::Column::Id)
+            .filter(inventory_items::Column::Id.in_subquery(
+                responsibilities::Entity::items_held_by(auth.user.id, &[role]),
+            ))
+            .order_by_asc(inventory_items::Column::Id)
+            .into_tuple::<i32>()
+            .all(&ctx.db)
+    };
+    let response = MyItemsResponse {
+        owner_of: items_in_role(Role::Owner).await?,
+        deputy_of: items_in_role(Role::Deputy).await?,
+    };
+    format::json(response)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/responsibilities")
+        .add("/mine", get(mine))
+}
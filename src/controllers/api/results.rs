@@ -0,0 +1,215 @@
+use loco_rs::prelude::*;
+use sea_orm::{PaginatorTrait, QueryOrder};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{pagination, ApiQuery, ApiUser};
+use crate::{
+    exts::StringExt,
+    initializers::app_cache::refresh_results_cache,
+    models::{
+        _entities::{
+            inventory_item_check_steps::Column as InventoryItemCheckStepsColumn,
+            inventory_item_checks::Column as InventoryItemChecksColumn,
+            results::Column as ResultsColumn,
+        },
+        api_keys::ApiScope,
+        inventory_item_check_steps, inventory_item_checks, results,
+    },
+    views::api::{ErrorResponse, PageView, ResultResponse},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListResultsQuery {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResultParams {
+    pub code: String,
+}
+
+async fn load_result(ctx: &AppContext, id: i32) -> Result<results::Model> {
+    results::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)
+}
+
+fn clean_code(params: ResultParams) -> Result<String> {
+    params
+        .code
+        .clean()
+        .ok_or_else(|| Error::BadRequest("Code must not be empty".to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/results",
+    tag = "results",
+    params(ListResultsQuery),
+    responses(
+        (status = 200, body = PageView<ResultResponse>),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(params): ApiQuery<ListResultsQuery>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let pagination = pagination(params.page, params.page_size);
+    let page = query::paginate(
+        &ctx.db,
+        results::Entity::find().order_by_asc(ResultsColumn::Id),
+        None,
+        &pagination,
+    )
+    .await?;
+
+    format::json(PageView::new(page, &pagination, ResultResponse::from))
+}
+
+#[utoipa::path(
+    get,
+    path = "/results/{id}",
+    tag = "results",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = ResultResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn get_one(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    format::json(ResultResponse::from(load_result(&ctx, id).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/results",
+    tag = "results",
+    request_body = ResultParams,
+    responses(
+        (status = 200, body = ResultResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<ResultParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let result = results::ActiveModel {
+        code: ActiveValue::set(clean_code(params)?),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await?;
+    refresh_results_cache(&ctx).await?;
+
+    format::json(ResultResponse::from(result))
+}
+
+#[utoipa::path(
+    put,
+    path = "/results/{id}",
+    tag = "results",
+    params(("id" = i32, Path)),
+    request_body = ResultParams,
+    responses(
+        (status = 200, body = ResultResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn update(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(params): Json<ResultParams>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let existing = load_result(&ctx, id).await?;
+
+    let mut result: results::ActiveModel = existing.into();
+    result.code = ActiveValue::set(clean_code(params)?);
+    let result = result.update(&ctx.db).await?;
+    refresh_results_cache(&ctx).await?;
+
+    format::json(ResultResponse::from(result))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/results/{id}",
+    tag = "results",
+    params(("id" = i32, Path)),
+    responses(
+        (status = 200, body = Object),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn remove(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    auth.require(ApiScope::Write)?;
+    auth.require_global_admin(&ctx).await?;
+    let result = load_result(&ctx, id).await?;
+
+    let checks_using = inventory_item_checks::Entity::find()
+        .filter(InventoryItemChecksColumn::ResultId.eq(id))
+        .count(&ctx.db)
+        .await?;
+    let steps_using = inventory_item_check_steps::Entity::find()
+        .filter(InventoryItemCheckStepsColumn::ResultId.eq(id))
+        .count(&ctx.db)
+        .await?;
+    if checks_using > 0 || steps_using > 0 {
+        return Err(Error::BadRequest(
+            "Result is still used by recorded checks".to_string(),
+        ));
+    }
+
+    result.delete(&ctx.db).await?;
+    refresh_results_cache(&ctx).await?;
+    format::empty_json()
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/results")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}", get(get_one))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
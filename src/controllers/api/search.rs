@@ -0,0 +1,48 @@
+use loco_rs::prelude::*;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use super::{ApiQuery, ApiUser};
+use crate::{
+    models::api_keys::ApiScope,
+    search,
+    views::api::{ErrorResponse, SearchResponse},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Words to find in item names, serial numbers, kinds, metadata values
+    /// and check notes. Each may be the start of a longer word.
+    pub q: String,
+}
+
+/// Full-text search over the items and checks of the current tenant,
+/// ranked and highlighted, at most 20 hits per entity type.
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "search",
+    params(SearchQuery),
+    responses(
+        (status = 200, body = SearchResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn find(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    ApiQuery(query): ApiQuery<SearchQuery>,
+) -> Result<Response> {
+    auth.require(ApiScope::Read)?;
+    let scope = auth.item_scope(&ctx).await?;
+    let results = search::search(&ctx.db, auth.tenant_id()?, &query.q, &scope).await?;
+    format::json(SearchResponse::from(results))
+}
+
+pub fn routes() -> Routes {
+    Routes::new().prefix("/search").add("/", get(find))
+}
@@ -0,0 +1,155 @@
+use loco_rs::prelude::*;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::{forbidden, ApiUser};
+use crate::{
+    exts::StringExt,
+    initializers::app_cache::{refresh_tenants_cache, refresh_users_cache, AppData},
+    models::{tenant_memberships, tenants, users},
+    views::api::{ErrorResponse, TenantResponse},
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTenantParams {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddMemberParams {
+    /// Email address of a registered user.
+    pub email: String,
+}
+
+fn tenant_response(
+    ctx: &AppContext,
+    auth: &ApiUser,
+    tenant: tenants::Model,
+) -> Result<TenantResponse> {
+    let mut members = ctx
+        .get_users(tenant.id)?
+        .into_values()
+        .map(|user| user.name)
+        .collect::<Vec<_>>();
+    members.sort();
+    Ok(TenantResponse {
+        current: auth
+            .tenant
+            .as_ref()
+            .is_some_and(|current| current.id == tenant.id),
+        id: tenant.id,
+        name: tenant.name,
+        members,
+    })
+}
+
+/// The tenants the current user belongs to, by name.
+#[utoipa::path(
+    get,
+    path = "/tenants",
+    tag = "tenants",
+    responses(
+        (status = 200, body = Vec<TenantResponse>),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn list(auth: ApiUser, State(ctx): State<AppContext>) -> Result<Response> {
+    auth.require_session()?;
+    let tenants = tenants::Entity::for_user(&ctx.db, auth.user.id)
+        .await?
+        .into_iter()
+        .map(|tenant| tenant_response(&ctx, &auth, tenant))
+        .collect::<Result<Vec<_>>>()?;
+    format::json(tenants)
+}
+
+/// Creates a tenant with the current user as its first member. Switch to it
+/// with `POST /api/auth/tenant`.
+#[utoipa::path(
+    post,
+    path = "/tenants",
+    tag = "tenants",
+    request_body = CreateTenantParams,
+    responses(
+        (status = 200, body = TenantResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Json(params): Json<CreateTenantParams>,
+) -> Result<Response> {
+    auth.require_session()?;
+    let Some(name) = params.name.clean() else {
+        return Err(Error::BadRequest(
+            "Tenant name must not be empty".to_string(),
+        ));
+    };
+    if tenants::Entity::find()
+        .filter(tenants::Column::Name.eq(&name))
+        .one(&ctx.db)
+        .await?
+        .is_some()
+    {
+        return Err(Error::BadRequest(format!("Tenant {name} already exists")));
+    }
+
+    let tenant = tenants::Entity::create_with_member(&ctx.db, &name, auth.user.id).await?;
+    refresh_tenants_cache(&ctx).await?;
+    refresh_users_cache(&ctx).await?;
+    format::json(tenant_response(&ctx, &auth, tenant)?)
+}
+
+/// Adds a registered user to a tenant the current user administers.
+#[utoipa::path(
+    post,
+    path = "/tenants/{id}/members",
+    tag = "tenants",
+    params(("id" = i32, Path)),
+    request_body = AddMemberParams,
+    responses(
+        (status = 200, body = TenantResponse),
+        (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+)]
+#[debug_handler]
+pub async fn add_member(
+    auth: ApiUser,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(params): Json<AddMemberParams>,
+) -> Result<Response> {
+    auth.require_session()?;
+    let tenant = tenants::Entity::for_user(&ctx.db, auth.user.id)
+        .await?
+        .into_iter()
+        .find(|tenant| tenant.id == id)
+        .ok_or(Error::NotFound)?;
+    if !tenant_memberships::Entity::is_admin(&ctx.db, tenant.id, auth.user.id).await? {
+        return Err(forbidden("only tenant admins may add members"));
+    }
+    let Ok(user) = users::Model::find_by_email(&ctx.db, params.email.trim()).await else {
+        return Err(Error::BadRequest("Unknown user".to_string()));
+    };
+
+    tenant_memberships::Entity::add(&ctx.db, tenant.id, user.id).await?;
+    refresh_users_cache(&ctx).await?;
+    format::json(tenant_response(&ctx, &auth, tenant)?)
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("/tenants")
+        .add("/", get(list))
+        .add("/", post(add))
+        .add("/{id}/members", post(add_member))
+}
@@ -0,0 +1,20 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use loco_rs::prelude::*;
+
+use crate::models::api_keys::ApiScope;
+
+/// Renders the key management page. Keys are loaded and changed client-side
+/// through `/api/v1/api_keys` using the token stored at login.
+#[debug_handler]
+pub async fn list(ViewEngine(v): ViewEngine<TeraView>) -> Result<Response> {
+    format::render().view(
+        &v,
+        "api_keys/list.html",
+        data!({ "scopes": ApiScope::ALL.map(ApiScope::as_str) }),
+    )
+}
+
+pub fn routes() -> Routes {
+    Routes::new().prefix("api_keys/").add("list", get(list))
+}
@@ -1,12 +1,16 @@
 use crate::{
+    i18n,
     initializers::app_cache::refresh_users_cache,
     mailers::auth::AuthMailer,
     models::{
         _entities::users,
+        tenant_memberships, tenants,
         users::{LoginParams, RegisterParams},
     },
-    views::auth::{CurrentResponse, LoginResponse},
+    session, timezone,
+    views::auth::{AbsenceResponse, CalendarResponse, CurrentResponse, LoginResponse},
 };
+use axum::http::header;
 use loco_rs::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -31,6 +35,27 @@ pub struct ResetParams {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LocaleParams {
+    pub locale: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TimezoneParams {
+    pub timezone: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TenantParams {
+    pub tenant_id: i32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AbsenceParams {
+    /// Last day of the absence, `None` when back.
+    pub absent_until: Option<chrono::NaiveDate>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MagicLinkParams {
     pub email: String,
@@ -135,6 +160,27 @@ async fn reset(State(ctx): State<AppContext>, Json(params): Json<ResetParams>) -
     format::json(())
 }
 
+/// Login response that also starts the session of the HTML pages in the
+/// user's current tenant and restores their preferred language and time
+/// zone in the cookies used by them.
+async fn login_response(ctx: &AppContext, user: &users::Model, token: &str) -> Result<Response> {
+    let mut response = format::render();
+    if let Some(locale) = user.locale.as_deref().and_then(i18n::match_locale) {
+        response = response.header(header::SET_COOKIE, i18n::lang_cookie(&locale));
+    }
+    if let Some(tz) = user.timezone.as_deref().and_then(timezone::parse_timezone) {
+        response = response.header(header::SET_COOKIE, timezone::timezone_cookie(tz));
+    }
+    if let Some(current) = tenants::Entity::current_for_user(&ctx.db, user).await? {
+        let jwt = ctx.config.get_jwt_config()?;
+        response = response.header(
+            header::SET_COOKIE,
+            session::session_cookie(&jwt.secret, user, current.id, jwt.expiration),
+        );
+    }
+    response.json(LoginResponse::new(user, token))
+}
+
 /// Creates a user login and returns a token
 #[debug_handler]
 async fn login(State(ctx): State<AppContext>, Json(params): Json<LoginParams>) -> Result<Response> {
@@ -158,7 +204,7 @@ async fn login(State(ctx): State<AppContext>, Json(params): Json<LoginParams>) -
         .generate_jwt(&jwt_secret.secret, jwt_secret.expiration)
         .or_else(|_| unauthorized("unauthorized!"))?;
 
-    format::json(LoginResponse::new(&user, &token))
+    login_response(&ctx, &user, &token).await
 }
 
 #[debug_handler]
@@ -167,6 +213,133 @@ async fn current(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Respo
     format::json(CurrentResponse::new(&user))
 }
 
+/// Stores the preferred language of the current user and remembers it in the
+/// language cookie.
+#[debug_handler]
+async fn set_locale(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Json(params): Json<LocaleParams>,
+) -> Result<Response> {
+    let Some(locale) = i18n::match_locale(&params.locale) else {
+        return bad_request(format!("unsupported locale: {}", params.locale));
+    };
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    let user = user
+        .into_active_model()
+        .set_locale(&ctx.db, &locale.to_string())
+        .await?;
+    format::render()
+        .header(header::SET_COOKIE, i18n::lang_cookie(&locale))
+        .json(CurrentResponse::new(&user))
+}
+
+/// Stores the time zone of the current user, used to enter and show dates,
+/// and remembers it in the time zone cookie.
+#[debug_handler]
+async fn set_timezone(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Json(params): Json<TimezoneParams>,
+) -> Result<Response> {
+    let Some(tz) = timezone::parse_timezone(&params.timezone) else {
+        return bad_request(format!("unknown time zone: {}", params.timezone));
+    };
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    let user = user
+        .into_active_model()
+        .set_timezone(&ctx.db, tz.name())
+        .await?;
+    format::render()
+        .header(header::SET_COOKIE, timezone::timezone_cookie(tz))
+        .json(CurrentResponse::new(&user))
+}
+
+/// Switches the current user to another of their tenants and signs the
+/// session of the HTML pages into it.
+#[debug_handler]
+async fn set_tenant(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Json(params): Json<TenantParams>,
+) -> Result<Response> {
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    if !tenant_memberships::Entity::is_member(&ctx.db, params.tenant_id, user.id).await? {
+        return bad_request(format!("not a member of tenant {}", params.tenant_id));
+    }
+    let user = user
+        .into_active_model()
+        .set_current_tenant(&ctx.db, params.tenant_id)
+        .await?;
+    let jwt = ctx.config.get_jwt_config()?;
+    format::render()
+        .header(
+            header::SET_COOKIE,
+            session::session_cookie(&jwt.secret, &user, params.tenant_id, jwt.expiration),
+        )
+        .json(CurrentResponse::new(&user))
+}
+
+/// Ends the session of the HTML pages. The JWT stays valid until it expires.
+#[debug_handler]
+async fn logout() -> Result<Response> {
+    format::render()
+        .header(header::SET_COOKIE, session::clear_session_cookie())
+        .json(())
+}
+
+/// Returns the last day the current user is away, if any.
+#[debug_handler]
+async fn absence(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    format::json(AbsenceResponse::new(&user))
+}
+
+/// Stores until when the current user is away. Meanwhile reminders for the
+/// items they own go to the deputies.
+#[debug_handler]
+async fn set_absence(
+    auth: auth::JWT,
+    State(ctx): State<AppContext>,
+    Json(params): Json<AbsenceParams>,
+) -> Result<Response> {
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    let user = user
+        .into_active_model()
+        .set_absent_until(&ctx.db, params.absent_until)
+        .await?;
+    refresh_users_cache(&ctx).await?;
+    format::json(AbsenceResponse::new(&user))
+}
+
+/// Returns whether the current user created a calendar feed. Its URL is only
+/// returned by [`reset_calendar`].
+#[debug_handler]
+async fn calendar(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    format::json(CalendarResponse::new(
+        &user,
+        &ctx.config.server.full_url(),
+        None,
+    ))
+}
+
+/// Creates a new calendar feed URL for the current user. Subscriptions to the
+/// previous URL stop working.
+#[debug_handler]
+async fn reset_calendar(auth: auth::JWT, State(ctx): State<AppContext>) -> Result<Response> {
+    let user = users::Model::find_by_pid(&ctx.db, &auth.claims.pid).await?;
+    let (user, token) = user
+        .into_active_model()
+        .reset_calendar_token(&ctx.db)
+        .await?;
+    format::json(CalendarResponse::new(
+        &user,
+        &ctx.config.server.full_url(),
+        Some(&token),
+    ))
+}
+
 /// Magic link authentication provides a secure and passwordless way to log in to the application.
 ///
 /// # Flow
@@ -228,7 +401,7 @@ async fn magic_link_verify(
         .generate_jwt(&jwt_secret.secret, jwt_secret.expiration)
         .or_else(|_| unauthorized("unauthorized!"))?;
 
-    format::json(LoginResponse::new(&user, &token))
+    login_response(&ctx, &user, &token).await
 }
 
 #[debug_handler]
@@ -270,9 +443,17 @@ pub fn routes() -> Routes {
         .add("/register", post(register))
         .add("/verify/{token}", get(verify))
         .add("/login", post(login))
+        .add("/logout", post(logout))
         .add("/forgot", post(forgot))
         .add("/reset", post(reset))
         .add("/current", get(current))
+        .add("/locale", post(set_locale))
+        .add("/timezone", post(set_timezone))
+        .add("/tenant", post(set_tenant))
+        .add("/absence", get(absence))
+        .add("/absence", post(set_absence))
+        .add("/calendar", get(calendar))
+        .add("/calendar", post(reset_calendar))
         .add("/magic-link", post(magic_link))
         .add("/magic-link/{token}", get(magic_link_verify))
         .add("/resend-verification-mail", post(resend_verification_email))
@@ -0,0 +1,176 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum::http::{header, HeaderValue};
+use chrono::Utc;
+use loco_rs::prelude::*;
+use sea_orm::QueryOrder;
+use serde::Deserialize;
+
+use crate::{
+    calendar::{Calendar, CalendarEvent, FEED_EXTENSION},
+    controllers::{inventory::load_metadata_value_maps_for_items, reports::location_of},
+    exts::OptionStringExt,
+    i18n::Translator,
+    initializers::app_cache::AppData,
+    models::{
+        _entities::inventory_items::Column as InventoryItemsColumn,
+        inventory_items,
+        teams::{self, ItemScope},
+        tenants, users,
+    },
+    tenant::RequestTenant,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    pub kind_id: Option<String>,
+    pub location: Option<String>,
+}
+
+/// One event per due inspection and one per expiry of every item of
+/// `tenant` in `scope` matching `query`, dated in the time zone of
+/// `translator`.
+async fn build_calendar(
+    ctx: &AppContext,
+    tenant: i32,
+    scope: &ItemScope,
+    query: FeedQuery,
+    translator: &Translator,
+) -> Result<Calendar> {
+    let kind_id = query
+        .kind_id
+        .clean()
+        .map(|kind_id| {
+            kind_id
+                .parse::<i32>()
+                .map_err(|_| Error::BadRequest(format!("invalid kind_id: {kind_id}")))
+        })
+        .transpose()?;
+    let location_needle = query
+        .location
+        .clean()
+        .map(|location| location.to_lowercase());
+
+    let intervals = ctx.get_intervals()?;
+    let item_kinds = ctx.get_item_kinds(tenant)?;
+
+    let mut select = inventory_items::Entity::find_in_tenant(tenant)
+        .filter(scope.condition(InventoryItemsColumn::Id))
+        .order_by_asc(InventoryItemsColumn::Id);
+    if let Some(kind_id) = kind_id {
+        select = select.filter(InventoryItemsColumn::InventoryItemKindId.eq(kind_id));
+    }
+    let items = select.all(&ctx.db).await?;
+    let item_ids = items.iter().map(|item| item.id).collect::<Vec<_>>();
+    let metadata_by_item_id = load_metadata_value_maps_for_items(&ctx.db, &item_ids).await?;
+
+    let tz = translator.timezone();
+    let base_url = ctx.config.server.full_url();
+    let mut events = Vec::new();
+    for item in items {
+        let location = location_of(metadata_by_item_id.get(&item.id));
+        if let Some(needle) = &location_needle {
+            if !location
+                .as_deref()
+                .is_some_and(|location| location.to_lowercase().contains(needle))
+            {
+                continue;
+            }
+        }
+
+        let description = [
+            item_kinds
+                .get(&item.inventory_item_kind_id)
+                .map(|kind| format!("{}: {}", translator.t("calendar-kind"), kind.name)),
+            item.serial_number
+                .clone()
+                .map(|serial| format!("{}: {serial}", translator.t("calendar-serial_number"))),
+            location.map(|location| format!("{}: {location}", translator.t("calendar-location"))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+        let event = |kind: &str, date: DateTimeWithTimeZone, label: &str| CalendarEvent {
+            uid: CalendarEvent::item_uid(item.id, kind),
+            date: date.with_timezone(&tz).date_naive(),
+            summary: format!("{}: {}", translator.t(label), item.name),
+            description: Some(description.clone()).filter(|description| !description.is_empty()),
+            url: Some(format!("{base_url}/inventory/item/{}", item.id)),
+            last_modified: item.updated_at.to_utc(),
+        };
+
+        if let Some(next_check) = item.next_check_at(intervals.get(&item.interval_id)) {
+            events.push(event("inspection", next_check, "calendar-inspection_due"));
+        }
+        if let Some(expiry) = item.expiry {
+            events.push(event("expiry", expiry, "calendar-expiry"));
+        }
+    }
+    events.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.uid.cmp(&b.uid)));
+
+    Ok(Calendar {
+        name: translator.t("calendar-name"),
+        timezone: tz.name().to_string(),
+        events,
+    })
+}
+
+/// Serves the feed of the user owning the secret in the URL, with the items
+/// of their current tenant they may see. Calendar clients cannot log in, so the URL
+/// itself is the credential. Dates follow
+/// the checks as they are submitted because the feed is built on every
+/// request.
+#[debug_handler]
+pub async fn feed(
+    State(ctx): State<AppContext>,
+    Path(file): Path<String>,
+    Query(query): Query<FeedQuery>,
+) -> Result<Response> {
+    let token = file.strip_suffix(FEED_EXTENSION).unwrap_or(&file);
+    let user = users::Model::find_by_calendar_token(&ctx.db, token)
+        .await
+        .map_err(|_| Error::NotFound)?;
+
+    let tenant = tenants::Entity::current_for_user(&ctx.db, &user)
+        .await?
+        .ok_or(Error::NotFound)?;
+    let scope = teams::Entity::item_scope_for_user(&ctx.db, tenant.id, user.id).await?;
+    let translator = Translator::for_user(&ctx, &user);
+
+    let calendar = build_calendar(&ctx, tenant.id, &scope, query, &translator).await?;
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/calendar; charset=utf-8"),
+        )],
+        calendar.render(Utc::now()),
+    )
+        .into_response())
+}
+
+/// Renders the subscription page. The feed URL is loaded and reset
+/// client-side through `/api/auth/calendar` using the token stored at login.
+#[debug_handler]
+pub async fn subscribe(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
+) -> Result<Response> {
+    let item_kinds = ctx
+        .get_item_kinds(tenant)?
+        .into_values()
+        .collect::<Vec<_>>();
+    format::render().view(
+        &v,
+        "calendar/subscribe.html",
+        data!({ "item_kinds": item_kinds }),
+    )
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("calendar/")
+        .add("subscribe", get(subscribe))
+        .add("{file}", get(feed))
+}
@@ -7,7 +7,9 @@ use serde::Deserialize;
 
 use crate::{
     exts::StringExt,
+    initializers::app_cache::refresh_checklists_cache,
     models::{checklist_steps, checklists, inventory_items},
+    tenant::RequestTenant,
 };
 
 #[derive(serde::Serialize)]
@@ -20,8 +22,9 @@ struct ChecklistWithSteps {
 pub async fn list(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
 ) -> Result<Response> {
-    let checklists = checklists::Entity::find()
+    let checklists = checklists::Entity::find_in_tenant(tenant)
         .find_with_related(checklist_steps::Entity)
         .all(&ctx.db)
         .await?
@@ -43,14 +46,14 @@ pub async fn add(ViewEngine(v): ViewEngine<TeraView>) -> Result<Response> {
     format::render().view(&v, "checklists/add.html", data!({}))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
 pub struct ChecklistStepInput {
     pub position: i32,
     pub name: String,
     pub description: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
 pub struct AddChecklistParams {
     pub name: String,
     pub description: String,
@@ -60,6 +63,7 @@ pub struct AddChecklistParams {
 #[debug_handler]
 pub async fn add_post(
     State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
     Json(params): Json<AddChecklistParams>,
 ) -> Result<Response> {
     let AddChecklistParams {
@@ -73,30 +77,10 @@ pub async fn add_post(
     })?;
     let description = description.clean();
 
-    let mut prepared_steps: Vec<_> = steps
-        .into_iter()
-        .map(|step| {
-            let step_name = step.name.clean().unwrap_or_default();
-            let step_description = step.description.clean();
-            (step.position, step_name, step_description)
-        })
-        .collect();
-
-    if prepared_steps.is_empty() {
-        return Err(loco_rs::Error::BadRequest(
-            "Please provide at least one checklist step".to_string(),
-        ));
-    }
-
-    if !has_unique_elements(prepared_steps.iter().map(|(pos, _, _)| pos)) {
-        return Err(loco_rs::Error::BadRequest(
-            "Checklist steps must all have unique positions".to_string(),
-        ));
-    }
-
-    prepared_steps.sort_by_key(|(position, _, _)| *position);
+    let prepared_steps = prepare_checklist_steps(steps)?;
 
     let checklist = checklists::ActiveModel {
+        tenant_id: ActiveValue::set(tenant),
         name: ActiveValue::set(name),
         description: ActiveValue::set(description),
         ..Default::default()
@@ -104,23 +88,36 @@ pub async fn add_post(
     .insert(&ctx.db)
     .await?;
 
-    for (position, step_name, description) in prepared_steps {
+    for prepared in prepared_steps {
         let step = checklist_steps::ActiveModel {
             checklist_id: ActiveValue::set(checklist.id),
-            position: ActiveValue::set(position),
-            name: ActiveValue::set(step_name),
-            description: ActiveValue::set(description),
+            position: ActiveValue::set(prepared.position),
+            name: ActiveValue::set(prepared.name),
+            description: ActiveValue::set(prepared.description),
             ..Default::default()
         };
         step.insert(&ctx.db).await?;
     }
+    refresh_checklists_cache(&ctx).await?;
 
     format::redirect("/checklists/list")
 }
 
 #[debug_handler]
-pub async fn remove(State(ctx): State<AppContext>, Path(id): Path<i32>) -> Result<Response> {
+pub async fn remove(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
+) -> Result<Response> {
+    delete_checklist(&ctx, tenant, id).await?;
+    refresh_checklists_cache(&ctx).await?;
+
+    format::json(data!({ "status": "ok" }))
+}
+
+pub(crate) async fn delete_checklist(ctx: &AppContext, tenant: i32, id: i32) -> Result<()> {
     use sea_orm::PaginatorTrait;
+    load_checklist(ctx, tenant, id).await?;
     let usage_count = inventory_items::Entity::find()
         .filter(crate::models::_entities::inventory_items::Column::ChecklistId.eq(id))
         .count(&ctx.db)
@@ -132,12 +129,21 @@ pub async fn remove(State(ctx): State<AppContext>, Path(id): Path<i32>) -> Resul
         ));
     }
 
-    let deleted = checklists::Entity::delete_by_id(id).exec(&ctx.db).await?;
-    if deleted.rows_affected == 0 {
-        return Err(loco_rs::Error::NotFound);
-    }
+    checklists::Entity::delete_by_id(id).exec(&ctx.db).await?;
 
-    format::json(data!({ "status": "ok" }))
+    Ok(())
+}
+
+/// Loads checklist `id` of `tenant`.
+pub(crate) async fn load_checklist(
+    ctx: &AppContext,
+    tenant: i32,
+    id: i32,
+) -> Result<checklists::Model> {
+    checklists::Entity::find_by_id_in_tenant(tenant, id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(loco_rs::Error::NotFound)
 }
 
 pub fn routes() -> Routes {
@@ -149,6 +155,41 @@ pub fn routes() -> Routes {
         .add("list", get(list))
 }
 
+pub(crate) struct PreparedChecklistStep {
+    pub position: i32,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+pub(crate) fn prepare_checklist_steps(
+    steps: Vec<ChecklistStepInput>,
+) -> Result<Vec<PreparedChecklistStep>> {
+    let mut prepared_steps: Vec<_> = steps
+        .into_iter()
+        .map(|step| PreparedChecklistStep {
+            position: step.position,
+            name: step.name.clean().unwrap_or_default(),
+            description: step.description.clean(),
+        })
+        .collect();
+
+    if prepared_steps.is_empty() {
+        return Err(loco_rs::Error::BadRequest(
+            "Please provide at least one checklist step".to_string(),
+        ));
+    }
+
+    if !has_unique_elements(prepared_steps.iter().map(|step| step.position)) {
+        return Err(loco_rs::Error::BadRequest(
+            "Checklist steps must all have unique positions".to_string(),
+        ));
+    }
+
+    prepared_steps.sort_by_key(|step| step.position);
+
+    Ok(prepared_steps)
+}
+
 fn has_unique_elements<T>(iter: T) -> bool
 where
     T: IntoIterator,
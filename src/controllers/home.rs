@@ -2,11 +2,30 @@
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
 
-use chrono::{Duration, Utc};
+use chrono::{Days, NaiveDate};
+use chrono_tz::Tz;
 use loco_rs::prelude::*;
+use sea_orm::{
+    sea_query::{NullOrdering, Order},
+    QueryOrder,
+};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-use crate::models::{intervals, inventory_item_checks, inventory_items};
+use crate::{
+    models::{
+        intervals, inventory_item_checks, inventory_items,
+        responsibilities::{self, Role},
+    },
+    session::RequestUser,
+    tenant::{RequestItemScope, RequestTenant},
+    timezone::{self, RequestTimezone},
+};
+
+/// Items shown per dashboard page.
+const PAGE_SIZE: u64 = 50;
+/// Latest checks loaded for each item on the dashboard.
+const RECENT_CHECKS: u64 = 3;
 
 #[derive(serde::Serialize)]
 struct HomeEntryCheck {
@@ -25,13 +44,45 @@ struct HomeEntry {
     checklist_description: Option<String>,
     item_kind_name: String,
     interval: intervals::Model,
-    next_expiry: DateTimeWithTimeZone,
+    next_expiry: Option<DateTimeWithTimeZone>,
     urgency: UrgencyLevel,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct HomeQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    /// Only the items the user is responsible for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mine: Option<bool>,
+}
+
+impl HomeQuery {
+    /// Link to `page` of the dashboard keeping the other parameters.
+    fn page_href(&self, page: u64) -> String {
+        let query = Self {
+            page: Some(page),
+            ..self.clone()
+        };
+        format!(
+            "/?{}",
+            serde_urlencoded::to_string(&query).unwrap_or_default()
+        )
+    }
+}
+
+#[derive(serde::Serialize)]
+struct PaginationView {
+    page: u64,
+    total_pages: u64,
+    total_items: u64,
+    previous_href: Option<String>,
+    next_href: Option<String>,
+}
+
 #[derive(Copy, Clone, Debug, serde::Serialize, Eq, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-enum UrgencyLevel {
+pub enum UrgencyLevel {
     Normal,
     Warning,
     Critical,
@@ -48,47 +99,84 @@ impl fmt::Display for UrgencyLevel {
     }
 }
 
-fn calculate_urgency(next_expiry: &DateTimeWithTimeZone) -> UrgencyLevel {
-    let now = Utc::now();
-    if *next_expiry < now {
+/// Urgency by calendar day in `tz`: overdue before `today`, a warning when
+/// due within the next week.
+pub(crate) fn calculate_urgency(
+    next_expiry: &DateTimeWithTimeZone,
+    today: NaiveDate,
+    tz: Tz,
+) -> UrgencyLevel {
+    let due = next_expiry.with_timezone(&tz).date_naive();
+    if due < today {
         UrgencyLevel::Critical
-    } else if *next_expiry <= now + Duration::days(7) {
+    } else if due <= today + Days::new(7) {
         UrgencyLevel::Warning
     } else {
         UrgencyLevel::Normal
     }
 }
 
+/// Items of the tenant the user may see, or with `mine` only those they
+/// own or deputize, sorted by their stored due date, most urgent first,
+/// page by page with the latest [`RECENT_CHECKS`] checks of each.
 pub async fn home(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
+    Query(query): Query<HomeQuery>,
+    RequestTimezone(tz): RequestTimezone,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+    RequestUser(user): RequestUser,
 ) -> Result<Response> {
     use crate::initializers::app_cache::AppData;
-    let checklists = ctx.get_checklists()?;
+    let today = timezone::today(tz);
+    let checklists = ctx.get_checklists(tenant)?;
     let intervals = ctx.get_intervals()?;
-    let item_kinds = ctx.get_item_kinds()?;
+    let item_kinds = ctx.get_item_kinds(tenant)?;
     let results = ctx.get_results()?;
-    let users = ctx.get_users()?;
+    let users = ctx.get_users(tenant)?;
 
-    let items = inventory_items::Entity::find()
-        .find_with_related(inventory_item_checks::Entity)
-        .all(&ctx.db)
-        .await?;
+    let page_number = query.page.unwrap_or(1).max(1);
+    let mine = query.mine.unwrap_or(false);
+    let mut condition = scope.condition(inventory_items::Column::Id);
+    if mine {
+        condition = condition.add(inventory_items::Column::Id.in_subquery(
+            responsibilities::Entity::items_held_by(user.id, &[Role::Owner, Role::Deputy]),
+        ));
+    }
+    let page = query::paginate(
+        &ctx.db,
+        inventory_items::Entity::find_in_tenant(tenant)
+            .order_by_with_nulls(
+                inventory_items::Column::NextDueAt,
+                Order::Asc,
+                NullOrdering::Last,
+            )
+            .order_by_asc(inventory_items::Column::Id),
+        Some(condition),
+        &query::PaginationQuery {
+            page: page_number,
+            page_size: PAGE_SIZE,
+        },
+    )
+    .await?;
+    let item_ids = page.page.iter().map(|item| item.id).collect::<Vec<_>>();
+    let mut checks_by_item =
+        inventory_item_checks::Entity::latest_by_item(&ctx.db, &item_ids, RECENT_CHECKS, None)
+            .await?;
 
-    let mut items: Vec<HomeEntry> = items
+    let items: Vec<HomeEntry> = page
+        .page
         .into_iter()
-        .filter_map(|(item, checks)| {
+        .filter_map(|item| {
             let checklist = checklists.get(&item.checklist_id)?;
             let item_kind = item_kinds.get(&item.inventory_item_kind_id)?;
             let interval = intervals.get(&item.interval_id)?;
-            let mut next_expiry =
-                interval.next_interval_expiry(&item.created_at, &item.last_checked_at);
-            if let Some(expiry) = item.expiry {
-                if expiry < next_expiry {
-                    next_expiry = expiry;
-                }
-            }
-            let urgency = calculate_urgency(&next_expiry);
+            let next_expiry = item.next_due_at;
+            let urgency = next_expiry.map_or(UrgencyLevel::Normal, |next_expiry| {
+                calculate_urgency(&next_expiry, today, tz)
+            });
+            let checks = checks_by_item.remove(&item.id).unwrap_or_default();
 
             Some(HomeEntry {
                 item,
@@ -117,9 +205,19 @@ pub async fn home(
             })
         })
         .collect();
-    items.sort_unstable_by_key(|i| i.next_expiry);
+    let pagination = PaginationView {
+        page: page_number,
+        total_pages: page.total_pages,
+        total_items: page.total_items,
+        previous_href: (page_number > 1).then(|| query.page_href(page_number - 1)),
+        next_href: (page_number < page.total_pages).then(|| query.page_href(page_number + 1)),
+    };
 
-    format::render().view(&v, "home/home.html", data!({ "items": items }))
+    format::render().view(
+        &v,
+        "home/home.html",
+        data!({ "items": items, "mine": mine, "pagination": pagination }),
+    )
 }
 
 #[debug_handler]
@@ -133,3 +231,18 @@ pub fn routes() -> Routes {
         .add("", get(home))
         .add("manage", get(manage))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::HomeQuery;
+
+    #[test]
+    fn page_links_keep_the_filter() {
+        let query = HomeQuery {
+            page: Some(2),
+            mine: Some(true),
+        };
+        assert_eq!(query.page_href(3), "/?page=3&mine=true");
+        assert_eq!(HomeQuery::default().page_href(2), "/?page=2");
+    }
+}
@@ -1,15 +1,13 @@
-use axum::{
-    http::{header, HeaderValue},
-    response::IntoResponse,
-};
 use axum_extra::extract::Form as HtmlForm;
 use chrono::Utc;
+use chrono_tz::Tz;
 use loco_rs::prelude::*;
 use sea_orm::{
+    sea_query::{Expr, Func},
     ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
     QueryOrder, TransactionTrait,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::models::_entities::{
     checklist_steps::Column as ChecklistStepsColumn,
@@ -21,17 +19,32 @@ use crate::models::_entities::{
     inventory_items::Column as InventoryItemsColumn,
 };
 use crate::{
+    controllers::{
+        reports::{generate_report, ReportRequest},
+        responsibilities::ResponsibilityView,
+    },
     exts::{BTreeMapExt, OptionStringExt, StringExt},
+    i18n::{RequestLocale, Translator},
     initializers::app_cache::{refresh_item_kinds_cache, AppData},
+    inventory_filter::{FilterContext, InventoryFilter, InventoryListQuery, ItemSort, PAGE_SIZES},
     models::{
         checklist_steps, checklists, executed_checklist_steps, executed_checklists, expiries,
-        intervals, inventory_item_check_steps, inventory_item_checks,
-        inventory_item_kind_metadata_fields, inventory_item_kinds, inventory_item_metadata_values,
-        inventory_items,
+        generated_reports::ReportIdentity, intervals, inventory_item_check_steps,
+        inventory_item_checks, inventory_item_kind_metadata_fields, inventory_item_kinds,
+        inventory_item_metadata_values, inventory_items, results, teams::ItemScope, users,
+        webhook_endpoints::WebhookEvent,
     },
-    reports::single_item_history::{
-        self, ReportCheck, ReportField, ReportItem, ReportStep, SingleItemHistoryReport,
+    reports::{
+        check_certificate::{CertificateStep, CheckCertificate},
+        compliance::ComplianceStatus,
+        single_item_history::{
+            ReportCheck, ReportField, ReportItem, ReportStep, SingleItemHistoryReport,
+        },
     },
+    search::{self, SearchEntity},
+    tenant::{RequestItemScope, RequestTenant},
+    timezone::{self, RequestTimezone},
+    webhooks,
 };
 
 #[derive(serde::Serialize)]
@@ -96,18 +109,32 @@ struct ItemMetadataValueView {
     value: String,
 }
 
-struct ItemDetailsData {
+pub(crate) struct ItemDetailsData {
     item: inventory_items::Model,
     item_kind_name: Option<String>,
     checklist_name: Option<String>,
     interval_name: Option<String>,
     metadata: Vec<ItemMetadataValueView>,
     checks: Vec<ItemCheckView>,
+    /// Set when only one page of the check history was loaded.
+    check_pages: Option<CheckPaginationView>,
 }
 
+/// Checks shown per page of the check history on the item details.
+const CHECKS_PAGE_SIZE: u64 = 10;
+
 #[derive(Debug, serde::Deserialize)]
-pub struct InventoryListQuery {
-    q: Option<String>,
+pub struct ItemDetailsQuery {
+    pub page: Option<u64>,
+}
+
+#[derive(serde::Serialize)]
+struct CheckPaginationView {
+    page: u64,
+    total_pages: u64,
+    total_items: u64,
+    previous_href: Option<String>,
+    next_href: Option<String>,
 }
 
 struct StepResultInput {
@@ -122,6 +149,7 @@ struct ValidatedCheckPayload {
     notes: Option<String>,
     checked_by: i32,
     result_id: i32,
+    signature: Option<String>,
 }
 
 struct ItemFormLookups {
@@ -142,11 +170,15 @@ struct MetadataFieldFormRow {
     name: String,
 }
 
-async fn build_item_form_lookups(ctx: &AppContext) -> Result<ItemFormLookups> {
-    let checklists = checklists::Entity::find().all(&ctx.db).await?;
+async fn build_item_form_lookups(ctx: &AppContext, tenant: i32) -> Result<ItemFormLookups> {
+    let checklists = checklists::Entity::find_in_tenant(tenant)
+        .all(&ctx.db)
+        .await?;
     let expiries = expiries::Entity::find().all(&ctx.db).await?;
     let intervals = intervals::Entity::find().all(&ctx.db).await?;
-    let item_kinds = inventory_item_kinds::Entity::find().all(&ctx.db).await?;
+    let item_kinds = inventory_item_kinds::Entity::find_in_tenant(tenant)
+        .all(&ctx.db)
+        .await?;
     Ok(ItemFormLookups {
         checklists,
         expiries,
@@ -158,10 +190,11 @@ async fn build_item_form_lookups(ctx: &AppContext) -> Result<ItemFormLookups> {
 async fn render_inventory_item_form(
     view: &TeraView,
     ctx: &AppContext,
+    tenant: i32,
     item: Option<inventory_items::Model>,
     form_action: String,
 ) -> Result<Response> {
-    let lookups = build_item_form_lookups(ctx).await?;
+    let lookups = build_item_form_lookups(ctx, tenant).await?;
     let ItemFormLookups {
         checklists,
         expiries,
@@ -217,8 +250,13 @@ async fn render_inventory_item_form(
     )
 }
 
-async fn build_item_kind_form_lookups(ctx: &AppContext) -> Result<ItemKindFormLookups> {
-    let checklists = checklists::Entity::find().all(&ctx.db).await?;
+async fn build_item_kind_form_lookups(
+    ctx: &AppContext,
+    tenant: i32,
+) -> Result<ItemKindFormLookups> {
+    let checklists = checklists::Entity::find_in_tenant(tenant)
+        .all(&ctx.db)
+        .await?;
     let expiries = expiries::Entity::find().all(&ctx.db).await?;
     let intervals = intervals::Entity::find().all(&ctx.db).await?;
     Ok(ItemKindFormLookups {
@@ -228,7 +266,7 @@ async fn build_item_kind_form_lookups(ctx: &AppContext) -> Result<ItemKindFormLo
     })
 }
 
-async fn load_metadata_field_names_by_kind_ids<C>(
+pub(crate) async fn load_metadata_field_names_by_kind_ids<C>(
     db: &C,
     kind_ids: Vec<i32>,
 ) -> Result<HashMap<i32, Vec<String>>>
@@ -256,7 +294,7 @@ where
     Ok(by_kind_id)
 }
 
-async fn load_metadata_fields_by_kind_ids<C>(
+pub(crate) async fn load_metadata_fields_by_kind_ids<C>(
     db: &C,
     kind_ids: Vec<i32>,
 ) -> Result<HashMap<i32, Vec<inventory_item_kind_metadata_fields::Model>>>
@@ -301,7 +339,7 @@ where
         .collect())
 }
 
-async fn load_metadata_value_maps_for_items<C>(
+pub(crate) async fn load_metadata_value_maps_for_items<C>(
     db: &C,
     item_ids: &[i32],
 ) -> Result<HashMap<i32, HashMap<String, String>>>
@@ -350,7 +388,7 @@ where
     Ok(values_by_item_id)
 }
 
-async fn load_metadata_value_maps_by_field_id_for_items<C>(
+pub(crate) async fn load_metadata_value_maps_by_field_id_for_items<C>(
     db: &C,
     item_ids: &[i32],
 ) -> Result<HashMap<i32, HashMap<i32, String>>>
@@ -453,7 +491,7 @@ fn metadata_values_from_form(
         .collect()
 }
 
-async fn replace_metadata_fields_for_kind<C>(
+pub(crate) async fn replace_metadata_fields_for_kind<C>(
     db: &C,
     item_kind_id: i32,
     field_names: &[String],
@@ -473,7 +511,7 @@ where
             created_at: ActiveValue::set(now.into()),
             inventory_item_kind_id: ActiveValue::set(item_kind_id),
             name: ActiveValue::set(field_name.clone()),
-            position: ActiveValue::set(position as i32),
+            position: ActiveValue::set(i32::try_from(position).unwrap_or(i32::MAX)),
             updated_at: ActiveValue::set(now.into()),
             ..Default::default()
         }
@@ -521,7 +559,7 @@ where
     Ok(())
 }
 
-async fn replace_metadata_values_for_item_by_field_id<C>(
+pub(crate) async fn replace_metadata_values_for_item_by_field_id<C>(
     db: &C,
     item_id: i32,
     metadata_fields: &[inventory_item_kind_metadata_fields::Model],
@@ -557,7 +595,7 @@ where
     Ok(())
 }
 
-async fn rebuild_metadata_values_for_kind_items<C>(
+pub(crate) async fn rebuild_metadata_values_for_kind_items<C>(
     db: &C,
     item_kind_id: i32,
     item_ids: &[i32],
@@ -586,10 +624,11 @@ where
 async fn render_item_kind_form(
     view: &TeraView,
     ctx: &AppContext,
+    tenant: i32,
     item_kind: Option<inventory_item_kinds::Model>,
     form_action: String,
 ) -> Result<Response> {
-    let lookups = build_item_kind_form_lookups(ctx).await?;
+    let lookups = build_item_kind_form_lookups(ctx, tenant).await?;
     let metadata_fields = match item_kind.as_ref() {
         Some(item_kind) => load_metadata_field_form_rows(&ctx.db, item_kind.id).await?,
         None => Vec::new(),
@@ -611,59 +650,97 @@ async fn render_item_kind_form(
     )
 }
 
-#[debug_handler]
-pub async fn list(
-    ViewEngine(v): ViewEngine<TeraView>,
-    State(ctx): State<AppContext>,
-    Query(query): Query<InventoryListQuery>,
-) -> Result<Response> {
-    let search_term = query.q.and_then(StringExt::clean);
-    let search_term_lower = search_term.as_ref().map(|term| term.to_lowercase());
-    let item_kinds = ctx.get_item_kinds()?;
-    let inventory = inventory_items::Entity::find().all(&ctx.db).await?;
-
-    if let Some(term_lower) = &search_term_lower {
-        if let Some(match_item) = inventory.iter().find(|item| {
-            item.serial_number
-                .as_deref()
-                .is_some_and(|serial| serial.to_lowercase() == *term_lower)
-        }) {
-            return format::redirect(&format!("/inventory/item/{}", match_item.id));
-        }
+/// Parses a `YYYY-MM-DD` date input as the start of that day in `tz`.
+pub(crate) fn parse_expiry_date(expiry: &str, tz: Tz) -> Result<DateTimeWithTimeZone> {
+    let naive_date = chrono::NaiveDate::parse_from_str(expiry, "%Y-%m-%d")
+        .map_err(|e| loco_rs::Error::BadRequest(e.to_string()))?;
+    Ok(timezone::start_of_day(naive_date, tz).into())
+}
+
+#[derive(serde::Serialize)]
+struct SortLink {
+    key: &'static str,
+    href: String,
+    active: bool,
+    descending: bool,
+}
+
+#[derive(serde::Serialize)]
+struct PaginationView {
+    page: u64,
+    page_size: u64,
+    total_pages: u64,
+    total_items: u64,
+    previous_href: Option<String>,
+    next_href: Option<String>,
+    page_sizes: [u64; 3],
+}
+
+fn list_href(query: &str) -> String {
+    if query.is_empty() {
+        "/inventory/list".to_string()
+    } else {
+        format!("/inventory/list?{query}")
     }
+}
 
-    let inventory = inventory
+/// Header links sorting by each column, reversing the current one.
+fn sort_links(filter: &InventoryFilter) -> Vec<SortLink> {
+    ItemSort::ALL
         .into_iter()
-        .filter(|item| {
-            if let Some(needle) = search_term_lower.as_deref() {
-                let name_match = item.name.to_lowercase().contains(needle);
-                let serial_match = item
-                    .serial_number
-                    .as_deref()
-                    .is_some_and(|serial| serial.to_lowercase().contains(needle));
-                let kind_match = item_kinds
-                    .get(&item.inventory_item_kind_id)
-                    .is_some_and(|kind| kind.name.to_lowercase().contains(needle));
-                name_match || serial_match || kind_match
-            } else {
-                true
+        .map(|sort| {
+            let active = filter.sort == sort;
+            SortLink {
+                key: sort.as_str(),
+                href: list_href(&filter.query_string(sort, active && !filter.descending, 1)),
+                active,
+                descending: active && filter.descending,
             }
         })
+        .collect()
+}
+
+/// Metadata columns of all item kinds of `tenant`, offered by the filter.
+async fn metadata_filter_columns(
+    ctx: &AppContext,
+    tenant: i32,
+) -> Result<Vec<InventoryMetadataColumn>> {
+    let kind_ids = ctx.get_item_kinds(tenant)?.into_keys().collect::<Vec<_>>();
+    let mut columns = load_metadata_fields_by_kind_ids(&ctx.db, kind_ids)
+        .await?
+        .into_values()
+        .flatten()
+        .map(|field| InventoryMetadataColumn {
+            key: metadata_column_key(&field.name),
+            name: field.name,
+        })
+        .filter(|column| !column.key.is_empty())
         .collect::<Vec<_>>();
+    columns.sort_by(|a, b| a.key.cmp(&b.key));
+    columns.dedup_by(|a, b| a.key == b.key);
+    Ok(columns)
+}
 
-    let item_ids = inventory.iter().map(|item| item.id).collect::<Vec<_>>();
-    let kind_ids = inventory
+/// Rows of the list with their metadata cells, one column per metadata field
+/// name occurring on the page.
+async fn inventory_list_rows(
+    ctx: &AppContext,
+    tenant: i32,
+    items: Vec<inventory_items::Model>,
+) -> Result<(Vec<InventoryListItem>, Vec<InventoryMetadataColumn>)> {
+    let item_kinds = ctx.get_item_kinds(tenant)?;
+    let item_ids = items.iter().map(|item| item.id).collect::<Vec<_>>();
+    let kind_ids = items
         .iter()
         .map(|item| item.inventory_item_kind_id)
         .collect::<Vec<_>>();
     let metadata_fields_by_kind_id = load_metadata_fields_by_kind_ids(&ctx.db, kind_ids).await?;
     let metadata_values_by_item_id =
         load_metadata_value_maps_by_field_id_for_items(&ctx.db, &item_ids).await?;
-    let metadata_columns =
-        build_inventory_metadata_columns(&inventory, &metadata_fields_by_kind_id);
+    let metadata_columns = build_inventory_metadata_columns(&items, &metadata_fields_by_kind_id);
     let empty_metadata_values = HashMap::new();
 
-    let inventory = inventory
+    let rows = items
         .into_iter()
         .map(|item| {
             let item_metadata_values = metadata_values_by_item_id
@@ -694,15 +771,102 @@ pub async fn list(
                 item,
             }
         })
+        .collect();
+    Ok((rows, metadata_columns))
+}
+
+/// The item of `tenant` in `scope` whose serial number is `serial`, ignoring
+/// case.
+pub(crate) async fn find_by_serial(
+    ctx: &AppContext,
+    tenant: i32,
+    scope: &ItemScope,
+    serial: &str,
+) -> Result<Option<inventory_items::Model>> {
+    Ok(inventory_items::Entity::find_in_tenant(tenant)
+        .filter(scope.condition(InventoryItemsColumn::Id))
+        .filter(
+            Expr::expr(Func::lower(Expr::col(InventoryItemsColumn::SerialNumber)))
+                .eq(serial.to_lowercase()),
+        )
+        .one(&ctx.db)
+        .await?)
+}
+
+/// Lists the items of the tenant page by page. Filters, sorting and the page
+/// come from the query string, see [`InventoryListQuery`]; a search matching
+/// a serial number exactly opens that item.
+#[debug_handler]
+pub async fn list(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Query(query): Query<InventoryListQuery>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+    RequestTimezone(tz): RequestTimezone,
+) -> Result<Response> {
+    let filter = InventoryFilter::try_from(query)?;
+    if let Some(search) = &filter.search {
+        if let Some(item) = find_by_serial(&ctx, tenant, &scope, search).await? {
+            return format::redirect(&format!("/inventory/item/{}", item.id));
+        }
+    }
+
+    let ok_result_ids = ctx
+        .get_results()?
+        .into_values()
+        .filter(results::Model::is_ok)
+        .map(|result| result.id)
         .collect::<Vec<_>>();
+    let condition = filter.condition(&FilterContext {
+        ok_result_ids: &ok_result_ids,
+        now: Utc::now(),
+        today: timezone::today(tz),
+        timezone: tz,
+    });
+    let pagination = query::PaginationQuery {
+        page: filter.page,
+        page_size: filter.page_size,
+    };
+    let page = query::paginate(
+        &ctx.db,
+        filter.sorted(inventory_items::Entity::find_in_tenant(tenant)),
+        Some(condition.add(scope.condition(InventoryItemsColumn::Id))),
+        &pagination,
+    )
+    .await?;
+    let (inventory, metadata_columns) = inventory_list_rows(&ctx, tenant, page.page).await?;
+
+    let mut item_kinds = ctx
+        .get_item_kinds(tenant)?
+        .into_values()
+        .collect::<Vec<_>>();
+    item_kinds.sort_by(|a, b| a.name.cmp(&b.name));
+    let page_href =
+        |page: u64| list_href(&filter.query_string(filter.sort, filter.descending, page));
+    let pagination = PaginationView {
+        page: filter.page,
+        page_size: filter.page_size,
+        total_pages: page.total_pages,
+        total_items: page.total_items,
+        previous_href: (filter.page > 1).then(|| page_href(filter.page - 1)),
+        next_href: (filter.page < page.total_pages).then(|| page_href(filter.page + 1)),
+        page_sizes: PAGE_SIZES,
+    };
 
     format::render().view(
         &v,
         "inventory/list.html",
         data!({
             "inventory": inventory,
-            "inventory_search": search_term,
+            "inventory_search": filter.search,
             "metadata_columns": metadata_columns,
+            "filter": filter.form_values(),
+            "filter_fields": metadata_filter_columns(&ctx, tenant).await?,
+            "item_kinds": item_kinds,
+            "statuses": ComplianceStatus::ALL.map(ComplianceStatus::as_str),
+            "sort_links": sort_links(&filter),
+            "pagination": pagination,
         }),
     )
 }
@@ -712,8 +876,12 @@ pub async fn show_item_details(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Path(id): Path<i32>,
+    Query(query): Query<ItemDetailsQuery>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
 ) -> Result<Response> {
-    let details = load_item_details_data(&ctx, id).await?;
+    let page = query.page.unwrap_or(1).max(1);
+    let details = load_item_details_data(&ctx, tenant, &scope, id, Some(page)).await?;
     let ItemDetailsData {
         item,
         item_kind_name,
@@ -721,18 +889,22 @@ pub async fn show_item_details(
         interval_name,
         metadata,
         checks,
+        check_pages,
     } = details;
+    let responsibility = ResponsibilityView::for_item(&ctx, &item).await?;
 
     format::render().view(
         &v,
         "inventory/item_details.html",
         data!({
+            "responsibility": responsibility,
             "item": item,
             "item_kind_name": item_kind_name,
             "checklist_name": checklist_name,
             "interval_name": interval_name,
             "metadata": metadata,
             "checks": checks,
+            "pagination": check_pages,
         }),
     )
 }
@@ -740,38 +912,203 @@ pub async fn show_item_details(
 #[debug_handler]
 pub async fn download_item_report(
     State(ctx): State<AppContext>,
+    RequestLocale(translator): RequestLocale,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
     Path(id): Path<i32>,
 ) -> Result<Response> {
-    let details = load_item_details_data(&ctx, id).await?;
-    let report = build_single_item_history_report(details);
-    let pdf = single_item_history::render_pdf(&report)?;
-    let filename = format!("inventory-item-{id}-history.pdf");
-    let content_disposition =
-        HeaderValue::from_str(&format!("attachment; filename=\"{filename}\""))
-            .map_err(|_| loco_rs::Error::InternalServerError)?;
-
-    Ok((
-        [
-            (
-                header::CONTENT_TYPE,
-                HeaderValue::from_static("application/pdf"),
-            ),
-            (header::CONTENT_DISPOSITION, content_disposition),
-        ],
-        pdf,
+    generate_report(
+        &ctx,
+        tenant,
+        &scope,
+        &ReportRequest::ItemHistory { item_id: id },
+        &translator,
+    )
+    .await?
+    .into_attachment()
+}
+
+#[debug_handler]
+pub async fn download_check_certificate(
+    State(ctx): State<AppContext>,
+    RequestLocale(translator): RequestLocale,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+    Path((id, check_id)): Path<(i32, i32)>,
+) -> Result<Response> {
+    generate_report(
+        &ctx,
+        tenant,
+        &scope,
+        &ReportRequest::CheckCertificate {
+            item_id: id,
+            check_id,
+        },
+        &translator,
     )
-        .into_response())
+    .await?
+    .into_attachment()
 }
 
-async fn load_item_details_data(ctx: &AppContext, id: i32) -> Result<ItemDetailsData> {
-    let Some(item) = inventory_items::Entity::find_by_id(id).one(&ctx.db).await? else {
+/// Collects the certificate of check `check_id` of item `item_id` of
+/// `tenant` in `scope` from the checklist snapshot stored with the check.
+pub(crate) async fn build_check_certificate(
+    ctx: &AppContext,
+    tenant: i32,
+    scope: &ItemScope,
+    item_id: i32,
+    check_id: i32,
+    identity: &ReportIdentity,
+    translator: &Translator,
+) -> Result<CheckCertificate> {
+    let Some(check) = inventory_item_checks::Entity::find_by_id(check_id)
+        .filter(InventoryItemChecksColumn::InventoryItemId.eq(item_id))
+        .one(&ctx.db)
+        .await?
+    else {
         return Err(loco_rs::Error::NotFound);
     };
+    let item = load_item(ctx, tenant, scope, item_id).await?;
 
-    let item_kinds = ctx.get_item_kinds()?;
+    let item_kinds = ctx.get_item_kinds(tenant)?;
     let results = ctx.get_results()?;
-    let users = ctx.get_users()?;
-    let checklists = ctx.get_checklists()?;
+    let users = ctx.get_users(tenant)?;
+    let item_kind = item_kinds.get(&item.inventory_item_kind_id);
+
+    let executed_checklist = executed_checklists::Entity::find_by_id(check.executed_checklist_id)
+        .one(&ctx.db)
+        .await?;
+    let executed_steps = executed_checklist_steps::Entity::find()
+        .filter(ExecutedChecklistStepsColumn::ExecutedChecklistId.eq(check.executed_checklist_id))
+        .order_by_asc(ExecutedChecklistStepsColumn::Position)
+        .all(&ctx.db)
+        .await?;
+    let step_results = inventory_item_check_steps::Entity::find()
+        .filter(InventoryItemCheckStepsColumn::InventoryItemCheckId.eq(check.id))
+        .all(&ctx.db)
+        .await?;
+
+    let verification_code =
+        check.verification_code(&ctx.config.get_jwt_config()?.secret, &step_results);
+    let step_map = step_results
+        .into_iter()
+        .map(|step| (step.executed_checklist_step_id, step))
+        .collect::<HashMap<_, _>>();
+    let steps = executed_steps
+        .into_iter()
+        .map(|step| {
+            let result = step_map.get(&step.id);
+            CertificateStep {
+                position: step.position,
+                name: step.name,
+                description: step.description,
+                result: result
+                    .and_then(|result| results.get(&result.result_id))
+                    .map(|result| translator.code(&result.code)),
+                notes: result.and_then(|result| result.notes.clone()),
+            }
+        })
+        .collect();
+
+    let mut item_fields = vec![
+        ReportField {
+            label: translator.t("report-inventory_item"),
+            value: Some(item.name.clone()),
+        },
+        ReportField {
+            label: translator.t("report-serial_number"),
+            value: item.serial_number.clone(),
+        },
+        ReportField {
+            label: translator.t("report-item_kind"),
+            value: item_kind.map(|kind| kind.name.clone()),
+        },
+        ReportField {
+            label: translator.t("report-test_standard"),
+            value: item_kind.and_then(|kind| kind.test_standard.clone().clean()),
+        },
+    ];
+    item_fields.extend(load_item_metadata_report_fields(ctx, &item).await?);
+
+    let overall_result = results.get(&check.result_id);
+    Ok(CheckCertificate {
+        title: translator.t("report-check_certificate-title"),
+        generated_at: translator.format_generated_at(identity.generated_at),
+        report_id: identity.report_id.clone(),
+        verify_url: identity.verify_url.clone(),
+        verification_code,
+        subject: item.name.clone(),
+        item: item_fields,
+        checklist: executed_checklist
+            .as_ref()
+            .map(|checklist| checklist.name.clone())
+            .unwrap_or_default(),
+        checklist_description: executed_checklist.and_then(|checklist| checklist.description),
+        checked_at: translator.format_datetime(check.checked_at),
+        inspector: users.get_cloned(&check.checked_by, |user| &user.name),
+        overall_result: overall_result.map(|result| translator.code(&result.code)),
+        passed: overall_result.is_some_and(results::Model::is_ok),
+        notes: check.notes,
+        steps,
+        signature: check
+            .signature
+            .as_deref()
+            .and_then(inventory_item_checks::decode_signature),
+    })
+}
+
+/// Loads the metadata of `item` in the order of its kind's metadata fields.
+async fn load_item_metadata_report_fields(
+    ctx: &AppContext,
+    item: &inventory_items::Model,
+) -> Result<Vec<ReportField>> {
+    let metadata_fields_by_kind_id =
+        load_metadata_fields_by_kind_ids(&ctx.db, vec![item.inventory_item_kind_id]).await?;
+    let metadata_values = load_metadata_value_maps_by_field_id_for_items(&ctx.db, &[item.id])
+        .await?
+        .remove(&item.id)
+        .unwrap_or_default();
+
+    Ok(metadata_fields_by_kind_id
+        .get(&item.inventory_item_kind_id)
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|field| ReportField {
+            label: field.name,
+            value: metadata_values.get(&field.id).cloned().clean(),
+        })
+        .collect())
+}
+
+/// Loads item `id` of `tenant`, if it is in `scope`.
+pub(crate) async fn load_item(
+    ctx: &AppContext,
+    tenant: i32,
+    scope: &ItemScope,
+    id: i32,
+) -> Result<inventory_items::Model> {
+    inventory_items::Entity::find_by_id_in_tenant(tenant, id)
+        .filter(scope.condition(InventoryItemsColumn::Id))
+        .one(&ctx.db)
+        .await?
+        .ok_or(loco_rs::Error::NotFound)
+}
+
+/// Loads item `id` of `tenant` in `scope` with its metadata and check
+/// history, newest check first. `checks_page` limits the history to one page of
+/// [`CHECKS_PAGE_SIZE`] checks, `None` loads all of them.
+pub(crate) async fn load_item_details_data(
+    ctx: &AppContext,
+    tenant: i32,
+    scope: &ItemScope,
+    id: i32,
+    checks_page: Option<u64>,
+) -> Result<ItemDetailsData> {
+    let item = load_item(ctx, tenant, scope, id).await?;
+
+    let item_kinds = ctx.get_item_kinds(tenant)?;
+    let checklists = ctx.get_checklists(tenant)?;
     let intervals = ctx.get_intervals()?;
     let metadata_fields_by_kind_id =
         load_metadata_fields_by_kind_ids(&ctx.db, vec![item.inventory_item_kind_id]).await?;
@@ -796,55 +1133,34 @@ async fn load_item_details_data(ctx: &AppContext, id: i32) -> Result<ItemDetails
         })
         .collect::<Vec<_>>();
 
-    let checks = inventory_item_checks::Entity::find()
+    let select = inventory_item_checks::Entity::find()
         .filter(InventoryItemChecksColumn::InventoryItemId.eq(item.id))
         .order_by_desc(InventoryItemChecksColumn::CheckedAt)
-        .all(&ctx.db)
-        .await?;
-
-    let mut rendered_checks = Vec::new();
-    for check in checks {
-        let executed_steps = executed_checklist_steps::Entity::find()
-            .filter(
-                ExecutedChecklistStepsColumn::ExecutedChecklistId.eq(check.executed_checklist_id),
+        .order_by_desc(InventoryItemChecksColumn::Id);
+    let (checks, check_pages) = match checks_page {
+        Some(page_number) => {
+            let page = query::paginate(
+                &ctx.db,
+                select,
+                None,
+                &query::PaginationQuery {
+                    page: page_number,
+                    page_size: CHECKS_PAGE_SIZE,
+                },
             )
-            .order_by_asc(ExecutedChecklistStepsColumn::Position)
-            .all(&ctx.db)
             .await?;
-
-        let step_results = inventory_item_check_steps::Entity::find()
-            .filter(InventoryItemCheckStepsColumn::InventoryItemCheckId.eq(check.id))
-            .all(&ctx.db)
-            .await?;
-        let step_map = step_results
-            .into_iter()
-            .map(|step| (step.executed_checklist_step_id, step))
-            .collect::<HashMap<_, _>>();
-
-        let mut steps_view = Vec::new();
-        for executed_step in executed_steps {
-            let result = step_map.get(&executed_step.id);
-            let result_code = result
-                .and_then(|s| results.get(&s.result_id))
-                .map(|r| r.code.clone());
-            let notes = result.and_then(|s| s.notes.clone());
-            steps_view.push(ItemCheckStepView {
-                position: executed_step.position,
-                name: executed_step.name.clone(),
-                description: executed_step.description.clone(),
-                result_code,
-                notes,
-            });
+            let page_href = |page: u64| format!("/inventory/item/{}?page={page}", item.id);
+            let pagination = CheckPaginationView {
+                page: page_number,
+                total_pages: page.total_pages,
+                total_items: page.total_items,
+                previous_href: (page_number > 1).then(|| page_href(page_number - 1)),
+                next_href: (page_number < page.total_pages).then(|| page_href(page_number + 1)),
+            };
+            (page.page, Some(pagination))
         }
-
-        let rendered = ItemCheckView {
-            checked_by: users.get_cloned(&check.checked_by, |user| &user.name),
-            result_code: results.get_cloned(&check.result_id, |result| &result.code),
-            check,
-            steps: steps_view,
-        };
-        rendered_checks.push(rendered);
-    }
+        None => (select.all(&ctx.db).await?, None),
+    };
 
     Ok(ItemDetailsData {
         item,
@@ -852,11 +1168,97 @@ async fn load_item_details_data(ctx: &AppContext, id: i32) -> Result<ItemDetails
         checklist_name,
         interval_name,
         metadata,
-        checks: rendered_checks,
+        checks: load_item_check_views(ctx, tenant, checks).await?,
+        check_pages,
     })
 }
 
-fn build_single_item_history_report(details: ItemDetailsData) -> SingleItemHistoryReport {
+/// Pairs `checks` with their executed steps and step results. The steps and
+/// results of all checks are loaded in one query each.
+async fn load_item_check_views(
+    ctx: &AppContext,
+    tenant: i32,
+    checks: Vec<inventory_item_checks::Model>,
+) -> Result<Vec<ItemCheckView>> {
+    if checks.is_empty() {
+        return Ok(Vec::new());
+    }
+    let results = ctx.get_results()?;
+    let users = ctx.get_users(tenant)?;
+
+    let executed_checklist_ids = checks
+        .iter()
+        .map(|check| check.executed_checklist_id)
+        .collect::<HashSet<_>>();
+    let mut steps_by_checklist_id = HashMap::<i32, Vec<executed_checklist_steps::Model>>::new();
+    for step in executed_checklist_steps::Entity::find()
+        .filter(ExecutedChecklistStepsColumn::ExecutedChecklistId.is_in(executed_checklist_ids))
+        .order_by_asc(ExecutedChecklistStepsColumn::Position)
+        .all(&ctx.db)
+        .await?
+    {
+        steps_by_checklist_id
+            .entry(step.executed_checklist_id)
+            .or_default()
+            .push(step);
+    }
+
+    let step_results = inventory_item_check_steps::Entity::find()
+        .filter(
+            InventoryItemCheckStepsColumn::InventoryItemCheckId
+                .is_in(checks.iter().map(|check| check.id)),
+        )
+        .all(&ctx.db)
+        .await?;
+    let step_map = step_results
+        .into_iter()
+        .map(|step| {
+            (
+                (
+                    step.inventory_item_check_id,
+                    step.executed_checklist_step_id,
+                ),
+                step,
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    Ok(checks
+        .into_iter()
+        .map(|check| {
+            let steps = steps_by_checklist_id
+                .get(&check.executed_checklist_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .map(|executed_step| {
+                    let result = step_map.get(&(check.id, executed_step.id));
+                    ItemCheckStepView {
+                        position: executed_step.position,
+                        name: executed_step.name.clone(),
+                        description: executed_step.description.clone(),
+                        result_code: result
+                            .and_then(|s| results.get(&s.result_id))
+                            .map(|r| r.code.clone()),
+                        notes: result.and_then(|s| s.notes.clone()),
+                    }
+                })
+                .collect();
+            ItemCheckView {
+                checked_by: users.get_cloned(&check.checked_by, |user| &user.name),
+                result_code: results.get_cloned(&check.result_id, |result| &result.code),
+                check,
+                steps,
+            }
+        })
+        .collect())
+}
+
+pub(crate) fn build_single_item_history_report(
+    details: ItemDetailsData,
+    identity: &ReportIdentity,
+    translator: &Translator,
+) -> SingleItemHistoryReport {
     let ItemDetailsData {
         item,
         item_kind_name,
@@ -864,24 +1266,26 @@ fn build_single_item_history_report(details: ItemDetailsData) -> SingleItemHisto
         interval_name,
         metadata,
         checks,
+        ..
     } = details;
 
     SingleItemHistoryReport {
-        title: "Inventory Item Check History".to_string(),
-        generated_at: single_item_history::format_generated_at(Utc::now()),
-        report_id: format!("ITEM-{}", item.id),
+        title: translator.t("report-item_history-title"),
+        generated_at: translator.format_generated_at(identity.generated_at),
+        report_id: identity.report_id.clone(),
+        verify_url: identity.verify_url.clone(),
         item: ReportItem {
             name: item.name,
             serial_number: item.serial_number,
             item_kind: item_kind_name,
             checklist: checklist_name,
-            interval: interval_name.map(|code| single_item_history::humanize_code(&code)),
-            created_at: single_item_history::format_timestamp(item.created_at),
-            updated_at: single_item_history::format_timestamp(item.updated_at),
+            interval: interval_name.map(|code| translator.code(&code)),
+            created_at: translator.format_datetime(item.created_at),
+            updated_at: translator.format_datetime(item.updated_at),
             last_checked_at: item
                 .last_checked_at
-                .map(single_item_history::format_timestamp),
-            expiry: item.expiry.map(single_item_history::format_timestamp),
+                .map(|value| translator.format_datetime(value)),
+            expiry: item.expiry.map(|value| translator.format_date(&value)),
         },
         metadata: metadata
             .into_iter()
@@ -893,11 +1297,9 @@ fn build_single_item_history_report(details: ItemDetailsData) -> SingleItemHisto
         checks: checks
             .into_iter()
             .map(|check| ReportCheck {
-                checked_at: single_item_history::format_timestamp(check.check.checked_at),
+                checked_at: translator.format_datetime(check.check.checked_at),
                 checked_by: check.checked_by,
-                overall_result: check
-                    .result_code
-                    .map(|code| single_item_history::humanize_code(&code)),
+                overall_result: check.result_code.map(|code| translator.code(&code)),
                 notes: check.check.notes,
                 steps: check
                     .steps
@@ -905,9 +1307,7 @@ fn build_single_item_history_report(details: ItemDetailsData) -> SingleItemHisto
                     .map(|step| ReportStep {
                         position: step.position,
                         name: step.name,
-                        result: step
-                            .result_code
-                            .map(|code| single_item_history::humanize_code(&code)),
+                        result: step.result_code.map(|code| translator.code(&code)),
                         notes: step.notes,
                     })
                     .collect(),
@@ -920,9 +1320,10 @@ fn build_single_item_history_report(details: ItemDetailsData) -> SingleItemHisto
 pub async fn list_item_kinds(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
 ) -> Result<Response> {
-    let item_kinds = ctx.get_item_kinds()?;
-    let checklists = ctx.get_checklists()?;
+    let item_kinds = ctx.get_item_kinds(tenant)?;
+    let checklists = ctx.get_checklists(tenant)?;
     let intervals = ctx.get_intervals()?;
     let expiries = ctx.get_expiries()?;
     let kind_ids = item_kinds.keys().copied().collect::<Vec<_>>();
@@ -955,15 +1356,16 @@ pub async fn show_item_kind(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
 ) -> Result<Response> {
-    let Some(kind) = inventory_item_kinds::Entity::find_by_id(id)
+    let Some(kind) = inventory_item_kinds::Entity::find_by_id_in_tenant(tenant, id)
         .one(&ctx.db)
         .await?
     else {
         return Err(loco_rs::Error::NotFound);
     };
 
-    let checklists = ctx.get_checklists()?;
+    let checklists = ctx.get_checklists(tenant)?;
     let intervals = ctx.get_intervals()?;
     let expiries = ctx.get_expiries()?;
     let metadata_fields = load_metadata_field_names_by_kind_ids(&ctx.db, vec![id]).await?;
@@ -975,11 +1377,12 @@ pub async fn show_item_kind(
         metadata_fields: metadata_fields.get(&id).cloned().unwrap_or_default(),
         kind,
     };
+    let responsibility = ResponsibilityView::for_kind(&ctx, &detail.kind).await?;
 
     format::render().view(
         &v,
         "inventory/item_kind_details.html",
-        data!({ "item_kind": detail }),
+        data!({ "item_kind": detail, "responsibility": responsibility }),
     )
 }
 
@@ -987,8 +1390,9 @@ pub async fn show_item_kind(
 pub async fn add_item(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
 ) -> Result<Response> {
-    render_inventory_item_form(&v, &ctx, None, "/inventory/add".to_string()).await
+    render_inventory_item_form(&v, &ctx, tenant, None, "/inventory/add".to_string()).await
 }
 
 #[derive(serde::Deserialize)]
@@ -1008,57 +1412,146 @@ pub struct AddItemPostParams {
 #[debug_handler]
 pub async fn add_item_post(
     State(ctx): State<AppContext>,
+    RequestTimezone(tz): RequestTimezone,
+    RequestTenant(tenant): RequestTenant,
     HtmlForm(params): HtmlForm<AddItemPostParams>,
 ) -> Result<Response> {
-    let AddItemPostParams {
-        name,
-        serial_number,
-        checklist_id,
-        interval_id,
-        item_kind_id,
-        expiry,
-        metadata_field_ids,
-        metadata_values,
-    } = params;
-    if !ctx.get_item_kinds()?.contains_key(&item_kind_id) {
-        return Err(loco_rs::Error::BadRequest("Unknown item kind".to_string()));
+    let input = ItemInput::from_form(params, tz)?;
+    create_item(&ctx, tenant, input).await?;
+    format::redirect("/inventory/list")
+}
+
+pub(crate) struct ItemInput {
+    pub name: String,
+    pub serial_number: Option<String>,
+    pub item_kind_id: i32,
+    pub checklist_id: i32,
+    pub interval_id: i32,
+    pub expiry: Option<DateTimeWithTimeZone>,
+    pub metadata_values_by_field_id: HashMap<i32, String>,
+}
+
+impl ItemInput {
+    /// Checks that the kind and checklist belong to `tenant`.
+    fn validate(&self, ctx: &AppContext, tenant: i32) -> Result<()> {
+        if !ctx.get_item_kinds(tenant)?.contains_key(&self.item_kind_id) {
+            return Err(loco_rs::Error::BadRequest("Unknown item kind".to_string()));
+        }
+        if !ctx.get_checklists(tenant)?.contains_key(&self.checklist_id) {
+            return Err(loco_rs::Error::BadRequest("Unknown checklist".to_string()));
+        }
+        Ok(())
     }
-    let submitted_metadata_values = metadata_values_from_form(metadata_field_ids, metadata_values);
-
-    let expiry = if let Some(expiry) = expiry {
-        let naive_date = chrono::NaiveDate::parse_from_str(&expiry, "%Y-%m-%d")
-            .map_err(|e| loco_rs::Error::BadRequest(e.to_string()))?;
-        let naive_datetime = naive_date.and_hms_opt(0, 0, 0).ok_or_else(|| {
-            loco_rs::Error::BadRequest("expiry date is outside valid range".to_string())
-        })?;
-        Some(naive_datetime.and_utc().into())
-    } else {
-        None
-    };
 
-    let serial_number = serial_number.clean();
+    fn from_form(params: AddItemPostParams, tz: Tz) -> Result<Self> {
+        let AddItemPostParams {
+            name,
+            serial_number,
+            checklist_id,
+            interval_id,
+            item_kind_id,
+            expiry,
+            metadata_field_ids,
+            metadata_values,
+        } = params;
+
+        Ok(Self {
+            name,
+            serial_number: serial_number.clean(),
+            item_kind_id,
+            checklist_id,
+            interval_id,
+            expiry: expiry
+                .map(|expiry| parse_expiry_date(&expiry, tz))
+                .transpose()?,
+            metadata_values_by_field_id: metadata_values_from_form(
+                metadata_field_ids,
+                metadata_values,
+            ),
+        })
+    }
+}
+
+pub(crate) async fn create_item(
+    ctx: &AppContext,
+    tenant: i32,
+    input: ItemInput,
+) -> Result<inventory_items::Model> {
+    input.validate(ctx, tenant)?;
+
     let item = crate::models::inventory_items::ActiveModel {
-        name: ActiveValue::set(name),
-        serial_number: ActiveValue::set(serial_number),
-        inventory_item_kind_id: ActiveValue::set(item_kind_id),
-        checklist_id: ActiveValue::set(checklist_id),
-        interval_id: ActiveValue::set(interval_id),
-        expiry: ActiveValue::set(expiry),
+        tenant_id: ActiveValue::set(tenant),
+        name: ActiveValue::set(input.name),
+        serial_number: ActiveValue::set(input.serial_number),
+        inventory_item_kind_id: ActiveValue::set(input.item_kind_id),
+        checklist_id: ActiveValue::set(input.checklist_id),
+        interval_id: ActiveValue::set(input.interval_id),
+        expiry: ActiveValue::set(input.expiry),
         ..Default::default()
     };
     let created_item = item.insert(&ctx.db).await?;
-    let metadata_fields = load_metadata_fields_by_kind_ids(&ctx.db, vec![item_kind_id]).await?;
+    let metadata_fields =
+        load_metadata_fields_by_kind_ids(&ctx.db, vec![input.item_kind_id]).await?;
     replace_metadata_values_for_item_by_field_id(
         &ctx.db,
         created_item.id,
         &metadata_fields
-            .get(&item_kind_id)
+            .get(&input.item_kind_id)
             .cloned()
             .unwrap_or_default(),
-        &submitted_metadata_values,
+        &input.metadata_values_by_field_id,
     )
     .await?;
-    format::redirect("/inventory/list")
+    search::index_item(&ctx.db, created_item.id).await?;
+    webhooks::notify(
+        ctx,
+        created_item.tenant_id,
+        WebhookEvent::ItemCreated,
+        webhooks::item_data(&created_item),
+    )
+    .await;
+
+    Ok(created_item)
+}
+
+pub(crate) async fn update_item(
+    ctx: &AppContext,
+    existing: inventory_items::Model,
+    input: ItemInput,
+) -> Result<inventory_items::Model> {
+    input.validate(ctx, existing.tenant_id)?;
+
+    let existing_item_id = existing.id;
+    let mut item: inventory_items::ActiveModel = existing.into();
+    item.name = ActiveValue::set(input.name);
+    item.serial_number = ActiveValue::set(input.serial_number);
+    item.inventory_item_kind_id = ActiveValue::set(input.item_kind_id);
+    item.checklist_id = ActiveValue::set(input.checklist_id);
+    item.interval_id = ActiveValue::set(input.interval_id);
+    item.expiry = ActiveValue::set(input.expiry);
+    let updated_item = item.update(&ctx.db).await?;
+    let metadata_fields =
+        load_metadata_fields_by_kind_ids(&ctx.db, vec![input.item_kind_id]).await?;
+    replace_metadata_values_for_item_by_field_id(
+        &ctx.db,
+        existing_item_id,
+        &metadata_fields
+            .get(&input.item_kind_id)
+            .cloned()
+            .unwrap_or_default(),
+        &input.metadata_values_by_field_id,
+    )
+    .await?;
+    search::index_item(&ctx.db, existing_item_id).await?;
+    webhooks::notify(
+        ctx,
+        updated_item.tenant_id,
+        WebhookEvent::ItemUpdated,
+        webhooks::item_data(&updated_item),
+    )
+    .await;
+
+    Ok(updated_item)
 }
 
 #[debug_handler]
@@ -1066,86 +1559,53 @@ pub async fn edit_item(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
 ) -> Result<Response> {
-    let Some(item) = inventory_items::Entity::find_by_id(id).one(&ctx.db).await? else {
-        return Err(loco_rs::Error::NotFound);
-    };
+    let item = load_item(&ctx, tenant, &scope, id).await?;
 
-    render_inventory_item_form(&v, &ctx, Some(item), format!("/inventory/item/{id}/edit")).await
+    render_inventory_item_form(
+        &v,
+        &ctx,
+        tenant,
+        Some(item),
+        format!("/inventory/item/{id}/edit"),
+    )
+    .await
 }
 
 #[debug_handler]
 pub async fn edit_item_post(
     State(ctx): State<AppContext>,
+    RequestTimezone(tz): RequestTimezone,
     Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
     HtmlForm(params): HtmlForm<AddItemPostParams>,
 ) -> Result<Response> {
-    let Some(existing) = inventory_items::Entity::find_by_id(id).one(&ctx.db).await? else {
-        return Err(loco_rs::Error::NotFound);
-    };
-    let existing_item_id = existing.id;
+    let existing = load_item(&ctx, tenant, &scope, id).await?;
 
-    let AddItemPostParams {
-        name,
-        serial_number,
-        checklist_id,
-        interval_id,
-        item_kind_id,
-        expiry,
-        metadata_field_ids,
-        metadata_values,
-    } = params;
-    if !ctx.get_item_kinds()?.contains_key(&item_kind_id) {
-        return Err(loco_rs::Error::BadRequest("Unknown item kind".to_string()));
-    }
-    let submitted_metadata_values = metadata_values_from_form(metadata_field_ids, metadata_values);
-
-    let expiry = if let Some(expiry) = expiry {
-        let naive_date = chrono::NaiveDate::parse_from_str(&expiry, "%Y-%m-%d")
-            .map_err(|e| loco_rs::Error::BadRequest(e.to_string()))?;
-        let naive_datetime = naive_date.and_hms_opt(0, 0, 0).ok_or_else(|| {
-            loco_rs::Error::BadRequest("expiry date is outside valid range".to_string())
-        })?;
-        Some(naive_datetime.and_utc().into())
-    } else {
-        None
-    };
-
-    let serial_number = serial_number.clean();
-    let mut item: inventory_items::ActiveModel = existing.into();
-    item.name = ActiveValue::set(name);
-    item.serial_number = ActiveValue::set(serial_number);
-    item.inventory_item_kind_id = ActiveValue::set(item_kind_id);
-    item.checklist_id = ActiveValue::set(checklist_id);
-    item.interval_id = ActiveValue::set(interval_id);
-    item.expiry = ActiveValue::set(expiry);
-    item.update(&ctx.db).await?;
-    let metadata_fields = load_metadata_fields_by_kind_ids(&ctx.db, vec![item_kind_id]).await?;
-    replace_metadata_values_for_item_by_field_id(
-        &ctx.db,
-        existing_item_id,
-        &metadata_fields
-            .get(&item_kind_id)
-            .cloned()
-            .unwrap_or_default(),
-        &submitted_metadata_values,
-    )
-    .await?;
+    let input = ItemInput::from_form(params, tz)?;
+    update_item(&ctx, existing, input).await?;
 
     format::redirect(&format!("/inventory/item/{id}"))
 }
 
-#[debug_handler]
-pub async fn show_item_check(
-    ViewEngine(v): ViewEngine<TeraView>,
-    State(ctx): State<AppContext>,
-    Path(id): Path<i32>,
-) -> Result<Response> {
-    let Some(item) = inventory_items::Entity::find_by_id(id).one(&ctx.db).await? else {
-        return Err(loco_rs::Error::NotFound);
-    };
+/// Everything the check forms need to record a check for one item.
+#[derive(serde::Serialize)]
+pub(crate) struct ItemCheckForm {
+    pub item: inventory_items::Model,
+    pub checklist: checklists::Model,
+    pub steps: Vec<checklist_steps::Model>,
+    pub results: Vec<results::Model>,
+    pub users: Vec<users::Model>,
+}
 
-    let checklists = ctx.get_checklists()?;
+pub(crate) async fn load_item_check_form(
+    ctx: &AppContext,
+    item: inventory_items::Model,
+) -> Result<ItemCheckForm> {
+    let checklists = ctx.get_checklists(item.tenant_id)?;
     let checklist = checklists
         .get(&item.checklist_id)
         .cloned()
@@ -1158,35 +1618,52 @@ pub async fn show_item_check(
         .await?;
 
     let results = ctx.get_results()?.values().cloned().collect::<Vec<_>>();
-    let users = ctx.get_users()?.values().cloned().collect::<Vec<_>>();
+    let users = ctx
+        .get_users(item.tenant_id)?
+        .values()
+        .cloned()
+        .collect::<Vec<_>>();
 
-    format::render().view(
-        &v,
-        "inventory/check_item.html",
-        data!({
-            "item": item,
-            "checklist": checklist,
-            "steps": steps,
-            "results": results,
-            "users": users,
-        }),
-    )
+    Ok(ItemCheckForm {
+        item,
+        checklist,
+        steps,
+        results,
+        users,
+    })
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[debug_handler]
+pub async fn show_item_check(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+) -> Result<Response> {
+    let item = load_item(&ctx, tenant, &scope, id).await?;
+
+    let form = load_item_check_form(&ctx, item).await?;
+
+    format::render().view(&v, "inventory/check_item.html", data!(form))
+}
+
+#[derive(Debug, serde::Deserialize, utoipa::ToSchema)]
 pub struct StepCheckPayload {
     pub checklist_step_id: i32,
     pub result_id: i32,
     pub notes: Option<String>,
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Deserialize, utoipa::ToSchema)]
 pub struct PerformCheckPayload {
     pub checked_by: i32,
     pub result_id: i32,
     pub notes: Option<String>,
     #[serde(default)]
     pub steps: Vec<StepCheckPayload>,
+    /// Signature of the inspector, captured as a PNG data URL.
+    pub signature: Option<String>,
 }
 
 impl PerformCheckPayload {
@@ -1208,7 +1685,7 @@ impl PerformCheckPayload {
             ));
         }
 
-        let users = ctx.get_users()?;
+        let users = ctx.get_users(checklist.tenant_id)?;
         if !users.contains_key(&self.checked_by) {
             return Err(loco_rs::Error::BadRequest(
                 "Unknown user for checklist".to_string(),
@@ -1231,6 +1708,16 @@ impl PerformCheckPayload {
             .map(|step| (step.id, step))
             .collect::<HashMap<_, _>>();
 
+        let signature = self.signature.clean();
+        if signature
+            .as_deref()
+            .is_some_and(|signature| inventory_item_checks::decode_signature(signature).is_none())
+        {
+            return Err(loco_rs::Error::BadRequest(
+                "Signature must be a PNG image".to_string(),
+            ));
+        }
+
         let notes = self.notes.clean();
         let mut steps = Vec::new();
 
@@ -1260,6 +1747,7 @@ impl PerformCheckPayload {
             notes,
             checked_by: self.checked_by,
             result_id: self.result_id,
+            signature,
         })
     }
 }
@@ -1268,19 +1756,34 @@ impl PerformCheckPayload {
 pub async fn submit_item_check(
     State(ctx): State<AppContext>,
     Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
     Json(payload): Json<PerformCheckPayload>,
 ) -> Result<Response> {
-    let Some(item) = inventory_items::Entity::find_by_id(id).one(&ctx.db).await? else {
-        return Err(loco_rs::Error::NotFound);
-    };
+    let item = load_item(&ctx, tenant, &scope, id).await?;
+
+    let item_check = record_item_check(&ctx, item, payload).await?;
+
+    format::json(data!({
+        "status": "ok",
+        "check_id": item_check.id
+    }))
+}
 
-    let checklists = ctx.get_checklists()?;
+/// Validates `payload` against the item's checklist and stores the check
+/// together with a snapshot of the checklist steps.
+pub(crate) async fn record_item_check(
+    ctx: &AppContext,
+    item: inventory_items::Model,
+    payload: PerformCheckPayload,
+) -> Result<inventory_item_checks::Model> {
+    let checklists = ctx.get_checklists(item.tenant_id)?;
     let checklist = checklists
         .get(&item.checklist_id)
         .cloned()
         .ok_or_else(|| loco_rs::Error::InternalServerError)?;
 
-    let validated = payload.validate(&ctx, &checklist).await?;
+    let validated = payload.validate(ctx, &checklist).await?;
 
     let trx = ctx.db.begin().await?;
 
@@ -1314,14 +1817,15 @@ pub async fn submit_item_check(
         executed_checklist_id: ActiveValue::set(executed_checklist.id),
         checked_by: ActiveValue::set(validated.checked_by),
         result_id: ActiveValue::set(validated.result_id),
+        signature: ActiveValue::set(validated.signature.clone()),
         ..Default::default()
     }
     .insert(&trx)
     .await?;
 
-    let mut item_update = item.clone().into_active_model();
+    let mut item_update = item.into_active_model();
     item_update.last_checked_at = ActiveValue::set(Some(item_check.checked_at));
-    item_update.update(&trx).await?;
+    let item = item_update.update(&trx).await?;
 
     for step in validated.steps {
         let Some(executed_step_id) = executed_step_map.get(&step.checklist_step_id) else {
@@ -1339,15 +1843,37 @@ pub async fn submit_item_check(
     }
 
     trx.commit().await?;
+    search::index_check(&ctx.db, item_check.id).await?;
+    webhooks::notify(
+        ctx,
+        item.tenant_id,
+        WebhookEvent::CheckSubmitted,
+        webhooks::check_data(
+            &item,
+            &item_check,
+            ctx.get_results()?.get(&item_check.result_id),
+        ),
+    )
+    .await;
 
-    format::json(data!({
-        "status": "ok",
-        "check_id": item_check.id
-    }))
+    Ok(item_check)
 }
 
 #[debug_handler]
-pub async fn remove_item(State(ctx): State<AppContext>, Path(id): Path<i32>) -> Result<Response> {
+pub async fn remove_item(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+) -> Result<Response> {
+    let item = load_item(&ctx, tenant, &scope, id).await?;
+    delete_item(&ctx, item).await?;
+
+    format::json(data!({ "status": "ok" }))
+}
+
+pub(crate) async fn delete_item(ctx: &AppContext, item: inventory_items::Model) -> Result<()> {
+    let id = item.id;
     let check_count = inventory_item_checks::Entity::find()
         .filter(InventoryItemChecksColumn::InventoryItemId.eq(id))
         .count(&ctx.db)
@@ -1359,22 +1885,35 @@ pub async fn remove_item(State(ctx): State<AppContext>, Path(id): Path<i32>) ->
         ));
     }
 
-    let deleted = inventory_items::Entity::delete_by_id(id)
+    inventory_items::Entity::delete_by_id(id)
         .exec(&ctx.db)
         .await?;
-    if deleted.rows_affected == 0 {
-        return Err(loco_rs::Error::NotFound);
-    }
+    search::remove(&ctx.db, SearchEntity::Item, id).await?;
+    webhooks::notify(
+        ctx,
+        item.tenant_id,
+        WebhookEvent::ItemRetired,
+        webhooks::item_data(&item),
+    )
+    .await;
 
-    format::json(data!({ "status": "ok" }))
+    Ok(())
 }
 
 #[debug_handler]
 pub async fn add_item_kind_new(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
 ) -> Result<Response> {
-    render_item_kind_form(&v, &ctx, None, "/inventory/item_kinds/new".to_string()).await
+    render_item_kind_form(
+        &v,
+        &ctx,
+        tenant,
+        None,
+        "/inventory/item_kinds/new".to_string(),
+    )
+    .await
 }
 
 pub struct AddItemKindPostParams {
@@ -1458,7 +1997,7 @@ impl<'de> serde::Deserialize<'de> for AddItemKindPostParams {
                             match map.next_value::<OneOrManyStrings>()? {
                                 OneOrManyStrings::One(value) => metadata_field_names.push(value),
                                 OneOrManyStrings::Many(values) => {
-                                    metadata_field_names.extend(values)
+                                    metadata_field_names.extend(values);
                                 }
                             }
                         }
@@ -1494,21 +2033,67 @@ impl<'de> serde::Deserialize<'de> for AddItemKindPostParams {
 #[debug_handler]
 pub async fn add_item_kind_new_post(
     State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
     HtmlForm(params): HtmlForm<AddItemKindPostParams>,
 ) -> Result<Response> {
-    let AddItemKindPostParams {
+    create_item_kind(&ctx, tenant, params.into()).await?;
+    format::redirect("/inventory/item_kinds")
+}
+
+pub(crate) struct ItemKindInput {
+    pub name: String,
+    pub test_standard: String,
+    pub default_checklist_id: i32,
+    pub default_interval_id: i32,
+    pub default_expiry_id: i32,
+    pub metadata_field_names: Vec<String>,
+}
+
+impl From<AddItemKindPostParams> for ItemKindInput {
+    fn from(params: AddItemKindPostParams) -> Self {
+        Self {
+            name: params.name,
+            test_standard: params.test_standard,
+            default_checklist_id: params.default_checklist_id,
+            default_interval_id: params.default_interval_id,
+            default_expiry_id: params.default_expiry_id,
+            metadata_field_names: params.metadata_field_names.unwrap_or_default(),
+        }
+    }
+}
+
+impl ItemKindInput {
+    /// Checks that the default checklist belongs to `tenant`.
+    fn validate(&self, ctx: &AppContext, tenant: i32) -> Result<()> {
+        if !ctx
+            .get_checklists(tenant)?
+            .contains_key(&self.default_checklist_id)
+        {
+            return Err(loco_rs::Error::BadRequest("Unknown checklist".to_string()));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn create_item_kind(
+    ctx: &AppContext,
+    tenant: i32,
+    input: ItemKindInput,
+) -> Result<inventory_item_kinds::Model> {
+    input.validate(ctx, tenant)?;
+    let ItemKindInput {
         name,
         test_standard,
         default_checklist_id,
         default_interval_id,
         default_expiry_id,
         metadata_field_names,
-    } = params;
-    let metadata_field_names = inventory_item_kind_metadata_fields::normalize_metadata_field_names(
-        metadata_field_names.unwrap_or_default(),
-    );
+    } = input;
+    let metadata_field_names =
+        inventory_item_kind_metadata_fields::normalize_metadata_field_names(metadata_field_names);
     let trx = ctx.db.begin().await?;
     let item = crate::models::inventory_item_kinds::ActiveModel {
+        tenant_id: ActiveValue::set(tenant),
         name: ActiveValue::set(name.trim().to_string()),
         test_standard: ActiveValue::set(test_standard.trim().to_string()),
         default_checklist_id: ActiveValue::set(default_checklist_id),
@@ -1519,8 +2104,9 @@ pub async fn add_item_kind_new_post(
     let created_kind = item.insert(&trx).await?;
     replace_metadata_fields_for_kind(&trx, created_kind.id, &metadata_field_names).await?;
     trx.commit().await?;
-    refresh_item_kinds_cache(&ctx).await?;
-    format::redirect("/inventory/item_kinds")
+    refresh_item_kinds_cache(ctx).await?;
+
+    Ok(created_kind)
 }
 
 #[debug_handler]
@@ -1528,17 +2114,14 @@ pub async fn edit_item_kind(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
     Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
 ) -> Result<Response> {
-    let Some(item_kind) = inventory_item_kinds::Entity::find_by_id(id)
-        .one(&ctx.db)
-        .await?
-    else {
-        return Err(loco_rs::Error::NotFound);
-    };
+    let item_kind = load_item_kind(&ctx, tenant, id).await?;
 
     render_item_kind_form(
         &v,
         &ctx,
+        tenant,
         Some(item_kind),
         format!("/inventory/item_kinds/{id}/edit"),
     )
@@ -1549,26 +2132,45 @@ pub async fn edit_item_kind(
 pub async fn edit_item_kind_post(
     State(ctx): State<AppContext>,
     Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
     HtmlForm(params): HtmlForm<AddItemKindPostParams>,
 ) -> Result<Response> {
-    let Some(existing) = inventory_item_kinds::Entity::find_by_id(id)
+    let existing = load_item_kind(&ctx, tenant, id).await?;
+
+    update_item_kind(&ctx, existing, params.into()).await?;
+
+    format::redirect("/inventory/item_kinds")
+}
+
+/// Loads item kind `id` of `tenant`.
+pub(crate) async fn load_item_kind(
+    ctx: &AppContext,
+    tenant: i32,
+    id: i32,
+) -> Result<inventory_item_kinds::Model> {
+    inventory_item_kinds::Entity::find_by_id_in_tenant(tenant, id)
         .one(&ctx.db)
         .await?
-    else {
-        return Err(loco_rs::Error::NotFound);
-    };
+        .ok_or(loco_rs::Error::NotFound)
+}
 
-    let AddItemKindPostParams {
+pub(crate) async fn update_item_kind(
+    ctx: &AppContext,
+    existing: inventory_item_kinds::Model,
+    input: ItemKindInput,
+) -> Result<inventory_item_kinds::Model> {
+    input.validate(ctx, existing.tenant_id)?;
+    let id = existing.id;
+    let ItemKindInput {
         name,
         test_standard,
         default_checklist_id,
         default_interval_id,
         default_expiry_id,
         metadata_field_names,
-    } = params;
-    let metadata_field_names = inventory_item_kind_metadata_fields::normalize_metadata_field_names(
-        metadata_field_names.unwrap_or_default(),
-    );
+    } = input;
+    let metadata_field_names =
+        inventory_item_kind_metadata_fields::normalize_metadata_field_names(metadata_field_names);
     let item_ids = inventory_items::Entity::find()
         .filter(InventoryItemsColumn::InventoryItemKindId.eq(id))
         .all(&ctx.db)
@@ -1585,7 +2187,7 @@ pub async fn edit_item_kind_post(
     item_kind.default_checklist_id = ActiveValue::set(default_checklist_id);
     item_kind.default_interval_id = ActiveValue::set(default_interval_id);
     item_kind.default_expiry_id = ActiveValue::set(default_expiry_id);
-    item_kind.update(&trx).await?;
+    let updated_kind = item_kind.update(&trx).await?;
     replace_metadata_fields_for_kind(&trx, id, &metadata_field_names).await?;
     rebuild_metadata_values_for_kind_items(
         &trx,
@@ -1595,17 +2197,26 @@ pub async fn edit_item_kind_post(
     )
     .await?;
     trx.commit().await?;
+    search::index_kind_items(&ctx.db, id).await?;
 
-    refresh_item_kinds_cache(&ctx).await?;
+    refresh_item_kinds_cache(ctx).await?;
 
-    format::redirect("/inventory/item_kinds")
+    Ok(updated_kind)
 }
 
 #[debug_handler]
 pub async fn remove_item_kind(
     State(ctx): State<AppContext>,
     Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
 ) -> Result<Response> {
+    delete_item_kind(&ctx, tenant, id).await?;
+
+    format::json(data!({ "status": "ok" }))
+}
+
+pub(crate) async fn delete_item_kind(ctx: &AppContext, tenant: i32, id: i32) -> Result<()> {
+    load_item_kind(ctx, tenant, id).await?;
     let usage_count = inventory_items::Entity::find()
         .filter(InventoryItemsColumn::InventoryItemKindId.eq(id))
         .count(&ctx.db)
@@ -1617,16 +2228,13 @@ pub async fn remove_item_kind(
         ));
     }
 
-    let deleted = inventory_item_kinds::Entity::delete_by_id(id)
+    inventory_item_kinds::Entity::delete_by_id(id)
         .exec(&ctx.db)
         .await?;
-    if deleted.rows_affected == 0 {
-        return Err(loco_rs::Error::NotFound);
-    }
 
-    refresh_item_kinds_cache(&ctx).await?;
+    refresh_item_kinds_cache(ctx).await?;
 
-    format::json(data!({ "status": "ok" }))
+    Ok(())
 }
 
 pub fn routes() -> Routes {
@@ -1636,6 +2244,10 @@ pub fn routes() -> Routes {
         .add("/add", post(add_item_post))
         .add("/item/{id}", get(show_item_details))
         .add("/item/{id}/report.pdf", get(download_item_report))
+        .add(
+            "/item/{id}/checks/{check_id}/certificate.pdf",
+            get(download_check_certificate),
+        )
         .add("/item/{id}/edit", get(edit_item))
         .add("/item/{id}/check", get(show_item_check))
         .add("/item/{id}/check", post(submit_item_check))
@@ -1673,6 +2285,8 @@ mod tests {
             inventory_item_kind_id,
             checklist_id: 1,
             interval_id: 1,
+            tenant_id: 1,
+            next_due_at: None,
         }
     }
 
@@ -0,0 +1,164 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum::{
+    http::{header, HeaderValue},
+    response::IntoResponse,
+};
+use loco_rs::prelude::*;
+use sea_orm::{ColumnTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controllers::scan::scan_url,
+    exts::OptionStringExt,
+    i18n::RequestLocale,
+    initializers::app_cache::AppData,
+    models::{_entities::inventory_items::Column as InventoryItemsColumn, inventory_items},
+    reports::labels::{self, LabelCode, LabelContent, LabelItem, LabelLayout, LabelSheet},
+    tenant::{RequestItemScope, RequestTenant},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct LabelSheetQuery {
+    /// Comma separated inventory item ids; takes precedence over `kind_id`.
+    pub ids: Option<String>,
+    pub kind_id: Option<String>,
+    #[serde(default)]
+    pub layout: LabelLayout,
+    #[serde(default)]
+    pub code: LabelCode,
+    #[serde(default)]
+    pub content: LabelContent,
+    pub skip: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct LabelOptionView {
+    value: &'static str,
+    name: &'static str,
+}
+
+fn parse_id(value: &str) -> Result<i32> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| Error::BadRequest(format!("invalid id: {value}")))
+}
+
+#[debug_handler]
+pub async fn new(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
+) -> Result<Response> {
+    let item_kinds = ctx
+        .get_item_kinds(tenant)?
+        .into_values()
+        .collect::<Vec<_>>();
+    let layouts = LabelLayout::ALL
+        .map(|layout| LabelOptionView {
+            value: layout.as_str(),
+            name: layout.name(),
+        })
+        .to_vec();
+
+    format::render().view(
+        &v,
+        "labels/new.html",
+        data!({
+            "item_kinds": item_kinds,
+            "layouts": layouts,
+            "codes": LabelCode::ALL.map(LabelCode::as_str),
+            "contents": LabelContent::ALL.map(LabelContent::as_str),
+        }),
+    )
+}
+
+#[debug_handler]
+pub async fn download_sheet(
+    State(ctx): State<AppContext>,
+    RequestLocale(translator): RequestLocale,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+    Query(query): Query<LabelSheetQuery>,
+) -> Result<Response> {
+    let mut select = inventory_items::Entity::find_in_tenant(tenant)
+        .filter(scope.condition(InventoryItemsColumn::Id))
+        .order_by_asc(InventoryItemsColumn::Name)
+        .order_by_asc(InventoryItemsColumn::Id);
+    if let Some(ids) = query.ids.clean() {
+        let ids = ids
+            .split(',')
+            .filter(|id| !id.trim().is_empty())
+            .map(parse_id)
+            .collect::<Result<Vec<_>>>()?;
+        select = select.filter(InventoryItemsColumn::Id.is_in(ids));
+    } else if let Some(kind_id) = query.kind_id.clean() {
+        select = select.filter(InventoryItemsColumn::InventoryItemKindId.eq(parse_id(&kind_id)?));
+    }
+    let items = select.all(&ctx.db).await?;
+    if items.is_empty() {
+        return Err(Error::BadRequest("no inventory items selected".to_string()));
+    }
+
+    let skip = match query.skip.clean() {
+        Some(skip) => skip
+            .parse::<u32>()
+            .map_err(|_| Error::BadRequest(format!("invalid skip: {skip}")))?,
+        None => 0,
+    };
+    if skip >= query.layout.geometry().labels_per_sheet() {
+        return Err(Error::BadRequest(
+            "skip must be smaller than the number of labels per sheet".to_string(),
+        ));
+    }
+
+    let labels = items
+        .into_iter()
+        .map(|item| {
+            let next_due = item.next_due_at.map(|due| translator.format_date(&due));
+            let code_data = match (query.content, &item.serial_number) {
+                (LabelContent::Serial, Some(serial)) => serial.clone(),
+                _ => scan_url(&ctx, &item),
+            };
+            LabelItem {
+                name: item.name,
+                serial_number: item.serial_number,
+                next_due,
+                code_data,
+            }
+        })
+        .collect();
+
+    let pdf = labels::render_pdf(
+        &LabelSheet {
+            layout: query.layout,
+            code: query.code,
+            skip,
+            labels,
+        },
+        &translator,
+    )?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/pdf"),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_static("attachment; filename=\"inventory-labels.pdf\""),
+            ),
+        ],
+        pdf,
+    )
+        .into_response())
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("labels/")
+        .add("new", get(new))
+        .add("sheet.pdf", get(download_sheet))
+}
@@ -0,0 +1,82 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum::http::header;
+use loco_rs::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    i18n,
+    timezone::{self, RequestTimezone},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct SwitchQuery {
+    pub redirect: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimezoneForm {
+    pub timezone: String,
+}
+
+/// Only follows local paths, `//host` and `/\host` would leave the site.
+fn local_redirect(redirect: Option<String>) -> String {
+    redirect
+        .filter(|path| path.starts_with('/') && !matches!(path.chars().nth(1), Some('/' | '\\')))
+        .unwrap_or_else(|| "/".to_string())
+}
+
+/// Remembers the language picked in the navbar in a cookie and returns to the
+/// page it was picked on.
+#[debug_handler]
+pub async fn switch(
+    Path(lang): Path<String>,
+    Query(query): Query<SwitchQuery>,
+) -> Result<Response> {
+    let locale = i18n::match_locale(&lang).ok_or(Error::NotFound)?;
+    format::render()
+        .header(header::SET_COOKIE, i18n::lang_cookie(&locale))
+        .redirect(&local_redirect(query.redirect))
+}
+
+#[debug_handler]
+pub async fn preferences(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestTimezone(tz): RequestTimezone,
+) -> Result<Response> {
+    let timezones = chrono_tz::TZ_VARIANTS
+        .iter()
+        .map(|timezone| timezone.name())
+        .collect::<Vec<_>>();
+    format::render().view(
+        &v,
+        "locale/preferences.html",
+        data!({
+            "timezone": tz.name(),
+            "site_timezone": timezone::site_timezone(&ctx.config).name(),
+            "timezones": timezones,
+        }),
+    )
+}
+
+/// Remembers the time zone dates are entered and shown in for this browser.
+#[debug_handler]
+pub async fn set_timezone(
+    Query(query): Query<SwitchQuery>,
+    Form(form): Form<TimezoneForm>,
+) -> Result<Response> {
+    let tz = timezone::parse_timezone(&form.timezone)
+        .ok_or_else(|| Error::BadRequest(format!("unknown time zone: {}", form.timezone)))?;
+    format::render()
+        .header(header::SET_COOKIE, timezone::timezone_cookie(tz))
+        .redirect(&local_redirect(query.redirect))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("locale/")
+        .add("preferences", get(preferences))
+        .add("timezone", post(set_timezone))
+        .add("{lang}", get(switch))
+}
@@ -1,8 +1,23 @@
+pub mod api;
+pub mod api_keys;
 pub mod auth;
+pub mod calendar;
 pub mod checklists;
 pub mod expiries;
 pub mod home;
 pub mod intervals;
 pub mod inventory;
+pub mod labels;
+pub mod locale;
+pub mod notifications;
+pub mod report_templates;
+pub mod reports;
+pub mod responsibilities;
+pub mod scan;
+pub mod search;
+pub mod teams;
+pub mod tenants;
 pub mod translations;
 pub mod users;
+pub mod verify;
+pub mod webhooks;
@@ -0,0 +1,61 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use loco_rs::prelude::*;
+use sea_orm::QueryOrder;
+
+use crate::{
+    initializers::app_cache::AppData,
+    models::{
+        notification_subscriptions::{
+            NotificationChannel, NotificationFrequency, NotificationLevel,
+        },
+        teams, webhook_endpoints,
+    },
+    tenant::RequestTenant,
+};
+
+/// Renders the subscription page. Subscriptions are loaded and changed
+/// client-side through `/api/v1/notifications/subscriptions` using the token
+/// stored at login.
+#[debug_handler]
+pub async fn list(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
+) -> Result<Response> {
+    let item_kinds = ctx
+        .get_item_kinds(tenant)?
+        .into_values()
+        .collect::<Vec<_>>();
+    let endpoints = webhook_endpoints::Entity::find_in_tenant(tenant)
+        .filter(webhook_endpoints::Column::Active.eq(true))
+        .order_by_asc(webhook_endpoints::Column::Name)
+        .all(&ctx.db)
+        .await?
+        .into_iter()
+        .map(|endpoint| data!({ "id": endpoint.id, "name": endpoint.name }))
+        .collect::<Vec<_>>();
+    let teams = teams::Entity::list(&ctx.db, tenant)
+        .await?
+        .into_iter()
+        .map(|team| data!({ "id": team.id, "name": team.name }))
+        .collect::<Vec<_>>();
+    format::render().view(
+        &v,
+        "notifications/list.html",
+        data!({
+            "item_kinds": item_kinds,
+            "endpoints": endpoints,
+            "teams": teams,
+            "levels": NotificationLevel::ALL.map(NotificationLevel::as_str),
+            "channels": NotificationChannel::ALL.map(NotificationChannel::as_str),
+            "frequencies": NotificationFrequency::ALL.map(NotificationFrequency::as_str),
+        }),
+    )
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("notifications/")
+        .add("list", get(list))
+}
@@ -0,0 +1,238 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum::{
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+};
+use loco_rs::prelude::*;
+use serde::Serialize;
+use typst::visualize::ImageFormat;
+
+use crate::{
+    i18n::{RequestLocale, Translator},
+    models::{generated_reports::ReportKind, report_templates},
+    reports::{self, CustomTemplate, TemplateDiagnostic, CUSTOM_LOGO_PATH},
+    tenant::RequestAdmin,
+};
+
+/// Largest accepted logo upload in bytes.
+const MAX_LOGO_SIZE: usize = 1024 * 1024;
+
+#[derive(Serialize)]
+struct TemplateRow {
+    kind: &'static str,
+    customized: bool,
+    updated_at: Option<DateTimeWithTimeZone>,
+}
+
+/// Fields of the template edit form.
+#[derive(Debug, Default)]
+struct TemplateForm {
+    action: String,
+    source: String,
+    logo: Option<Vec<u8>>,
+    remove_logo: bool,
+}
+
+async fn read_template_form(mut multipart: Multipart) -> Result<TemplateForm> {
+    let bad_request = |error: axum::extract::multipart::MultipartError| {
+        Error::BadRequest(format!("invalid form data: {error}"))
+    };
+
+    let mut form = TemplateForm::default();
+    while let Some(field) = multipart.next_field().await.map_err(bad_request)? {
+        match field.name() {
+            Some("action") => form.action = field.text().await.map_err(bad_request)?,
+            Some("source") => form.source = field.text().await.map_err(bad_request)?,
+            Some("remove_logo") => form.remove_logo = true,
+            Some("logo") => {
+                let logo = field.bytes().await.map_err(bad_request)?;
+                if !logo.is_empty() {
+                    form.logo = Some(logo.to_vec());
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(form)
+}
+
+fn validate_logo(logo: &[u8]) -> Result<()> {
+    if logo.len() > MAX_LOGO_SIZE {
+        return Err(Error::BadRequest(format!(
+            "Logo must not be larger than {} KiB",
+            MAX_LOGO_SIZE / 1024
+        )));
+    }
+    if ImageFormat::detect(logo).is_none() {
+        return Err(Error::BadRequest(
+            "Logo must be a PNG, JPEG, GIF, WebP, SVG or PDF image".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_kind(kind: &str) -> Result<ReportKind> {
+    ReportKind::parse(kind).ok_or(Error::NotFound)
+}
+
+fn render_edit(
+    v: &TeraView,
+    kind: ReportKind,
+    source: &str,
+    stored: Option<&report_templates::Model>,
+    diagnostics: &[TemplateDiagnostic],
+) -> Result<Response> {
+    let status = if diagnostics.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    format::render().status(status).view(
+        v,
+        "report_templates/edit.html",
+        data!({
+            "kind": kind.as_str(),
+            "source": source,
+            "customized": stored.is_some(),
+            "updated_at": stored.map(|template| template.updated_at),
+            "has_logo": stored.is_some_and(|template| template.logo.is_some()),
+            "logo_path": CUSTOM_LOGO_PATH,
+            "diagnostics": diagnostics,
+        }),
+    )
+}
+
+/// Compiles `template` with sample data off the async runtime.
+async fn preview(
+    kind: ReportKind,
+    template: CustomTemplate,
+    translator: Translator,
+) -> Result<std::result::Result<Vec<u8>, Vec<TemplateDiagnostic>>> {
+    tokio::task::spawn_blocking(move || reports::preview_pdf(kind, &template, &translator))
+        .await
+        .map_err(|err| Error::string(&format!("template preview failed: {err}")))
+}
+
+#[debug_handler]
+pub async fn list(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestAdmin(tenant): RequestAdmin,
+) -> Result<Response> {
+    let stored = report_templates::Entity::find_in_tenant(tenant)
+        .all(&ctx.db)
+        .await?;
+    let templates = ReportKind::ALL
+        .into_iter()
+        .map(|kind| {
+            let template = stored
+                .iter()
+                .find(|template| template.kind == kind.as_str());
+            TemplateRow {
+                kind: kind.as_str(),
+                customized: template.is_some(),
+                updated_at: template.map(|template| template.updated_at),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    format::render().view(
+        &v,
+        "report_templates/list.html",
+        data!({ "templates": templates }),
+    )
+}
+
+#[debug_handler]
+pub async fn edit(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestAdmin(tenant): RequestAdmin,
+    Path(kind): Path<String>,
+) -> Result<Response> {
+    let kind = parse_kind(&kind)?;
+    let stored = report_templates::Model::find_by_kind(&ctx.db, tenant, kind).await?;
+    let source = stored
+        .as_ref()
+        .map_or(reports::builtin_template(kind), |template| &template.source);
+    render_edit(&v, kind, source, stored.as_ref(), &[])
+}
+
+/// Validates the submitted template by compiling it with sample data, then
+/// either returns the preview PDF or stores the template.
+#[debug_handler]
+pub async fn update(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestLocale(translator): RequestLocale,
+    RequestAdmin(tenant): RequestAdmin,
+    Path(kind): Path<String>,
+    multipart: Multipart,
+) -> Result<Response> {
+    let kind = parse_kind(&kind)?;
+    let form = read_template_form(multipart).await?;
+    if let Some(logo) = &form.logo {
+        validate_logo(logo)?;
+    }
+    let stored = report_templates::Model::find_by_kind(&ctx.db, tenant, kind).await?;
+    let logo = match (form.logo, form.remove_logo) {
+        (Some(logo), _) => Some(logo),
+        (None, true) => None,
+        (None, false) => stored.as_ref().and_then(|template| template.logo.clone()),
+    };
+    let template = CustomTemplate {
+        source: form.source,
+        logo,
+    };
+
+    let pdf = match preview(kind, template.clone(), translator).await? {
+        Ok(pdf) => pdf,
+        Err(diagnostics) => {
+            return render_edit(&v, kind, &template.source, stored.as_ref(), &diagnostics);
+        }
+    };
+
+    if form.action == "preview" {
+        return Ok((
+            [
+                (
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/pdf"),
+                ),
+                (
+                    header::CONTENT_DISPOSITION,
+                    HeaderValue::from_static("inline; filename=\"template-preview.pdf\""),
+                ),
+            ],
+            pdf,
+        )
+            .into_response());
+    }
+
+    report_templates::ActiveModel::save_for_kind(&ctx.db, tenant, kind, template).await?;
+    format::redirect(&format!("/report_templates/{}", kind.as_str()))
+}
+
+/// Removes the custom template so the built-in one is used again.
+#[debug_handler]
+pub async fn reset(
+    State(ctx): State<AppContext>,
+    RequestAdmin(tenant): RequestAdmin,
+    Path(kind): Path<String>,
+) -> Result<Response> {
+    let kind = parse_kind(&kind)?;
+    if let Some(template) = report_templates::Model::find_by_kind(&ctx.db, tenant, kind).await? {
+        template.delete(&ctx.db).await?;
+    }
+    format::redirect(&format!("/report_templates/{}", kind.as_str()))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("report_templates/")
+        .add("", get(list))
+        .add("{kind}", get(edit))
+        .add("{kind}", post(update))
+        .add("{kind}/reset", post(reset))
+}
@@ -0,0 +1,654 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{
+    http::{header, HeaderValue},
+    response::IntoResponse,
+};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use loco_rs::prelude::*;
+use sea_orm::{ColumnTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controllers::inventory::{
+        build_check_certificate, build_single_item_history_report, load_item_details_data,
+        load_metadata_value_maps_for_items,
+    },
+    exts::{BTreeMapExt, OptionStringExt, StringExt},
+    i18n::{RequestLocale, Translator},
+    initializers::app_cache::AppData,
+    models::{
+        _entities::inventory_items::Column as InventoryItemsColumn,
+        generated_reports::{ReportIdentity, ReportKind},
+        inventory_item_checks, inventory_item_kinds, inventory_items,
+        report_jobs::{self, ReportJobStatus},
+        report_templates, results,
+        teams::ItemScope,
+    },
+    reports::{
+        check_certificate,
+        compliance::{
+            self, ComplianceCounts, ComplianceItem, ComplianceKindSummary, ComplianceReport,
+            ComplianceStatus,
+        },
+        single_item_history::{self, ReportField, SingleItemHistoryReport},
+        CustomTemplate,
+    },
+    session::RequestUser,
+    tenant::{RequestItemScope, RequestTenant},
+    timezone::start_of_day,
+    workers::report::{ReportWorker, ReportWorkerArgs},
+};
+
+/// Name of the item kind metadata field holding an item's location.
+pub(crate) const LOCATION_FIELD: &str = "location";
+
+#[derive(Debug, Deserialize)]
+pub struct ComplianceQuery {
+    pub kind_id: Option<String>,
+    pub location: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Selection of a compliance report. The state of each item is evaluated at
+/// the end of `to` (or now), `from` only limits which checks are counted as
+/// performed in the period.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceFilter {
+    pub kind_id: Option<i32>,
+    pub location: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl TryFrom<ComplianceQuery> for ComplianceFilter {
+    type Error = Error;
+
+    fn try_from(query: ComplianceQuery) -> Result<Self> {
+        let parse_date = |value: Option<String>, name: &str| {
+            value
+                .clean()
+                .map(|value| {
+                    NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                        .map_err(|_| Error::BadRequest(format!("invalid {name} date: {value}")))
+                })
+                .transpose()
+        };
+
+        let filter = Self {
+            kind_id: query
+                .kind_id
+                .clean()
+                .map(|kind_id| {
+                    kind_id
+                        .parse()
+                        .map_err(|_| Error::BadRequest(format!("invalid kind_id: {kind_id}")))
+                })
+                .transpose()?,
+            location: query.location.clean(),
+            from: parse_date(query.from, "from")?,
+            to: parse_date(query.to, "to")?,
+        };
+
+        if let (Some(from), Some(to)) = (filter.from, filter.to) {
+            if from > to {
+                return Err(Error::BadRequest(
+                    "from date must not be after to date".to_string(),
+                ));
+            }
+        }
+        Ok(filter)
+    }
+}
+
+pub(crate) fn location_of(metadata: Option<&HashMap<String, String>>) -> Option<String> {
+    metadata?
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(LOCATION_FIELD))
+        .and_then(|(_, value)| value.clone().clean())
+}
+
+fn compliance_status(
+    item: &inventory_items::Model,
+    last_check_ok: Option<bool>,
+    next_due: Option<DateTime<Utc>>,
+    as_of: DateTime<Utc>,
+) -> ComplianceStatus {
+    if item.expiry.is_some_and(|expiry| expiry <= as_of) {
+        ComplianceStatus::Expired
+    } else if last_check_ok == Some(false) {
+        ComplianceStatus::Failed
+    } else if next_due.is_some_and(|next_due| next_due < as_of) {
+        ComplianceStatus::Overdue
+    } else {
+        ComplianceStatus::Compliant
+    }
+}
+
+fn describe_filters(
+    filter: &ComplianceFilter,
+    item_kinds: &BTreeMap<i32, inventory_item_kinds::Model>,
+    translator: &Translator,
+) -> Vec<ReportField> {
+    let mut filters = Vec::new();
+    if let Some(kind_id) = filter.kind_id {
+        filters.push(ReportField {
+            label: translator.t("report-item_kind"),
+            value: item_kinds.get_cloned(&kind_id, |kind| &kind.name),
+        });
+    }
+    if let Some(location) = &filter.location {
+        filters.push(ReportField {
+            label: translator.t("report-location"),
+            value: Some(location.clone()),
+        });
+    }
+    if filter.from.is_some() || filter.to.is_some() {
+        let format_date = |date: Option<NaiveDate>| {
+            date.map_or_else(
+                || "…".to_string(),
+                |date| translator.format_date(&start_of_day(date, translator.timezone())),
+            )
+        };
+        filters.push(ReportField {
+            label: translator.t("report-period"),
+            value: Some(format!(
+                "{} – {}",
+                format_date(filter.from),
+                format_date(filter.to)
+            )),
+        });
+    }
+    filters
+}
+
+/// The latest check of each of `item_ids` performed before `as_of`, and the
+/// number of their checks from `period_start` until then.
+async fn load_checks_before(
+    ctx: &AppContext,
+    item_ids: &[i32],
+    period_start: Option<DateTime<Utc>>,
+    as_of: DateTime<Utc>,
+) -> Result<(
+    HashMap<i32, inventory_item_checks::Model>,
+    HashMap<i32, u64>,
+)> {
+    let last_checks = inventory_item_checks::Entity::latest_by_item(
+        &ctx.db,
+        item_ids,
+        1,
+        Some(as_of.fixed_offset()),
+    )
+    .await?
+    .into_iter()
+    .filter_map(|(item_id, checks)| Some((item_id, checks.into_iter().next()?)))
+    .collect();
+    let checks_in_period = inventory_item_checks::Entity::count_by_item(
+        &ctx.db,
+        item_ids,
+        period_start.map(|start| start.fixed_offset()),
+        as_of.fixed_offset(),
+    )
+    .await?;
+    Ok((last_checks, checks_in_period))
+}
+
+/// Collects the compliance state of every item of `tenant` in `scope`
+/// matching `filter`.
+///
+/// # Errors
+///
+/// When the app caches are not loaded or DB query error
+pub async fn build_compliance_report(
+    ctx: &AppContext,
+    tenant: i32,
+    scope: &ItemScope,
+    filter: &ComplianceFilter,
+    identity: &ReportIdentity,
+    translator: &Translator,
+) -> Result<ComplianceReport> {
+    let now = identity.generated_at;
+    let tz = translator.timezone();
+    let as_of = filter
+        .to
+        .and_then(|to| to.checked_add_days(Days::new(1)))
+        .map_or(now, |end| start_of_day(end, tz))
+        .min(now);
+    let period_start = filter.from.map(|from| start_of_day(from, tz));
+
+    let item_kinds = ctx.get_item_kinds(tenant)?;
+    let intervals = ctx.get_intervals()?;
+    let results = ctx.get_results()?;
+
+    let mut select = inventory_items::Entity::find_in_tenant(tenant)
+        .filter(scope.condition(InventoryItemsColumn::Id))
+        .order_by_asc(InventoryItemsColumn::Name)
+        .order_by_asc(InventoryItemsColumn::Id);
+    if let Some(kind_id) = filter.kind_id {
+        select = select.filter(InventoryItemsColumn::InventoryItemKindId.eq(kind_id));
+    }
+    let items = select.all(&ctx.db).await?;
+
+    let item_ids = items.iter().map(|item| item.id).collect::<Vec<_>>();
+    let metadata_by_item_id = load_metadata_value_maps_for_items(&ctx.db, &item_ids).await?;
+    let location_needle = filter.location.as_deref().map(str::to_lowercase);
+    let items = items
+        .into_iter()
+        .map(|item| {
+            let location = location_of(metadata_by_item_id.get(&item.id));
+            (item, location)
+        })
+        .filter(|(_, location)| match &location_needle {
+            Some(needle) => location
+                .as_deref()
+                .is_some_and(|location| location.to_lowercase().contains(needle)),
+            None => true,
+        })
+        .collect::<Vec<_>>();
+
+    let item_ids = items.iter().map(|(item, _)| item.id).collect::<Vec<_>>();
+    let (mut last_check_by_item_id, checks_in_period_by_item_id) =
+        load_checks_before(ctx, &item_ids, period_start, as_of).await?;
+
+    let mut summary = ComplianceCounts::default();
+    let mut counts_by_kind = BTreeMap::<String, ComplianceCounts>::new();
+    let mut report_items = Vec::with_capacity(items.len());
+    for (item, location) in items {
+        let last_check = last_check_by_item_id.remove(&item.id);
+        let last_check = last_check.as_ref();
+        let last_result = last_check.and_then(|check| results.get(&check.result_id));
+        let checks_in_period = checks_in_period_by_item_id
+            .get(&item.id)
+            .copied()
+            .unwrap_or_default();
+
+        let as_checked = inventory_items::Model {
+            last_checked_at: last_check.map(|check| check.checked_at),
+            ..item.clone()
+        };
+        let next_due = as_checked
+            .compute_next_due_at(intervals.get(&item.interval_id))
+            .map(|due| due.with_timezone(&Utc));
+        let status = compliance_status(
+            &item,
+            last_check.map(|_| last_result.is_some_and(results::Model::is_ok)),
+            next_due,
+            as_of,
+        );
+        let item_kind = item_kinds.get_cloned(&item.inventory_item_kind_id, |kind| &kind.name);
+
+        let report_item = ComplianceItem {
+            name: item.name,
+            serial_number: item.serial_number,
+            location,
+            last_checked_at: last_check.map(|check| translator.format_datetime(check.checked_at)),
+            last_result: last_result.map(|result| translator.code(&result.code)),
+            checks_in_period,
+            next_due: next_due.map(|due| translator.format_date(&due)),
+            status,
+            item_kind: item_kind.clone(),
+        };
+        summary.add(&report_item);
+        counts_by_kind
+            .entry(item_kind.unwrap_or_default())
+            .or_default()
+            .add(&report_item);
+        report_items.push(report_item);
+    }
+
+    Ok(ComplianceReport {
+        title: translator.t("report-compliance-title"),
+        generated_at: translator.format_generated_at(now),
+        report_id: identity.report_id.clone(),
+        verify_url: identity.verify_url.clone(),
+        filters: describe_filters(filter, &item_kinds, translator),
+        summary,
+        kinds: counts_by_kind
+            .into_iter()
+            .map(|(item_kind, counts)| ComplianceKindSummary { item_kind, counts })
+            .collect(),
+        items: report_items,
+    })
+}
+
+/// Collects the full check history of item `item_id` of `tenant` in
+/// `scope`.
+///
+/// # Errors
+///
+/// When the item does not exist, the app caches are not loaded or DB query
+/// error
+pub(crate) async fn build_item_history_report(
+    ctx: &AppContext,
+    tenant: i32,
+    scope: &ItemScope,
+    item_id: i32,
+    identity: &ReportIdentity,
+    translator: &Translator,
+) -> Result<SingleItemHistoryReport> {
+    let details = load_item_details_data(ctx, tenant, scope, item_id, None).await?;
+    Ok(build_single_item_history_report(
+        details, identity, translator,
+    ))
+}
+
+/// A report that can be rendered on the request thread or queued for the
+/// `ReportWorker`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReportRequest {
+    ItemHistory { item_id: i32 },
+    Compliance(ComplianceFilter),
+    CheckCertificate { item_id: i32, check_id: i32 },
+}
+
+impl ReportRequest {
+    #[must_use]
+    pub fn kind(&self) -> ReportKind {
+        match self {
+            Self::ItemHistory { .. } => ReportKind::ItemHistory,
+            Self::Compliance(_) => ReportKind::Compliance,
+            Self::CheckCertificate { .. } => ReportKind::CheckCertificate,
+        }
+    }
+
+    #[must_use]
+    pub fn filename(&self) -> String {
+        match self {
+            Self::ItemHistory { item_id } => format!("inventory-item-{item_id}-history.pdf"),
+            Self::Compliance(_) => "compliance-report.pdf".to_string(),
+            Self::CheckCertificate { item_id, check_id } => {
+                format!("inventory-item-{item_id}-check-{check_id}-certificate.pdf")
+            }
+        }
+    }
+}
+
+/// A rendered report PDF, already stored for verification.
+#[derive(Clone, Debug)]
+pub struct RenderedReport {
+    pub report_id: String,
+    pub title: String,
+    pub filename: String,
+    pub content: Vec<u8>,
+}
+
+impl RenderedReport {
+    /// Serves the report as a PDF download.
+    pub fn into_attachment(self) -> Result<Response> {
+        pdf_attachment(&self.filename, self.content)
+    }
+}
+
+pub(crate) fn pdf_attachment(filename: &str, content: Vec<u8>) -> Result<Response> {
+    let content_disposition =
+        HeaderValue::from_str(&format!("attachment; filename=\"{filename}\""))
+            .map_err(|_| Error::InternalServerError)?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/pdf"),
+            ),
+            (header::CONTENT_DISPOSITION, content_disposition),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+/// Runs a Typst compilation on the blocking thread pool so it does not stall
+/// the async runtime.
+async fn render_blocking<T: Send + 'static>(
+    report: T,
+    translator: &Translator,
+    template: Option<CustomTemplate>,
+    render: fn(&T, &Translator, Option<&CustomTemplate>) -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let translator = translator.clone();
+    tokio::task::spawn_blocking(move || render(&report, &translator, template.as_ref()))
+        .await
+        .map_err(|err| Error::string(&format!("report rendering failed: {err}")))?
+}
+
+/// Builds, renders and stores the report described by `request` from the
+/// items of `tenant` in `scope`.
+///
+/// # Errors
+///
+/// When the report subject does not exist, rendering fails or DB query error
+pub async fn generate_report(
+    ctx: &AppContext,
+    tenant: i32,
+    scope: &ItemScope,
+    request: &ReportRequest,
+    translator: &Translator,
+) -> Result<RenderedReport> {
+    let identity = ReportIdentity::new(ctx);
+    let template = report_templates::Model::find_by_kind(&ctx.db, tenant, request.kind())
+        .await?
+        .map(|template| template.custom_template());
+    let (title, subject, content) = match request {
+        ReportRequest::ItemHistory { item_id } => {
+            let report =
+                build_item_history_report(ctx, tenant, scope, *item_id, &identity, translator)
+                    .await?;
+            let title = report.title.clone();
+            let subject = Some(report.item.name.clone());
+            let content = render_blocking(
+                report,
+                translator,
+                template,
+                single_item_history::render_pdf,
+            )
+            .await?;
+            (title, subject, content)
+        }
+        ReportRequest::Compliance(filter) => {
+            let report =
+                build_compliance_report(ctx, tenant, scope, filter, &identity, translator).await?;
+            let title = report.title.clone();
+            let subject = report
+                .filters
+                .iter()
+                .filter_map(|field| Some(format!("{}: {}", field.label, field.value.as_ref()?)))
+                .collect::<Vec<_>>()
+                .join(", ")
+                .clean();
+            let content =
+                render_blocking(report, translator, template, compliance::render_pdf).await?;
+            (title, subject, content)
+        }
+        ReportRequest::CheckCertificate { item_id, check_id } => {
+            let certificate = build_check_certificate(
+                ctx, tenant, scope, *item_id, *check_id, &identity, translator,
+            )
+            .await?;
+            let title = certificate.title.clone();
+            let subject = Some(format!(
+                "{} – {}",
+                certificate.subject, certificate.checked_at
+            ));
+            let content = render_blocking(
+                certificate,
+                translator,
+                template,
+                check_certificate::render_pdf,
+            )
+            .await?;
+            (title, subject, content)
+        }
+    };
+    identity
+        .store(&ctx.db, tenant, request.kind(), &title, subject, &content)
+        .await?;
+
+    Ok(RenderedReport {
+        report_id: identity.report_id,
+        title,
+        filename: request.filename(),
+        content,
+    })
+}
+
+#[debug_handler]
+pub async fn compliance_form(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
+) -> Result<Response> {
+    let item_kinds = ctx
+        .get_item_kinds(tenant)?
+        .into_values()
+        .collect::<Vec<_>>();
+    format::render().view(
+        &v,
+        "reports/compliance.html",
+        data!({ "item_kinds": item_kinds }),
+    )
+}
+
+#[debug_handler]
+pub async fn download_compliance_report(
+    State(ctx): State<AppContext>,
+    RequestLocale(translator): RequestLocale,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+    Query(query): Query<ComplianceQuery>,
+) -> Result<Response> {
+    let filter = ComplianceFilter::try_from(query)?;
+    generate_report(
+        &ctx,
+        tenant,
+        &scope,
+        &ReportRequest::Compliance(filter),
+        &translator,
+    )
+    .await?
+    .into_attachment()
+}
+
+/// Form to queue a report job, `kind` selects which of the other fields are
+/// used.
+#[derive(Debug, Deserialize)]
+pub struct QueueReportForm {
+    pub kind: String,
+    pub item_id: Option<String>,
+    pub check_id: Option<String>,
+    #[serde(flatten)]
+    pub compliance: ComplianceQuery,
+}
+
+impl TryFrom<QueueReportForm> for ReportRequest {
+    type Error = Error;
+
+    fn try_from(form: QueueReportForm) -> Result<Self> {
+        let parse_id = |value: Option<String>, name: &str| -> Result<i32> {
+            let value = value
+                .clean()
+                .ok_or_else(|| Error::BadRequest(format!("missing {name}")))?;
+            value
+                .parse()
+                .map_err(|_| Error::BadRequest(format!("invalid {name}: {value}")))
+        };
+
+        match ReportKind::parse(&form.kind) {
+            Some(ReportKind::ItemHistory) => Ok(Self::ItemHistory {
+                item_id: parse_id(form.item_id, "item_id")?,
+            }),
+            Some(ReportKind::Compliance) => Ok(Self::Compliance(ComplianceFilter::try_from(
+                form.compliance,
+            )?)),
+            Some(ReportKind::CheckCertificate) => Ok(Self::CheckCertificate {
+                item_id: parse_id(form.item_id, "item_id")?,
+                check_id: parse_id(form.check_id, "check_id")?,
+            }),
+            None => Err(Error::BadRequest(format!(
+                "invalid report kind: {}",
+                form.kind
+            ))),
+        }
+    }
+}
+
+/// Number of jobs shown in the download center.
+const RECENT_JOBS_LIMIT: u64 = 50;
+
+#[debug_handler]
+pub async fn queue_report(
+    State(ctx): State<AppContext>,
+    RequestLocale(translator): RequestLocale,
+    RequestTenant(tenant): RequestTenant,
+    RequestUser(user): RequestUser,
+    Form(form): Form<QueueReportForm>,
+) -> Result<Response> {
+    let request = ReportRequest::try_from(form)?;
+    let job = report_jobs::ActiveModel::queue(
+        tenant,
+        user.id,
+        request.kind().as_str(),
+        serde_json::to_string(&request)?,
+        translator.locale().to_string(),
+        translator.timezone().name().to_string(),
+    )
+    .insert(&ctx.db)
+    .await?;
+    ReportWorker::perform_later(&ctx, ReportWorkerArgs { job_id: job.id }).await?;
+
+    format::redirect("/reports/jobs")
+}
+
+#[debug_handler]
+pub async fn list_jobs(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
+    RequestUser(user): RequestUser,
+) -> Result<Response> {
+    let jobs = report_jobs::Model::list_recent(&ctx.db, tenant, user.id, RECENT_JOBS_LIMIT).await?;
+    format::render().view(&v, "reports/jobs.html", data!({ "jobs": jobs }))
+}
+
+#[debug_handler]
+pub async fn jobs_status(
+    State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
+    RequestUser(user): RequestUser,
+) -> Result<Response> {
+    format::json(
+        report_jobs::Model::list_recent(&ctx.db, tenant, user.id, RECENT_JOBS_LIMIT).await?,
+    )
+}
+
+#[debug_handler]
+pub async fn download_job(
+    State(ctx): State<AppContext>,
+    RequestTenant(tenant): RequestTenant,
+    RequestUser(user): RequestUser,
+    Path(pid): Path<String>,
+) -> Result<Response> {
+    let job = report_jobs::Model::find_by_pid(&ctx.db, tenant, user.id, &pid)
+        .await?
+        .ok_or(Error::NotFound)?;
+    match (job.status(), job.filename, job.content) {
+        (Some(ReportJobStatus::Completed), Some(filename), Some(content)) => {
+            pdf_attachment(&filename, content)
+        }
+        _ => Err(Error::NotFound),
+    }
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("reports/")
+        .add("compliance", get(compliance_form))
+        .add("compliance.pdf", get(download_compliance_report))
+        .add("jobs", get(list_jobs))
+        .add("jobs", post(queue_report))
+        .add("jobs/status", get(jobs_status))
+        .add("jobs/{pid}/download", get(download_job))
+}
@@ -0,0 +1,204 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum_extra::extract::Form as HtmlForm;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    initializers::app_cache::AppData,
+    models::{
+        inventory_item_kinds, inventory_items,
+        responsibilities::{self, Assignee, Assignment, ResponsibilityTarget},
+        teams,
+    },
+    tenant::{RequestAdmin, RequestItemScope, RequestTenant},
+};
+
+/// Owner and deputy picked in the responsibility form, as
+/// [`Assignee::key`]s, empty for none.
+#[derive(Debug, Deserialize)]
+pub struct AssignmentParams {
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub deputy: String,
+}
+
+impl AssignmentParams {
+    /// Users must belong to `tenant`.
+    async fn into_assignment(self, ctx: &AppContext, tenant: i32) -> Result<Assignment> {
+        let users = ctx.get_users(tenant)?;
+        let team_ids = teams::Entity::list(&ctx.db, tenant)
+            .await?
+            .into_iter()
+            .map(|team| team.id)
+            .collect::<Vec<_>>();
+        let assignee = |value: &str| -> Result<Option<Assignee>> {
+            if value.trim().is_empty() {
+                return Ok(None);
+            }
+            Assignee::parse(value)
+                .filter(|assignee| match assignee {
+                    Assignee::User(id) => users.contains_key(id),
+                    Assignee::Team(id) => team_ids.contains(id),
+                })
+                .map(Some)
+                .ok_or_else(|| Error::BadRequest(format!("unknown user or team: {value}")))
+        };
+        let assignment = Assignment {
+            owner: assignee(&self.owner)?,
+            deputy: assignee(&self.deputy)?,
+        };
+        if assignment.owner.is_some() && assignment.owner == assignment.deputy {
+            return Err(Error::BadRequest(
+                "Owner and deputy must be different".to_string(),
+            ));
+        }
+        Ok(assignment)
+    }
+}
+
+#[derive(Serialize)]
+struct AssigneeOption {
+    key: String,
+    name: String,
+}
+
+/// The responsibility card of the item and kind detail pages.
+#[derive(Serialize)]
+pub(crate) struct ResponsibilityView {
+    action: String,
+    /// Whether unassigned roles fall back to the item's kind.
+    inherits: bool,
+    /// Keys of the owner and deputy assigned to the item or kind itself.
+    owner_key: Option<String>,
+    deputy_key: Option<String>,
+    /// Names of the effective owner and deputy, including the kind's.
+    owner_name: Option<String>,
+    deputy_name: Option<String>,
+    /// Whether the roles are taken from the item's kind.
+    owner_inherited: bool,
+    deputy_inherited: bool,
+    users: Vec<AssigneeOption>,
+    teams: Vec<AssigneeOption>,
+}
+
+impl ResponsibilityView {
+    async fn new(
+        ctx: &AppContext,
+        tenant: i32,
+        action: String,
+        inherits: bool,
+        assigned: Assignment,
+        effective: Assignment,
+    ) -> Result<Self> {
+        let users = ctx.get_users(tenant)?;
+        let teams = teams::Entity::list(&ctx.db, tenant).await?;
+        let name = |assignee: Option<Assignee>| match assignee? {
+            Assignee::User(id) => users.get(&id).map(|user| user.name.clone()),
+            Assignee::Team(id) => teams
+                .iter()
+                .find(|team| team.id == id)
+                .map(|team| team.name.clone()),
+        };
+        let mut user_options = users
+            .values()
+            .map(|user| AssigneeOption {
+                key: Assignee::User(user.id).key(),
+                name: user.name.clone(),
+            })
+            .collect::<Vec<_>>();
+        user_options.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Self {
+            action,
+            inherits,
+            owner_key: assigned.owner.map(Assignee::key),
+            deputy_key: assigned.deputy.map(Assignee::key),
+            owner_name: name(effective.owner),
+            deputy_name: name(effective.deputy),
+            owner_inherited: assigned.owner.is_none() && effective.owner.is_some(),
+            deputy_inherited: assigned.deputy.is_none() && effective.deputy.is_some(),
+            users: user_options,
+            teams: teams
+                .iter()
+                .map(|team| AssigneeOption {
+                    key: Assignee::Team(team.id).key(),
+                    name: team.name.clone(),
+                })
+                .collect(),
+        })
+    }
+
+    pub(crate) async fn for_item(ctx: &AppContext, item: &inventory_items::Model) -> Result<Self> {
+        let assignments = responsibilities::Entity::load_for_item(&ctx.db, item).await?;
+        Self::new(
+            ctx,
+            item.tenant_id,
+            format!("/responsibilities/item/{}", item.id),
+            true,
+            assignments.get(ResponsibilityTarget::Item(item.id)),
+            assignments.for_item(item),
+        )
+        .await
+    }
+
+    pub(crate) async fn for_kind(
+        ctx: &AppContext,
+        kind: &inventory_item_kinds::Model,
+    ) -> Result<Self> {
+        let kind_id = kind.id;
+        let assigned =
+            responsibilities::Entity::load_for(&ctx.db, ResponsibilityTarget::Kind(kind_id))
+                .await?;
+        Self::new(
+            ctx,
+            kind.tenant_id,
+            format!("/responsibilities/kind/{kind_id}"),
+            false,
+            assigned,
+            assigned,
+        )
+        .await
+    }
+}
+
+#[debug_handler]
+pub async fn assign_item(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+    HtmlForm(params): HtmlForm<AssignmentParams>,
+) -> Result<Response> {
+    inventory_items::Entity::find_by_id_in_tenant(tenant, id)
+        .filter(scope.condition(inventory_items::Column::Id))
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    let assignment = params.into_assignment(&ctx, tenant).await?;
+    responsibilities::Entity::assign(&ctx.db, ResponsibilityTarget::Item(id), assignment).await?;
+    format::redirect(&format!("/inventory/item/{id}"))
+}
+
+#[debug_handler]
+pub async fn assign_kind(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestAdmin(tenant): RequestAdmin,
+    HtmlForm(params): HtmlForm<AssignmentParams>,
+) -> Result<Response> {
+    inventory_item_kinds::Entity::find_by_id_in_tenant(tenant, id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    let assignment = params.into_assignment(&ctx, tenant).await?;
+    responsibilities::Entity::assign(&ctx.db, ResponsibilityTarget::Kind(id), assignment).await?;
+    format::redirect(&format!("/inventory/item_kinds/{id}"))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("responsibilities/")
+        .add("item/{id}", post(assign_item))
+        .add("kind/{id}", post(assign_kind))
+}
@@ -0,0 +1,87 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum::http::StatusCode;
+use loco_rs::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    controllers::inventory::load_item_check_form,
+    exts::OptionStringExt,
+    initializers::app_cache::AppData,
+    models::inventory_items,
+    tenant::{RequestItemScope, RequestTenant},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ScannerQuery {
+    /// Name of the item whose check was just saved.
+    pub saved: Option<String>,
+}
+
+/// Absolute URL opening the mobile check page for `item`, as encoded in
+/// label codes.
+pub(crate) fn scan_url(ctx: &AppContext, item: &inventory_items::Model) -> String {
+    format!("{}/scan/{}", ctx.config.server.full_url(), item.scan_code())
+}
+
+#[debug_handler]
+pub async fn scanner(
+    ViewEngine(v): ViewEngine<TeraView>,
+    Query(query): Query<ScannerQuery>,
+) -> Result<Response> {
+    format::render().view(
+        &v,
+        "scan/scanner.html",
+        data!({ "saved": query.saved.clean() }),
+    )
+}
+
+#[debug_handler]
+pub async fn check_scanned_item(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Path(code): Path<String>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+) -> Result<Response> {
+    let item = match inventory_items::Model::find_by_scan_code(&ctx.db, tenant, &scope, &code).await
+    {
+        Ok(item) => item,
+        Err(ModelError::EntityNotFound) => {
+            return format::render().status(StatusCode::NOT_FOUND).view(
+                &v,
+                "scan/scanner.html",
+                data!({ "not_found": code }),
+            );
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    let next_due_at = item.next_due_at;
+    let item_kind_name = ctx
+        .get_item_kinds(tenant)?
+        .get(&item.inventory_item_kind_id)
+        .map(|kind| kind.name.clone());
+    let form = load_item_check_form(&ctx, item).await?;
+
+    format::render().view(
+        &v,
+        "scan/check.html",
+        data!({
+            "item": form.item,
+            "item_kind_name": item_kind_name,
+            "next_due_at": next_due_at,
+            "checklist": form.checklist,
+            "steps": form.steps,
+            "results": form.results,
+            "users": form.users,
+        }),
+    )
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("scan/")
+        .add("", get(scanner))
+        .add("{code}", get(check_scanned_item))
+}
@@ -0,0 +1,48 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use loco_rs::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    controllers::inventory::find_by_serial,
+    exts::OptionStringExt,
+    search,
+    tenant::{RequestItemScope, RequestTenant},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+}
+
+/// Full-text search from the navbar. A query matching a serial number
+/// exactly opens that item, e.g. after scanning it.
+#[debug_handler]
+pub async fn show(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Query(query): Query<SearchQuery>,
+    RequestTenant(tenant): RequestTenant,
+    RequestItemScope(scope): RequestItemScope,
+) -> Result<Response> {
+    let Some(q) = query.q.clean() else {
+        return format::render().view(&v, "search/show.html", data!({}));
+    };
+    if let Some(item) = find_by_serial(&ctx, tenant, &scope, &q).await? {
+        return format::redirect(&format!("/inventory/item/{}", item.id));
+    }
+    let results = search::search(&ctx.db, tenant, &q, &scope).await?;
+    format::render().view(
+        &v,
+        "search/show.html",
+        data!({
+            "inventory_search": q,
+            "results": results,
+            "empty": results.is_empty(),
+        }),
+    )
+}
+
+pub fn routes() -> Routes {
+    Routes::new().prefix("search").add("", get(show))
+}
@@ -0,0 +1,194 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum_extra::extract::Form as HtmlForm;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    exts::StringExt,
+    initializers::app_cache::AppData,
+    models::{team_memberships, teams},
+    tenant::RequestAdmin,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct TeamParams {
+    pub name: String,
+    /// Checkbox, only sent when checked.
+    pub scoped: Option<String>,
+}
+
+struct TeamInput {
+    name: String,
+    scoped: bool,
+}
+
+impl TryFrom<TeamParams> for TeamInput {
+    type Error = Error;
+
+    fn try_from(params: TeamParams) -> Result<Self> {
+        let name = params
+            .name
+            .clean()
+            .ok_or_else(|| Error::BadRequest("Name must not be empty".to_string()))?;
+        Ok(Self {
+            name,
+            scoped: params.scoped.is_some(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemberParams {
+    pub user_id: i32,
+}
+
+#[derive(Serialize)]
+struct MemberView {
+    id: i32,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct TeamView {
+    team: teams::Model,
+    members: Vec<MemberView>,
+    /// Users who can still be added.
+    others: Vec<MemberView>,
+}
+
+async fn load_team(ctx: &AppContext, tenant: i32, id: i32) -> Result<teams::Model> {
+    teams::Entity::find_by_id_in_tenant(tenant, id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)
+}
+
+#[debug_handler]
+pub async fn list(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestAdmin(tenant): RequestAdmin,
+) -> Result<Response> {
+    let users = ctx.get_users(tenant)?;
+    let mut users = users
+        .values()
+        .map(|user| MemberView {
+            id: user.id,
+            name: user.name.clone(),
+        })
+        .collect::<Vec<_>>();
+    users.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut members_by_team = teams::Entity::members_by_team(&ctx.db).await?;
+    let teams = teams::Entity::list(&ctx.db, tenant)
+        .await?
+        .into_iter()
+        .map(|team| {
+            let member_ids = members_by_team.remove(&team.id).unwrap_or_default();
+            let (members, others) = users
+                .iter()
+                .map(|user| MemberView {
+                    id: user.id,
+                    name: user.name.clone(),
+                })
+                .partition(|user| member_ids.contains(&user.id));
+            TeamView {
+                team,
+                members,
+                others,
+            }
+        })
+        .collect::<Vec<_>>();
+    format::render().view(&v, "teams/list.html", data!({ "teams": teams }))
+}
+
+#[debug_handler]
+pub async fn add(
+    State(ctx): State<AppContext>,
+    RequestAdmin(tenant): RequestAdmin,
+    HtmlForm(params): HtmlForm<TeamParams>,
+) -> Result<Response> {
+    let input = TeamInput::try_from(params)?;
+    teams::ActiveModel {
+        name: ActiveValue::set(input.name),
+        scoped: ActiveValue::set(input.scoped),
+        tenant_id: ActiveValue::set(tenant),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await?;
+    format::redirect("/teams/list")
+}
+
+#[debug_handler]
+pub async fn update(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestAdmin(tenant): RequestAdmin,
+    HtmlForm(params): HtmlForm<TeamParams>,
+) -> Result<Response> {
+    let input = TeamInput::try_from(params)?;
+    let mut team = load_team(&ctx, tenant, id).await?.into_active_model();
+    team.name = ActiveValue::set(input.name);
+    team.scoped = ActiveValue::set(input.scoped);
+    team.update(&ctx.db).await?;
+    format::redirect("/teams/list")
+}
+
+#[debug_handler]
+pub async fn remove(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestAdmin(tenant): RequestAdmin,
+) -> Result<Response> {
+    let deleted = teams::Entity::delete_many()
+        .filter(teams::Column::Id.eq(id))
+        .filter(teams::Column::TenantId.eq(tenant))
+        .exec(&ctx.db)
+        .await?;
+    if deleted.rows_affected == 0 {
+        return Err(Error::NotFound);
+    }
+    format::json(data!({ "status": "ok" }))
+}
+
+#[debug_handler]
+pub async fn add_member(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestAdmin(tenant): RequestAdmin,
+    HtmlForm(params): HtmlForm<MemberParams>,
+) -> Result<Response> {
+    load_team(&ctx, tenant, id).await?;
+    if !ctx.get_users(tenant)?.contains_key(&params.user_id) {
+        return Err(Error::BadRequest("Unknown user".to_string()));
+    }
+    team_memberships::Entity::add(&ctx.db, id, params.user_id).await?;
+    format::redirect("/teams/list")
+}
+
+#[debug_handler]
+pub async fn remove_member(
+    State(ctx): State<AppContext>,
+    Path((id, user_id)): Path<(i32, i32)>,
+    RequestAdmin(tenant): RequestAdmin,
+) -> Result<Response> {
+    load_team(&ctx, tenant, id).await?;
+    team_memberships::Entity::delete_many()
+        .filter(team_memberships::Column::TeamId.eq(id))
+        .filter(team_memberships::Column::UserId.eq(user_id))
+        .exec(&ctx.db)
+        .await?;
+    format::redirect("/teams/list")
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("teams/")
+        .add("list", get(list))
+        .add("", post(add))
+        .add("{id}", post(update))
+        .add("{id}", delete(remove))
+        .add("{id}/members", post(add_member))
+        .add("{id}/members/{user_id}/remove", post(remove_member))
+}
@@ -0,0 +1,15 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use loco_rs::prelude::*;
+
+/// Renders the tenant page. Tenants are listed, created and switched
+/// client-side through `/api/v1/tenants` and `/api/auth/tenant` using the
+/// token stored at login.
+#[debug_handler]
+pub async fn list(ViewEngine(v): ViewEngine<TeraView>) -> Result<Response> {
+    format::render().view(&v, "tenants/list.html", data!({}))
+}
+
+pub fn routes() -> Routes {
+    Routes::new().prefix("tenants/").add("list", get(list))
+}
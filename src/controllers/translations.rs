@@ -1,23 +1,122 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::unnecessary_struct_initialization)]
 #![allow(clippy::unused_async)]
+use std::collections::BTreeSet;
+
 use loco_rs::prelude::*;
+use serde::Deserialize;
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    exts::StringExt,
+    initializers::app_cache::{refresh_translations_cache, AppData},
+    models::translations::{self, Entity},
+    tenant::RequestGlobalAdmin,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct TranslationParams {
+    pub code: String,
+    pub lang: String,
+    pub text: String,
+}
 
-use crate::models::translations::Entity;
+#[derive(Debug, Deserialize)]
+pub struct UpdateTranslationParams {
+    pub text: String,
+}
+
+/// Codes of the cached lookup tables, offered as suggestions when adding a
+/// translation.
+fn lookup_codes(ctx: &AppContext) -> Result<BTreeSet<String>> {
+    Ok(ctx
+        .get_intervals()?
+        .into_values()
+        .map(|interval| interval.code)
+        .chain(ctx.get_expiries()?.into_values().map(|expiry| expiry.code))
+        .chain(ctx.get_results()?.into_values().map(|result| result.code))
+        .collect())
+}
+
+fn required(value: String, name: &str) -> Result<String> {
+    value
+        .clean()
+        .ok_or_else(|| Error::BadRequest(format!("{name} must not be empty")))
+}
 
 #[debug_handler]
 pub async fn list(
     ViewEngine(v): ViewEngine<TeraView>,
     State(ctx): State<AppContext>,
 ) -> Result<Response> {
-    let translations = Entity::find().all(&ctx.db).await?;
+    let translations = Entity::list_ordered(&ctx.db).await?;
     format::render().view(
         &v,
         "translations/list.html",
-        data!({ "translations": translations }),
+        data!({
+            "translations": translations,
+            "codes": lookup_codes(&ctx)?,
+        }),
     )
 }
 
+#[debug_handler]
+pub async fn add(
+    State(ctx): State<AppContext>,
+    RequestGlobalAdmin(_): RequestGlobalAdmin,
+    Form(params): Form<TranslationParams>,
+) -> Result<Response> {
+    let code = required(params.code, "Code")?;
+    let lang = required(params.lang, "Language")?;
+    if lang.parse::<LanguageIdentifier>().is_err() {
+        return Err(Error::BadRequest(format!("invalid language: {lang}")));
+    }
+    let text = required(params.text, "Text")?;
+
+    translations::ActiveModel::upsert(&ctx.db, code, lang, text).await?;
+    refresh_translations_cache(&ctx).await?;
+    format::redirect("/translations/list")
+}
+
+#[debug_handler]
+pub async fn update(
+    State(ctx): State<AppContext>,
+    RequestGlobalAdmin(_): RequestGlobalAdmin,
+    Path(id): Path<i32>,
+    Form(params): Form<UpdateTranslationParams>,
+) -> Result<Response> {
+    let text = required(params.text, "Text")?;
+    let translation = Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    let mut translation = translation.into_active_model();
+    translation.text = ActiveValue::set(text);
+    translation.update(&ctx.db).await?;
+    refresh_translations_cache(&ctx).await?;
+    format::redirect("/translations/list")
+}
+
+#[debug_handler]
+pub async fn remove(
+    State(ctx): State<AppContext>,
+    RequestGlobalAdmin(_): RequestGlobalAdmin,
+    Path(id): Path<i32>,
+) -> Result<Response> {
+    let deleted = Entity::delete_by_id(id).exec(&ctx.db).await?;
+    if deleted.rows_affected == 0 {
+        return Err(Error::NotFound);
+    }
+    refresh_translations_cache(&ctx).await?;
+    format::json(data!({ "status": "ok" }))
+}
+
 pub fn routes() -> Routes {
-    Routes::new().prefix("translations/").add("list", get(list))
+    Routes::new()
+        .prefix("translations/")
+        .add("list", get(list))
+        .add("", post(add))
+        .add("{id}", post(update))
+        .add("{id}", delete(remove))
 }
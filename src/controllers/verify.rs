@@ -0,0 +1,66 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum::{body::Bytes, http::StatusCode};
+use loco_rs::prelude::*;
+use serde::Deserialize;
+
+use crate::{exts::OptionStringExt, models::generated_reports};
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyQuery {
+    pub report_id: Option<String>,
+}
+
+#[debug_handler]
+pub async fn verify_form(
+    ViewEngine(v): ViewEngine<TeraView>,
+    Query(query): Query<VerifyQuery>,
+) -> Result<Response> {
+    if let Some(report_id) = query.report_id.clean() {
+        return format::redirect(&format!(
+            "/verify/{}",
+            generated_reports::normalize_report_id(&report_id)
+        ));
+    }
+    format::render().view(&v, "verify/index.html", data!({}))
+}
+
+#[debug_handler]
+pub async fn show_report(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    Path(report_id): Path<String>,
+) -> Result<Response> {
+    match generated_reports::Model::find_by_report_id(&ctx.db, &report_id).await {
+        Ok(report) => format::render().view(&v, "verify/show.html", data!({ "report": report })),
+        Err(ModelError::EntityNotFound) => format::render().status(StatusCode::NOT_FOUND).view(
+            &v,
+            "verify/index.html",
+            data!({ "not_found": report_id }),
+        ),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Compares the uploaded PDF with the stored hash of the report.
+#[debug_handler]
+pub async fn verify_file(
+    State(ctx): State<AppContext>,
+    Path(report_id): Path<String>,
+    content: Bytes,
+) -> Result<Response> {
+    let report = generated_reports::Model::find_by_report_id(&ctx.db, &report_id).await?;
+    format::json(data!({
+        "report_id": report.report_id,
+        "authentic": report.matches(&content),
+        "content_hash": generated_reports::content_hash(&content),
+    }))
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("verify/")
+        .add("", get(verify_form))
+        .add("{report_id}", get(show_report))
+        .add("{report_id}", post(verify_file))
+}
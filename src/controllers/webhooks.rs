@@ -0,0 +1,220 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::unused_async)]
+use axum_extra::extract::Form as HtmlForm;
+use loco_rs::prelude::*;
+use sea_orm::QueryOrder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    exts::StringExt,
+    models::{
+        webhook_deliveries,
+        webhook_endpoints::{self, join_events, WebhookEvent},
+    },
+    tenant::RequestAdmin,
+    webhooks,
+    workers::webhook::{WebhookWorker, WebhookWorkerArgs},
+};
+
+/// Number of deliveries shown in the delivery log.
+const RECENT_DELIVERIES_LIMIT: u64 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct EndpointParams {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Checkbox, only sent when checked. New endpoints are always active.
+    pub active: Option<String>,
+}
+
+struct EndpointInput {
+    name: String,
+    url: String,
+    events: Vec<WebhookEvent>,
+}
+
+impl EndpointInput {
+    /// Validates `params`, rejecting URLs of local or private addresses
+    /// unless the config allows them.
+    fn parse(ctx: &AppContext, params: EndpointParams) -> Result<Self> {
+        let name = params
+            .name
+            .clean()
+            .ok_or_else(|| Error::BadRequest("Name must not be empty".to_string()))?;
+        let url = params.url.trim().to_string();
+        webhooks::check_target(&url, webhooks::allows_private_targets(&ctx.config))
+            .map_err(Error::BadRequest)?;
+        let events = params
+            .events
+            .iter()
+            .map(|event| {
+                WebhookEvent::parse(event)
+                    .ok_or_else(|| Error::BadRequest(format!("unknown event: {event}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if events.is_empty() {
+            return Err(Error::BadRequest("Select at least one event".to_string()));
+        }
+        Ok(Self { name, url, events })
+    }
+}
+
+/// An endpoint as listed, with the secret masked.
+#[derive(Serialize)]
+struct EndpointView {
+    id: i32,
+    name: String,
+    url: String,
+    events: String,
+    active: bool,
+    secret_hint: String,
+}
+
+impl From<webhook_endpoints::Model> for EndpointView {
+    fn from(endpoint: webhook_endpoints::Model) -> Self {
+        let last = endpoint.secret.len().saturating_sub(4);
+        Self {
+            secret_hint: format!(
+                "{}…{}",
+                webhook_endpoints::SECRET_PREFIX,
+                endpoint.secret.get(last..).unwrap_or_default()
+            ),
+            id: endpoint.id,
+            name: endpoint.name,
+            url: endpoint.url,
+            events: endpoint.events,
+            active: endpoint.active,
+        }
+    }
+}
+
+/// Renders the endpoints and recent deliveries of `tenant`. The secret of a
+/// `created` endpoint is shown once so it can be copied to the receiver.
+async fn render_list(
+    v: &TeraView,
+    ctx: &AppContext,
+    tenant: i32,
+    created: Option<&webhook_endpoints::Model>,
+) -> Result<Response> {
+    let endpoints = webhook_endpoints::Entity::find_in_tenant(tenant)
+        .order_by_asc(webhook_endpoints::Column::Name)
+        .all(&ctx.db)
+        .await?
+        .into_iter()
+        .map(EndpointView::from)
+        .collect::<Vec<_>>();
+    let deliveries =
+        webhook_deliveries::Entity::list_recent(&ctx.db, tenant, RECENT_DELIVERIES_LIMIT).await?;
+    format::render().view(
+        v,
+        "webhooks/list.html",
+        data!({
+            "endpoints": endpoints,
+            "deliveries": deliveries,
+            "events": WebhookEvent::ALL.map(WebhookEvent::as_str),
+            "created": created.map(|endpoint| data!({
+                "name": endpoint.name,
+                "secret": endpoint.secret,
+            })),
+        }),
+    )
+}
+
+#[debug_handler]
+pub async fn list(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestAdmin(tenant): RequestAdmin,
+) -> Result<Response> {
+    render_list(&v, &ctx, tenant, None).await
+}
+
+#[debug_handler]
+pub async fn add(
+    ViewEngine(v): ViewEngine<TeraView>,
+    State(ctx): State<AppContext>,
+    RequestAdmin(tenant): RequestAdmin,
+    HtmlForm(params): HtmlForm<EndpointParams>,
+) -> Result<Response> {
+    let input = EndpointInput::parse(&ctx, params)?;
+    let endpoint =
+        webhook_endpoints::ActiveModel::generate(tenant, input.name, input.url, &input.events)
+            .insert(&ctx.db)
+            .await?;
+    render_list(&v, &ctx, tenant, Some(&endpoint)).await
+}
+
+#[debug_handler]
+pub async fn update(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestAdmin(tenant): RequestAdmin,
+    HtmlForm(params): HtmlForm<EndpointParams>,
+) -> Result<Response> {
+    let active = params.active.is_some();
+    let input = EndpointInput::parse(&ctx, params)?;
+    let endpoint = webhook_endpoints::Entity::find_by_id_in_tenant(tenant, id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    let mut endpoint = endpoint.into_active_model();
+    endpoint.name = ActiveValue::set(input.name);
+    endpoint.url = ActiveValue::set(input.url);
+    endpoint.events = ActiveValue::set(join_events(&input.events));
+    endpoint.active = ActiveValue::set(active);
+    endpoint.update(&ctx.db).await?;
+    format::redirect("/webhooks/list")
+}
+
+#[debug_handler]
+pub async fn remove(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestAdmin(tenant): RequestAdmin,
+) -> Result<Response> {
+    let deleted = webhook_endpoints::Entity::delete_many()
+        .filter(webhook_endpoints::Column::Id.eq(id))
+        .filter(webhook_endpoints::Column::TenantId.eq(tenant))
+        .exec(&ctx.db)
+        .await?;
+    if deleted.rows_affected == 0 {
+        return Err(Error::NotFound);
+    }
+    format::json(data!({ "status": "ok" }))
+}
+
+/// Sends a delivery again with a fresh set of attempts, e.g. after the
+/// receiving system was fixed.
+#[debug_handler]
+pub async fn redeliver(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    RequestAdmin(tenant): RequestAdmin,
+) -> Result<Response> {
+    let delivery = webhook_deliveries::Entity::find_by_id_in_tenant(tenant, id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    let delivery = delivery.reset(&ctx.db).await?;
+    WebhookWorker::perform_later(
+        &ctx,
+        WebhookWorkerArgs {
+            delivery_id: delivery.id,
+        },
+    )
+    .await?;
+    format::redirect("/webhooks/list")
+}
+
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("webhooks/")
+        .add("list", get(list))
+        .add("", post(add))
+        .add("{id}", post(update))
+        .add("{id}", delete(remove))
+        .add("deliveries/{id}/redeliver", post(redeliver))
+}
@@ -0,0 +1,569 @@
+//! Fluent translations shared by the Tera views and the PDF reports.
+use std::{
+    collections::{BTreeSet, HashMap},
+    convert::Infallible,
+    future::Future,
+    path::Path,
+    sync::{Arc, LazyLock},
+};
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{header, request::Parts, HeaderMap, Uri},
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use chrono_tz::Tz;
+use fluent_templates::{ArcLoader, Loader};
+use loco_rs::{app::AppContext, Error, Result};
+use serde::{Deserialize, Serialize};
+use unic_langid::{langid, LanguageIdentifier};
+
+use crate::{
+    models::users,
+    timezone::{self, RequestTimezone},
+};
+
+pub const I18N_DIR: &str = "assets/i18n";
+pub const I18N_SHARED: &str = "assets/i18n/shared.ftl";
+pub const DEFAULT_LOCALE: LanguageIdentifier = langid!("en-US");
+
+/// Fluent key of the `chrono` format string for dates in the given locale.
+const DATE_FORMAT_KEY: &str = "format-date";
+/// Fluent key of the `chrono` format string for timestamps in the given locale.
+const DATETIME_FORMAT_KEY: &str = "format-datetime";
+const FALLBACK_DATE_FORMAT: &str = "%Y-%m-%d";
+const FALLBACK_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Cookie remembering the language picked in the navbar.
+pub const LANG_COOKIE: &str = "lang";
+/// How long the language cookie is kept, one year.
+const LANG_COOKIE_MAX_AGE: u32 = 365 * 24 * 60 * 60;
+
+tokio::task_local! {
+    static CURRENT_LOCALE: LanguageIdentifier;
+}
+
+static LOADER: LazyLock<std::result::Result<Arc<ArcLoader>, String>> = LazyLock::new(|| {
+    ArcLoader::builder(&I18N_DIR, DEFAULT_LOCALE)
+        .shared_resources(Some(&[I18N_SHARED.into()]))
+        .customize(|bundle| bundle.set_use_isolating(false))
+        .build()
+        .map(Arc::new)
+        .map_err(|error| error.to_string())
+});
+
+/// Returns the Fluent resources loaded from [`I18N_DIR`].
+///
+/// # Errors
+///
+/// When the resources could not be loaded
+pub fn loader() -> Result<Arc<ArcLoader>> {
+    LOADER.clone().map_err(|error| Error::string(&error))
+}
+
+/// Locales with Fluent resources, the default locale first.
+#[must_use]
+pub fn supported_locales() -> Vec<LanguageIdentifier> {
+    let mut locales = loader()
+        .map(|loader| loader.locales().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    locales.sort_by_key(|locale| (*locale != DEFAULT_LOCALE, locale.to_string()));
+    if locales.is_empty() {
+        locales.push(DEFAULT_LOCALE);
+    }
+    locales
+}
+
+/// Picks the best supported locale for `requested`, e.g. `de` or `de-AT`
+/// resolve to `de-DE`.
+#[must_use]
+pub fn match_locale(requested: &str) -> Option<LanguageIdentifier> {
+    let requested = requested.trim().parse::<LanguageIdentifier>().ok()?;
+    let supported = supported_locales();
+    supported
+        .iter()
+        .find(|locale| **locale == requested)
+        .or_else(|| {
+            supported
+                .iter()
+                .find(|locale| locale.language == requested.language)
+        })
+        .cloned()
+}
+
+/// Value of the cookie `name` sent with a request.
+#[must_use]
+pub fn cookie<'h>(headers: &'h HeaderMap, name: &str) -> Option<&'h str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|cookie| {
+            let (key, value) = cookie.trim().split_once('=')?;
+            (key == name).then_some(value)
+        })
+}
+
+/// Language stored in the [`LANG_COOKIE`] cookie.
+#[must_use]
+pub fn cookie_locale(headers: &HeaderMap) -> Option<&str> {
+    cookie(headers, LANG_COOKIE)
+}
+
+/// `Set-Cookie` value remembering `locale` for later requests.
+#[must_use]
+pub fn lang_cookie(locale: &LanguageIdentifier) -> String {
+    format!("{LANG_COOKIE}={locale}; Path=/; Max-Age={LANG_COOKIE_MAX_AGE}; SameSite=Lax")
+}
+
+/// Chooses the locale of a response from an explicitly requested language,
+/// the [`LANG_COOKIE`] cookie, the user's preference and the
+/// `Accept-Language` header, in that order.
+#[must_use]
+pub fn negotiate_locale(
+    requested: Option<&str>,
+    preference: Option<&str>,
+    headers: &HeaderMap,
+) -> LanguageIdentifier {
+    let accepted = headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let mut accepted = accepted
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|part| part.trim().strip_prefix("q="))
+                .and_then(|quality| quality.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (!tag.is_empty() && tag != "*").then_some((tag, quality))
+        })
+        .collect::<Vec<_>>();
+    accepted.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    requested
+        .into_iter()
+        .chain(cookie_locale(headers))
+        .chain(preference)
+        .chain(accepted.into_iter().map(|(tag, _)| tag))
+        .find_map(match_locale)
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Runs `future` with `locale` as the [`current_locale`], so views rendered
+/// while handling a request use the negotiated language.
+pub async fn scope_locale<F: Future>(locale: LanguageIdentifier, future: F) -> F::Output {
+    CURRENT_LOCALE.scope(locale, future).await
+}
+
+/// Locale negotiated for the request being handled, or [`DEFAULT_LOCALE`]
+/// outside of a request.
+#[must_use]
+pub fn current_locale() -> LanguageIdentifier {
+    CURRENT_LOCALE
+        .try_with(Clone::clone)
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Translations of lookup codes maintained in the `translations` table.
+/// Languages are matched case-insensitively by full tag (`de-DE`) or by
+/// language (`DE`).
+#[derive(Clone, Debug, Default)]
+pub struct CodeTranslations(Arc<HashMap<(String, String), String>>);
+
+impl CodeTranslations {
+    #[must_use]
+    pub fn new<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String, String)>,
+    {
+        Self(Arc::new(
+            entries
+                .into_iter()
+                .map(|(code, lang, text)| ((code, lang.trim().to_lowercase()), text))
+                .collect(),
+        ))
+    }
+
+    #[must_use]
+    pub fn lookup(&self, code: &str, locale: &LanguageIdentifier) -> Option<&str> {
+        [
+            locale.to_string().to_lowercase(),
+            locale.language.as_str().to_lowercase(),
+        ]
+        .into_iter()
+        .find_map(|lang| self.0.get(&(code.to_string(), lang)))
+        .map(String::as_str)
+    }
+}
+
+/// Looks up translations and formats dates for one locale and time zone.
+#[derive(Clone)]
+pub struct Translator {
+    locale: LanguageIdentifier,
+    loader: Option<Arc<ArcLoader>>,
+    codes: CodeTranslations,
+    timezone: Tz,
+}
+
+impl std::fmt::Debug for Translator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Translator")
+            .field("locale", &self.locale)
+            .field("timezone", &self.timezone)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Translator {
+    #[must_use]
+    pub fn new(locale: LanguageIdentifier) -> Self {
+        Self {
+            locale,
+            loader: loader().ok(),
+            codes: CodeTranslations::default(),
+            timezone: Tz::UTC,
+        }
+    }
+
+    /// Formats dates in `timezone` instead of UTC.
+    #[must_use]
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Uses `codes` for lookup codes before falling back to Fluent.
+    #[must_use]
+    pub fn with_codes(mut self, codes: CodeTranslations) -> Self {
+        self.codes = codes;
+        self
+    }
+
+    /// Translator for `locale` with the lookup code translations cached in
+    /// `ctx`.
+    #[must_use]
+    pub fn for_context(ctx: &AppContext, locale: LanguageIdentifier) -> Self {
+        Self::new(locale).with_codes(
+            ctx.shared_store
+                .get::<CodeTranslations>()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Translator in the locale and time zone saved by `user`, falling back
+    /// to [`DEFAULT_LOCALE`] and the site time zone.
+    #[must_use]
+    pub fn for_user(ctx: &AppContext, user: &users::Model) -> Self {
+        let locale = user
+            .locale
+            .as_deref()
+            .and_then(match_locale)
+            .unwrap_or(DEFAULT_LOCALE);
+        Self::for_context(ctx, locale).with_timezone(timezone::user_timezone(&ctx.config, user))
+    }
+
+    #[must_use]
+    pub fn locale(&self) -> &LanguageIdentifier {
+        &self.locale
+    }
+
+    #[must_use]
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Translation of `key`, falling back to the default locale.
+    #[must_use]
+    pub fn try_t(&self, key: &str) -> Option<String> {
+        self.loader.as_ref()?.try_lookup(&self.locale, key)
+    }
+
+    /// Translation of `key`, or `key` itself when there is none.
+    #[must_use]
+    pub fn t(&self, key: &str) -> String {
+        self.try_t(key).unwrap_or_else(|| key.to_string())
+    }
+
+    /// Translation of a code such as `RESULT_OK` or `INTERVAL_YEARLY`, from
+    /// the `translations` table, then Fluent.
+    #[must_use]
+    pub fn code(&self, code: &str) -> String {
+        self.codes
+            .lookup(code, &self.locale)
+            .map(ToString::to_string)
+            .or_else(|| self.try_t(code))
+            .unwrap_or_else(|| humanize_code(code))
+    }
+
+    /// Local date of `value` in the translator's time zone.
+    #[must_use]
+    pub fn format_date<T: chrono::TimeZone>(&self, value: &DateTime<T>) -> String {
+        let format = self
+            .try_t(DATE_FORMAT_KEY)
+            .unwrap_or_else(|| FALLBACK_DATE_FORMAT.to_string());
+        value
+            .with_timezone(&self.timezone)
+            .format(&format)
+            .to_string()
+    }
+
+    /// Local date and time of `value` in the translator's time zone.
+    #[must_use]
+    pub fn format_datetime(&self, value: DateTime<FixedOffset>) -> String {
+        let format = self
+            .try_t(DATETIME_FORMAT_KEY)
+            .unwrap_or_else(|| FALLBACK_DATETIME_FORMAT.to_string());
+        value
+            .with_timezone(&self.timezone)
+            .format(&format)
+            .to_string()
+    }
+
+    /// Local date and time of `value` followed by the time zone name.
+    #[must_use]
+    pub fn format_generated_at(&self, value: DateTime<Utc>) -> String {
+        format!(
+            "{} {}",
+            self.format_datetime(value.fixed_offset()),
+            self.timezone.name()
+        )
+    }
+}
+
+impl Default for Translator {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCALE)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LangQuery {
+    lang: Option<String>,
+}
+
+/// Language requested with the `lang` query parameter.
+#[must_use]
+pub fn query_locale(uri: &Uri) -> Option<String> {
+    Query::<LangQuery>::try_from_uri(uri)
+        .ok()
+        .and_then(|Query(query)| query.lang)
+}
+
+/// Translator for the locale and time zone negotiated by the locale
+/// middleware, or else for the `lang` query parameter, the language cookie or
+/// the `Accept-Language` header.
+#[derive(Clone, Debug)]
+pub struct RequestLocale(pub Translator);
+
+impl FromRequestParts<AppContext> for RequestLocale {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        ctx: &AppContext,
+    ) -> Result<Self, Self::Rejection> {
+        let locale = if let Some(locale) = parts.extensions.get::<LanguageIdentifier>() {
+            locale.clone()
+        } else {
+            negotiate_locale(query_locale(&parts.uri).as_deref(), None, &parts.headers)
+        };
+        let RequestTimezone(timezone) = RequestTimezone::from_request_parts(parts, ctx).await?;
+        Ok(Self(
+            Translator::for_context(ctx, locale).with_timezone(timezone),
+        ))
+    }
+}
+
+/// Keys of one locale compared with the [`DEFAULT_LOCALE`], as `file: key`.
+#[derive(Debug, Default, Serialize)]
+pub struct LocaleKeyDiff {
+    pub locale: String,
+    /// Keys of the default locale without translation.
+    pub missing: Vec<String>,
+    /// Keys the default locale does not have, usually renamed or removed ones.
+    pub extra: Vec<String>,
+}
+
+/// Message, term (`-name`) and attribute (`name.attribute`) keys of the
+/// Fluent files in `dir`, as `file: key`.
+///
+/// # Errors
+///
+/// When a file cannot be read or has syntax errors
+pub fn locale_keys(dir: &Path) -> Result<BTreeSet<String>> {
+    let mut keys = BTreeSet::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "ftl") {
+            continue;
+        }
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let source = std::fs::read_to_string(&path)?;
+        let resource = fluent_syntax::parser::parse(source.as_str())
+            .map_err(|(_, errors)| Error::string(&format!("{}: {errors:?}", path.display())))?;
+        for entry in resource.body {
+            let (id, attributes) = match entry {
+                fluent_syntax::ast::Entry::Message(message) => {
+                    (message.id.name.to_string(), message.attributes)
+                }
+                fluent_syntax::ast::Entry::Term(term) => {
+                    (format!("-{}", term.id.name), term.attributes)
+                }
+                _ => continue,
+            };
+            for attribute in attributes {
+                keys.insert(format!("{file}: {id}.{}", attribute.id.name));
+            }
+            keys.insert(format!("{file}: {id}"));
+        }
+    }
+    Ok(keys)
+}
+
+/// Compares the keys of every locale folder in `dir` with the
+/// [`DEFAULT_LOCALE`].
+///
+/// # Errors
+///
+/// When the folders cannot be read or a file has syntax errors
+pub fn diff_locales(dir: &Path) -> Result<Vec<LocaleKeyDiff>> {
+    let reference = locale_keys(&dir.join(DEFAULT_LOCALE.to_string()))?;
+    let mut locales = std::fs::read_dir(dir)?
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|locale| *locale != DEFAULT_LOCALE.to_string())
+        .collect::<Vec<_>>();
+    locales.sort();
+
+    locales
+        .into_iter()
+        .map(|locale| {
+            let keys = locale_keys(&dir.join(&locale))?;
+            Ok(LocaleKeyDiff {
+                missing: reference.difference(&keys).cloned().collect(),
+                extra: keys.difference(&reference).cloned().collect(),
+                locale,
+            })
+        })
+        .collect()
+}
+
+/// Readable fallback for codes without translation: `RESULT_NOT_OK` becomes
+/// `Not Ok`.
+#[must_use]
+pub fn humanize_code(code: &str) -> String {
+    let trimmed = code
+        .strip_prefix("RESULT_")
+        .or_else(|| code.strip_prefix("INTERVAL_"))
+        .unwrap_or(code);
+
+    trimmed
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let Some(first) = chars.next() else {
+                return String::new();
+            };
+            let rest = chars.as_str().to_ascii_lowercase();
+            format!("{}{}", first.to_ascii_uppercase(), rest)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn negotiates_supported_locale() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(negotiate_locale(None, None, &headers), DEFAULT_LOCALE);
+        assert_eq!(
+            negotiate_locale(None, Some("de"), &headers),
+            langid!("de-DE")
+        );
+
+        headers.insert(
+            header::ACCEPT_LANGUAGE,
+            HeaderValue::from_static("fr;q=0.9, de-AT;q=0.8, en;q=0.5"),
+        );
+        assert_eq!(negotiate_locale(None, None, &headers), langid!("de-DE"));
+        assert_eq!(negotiate_locale(Some("en"), None, &headers), DEFAULT_LOCALE);
+        assert_eq!(
+            negotiate_locale(Some("xx"), None, &headers),
+            langid!("de-DE")
+        );
+        assert_eq!(
+            negotiate_locale(None, Some("en-US"), &headers),
+            DEFAULT_LOCALE
+        );
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; lang=de-DE"),
+        );
+        assert_eq!(cookie_locale(&headers), Some("de-DE"));
+        assert_eq!(
+            negotiate_locale(None, Some("en-US"), &headers),
+            langid!("de-DE")
+        );
+        assert_eq!(negotiate_locale(Some("en"), None, &headers), DEFAULT_LOCALE);
+    }
+
+    #[test]
+    fn translates_codes_and_dates() {
+        let german = Translator::new(langid!("de-DE"));
+        assert_eq!(german.code("RESULT_FAIL"), "Nicht OK");
+        assert_eq!(german.code("RESULT_UNKNOWN_CODE"), "Unknown Code");
+
+        let value = DateTime::parse_from_rfc3339("2025-08-16T09:45:00+00:00").unwrap();
+        assert_eq!(german.format_datetime(value), "16.08.2025 09:45");
+        assert_eq!(german.format_date(&value), "16.08.2025");
+        assert_eq!(Translator::default().format_date(&value), "08/16/2025");
+
+        let berlin = german.with_timezone(Tz::Europe__Berlin);
+        assert_eq!(berlin.format_datetime(value), "16.08.2025 11:45");
+        let late = DateTime::parse_from_rfc3339("2025-08-16T22:30:00+00:00").unwrap();
+        assert_eq!(berlin.format_date(&late), "17.08.2025");
+        assert_eq!(
+            berlin.format_generated_at(late.to_utc()),
+            "17.08.2025 00:30 Europe/Berlin"
+        );
+    }
+
+    #[test]
+    fn prefers_code_translations_from_the_database() {
+        let codes = CodeTranslations::new([
+            (
+                "RESULT_FAIL".to_string(),
+                "DE".to_string(),
+                "Mangelhaft".to_string(),
+            ),
+            (
+                "RESULT_DAMAGED".to_string(),
+                "en-US".to_string(),
+                "Damaged".to_string(),
+            ),
+        ]);
+        let german = Translator::new(langid!("de-DE")).with_codes(codes.clone());
+        assert_eq!(german.code("RESULT_FAIL"), "Mangelhaft");
+        assert_eq!(german.code("RESULT_OK"), "OK");
+        assert_eq!(german.code("RESULT_DAMAGED"), "Damaged");
+
+        let english = Translator::default().with_codes(codes);
+        assert_eq!(english.code("RESULT_FAIL"), "Not OK");
+        assert_eq!(english.code("RESULT_DAMAGED"), "Damaged");
+    }
+}
@@ -1,35 +1,38 @@
-use crate::models::{checklists, expiries, intervals, inventory_item_kinds, results, users};
+use crate::{
+    i18n::CodeTranslations,
+    models::{
+        checklists, expiries, intervals, inventory_item_kinds, results, tenant_memberships,
+        tenants, translations, users,
+    },
+};
 use std::collections::BTreeMap;
 
+/// Cached rows of every tenant, by tenant id and then by row id.
+pub type ByTenant<T> = BTreeMap<i32, BTreeMap<i32, T>>;
+
+/// The rows of `tenant_id` from a [`ByTenant`] cache in the shared store.
+fn tenant_rows<T: Clone + Send + Sync + 'static>(
+    ctx: &loco_rs::app::AppContext,
+    tenant_id: i32,
+) -> loco_rs::Result<BTreeMap<i32, T>> {
+    ctx.shared_store
+        .get_ref::<ByTenant<T>>()
+        .map(|rows| rows.get(&tenant_id).cloned().unwrap_or_default())
+        .ok_or(loco_rs::Error::InternalServerError)
+}
+
 pub struct AppCacheInitializer;
 
 impl AppCacheInitializer {
-    async fn reload_cached(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
-        use sea_orm::EntityTrait;
-
-        let _: Option<BTreeMap<i32, checklists::Model>> = ctx.shared_store.remove();
-        let checklists = checklists::Entity::find().all(&ctx.db).await?;
-        let checklists: BTreeMap<_, _> = checklists.into_iter().map(|i| (i.id, i)).collect();
-        ctx.shared_store.insert(checklists);
-
-        let _: Option<BTreeMap<i32, intervals::Model>> = ctx.shared_store.remove();
-        let intervals = intervals::Entity::find().all(&ctx.db).await?;
-        let intervals: BTreeMap<_, _> = intervals.into_iter().map(|i| (i.id, i)).collect();
-        ctx.shared_store.insert(intervals);
-
-        let _: Option<BTreeMap<i32, expiries::Model>> = ctx.shared_store.remove();
-        let expiries = expiries::Entity::find().all(&ctx.db).await?;
-        let expiries: BTreeMap<_, _> = expiries.into_iter().map(|i| (i.id, i)).collect();
-        ctx.shared_store.insert(expiries);
-
+    pub async fn reload_cached(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
+        refresh_checklists_cache(ctx).await?;
+        refresh_intervals_cache(ctx).await?;
+        refresh_expiries_cache(ctx).await?;
         refresh_item_kinds_cache(ctx).await?;
-
-        let _: Option<BTreeMap<i32, results::Model>> = ctx.shared_store.remove();
-        let results = results::Entity::find().all(&ctx.db).await?;
-        let results: BTreeMap<_, _> = results.into_iter().map(|i| (i.id, i)).collect();
-        ctx.shared_store.insert(results);
-
+        refresh_results_cache(ctx).await?;
         refresh_users_cache(ctx).await?;
+        refresh_tenants_cache(ctx).await?;
+        refresh_translations_cache(ctx).await?;
 
         Ok(())
     }
@@ -46,20 +49,25 @@ impl loco_rs::app::Initializer for AppCacheInitializer {
     }
 }
 
+/// Cached lookup tables. Checklists, item kinds and users are those of one
+/// tenant; intervals, expiries, results and translations are shared.
 pub trait AppData {
-    fn get_checklists(&self) -> loco_rs::Result<BTreeMap<i32, checklists::Model>>;
+    fn get_checklists(&self, tenant_id: i32) -> loco_rs::Result<BTreeMap<i32, checklists::Model>>;
     fn get_intervals(&self) -> loco_rs::Result<BTreeMap<i32, intervals::Model>>;
     fn get_expiries(&self) -> loco_rs::Result<BTreeMap<i32, expiries::Model>>;
-    fn get_item_kinds(&self) -> loco_rs::Result<BTreeMap<i32, inventory_item_kinds::Model>>;
+    fn get_item_kinds(
+        &self,
+        tenant_id: i32,
+    ) -> loco_rs::Result<BTreeMap<i32, inventory_item_kinds::Model>>;
     fn get_results(&self) -> loco_rs::Result<BTreeMap<i32, results::Model>>;
-    fn get_users(&self) -> loco_rs::Result<BTreeMap<i32, users::Model>>;
+    /// Members of the tenant.
+    fn get_users(&self, tenant_id: i32) -> loco_rs::Result<BTreeMap<i32, users::Model>>;
+    fn get_tenants(&self) -> loco_rs::Result<BTreeMap<i32, tenants::Model>>;
 }
 
 impl AppData for loco_rs::app::AppContext {
-    fn get_checklists(&self) -> loco_rs::Result<BTreeMap<i32, checklists::Model>> {
-        self.shared_store
-            .get::<BTreeMap<i32, checklists::Model>>()
-            .ok_or(loco_rs::Error::InternalServerError)
+    fn get_checklists(&self, tenant_id: i32) -> loco_rs::Result<BTreeMap<i32, checklists::Model>> {
+        tenant_rows(self, tenant_id)
     }
 
     fn get_intervals(&self) -> loco_rs::Result<BTreeMap<i32, intervals::Model>> {
@@ -74,10 +82,11 @@ impl AppData for loco_rs::app::AppContext {
             .ok_or(loco_rs::Error::InternalServerError)
     }
 
-    fn get_item_kinds(&self) -> loco_rs::Result<BTreeMap<i32, inventory_item_kinds::Model>> {
-        self.shared_store
-            .get::<BTreeMap<i32, inventory_item_kinds::Model>>()
-            .ok_or(loco_rs::Error::InternalServerError)
+    fn get_item_kinds(
+        &self,
+        tenant_id: i32,
+    ) -> loco_rs::Result<BTreeMap<i32, inventory_item_kinds::Model>> {
+        tenant_rows(self, tenant_id)
     }
 
     fn get_results(&self) -> loco_rs::Result<BTreeMap<i32, results::Model>> {
@@ -86,9 +95,13 @@ impl AppData for loco_rs::app::AppContext {
             .ok_or(loco_rs::Error::InternalServerError)
     }
 
-    fn get_users(&self) -> loco_rs::Result<BTreeMap<i32, users::Model>> {
+    fn get_users(&self, tenant_id: i32) -> loco_rs::Result<BTreeMap<i32, users::Model>> {
+        tenant_rows(self, tenant_id)
+    }
+
+    fn get_tenants(&self) -> loco_rs::Result<BTreeMap<i32, tenants::Model>> {
         self.shared_store
-            .get::<BTreeMap<i32, users::Model>>()
+            .get::<BTreeMap<i32, tenants::Model>>()
             .ok_or(loco_rs::Error::InternalServerError)
     }
 }
@@ -96,8 +109,13 @@ impl AppData for loco_rs::app::AppContext {
 pub async fn refresh_item_kinds_cache(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
     use sea_orm::EntityTrait;
     let item_kinds = inventory_item_kinds::Entity::find().all(&ctx.db).await?;
-    let map: BTreeMap<_, _> = item_kinds.into_iter().map(|i| (i.id, i)).collect();
-    let _: Option<BTreeMap<i32, inventory_item_kinds::Model>> = ctx.shared_store.remove();
+    let mut map = ByTenant::new();
+    for item_kind in item_kinds {
+        map.entry(item_kind.tenant_id)
+            .or_default()
+            .insert(item_kind.id, item_kind);
+    }
+    let _: Option<ByTenant<inventory_item_kinds::Model>> = ctx.shared_store.remove();
     ctx.shared_store.insert(map);
     Ok(())
 }
@@ -105,8 +123,82 @@ pub async fn refresh_item_kinds_cache(ctx: &loco_rs::prelude::AppContext) -> loc
 pub async fn refresh_users_cache(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
     use sea_orm::EntityTrait;
     let users = users::Entity::find().all(&ctx.db).await?;
-    let map: BTreeMap<_, _> = users.into_iter().map(|i| (i.id, i)).collect();
-    let _: Option<BTreeMap<i32, users::Model>> = ctx.shared_store.remove();
+    let users: BTreeMap<_, _> = users.into_iter().map(|i| (i.id, i)).collect();
+    let map: ByTenant<users::Model> = tenant_memberships::Entity::members_by_tenant(&ctx.db)
+        .await?
+        .into_iter()
+        .map(|(tenant_id, user_ids)| {
+            let members = user_ids
+                .into_iter()
+                .filter_map(|user_id| Some((user_id, users.get(&user_id)?.clone())))
+                .collect();
+            (tenant_id, members)
+        })
+        .collect();
+    let _: Option<ByTenant<users::Model>> = ctx.shared_store.remove();
     ctx.shared_store.insert(map);
     Ok(())
 }
+
+pub async fn refresh_checklists_cache(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
+    use sea_orm::EntityTrait;
+    let checklists = checklists::Entity::find().all(&ctx.db).await?;
+    let mut map = ByTenant::new();
+    for checklist in checklists {
+        map.entry(checklist.tenant_id)
+            .or_default()
+            .insert(checklist.id, checklist);
+    }
+    let _: Option<ByTenant<checklists::Model>> = ctx.shared_store.remove();
+    ctx.shared_store.insert(map);
+    Ok(())
+}
+
+pub async fn refresh_intervals_cache(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
+    use sea_orm::EntityTrait;
+    let intervals = intervals::Entity::find().all(&ctx.db).await?;
+    let map: BTreeMap<_, _> = intervals.into_iter().map(|i| (i.id, i)).collect();
+    let _: Option<BTreeMap<i32, intervals::Model>> = ctx.shared_store.remove();
+    ctx.shared_store.insert(map);
+    Ok(())
+}
+
+pub async fn refresh_expiries_cache(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
+    use sea_orm::EntityTrait;
+    let expiries = expiries::Entity::find().all(&ctx.db).await?;
+    let map: BTreeMap<_, _> = expiries.into_iter().map(|i| (i.id, i)).collect();
+    let _: Option<BTreeMap<i32, expiries::Model>> = ctx.shared_store.remove();
+    ctx.shared_store.insert(map);
+    Ok(())
+}
+
+pub async fn refresh_results_cache(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
+    use sea_orm::EntityTrait;
+    let results = results::Entity::find().all(&ctx.db).await?;
+    let map: BTreeMap<_, _> = results.into_iter().map(|i| (i.id, i)).collect();
+    let _: Option<BTreeMap<i32, results::Model>> = ctx.shared_store.remove();
+    ctx.shared_store.insert(map);
+    Ok(())
+}
+
+pub async fn refresh_tenants_cache(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
+    use sea_orm::EntityTrait;
+    let tenants = tenants::Entity::find().all(&ctx.db).await?;
+    let map: BTreeMap<_, _> = tenants.into_iter().map(|i| (i.id, i)).collect();
+    let _: Option<BTreeMap<i32, tenants::Model>> = ctx.shared_store.remove();
+    ctx.shared_store.insert(map);
+    Ok(())
+}
+
+pub async fn refresh_translations_cache(ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
+    use sea_orm::EntityTrait;
+    let translations = translations::Entity::find().all(&ctx.db).await?;
+    let codes = CodeTranslations::new(
+        translations
+            .into_iter()
+            .map(|translation| (translation.code, translation.lang, translation.text)),
+    );
+    let _: Option<CodeTranslations> = ctx.shared_store.remove();
+    ctx.shared_store.insert(codes);
+    Ok(())
+}
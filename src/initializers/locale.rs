@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::{self, Next},
+    response::Response,
+    Router as AxumRouter,
+};
+use loco_rs::{
+    app::{AppContext, Initializer},
+    auth::jwt::JWT,
+    Result,
+};
+
+use crate::{i18n, models::users, session, tenant, timezone};
+
+/// User authenticated with a bearer token, for their language, time zone and
+/// tenant preferences.
+async fn bearer_user(ctx: &AppContext, headers: &HeaderMap) -> Option<users::Model> {
+    let token = headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+    let secret = ctx.config.get_jwt_config().ok()?.secret.clone();
+    let claims = JWT::new(&secret).validate(token).ok()?.claims;
+    users::Model::find_by_pid(&ctx.db, &claims.pid).await.ok()
+}
+
+/// User authenticated with a bearer token or else the session cookie, with
+/// the tenant requested by the session.
+async fn request_user(
+    ctx: &AppContext,
+    headers: &HeaderMap,
+) -> Option<(users::Model, Option<i32>)> {
+    if let Some(user) = bearer_user(ctx, headers).await {
+        return Some((user, None));
+    }
+    session::cookie_session(ctx, headers)
+        .await
+        .map(|(user, tenant_id)| (user, Some(tenant_id)))
+}
+
+/// Negotiates the user, locale, time zone and tenant of every request and
+/// makes them available to the handlers as request extensions and to the
+/// views as `request_lang()`, `request_tenant()` and the `local_date` and
+/// `local_datetime` filters.
+async fn negotiate(State(ctx): State<AppContext>, mut request: Request, next: Next) -> Response {
+    let (user, requested_tenant) = request_user(&ctx, request.headers())
+        .await
+        .map_or((None, None), |(user, tenant)| (Some(user), tenant));
+    let locale = i18n::negotiate_locale(
+        i18n::query_locale(request.uri()).as_deref(),
+        user.as_ref().and_then(|user| user.locale.as_deref()),
+        request.headers(),
+    );
+    let timezone = timezone::negotiate_timezone(
+        user.as_ref().and_then(|user| user.timezone.as_deref()),
+        request.headers(),
+        timezone::site_timezone(&ctx.config),
+    );
+    let tenant = match &user {
+        Some(user) => tenant::negotiate_tenant(&ctx, user, requested_tenant).await,
+        None => None,
+    };
+    request.extensions_mut().insert(locale.clone());
+    request.extensions_mut().insert(timezone);
+    if let Some(user) = user {
+        request.extensions_mut().insert(user);
+    }
+    let response = timezone::scope_timezone(timezone, async move {
+        match tenant {
+            Some(tenant) => {
+                request.extensions_mut().insert(tenant.clone());
+                tenant::scope_tenant(tenant, next.run(request)).await
+            }
+            None => next.run(request).await,
+        }
+    });
+    i18n::scope_locale(locale, response).await
+}
+
+#[allow(clippy::module_name_repetitions)]
+pub struct LocaleInitializer;
+
+#[async_trait]
+impl Initializer for LocaleInitializer {
+    fn name(&self) -> String {
+        "locale".to_string()
+    }
+
+    async fn after_routes(&self, router: AxumRouter, ctx: &AppContext) -> Result<AxumRouter> {
+        Ok(router.layer(middleware::from_fn_with_state(ctx.clone(), negotiate)))
+    }
+}
@@ -1,2 +1,4 @@
 pub mod app_cache;
+pub mod locale;
+pub mod search_index;
 pub mod view_engine;
@@ -0,0 +1,20 @@
+use crate::search;
+
+/// Fills the full-text search index on the first start after it was
+/// created, so existing items and checks can be found.
+pub struct SearchIndexInitializer;
+
+#[async_trait::async_trait]
+impl loco_rs::app::Initializer for SearchIndexInitializer {
+    fn name(&self) -> String {
+        "search-index".to_owned()
+    }
+
+    async fn before_run(&self, ctx: &loco_rs::prelude::AppContext) -> loco_rs::Result<()> {
+        if search::is_empty(&ctx.db).await? {
+            let indexed = search::reindex_all(&ctx.db).await?;
+            tracing::info!(indexed, "built the search index");
+        }
+        Ok(())
+    }
+}
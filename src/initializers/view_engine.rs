@@ -1,15 +1,154 @@
+use std::{collections::HashMap, sync::Arc};
+
 use async_trait::async_trait;
 use axum::{Extension, Router as AxumRouter};
-use fluent_templates::{ArcLoader, FluentLoader};
+use chrono::DateTime;
+use fluent_templates::FluentLoader;
 use loco_rs::{
-    app::{AppContext, Initializer},
+    app::{AppContext, Initializer, SharedStore},
     controller::views::{engines, ViewEngine},
-    Error, Result,
+    Result,
 };
 use tracing::info;
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    i18n::{self, CodeTranslations, Translator, DEFAULT_LOCALE, I18N_DIR},
+    tenant, timezone,
+};
+
+/// Tera function `code_label(code=..., lang=...)` translating lookup codes
+/// such as `INTERVAL_MONTHLY` with the `translations` table, falling back to
+/// Fluent and finally a readable form of the code.
+struct CodeLabel {
+    shared_store: Arc<SharedStore>,
+}
+
+impl tera::Function for CodeLabel {
+    fn call(&self, args: &HashMap<String, tera::Value>) -> tera::Result<tera::Value> {
+        let Some(code) = args.get("code") else {
+            return Err(tera::Error::msg("code_label: missing `code` argument"));
+        };
+        let Some(code) = code.as_str() else {
+            return Ok(code.clone());
+        };
+        let locale = args
+            .get("lang")
+            .and_then(tera::Value::as_str)
+            .and_then(|lang| lang.parse::<LanguageIdentifier>().ok())
+            .unwrap_or(DEFAULT_LOCALE);
+        let translator = Translator::new(locale).with_codes(
+            self.shared_store
+                .get::<CodeTranslations>()
+                .unwrap_or_default(),
+        );
+        Ok(tera::Value::String(translator.code(code)))
+    }
+
+    fn is_safe(&self) -> bool {
+        false
+    }
+}
+
+/// Tera function `request_lang()` returning the locale negotiated for the
+/// request being rendered, e.g. `de-DE`.
+#[allow(clippy::unnecessary_wraps)]
+fn request_lang(_args: &HashMap<String, tera::Value>) -> tera::Result<tera::Value> {
+    Ok(tera::Value::String(i18n::current_locale().to_string()))
+}
+
+/// Tera function `request_timezone()` returning the time zone negotiated
+/// for the request being rendered, e.g. `Europe/Berlin`.
+#[allow(clippy::unnecessary_wraps)]
+fn request_timezone(_args: &HashMap<String, tera::Value>) -> tera::Result<tera::Value> {
+    Ok(tera::Value::String(
+        timezone::current_timezone().name().to_string(),
+    ))
+}
+
+/// Tera function `request_tenant()` returning the name of the tenant
+/// negotiated for the request being rendered, empty outside of requests.
+#[allow(clippy::unnecessary_wraps)]
+fn request_tenant(_args: &HashMap<String, tera::Value>) -> tera::Result<tera::Value> {
+    Ok(tera::Value::String(
+        tenant::current_tenant()
+            .map(|tenant| tenant.name)
+            .unwrap_or_default(),
+    ))
+}
+
+/// Formats an RFC 3339 timestamp in the request's time zone, with the date
+/// format of the request's locale or an explicit `format` argument. Other
+/// values such as "never" placeholders are passed through.
+fn format_local(
+    value: &tera::Value,
+    args: &HashMap<String, tera::Value>,
+    with_time: bool,
+) -> tera::Value {
+    let Some(timestamp) = value
+        .as_str()
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+    else {
+        return value.clone();
+    };
+    let translator =
+        Translator::new(i18n::current_locale()).with_timezone(timezone::current_timezone());
+    let formatted = match args.get("format").and_then(tera::Value::as_str) {
+        Some(format) => timestamp
+            .with_timezone(&translator.timezone())
+            .format(format)
+            .to_string(),
+        None if with_time => translator.format_datetime(timestamp),
+        None => translator.format_date(&timestamp),
+    };
+    tera::Value::String(formatted)
+}
+
+/// Tera filter `local_datetime`, see [`format_local`].
+#[allow(clippy::unnecessary_wraps)]
+fn local_datetime(
+    value: &tera::Value,
+    args: &HashMap<String, tera::Value>,
+) -> tera::Result<tera::Value> {
+    Ok(format_local(value, args, true))
+}
+
+/// Tera filter `local_date`, see [`format_local`].
+#[allow(clippy::unnecessary_wraps)]
+fn local_date(
+    value: &tera::Value,
+    args: &HashMap<String, tera::Value>,
+) -> tera::Result<tera::Value> {
+    Ok(format_local(value, args, false))
+}
+
+/// Tera function `languages()` listing the locale folders found in
+/// [`I18N_DIR`] for the language switcher, each with its `code`, its `name`
+/// in itself and the `flag` in `/static/flags/`.
+#[allow(clippy::unnecessary_wraps)]
+fn languages(_args: &HashMap<String, tera::Value>) -> tera::Result<tera::Value> {
+    let languages = i18n::supported_locales()
+        .into_iter()
+        .map(|locale| {
+            let translator = Translator::new(locale.clone());
+            let flag = translator.try_t("gui-language-flag").unwrap_or_else(|| {
+                locale.region.map_or_else(
+                    || locale.language.as_str().to_string(),
+                    |region| region.as_str().to_lowercase(),
+                )
+            });
+            serde_json::json!({
+                "code": locale.to_string(),
+                "name": translator
+                    .try_t("gui-language-name")
+                    .unwrap_or_else(|| locale.to_string()),
+                "flag": flag,
+            })
+        })
+        .collect();
+    Ok(tera::Value::Array(languages))
+}
 
-const I18N_DIR: &str = "assets/i18n";
-const I18N_SHARED: &str = "assets/i18n/shared.ftl";
 #[allow(clippy::module_name_repetitions)]
 pub struct ViewEngineInitializer;
 
@@ -19,19 +158,26 @@ impl Initializer for ViewEngineInitializer {
         "view-engine".to_string()
     }
 
-    async fn after_routes(&self, router: AxumRouter, _ctx: &AppContext) -> Result<AxumRouter> {
+    async fn after_routes(&self, router: AxumRouter, ctx: &AppContext) -> Result<AxumRouter> {
         let tera_engine = if std::path::Path::new(I18N_DIR).exists() {
-            let arc = std::sync::Arc::new(
-                ArcLoader::builder(&I18N_DIR, unic_langid::langid!("en-US"))
-                    .shared_resources(Some(&[I18N_SHARED.into()]))
-                    .customize(|bundle| bundle.set_use_isolating(false))
-                    .build()
-                    .map_err(|e| Error::string(&e.to_string()))?,
-            );
+            let arc = i18n::loader()?;
             info!("locales loaded");
 
+            let shared_store = ctx.shared_store.clone();
             engines::TeraView::build()?.post_process(move |tera| {
                 tera.register_function("t", FluentLoader::new(arc.clone()));
+                tera.register_function("request_lang", request_lang);
+                tera.register_function("languages", languages);
+                tera.register_function("request_timezone", request_timezone);
+                tera.register_function("request_tenant", request_tenant);
+                tera.register_filter("local_datetime", local_datetime);
+                tera.register_filter("local_date", local_date);
+                tera.register_function(
+                    "code_label",
+                    CodeLabel {
+                        shared_store: shared_store.clone(),
+                    },
+                );
                 Ok(())
             })?
         } else {
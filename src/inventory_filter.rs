@@ -0,0 +1,508 @@
+//! Filtering, sorting and pagination of the inventory list, evaluated by the
+//! database so only the shown page is loaded.
+//!
+//! The whole state is kept in the query string of `/inventory/list`, see
+//! [`InventoryListQuery`], so filtered views can be bookmarked and shared.
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use chrono_tz::Tz;
+use loco_rs::prelude::*;
+use sea_orm::{
+    sea_query::{Alias, Expr, Func, LikeExpr, Query, SimpleExpr},
+    Condition, JoinType, Order, QueryOrder, QuerySelect, RelationTrait, Select,
+};
+use serde::Deserialize;
+
+use crate::{
+    controllers::{home::UrgencyLevel, reports::LOCATION_FIELD},
+    exts::OptionStringExt,
+    models::{
+        _entities::{
+            inventory_item_checks::Column as ChecksColumn,
+            inventory_item_kind_metadata_fields::Column as FieldsColumn,
+            inventory_item_metadata_values::Column as ValuesColumn,
+            inventory_items::{Column, Relation},
+        },
+        inventory_item_checks, inventory_item_kind_metadata_fields, inventory_item_kinds,
+        inventory_item_metadata_values, inventory_items,
+    },
+    reports::compliance::ComplianceStatus,
+};
+
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+/// Page sizes offered by the list, larger requests are clamped to the last.
+pub const PAGE_SIZES: [u64; 3] = [25, 50, 100];
+
+/// Query string of the inventory list. Everything is optional and arrives as
+/// text, because forms submit unselected fields as empty strings.
+#[derive(Debug, Default, Deserialize)]
+pub struct InventoryListQuery {
+    /// Matches name, serial number or kind name.
+    pub q: Option<String>,
+    pub kind_id: Option<String>,
+    /// A [`ComplianceStatus`], e.g. `overdue`.
+    pub status: Option<String>,
+    /// Dashboard urgency: `normal`, `warning` or `critical`.
+    pub urgency: Option<String>,
+    /// Matches the `location` metadata field.
+    pub location: Option<String>,
+    /// Metadata column key, filtered by `value`.
+    pub field: Option<String>,
+    pub value: Option<String>,
+    /// An [`ItemSort`] column, `id` by default.
+    pub sort: Option<String>,
+    /// `asc` or `desc`.
+    pub dir: Option<String>,
+    pub page: Option<String>,
+    pub page_size: Option<String>,
+}
+
+/// Sortable columns of the inventory list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ItemSort {
+    #[default]
+    Id,
+    Name,
+    Kind,
+    Serial,
+    LastChecked,
+    Expiry,
+}
+
+impl ItemSort {
+    pub const ALL: [Self; 6] = [
+        Self::Id,
+        Self::Name,
+        Self::Kind,
+        Self::Serial,
+        Self::LastChecked,
+        Self::Expiry,
+    ];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Name => "name",
+            Self::Kind => "kind",
+            Self::Serial => "serial",
+            Self::LastChecked => "last_checked",
+            Self::Expiry => "expiry",
+        }
+    }
+}
+
+fn urgency_str(urgency: UrgencyLevel) -> &'static str {
+    match urgency {
+        UrgencyLevel::Normal => "normal",
+        UrgencyLevel::Warning => "warning",
+        UrgencyLevel::Critical => "critical",
+    }
+}
+
+/// Validated [`InventoryListQuery`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InventoryFilter {
+    pub search: Option<String>,
+    pub kind_id: Option<i32>,
+    pub status: Option<ComplianceStatus>,
+    pub urgency: Option<UrgencyLevel>,
+    pub location: Option<String>,
+    /// Metadata column key and the value it has to contain.
+    pub metadata: Option<(String, String)>,
+    pub sort: ItemSort,
+    pub descending: bool,
+    pub page: u64,
+    pub page_size: u64,
+}
+
+impl TryFrom<InventoryListQuery> for InventoryFilter {
+    type Error = Error;
+
+    fn try_from(query: InventoryListQuery) -> Result<Self> {
+        fn parse<T>(
+            value: Option<String>,
+            name: &str,
+            parse: impl Fn(&str) -> Option<T>,
+        ) -> Result<Option<T>> {
+            value
+                .clean()
+                .map(|value| {
+                    parse(&value)
+                        .ok_or_else(|| Error::BadRequest(format!("invalid {name}: {value}")))
+                })
+                .transpose()
+        }
+
+        let metadata = match (query.field.clean(), query.value.clean()) {
+            (Some(field), Some(value)) => Some((field.to_lowercase(), value)),
+            _ => None,
+        };
+        let page_size = parse(query.page_size, "page_size", |size| size.parse().ok())?
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, PAGE_SIZES[PAGE_SIZES.len() - 1]);
+        Ok(Self {
+            search: query.q.clean(),
+            kind_id: parse(query.kind_id, "kind_id", |id| id.parse().ok())?,
+            status: parse(query.status, "status", |status| {
+                ComplianceStatus::ALL
+                    .into_iter()
+                    .find(|candidate| candidate.as_str() == status)
+            })?,
+            urgency: parse(query.urgency, "urgency", |urgency| {
+                [
+                    UrgencyLevel::Normal,
+                    UrgencyLevel::Warning,
+                    UrgencyLevel::Critical,
+                ]
+                .into_iter()
+                .find(|candidate| urgency_str(*candidate) == urgency)
+            })?,
+            location: query.location.clean(),
+            metadata,
+            sort: parse(query.sort, "sort", |sort| {
+                ItemSort::ALL
+                    .into_iter()
+                    .find(|candidate| candidate.as_str() == sort)
+            })?
+            .unwrap_or_default(),
+            descending: parse(query.dir, "dir", |dir| match dir {
+                "asc" => Some(false),
+                "desc" => Some(true),
+                _ => None,
+            })?
+            .unwrap_or_default(),
+            page: parse(query.page, "page", |page| page.parse().ok())?
+                .unwrap_or(1)
+                .max(1),
+            page_size,
+        })
+    }
+}
+
+/// Case-insensitive `LIKE` pattern matching `needle` anywhere.
+fn contains_pattern(needle: &str) -> LikeExpr {
+    let escaped = needle
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    LikeExpr::new(format!("%{escaped}%")).escape('\\')
+}
+
+fn lower(expr: impl Into<SimpleExpr>) -> Expr {
+    Expr::expr(Func::lower(expr.into()))
+}
+
+/// Items with a metadata value containing `value` for a field whose trimmed,
+/// lowercase name is `key`.
+fn metadata_contains(key: &str, value: &str) -> SimpleExpr {
+    let values = Query::select()
+        .column((
+            inventory_item_metadata_values::Entity,
+            ValuesColumn::InventoryItemId,
+        ))
+        .from(inventory_item_metadata_values::Entity)
+        .inner_join(
+            inventory_item_kind_metadata_fields::Entity,
+            Expr::col((
+                inventory_item_kind_metadata_fields::Entity,
+                FieldsColumn::Id,
+            ))
+            .equals((
+                inventory_item_metadata_values::Entity,
+                ValuesColumn::InventoryItemKindMetadataFieldId,
+            )),
+        )
+        .and_where(
+            lower(Func::cust(Alias::new("TRIM")).arg(Expr::col((
+                inventory_item_kind_metadata_fields::Entity,
+                FieldsColumn::Name,
+            ))))
+            .eq(key),
+        )
+        .and_where(
+            lower(Expr::col((
+                inventory_item_metadata_values::Entity,
+                ValuesColumn::Value,
+            )))
+            .like(contains_pattern(value)),
+        )
+        .to_owned();
+    Column::Id.in_subquery(values)
+}
+
+/// Items whose latest check did not end with one of `ok_result_ids`.
+fn last_check_failed(ok_result_ids: &[i32]) -> SimpleExpr {
+    let latest = Alias::new("latest");
+    let latest_checked_at = Query::select()
+        .expr(Expr::col((latest.clone(), ChecksColumn::CheckedAt)).max())
+        .from_as(inventory_item_checks::Entity, latest.clone())
+        .and_where(
+            Expr::col((latest, ChecksColumn::InventoryItemId))
+                .equals((inventory_item_checks::Entity, ChecksColumn::InventoryItemId)),
+        )
+        .to_owned();
+    let failed = Query::select()
+        .column((inventory_item_checks::Entity, ChecksColumn::InventoryItemId))
+        .from(inventory_item_checks::Entity)
+        .and_where(
+            Expr::col((inventory_item_checks::Entity, ChecksColumn::ResultId))
+                .is_not_in(ok_result_ids.iter().copied()),
+        )
+        .and_where(
+            Expr::col((inventory_item_checks::Entity, ChecksColumn::CheckedAt))
+                .in_subquery(latest_checked_at),
+        )
+        .to_owned();
+    Column::Id.in_subquery(failed)
+}
+
+/// Items due, by check or expiry, before `due` according to their stored
+/// `next_due_at`.
+fn due_before(due: DateTime<Utc>) -> Condition {
+    Condition::all()
+        .add(Column::NextDueAt.is_not_null())
+        .add(Column::NextDueAt.lt(due.fixed_offset()))
+}
+
+/// What the evaluation of a filter depends on besides the database.
+pub struct FilterContext<'a> {
+    /// Results counting as passed, see `results::Model::is_ok`.
+    pub ok_result_ids: &'a [i32],
+    pub now: DateTime<Utc>,
+    /// Today in `timezone`, urgency goes by calendar day like the dashboard.
+    pub today: NaiveDate,
+    pub timezone: Tz,
+}
+
+impl InventoryFilter {
+    /// Condition selecting the matching items. Statuses follow the
+    /// precedence of the compliance report, urgencies the dashboard.
+    #[must_use]
+    pub fn condition(&self, context: &FilterContext<'_>) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(search) = &self.search {
+            let pattern = contains_pattern(search);
+            let kinds = Query::select()
+                .column(inventory_item_kinds::Column::Id)
+                .from(inventory_item_kinds::Entity)
+                .and_where(
+                    lower(Expr::col((
+                        inventory_item_kinds::Entity,
+                        inventory_item_kinds::Column::Name,
+                    )))
+                    .like(pattern.clone()),
+                )
+                .to_owned();
+            condition = condition.add(
+                Condition::any()
+                    .add(
+                        lower(Expr::col((inventory_items::Entity, Column::Name)))
+                            .like(pattern.clone()),
+                    )
+                    .add(
+                        lower(Expr::col((inventory_items::Entity, Column::SerialNumber)))
+                            .like(pattern),
+                    )
+                    .add(Column::InventoryItemKindId.in_subquery(kinds)),
+            );
+        }
+        if let Some(kind_id) = self.kind_id {
+            condition = condition.add(Column::InventoryItemKindId.eq(kind_id));
+        }
+        if let Some(location) = &self.location {
+            condition = condition.add(metadata_contains(LOCATION_FIELD, location));
+        }
+        if let Some((key, value)) = &self.metadata {
+            condition = condition.add(metadata_contains(key, value));
+        }
+        if let Some(status) = self.status {
+            let expired = Condition::all()
+                .add(Column::Expiry.is_not_null())
+                .add(Column::Expiry.lte(context.now.fixed_offset()));
+            let failed = Condition::all().add(last_check_failed(context.ok_result_ids));
+            // not expired, so an item due by now has its check due
+            let overdue = due_before(context.now);
+            condition = condition.add(match status {
+                ComplianceStatus::Expired => expired,
+                ComplianceStatus::Failed => Condition::all().add(expired.not()).add(failed),
+                ComplianceStatus::Overdue => Condition::all()
+                    .add(expired.not())
+                    .add(failed.not())
+                    .add(overdue),
+                ComplianceStatus::Compliant => Condition::all()
+                    .add(expired.not())
+                    .add(failed.not())
+                    .add(overdue.not()),
+            });
+        }
+        if let Some(urgency) = self.urgency {
+            let today = crate::timezone::start_of_day(context.today, context.timezone);
+            let next_week =
+                crate::timezone::start_of_day(context.today + Days::new(8), context.timezone);
+            let critical = due_before(today);
+            let soon = due_before(next_week);
+            condition = condition.add(match urgency {
+                UrgencyLevel::Critical => critical,
+                UrgencyLevel::Warning => Condition::all().add(critical.not()).add(soon),
+                UrgencyLevel::Normal => soon.not(),
+            });
+        }
+        condition
+    }
+
+    /// Orders `select` by the chosen column, then by id so pages are stable.
+    #[must_use]
+    pub fn sorted(
+        &self,
+        select: Select<inventory_items::Entity>,
+    ) -> Select<inventory_items::Entity> {
+        let order = if self.descending {
+            Order::Desc
+        } else {
+            Order::Asc
+        };
+        let select = match self.sort {
+            ItemSort::Id => select,
+            ItemSort::Name => select.order_by(Column::Name, order.clone()),
+            ItemSort::Kind => select
+                .join(JoinType::LeftJoin, Relation::InventoryItemKinds.def())
+                .order_by(inventory_item_kinds::Column::Name, order.clone()),
+            ItemSort::Serial => select.order_by(Column::SerialNumber, order.clone()),
+            ItemSort::LastChecked => select.order_by(Column::LastCheckedAt, order.clone()),
+            ItemSort::Expiry => select.order_by(Column::Expiry, order.clone()),
+        };
+        select.order_by(Column::Id, order)
+    }
+
+    /// Query string reproducing this filter with another sort or page,
+    /// without the parameters left at their defaults.
+    #[must_use]
+    pub fn query_string(&self, sort: ItemSort, descending: bool, page: u64) -> String {
+        let mut pairs = Vec::<(&str, String)>::new();
+        if let Some(search) = &self.search {
+            pairs.push(("q", search.clone()));
+        }
+        if let Some(kind_id) = self.kind_id {
+            pairs.push(("kind_id", kind_id.to_string()));
+        }
+        if let Some(status) = self.status {
+            pairs.push(("status", status.as_str().to_string()));
+        }
+        if let Some(urgency) = self.urgency {
+            pairs.push(("urgency", urgency_str(urgency).to_string()));
+        }
+        if let Some(location) = &self.location {
+            pairs.push(("location", location.clone()));
+        }
+        if let Some((field, value)) = &self.metadata {
+            pairs.push(("field", field.clone()));
+            pairs.push(("value", value.clone()));
+        }
+        if sort != ItemSort::default() {
+            pairs.push(("sort", sort.as_str().to_string()));
+        }
+        if descending {
+            pairs.push(("dir", "desc".to_string()));
+        }
+        if page > 1 {
+            pairs.push(("page", page.to_string()));
+        }
+        if self.page_size != DEFAULT_PAGE_SIZE {
+            pairs.push(("page_size", self.page_size.to_string()));
+        }
+        serde_urlencoded::to_string(pairs).unwrap_or_default()
+    }
+
+    /// Current values for the filter form.
+    #[must_use]
+    pub fn form_values(&self) -> serde_json::Value {
+        serde_json::json!({
+            "q": self.search,
+            "kind_id": self.kind_id,
+            "status": self.status.map(ComplianceStatus::as_str),
+            "urgency": self.urgency.map(urgency_str),
+            "location": self.location,
+            "field": self.metadata.as_ref().map(|(field, _)| field),
+            "value": self.metadata.as_ref().map(|(_, value)| value),
+            "sort": self.sort.as_str(),
+            "dir": if self.descending { "desc" } else { "asc" },
+            "page_size": self.page_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, QueryFilter, QueryTrait};
+
+    use super::*;
+
+    fn sql(filter: &InventoryFilter) -> String {
+        let context = FilterContext {
+            ok_result_ids: &[1],
+            now: Utc::now(),
+            today: Utc::now().date_naive(),
+            timezone: Tz::UTC,
+        };
+        filter
+            .sorted(inventory_items::Entity::find().filter(filter.condition(&context)))
+            .build(DbBackend::Sqlite)
+            .to_string()
+    }
+
+    #[test]
+    fn parses_the_query_string() {
+        let query = serde_urlencoded::from_str::<InventoryListQuery>(
+            "q=+rope+&kind_id=&status=overdue&urgency=critical&field=Color&value=red&sort=kind&dir=desc&page=2&page_size=1000",
+        )
+        .unwrap();
+        let filter = InventoryFilter::try_from(query).unwrap();
+        assert_eq!(filter.search.as_deref(), Some("rope"));
+        assert_eq!(filter.kind_id, None);
+        assert_eq!(filter.status, Some(ComplianceStatus::Overdue));
+        assert_eq!(filter.urgency, Some(UrgencyLevel::Critical));
+        assert_eq!(
+            filter.metadata,
+            Some(("color".to_string(), "red".to_string()))
+        );
+        assert_eq!(filter.sort, ItemSort::Kind);
+        assert!(filter.descending);
+        assert_eq!((filter.page, filter.page_size), (2, 100));
+        assert_eq!(
+            filter.query_string(ItemSort::Name, false, 1),
+            "q=rope&status=overdue&urgency=critical&field=color&value=red&sort=name&page_size=100"
+        );
+
+        for invalid in [
+            "kind_id=x",
+            "status=late",
+            "sort=color",
+            "dir=up",
+            "page=-1",
+        ] {
+            let query = serde_urlencoded::from_str::<InventoryListQuery>(invalid).unwrap();
+            assert!(InventoryFilter::try_from(query).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn escapes_search_patterns() {
+        let filter = InventoryFilter {
+            search: Some("50%_A".to_string()),
+            ..Default::default()
+        };
+        assert!(sql(&filter).contains(r"LIKE '%50\%\_a%' ESCAPE '\'"));
+    }
+
+    #[test]
+    fn overdue_goes_by_stored_due_dates() {
+        let filter = InventoryFilter {
+            status: Some(ComplianceStatus::Overdue),
+            ..Default::default()
+        };
+        let sql = sql(&filter);
+        assert!(sql.contains(r#""next_due_at" <"#));
+        assert!(!sql.contains("COALESCE"));
+    }
+}
@@ -1,11 +1,20 @@
 pub mod app;
+pub mod calendar;
 pub mod controllers;
 pub mod data;
 pub mod exts;
+pub mod i18n;
 pub mod initializers;
+pub mod inventory_filter;
 pub mod mailers;
 pub mod models;
+pub mod notifications;
 pub mod reports;
+pub mod search;
+pub mod session;
 pub mod tasks;
+pub mod tenant;
+pub mod timezone;
 pub mod views;
+pub mod webhooks;
 pub mod workers;
@@ -0,0 +1,39 @@
+// notification mailer
+#![allow(non_upper_case_globals)]
+
+use loco_rs::prelude::*;
+use serde_json::Value;
+
+use crate::models::users;
+
+static due_items: Dir<'_> = include_dir!("src/mailers/notification/due_items");
+
+#[allow(clippy::module_name_repetitions)]
+pub struct NotificationMailer {}
+impl Mailer for NotificationMailer {}
+impl NotificationMailer {
+    /// Sending the items that became due to a subscribed user. `locals` are
+    /// already translated to the user's language.
+    ///
+    /// # Errors
+    ///
+    /// When email sending is failed
+    pub async fn send_due_items(
+        ctx: &AppContext,
+        user: &users::Model,
+        locals: Value,
+    ) -> Result<()> {
+        Self::mail_template(
+            ctx,
+            &due_items,
+            mailer::Args {
+                to: user.email.clone(),
+                locals,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+}
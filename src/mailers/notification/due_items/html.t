@@ -0,0 +1,18 @@
+<html>
+
+<body>
+  <p>{{greeting}}</p>
+  <p>{{intro}}</p>
+  <ul>
+    {% for item in items %}
+    <li>
+      <strong>{{item.urgency}}</strong>:
+      <a href="{{item.url}}">{{item.name}}</a>{% if item.serial_number %} ({{item.serial_number}}){% endif %},
+      {{item.due_label}} {{item.due}}
+    </li>
+    {% endfor %}
+  </ul>
+  <p><a href="{{manage_url}}">{{manage}}</a></p>
+</body>
+
+</html>
@@ -0,0 +1 @@
+{{subject}}
\ No newline at end of file
@@ -0,0 +1,9 @@
+{{greeting}}
+
+{{intro}}
+{% for item in items %}
+- {{item.urgency}}: {{item.name}}{% if item.serial_number %} ({{item.serial_number}}){% endif %}, {{item.due_label}} {{item.due}}
+  {{item.url}}
+{% endfor %}
+{{manage}}
+{{manage_url}}
@@ -18,6 +18,8 @@ pub struct Model {
     pub executed_checklist_id: i32,
     pub checked_by: i32,
     pub result_id: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub signature: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -16,6 +16,7 @@ pub struct Model {
     pub default_checklist_id: i32,
     pub default_interval_id: i32,
     pub default_expiry_id: i32,
+    pub tenant_id: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -46,6 +47,10 @@ pub enum Relation {
     Intervals,
     #[sea_orm(has_many = "super::inventory_items::Entity")]
     InventoryItems,
+    #[sea_orm(has_many = "super::notification_subscriptions::Entity")]
+    NotificationSubscriptions,
+    #[sea_orm(has_many = "super::responsibilities::Entity")]
+    Responsibilities,
 }
 
 impl Related<super::checklists::Entity> for Entity {
@@ -71,3 +76,15 @@ impl Related<super::inventory_items::Entity> for Entity {
         Relation::InventoryItems.def()
     }
 }
+
+impl Related<super::notification_subscriptions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NotificationSubscriptions.def()
+    }
+}
+
+impl Related<super::responsibilities::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Responsibilities.def()
+    }
+}
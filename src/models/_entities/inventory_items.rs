@@ -17,6 +17,8 @@ pub struct Model {
     pub inventory_item_kind_id: i32,
     pub checklist_id: i32,
     pub interval_id: i32,
+    pub tenant_id: i32,
+    pub next_due_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -47,6 +49,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     InventoryItemKinds,
+    #[sea_orm(has_many = "super::notification_subscriptions::Entity")]
+    NotificationSubscriptions,
+    #[sea_orm(has_many = "super::responsibilities::Entity")]
+    Responsibilities,
+    #[sea_orm(has_many = "super::sent_notifications::Entity")]
+    SentNotifications,
+    #[sea_orm(has_many = "super::webhook_item_states::Entity")]
+    WebhookItemStates,
 }
 
 impl Related<super::checklists::Entity> for Entity {
@@ -72,3 +82,27 @@ impl Related<super::inventory_item_kinds::Entity> for Entity {
         Relation::InventoryItemKinds.def()
     }
 }
+
+impl Related<super::notification_subscriptions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NotificationSubscriptions.def()
+    }
+}
+
+impl Related<super::responsibilities::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Responsibilities.def()
+    }
+}
+
+impl Related<super::sent_notifications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SentNotifications.def()
+    }
+}
+
+impl Related<super::webhook_item_states::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WebhookItemStates.def()
+    }
+}
@@ -2,11 +2,13 @@
 
 pub mod prelude;
 
+pub mod api_keys;
 pub mod checklist_steps;
 pub mod checklists;
 pub mod executed_checklist_steps;
 pub mod executed_checklists;
 pub mod expiries;
+pub mod generated_reports;
 pub mod intervals;
 pub mod inventory_item_check_steps;
 pub mod inventory_item_checks;
@@ -14,6 +16,18 @@ pub mod inventory_item_kind_metadata_fields;
 pub mod inventory_item_kinds;
 pub mod inventory_item_metadata_values;
 pub mod inventory_items;
+pub mod notification_subscriptions;
+pub mod report_jobs;
+pub mod report_templates;
+pub mod responsibilities;
 pub mod results;
+pub mod sent_notifications;
+pub mod team_memberships;
+pub mod teams;
+pub mod tenant_memberships;
+pub mod tenants;
 pub mod translations;
 pub mod users;
+pub mod webhook_deliveries;
+pub mod webhook_endpoints;
+pub mod webhook_item_states;
@@ -1,10 +1,12 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.17
 
+pub use super::api_keys::Entity as ApiKeys;
 pub use super::checklist_steps::Entity as ChecklistSteps;
 pub use super::checklists::Entity as Checklists;
 pub use super::executed_checklist_steps::Entity as ExecutedChecklistSteps;
 pub use super::executed_checklists::Entity as ExecutedChecklists;
 pub use super::expiries::Entity as Expiries;
+pub use super::generated_reports::Entity as GeneratedReports;
 pub use super::intervals::Entity as Intervals;
 pub use super::inventory_item_check_steps::Entity as InventoryItemCheckSteps;
 pub use super::inventory_item_checks::Entity as InventoryItemChecks;
@@ -12,6 +14,18 @@ pub use super::inventory_item_kind_metadata_fields::Entity as InventoryItemKindM
 pub use super::inventory_item_kinds::Entity as InventoryItemKinds;
 pub use super::inventory_item_metadata_values::Entity as InventoryItemMetadataValues;
 pub use super::inventory_items::Entity as InventoryItems;
+pub use super::notification_subscriptions::Entity as NotificationSubscriptions;
+pub use super::report_jobs::Entity as ReportJobs;
+pub use super::report_templates::Entity as ReportTemplates;
+pub use super::responsibilities::Entity as Responsibilities;
 pub use super::results::Entity as Results;
+pub use super::sent_notifications::Entity as SentNotifications;
+pub use super::team_memberships::Entity as TeamMemberships;
+pub use super::teams::Entity as Teams;
+pub use super::tenant_memberships::Entity as TenantMemberships;
+pub use super::tenants::Entity as Tenants;
 pub use super::translations::Entity as Translations;
 pub use super::users::Entity as Users;
+pub use super::webhook_deliveries::Entity as WebhookDeliveries;
+pub use super::webhook_endpoints::Entity as WebhookEndpoints;
+pub use super::webhook_item_states::Entity as WebhookItemStates;
@@ -24,12 +24,44 @@ pub struct Model {
     pub email_verified_at: Option<DateTimeWithTimeZone>,
     pub magic_link_token: Option<String>,
     pub magic_link_expiration: Option<DateTimeWithTimeZone>,
+    pub locale: Option<String>,
+    pub timezone: Option<String>,
+    #[sea_orm(unique)]
+    pub calendar_token_hash: Option<String>,
+    pub absent_until: Option<Date>,
+    pub current_tenant_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::api_keys::Entity")]
+    ApiKeys,
     #[sea_orm(has_many = "super::inventory_item_checks::Entity")]
     InventoryItemChecks,
+    #[sea_orm(has_many = "super::notification_subscriptions::Entity")]
+    NotificationSubscriptions,
+    #[sea_orm(has_many = "super::report_jobs::Entity")]
+    ReportJobs,
+    #[sea_orm(has_many = "super::sent_notifications::Entity")]
+    SentNotifications,
+    #[sea_orm(has_many = "super::team_memberships::Entity")]
+    TeamMemberships,
+    #[sea_orm(has_many = "super::tenant_memberships::Entity")]
+    TenantMemberships,
+    #[sea_orm(
+        belongs_to = "super::tenants::Entity",
+        from = "Column::CurrentTenantId",
+        to = "super::tenants::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Tenants,
+}
+
+impl Related<super::api_keys::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApiKeys.def()
+    }
 }
 
 impl Related<super::inventory_item_checks::Entity> for Entity {
@@ -37,3 +69,39 @@ impl Related<super::inventory_item_checks::Entity> for Entity {
         Relation::InventoryItemChecks.def()
     }
 }
+
+impl Related<super::notification_subscriptions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NotificationSubscriptions.def()
+    }
+}
+
+impl Related<super::report_jobs::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReportJobs.def()
+    }
+}
+
+impl Related<super::sent_notifications::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SentNotifications.def()
+    }
+}
+
+impl Related<super::team_memberships::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TeamMemberships.def()
+    }
+}
+
+impl Related<super::tenant_memberships::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TenantMemberships.def()
+    }
+}
+
+impl Related<super::tenants::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenants.def()
+    }
+}
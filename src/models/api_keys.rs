@@ -0,0 +1,163 @@
+use chrono::Utc;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub use super::_entities::api_keys::{self, ActiveModel, Column, Entity, Model};
+
+/// Prefix of every generated API key, used to tell them apart from JWTs.
+pub const API_TOKEN_PREFIX: &str = "bbk_";
+/// Number of leading characters kept in plain text so users can recognise a key.
+pub const KEY_PREFIX_LENGTH: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// List and fetch every resource.
+    Read,
+    /// Record checks for inventory items.
+    SubmitChecks,
+    /// Full access, implies every other scope.
+    Write,
+}
+
+impl ApiScope {
+    pub const ALL: [Self; 3] = [Self::Read, Self::SubmitChecks, Self::Write];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::SubmitChecks => "submit_checks",
+            Self::Write => "write",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == value.trim())
+    }
+}
+
+/// Hashes a plain text API key, or another secret token, for storage and
+/// lookup.
+#[must_use]
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    #[must_use]
+    pub fn scopes(&self) -> Vec<ApiScope> {
+        self.scopes.split(',').filter_map(ApiScope::parse).collect()
+    }
+
+    #[must_use]
+    pub fn has_scope(&self, scope: ApiScope) -> bool {
+        let scopes = self.scopes();
+        scopes.contains(&ApiScope::Write) || scopes.contains(&scope)
+    }
+
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+            && self
+                .expires_at
+                .is_none_or(|expires_at| expires_at > Utc::now())
+    }
+
+    /// finds an active (not revoked, not expired) key by its plain text token
+    ///
+    /// # Errors
+    ///
+    /// When no active key matches the token or DB query error
+    pub async fn find_active_by_token(db: &DatabaseConnection, token: &str) -> ModelResult<Self> {
+        let key = Entity::find()
+            .filter(Column::KeyHash.eq(hash_token(token)))
+            .one(db)
+            .await?
+            .ok_or(ModelError::EntityNotFound)?;
+
+        if key.is_active() {
+            Ok(key)
+        } else {
+            Err(ModelError::EntityNotFound)
+        }
+    }
+
+    /// records that the key was just used to authenticate a request
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn touch_last_used(&self, db: &DatabaseConnection) -> ModelResult<()> {
+        Entity::update_many()
+            .col_expr(
+                Column::LastUsedAt,
+                sea_orm::sea_query::Expr::value(Some(DateTimeWithTimeZone::from(Utc::now()))),
+            )
+            .filter(Column::Id.eq(self.id))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Builds a new key for `user_id` and returns it together with the plain
+    /// text token, which is only available at this point.
+    #[must_use]
+    pub fn generate(
+        user_id: i32,
+        name: String,
+        scopes: &[ApiScope],
+        expires_at: Option<DateTimeWithTimeZone>,
+    ) -> (Self, String) {
+        let token = format!(
+            "{API_TOKEN_PREFIX}{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+        let scopes = scopes
+            .iter()
+            .map(|scope| scope.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let key = Self {
+            name: ActiveValue::set(name),
+            key_prefix: ActiveValue::set(token[..KEY_PREFIX_LENGTH].to_string()),
+            key_hash: ActiveValue::set(hash_token(&token)),
+            scopes: ActiveValue::set(scopes),
+            expires_at: ActiveValue::set(expires_at),
+            user_id: ActiveValue::set(user_id),
+            ..Default::default()
+        };
+        (key, token)
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -1,4 +1,4 @@
-pub use super::_entities::checklists::{ActiveModel, Entity, Model};
+pub use super::_entities::checklists::{ActiveModel, Column, Entity, Model};
 use sea_orm::entity::prelude::*;
 pub type Checklists = Entity;
 
@@ -25,4 +25,16 @@ impl Model {}
 impl ActiveModel {}
 
 // implement your custom finders, selectors oriented logic here
-impl Entity {}
+impl Entity {
+    /// Checklists of `tenant_id`.
+    #[must_use]
+    pub fn find_in_tenant(tenant_id: i32) -> Select<Self> {
+        Self::find().filter(Column::TenantId.eq(tenant_id))
+    }
+
+    /// The checklist with `id`, if it belongs to `tenant_id`.
+    #[must_use]
+    pub fn find_by_id_in_tenant(tenant_id: i32, id: i32) -> Select<Self> {
+        Self::find_by_id(id).filter(Column::TenantId.eq(tenant_id))
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+pub use super::_entities::generated_reports::{self, ActiveModel, Column, Entity, Model};
+
+/// Prefix of every stored report ID.
+pub const REPORT_ID_PREFIX: &str = "RPT-";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    ItemHistory,
+    Compliance,
+    CheckCertificate,
+}
+
+impl ReportKind {
+    pub const ALL: [Self; 3] = [Self::ItemHistory, Self::Compliance, Self::CheckCertificate];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ItemHistory => "item_history",
+            Self::Compliance => "compliance",
+            Self::CheckCertificate => "check_certificate",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+/// Generates a new, random report ID such as `RPT-3F9A-01BC-77D2-90E4`.
+#[must_use]
+pub fn generate_report_id() -> String {
+    let random = Uuid::new_v4().simple().to_string().to_uppercase();
+    let groups = random.as_bytes()[..16]
+        .chunks(4)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect::<Vec<_>>();
+    format!("{REPORT_ID_PREFIX}{}", groups.join("-"))
+}
+
+/// Normalises a report ID as typed by a user for lookup.
+#[must_use]
+pub fn normalize_report_id(report_id: &str) -> String {
+    report_id.trim().to_uppercase()
+}
+
+/// ID, verification URL and generation time of a report, known before the
+/// report is rendered so they can be printed on it.
+#[derive(Clone, Debug)]
+pub struct ReportIdentity {
+    pub report_id: String,
+    pub verify_url: String,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl ReportIdentity {
+    #[must_use]
+    pub fn new(ctx: &AppContext) -> Self {
+        let report_id = generate_report_id();
+        Self {
+            verify_url: format!("{}/verify/{report_id}", ctx.config.server.full_url()),
+            report_id,
+            generated_at: Utc::now(),
+        }
+    }
+
+    /// Stores the ID and content hash of the report rendered from the data
+    /// of `tenant_id` so it can be verified later.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn store(
+        &self,
+        db: &DatabaseConnection,
+        tenant_id: i32,
+        kind: ReportKind,
+        title: &str,
+        subject: Option<String>,
+        content: &[u8],
+    ) -> ModelResult<Model> {
+        Ok(ActiveModel::record(
+            tenant_id,
+            self.report_id.clone(),
+            kind,
+            title.to_string(),
+            subject,
+            self.generated_at,
+            content,
+        )
+        .insert(db)
+        .await?)
+    }
+}
+
+/// Hashes the content of a generated report.
+#[must_use]
+pub fn content_hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    #[must_use]
+    pub fn kind(&self) -> Option<ReportKind> {
+        ReportKind::parse(&self.kind)
+    }
+
+    /// Whether `content` is byte for byte the report that was generated.
+    #[must_use]
+    pub fn matches(&self, content: &[u8]) -> bool {
+        content_hash(content) == self.content_hash
+    }
+
+    /// finds a stored report by its report ID
+    ///
+    /// # Errors
+    ///
+    /// When no report has the ID or DB query error
+    pub async fn find_by_report_id(db: &DatabaseConnection, report_id: &str) -> ModelResult<Self> {
+        Entity::find()
+            .filter(Column::ReportId.eq(normalize_report_id(report_id)))
+            .one(db)
+            .await?
+            .ok_or(ModelError::EntityNotFound)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Builds the record of the report `report_id` generated for
+    /// `tenant_id` at `generated_at` with the given `content`.
+    #[must_use]
+    pub fn record(
+        tenant_id: i32,
+        report_id: String,
+        kind: ReportKind,
+        title: String,
+        subject: Option<String>,
+        generated_at: DateTime<Utc>,
+        content: &[u8],
+    ) -> Self {
+        Self {
+            tenant_id: ActiveValue::set(tenant_id),
+            report_id: ActiveValue::set(report_id),
+            kind: ActiveValue::set(kind.as_str().to_string()),
+            title: ActiveValue::set(title),
+            subject: ActiveValue::set(subject),
+            content_hash: ActiveValue::set(content_hash(content)),
+            byte_size: ActiveValue::set(i64::try_from(content.len()).unwrap_or(i64::MAX)),
+            created_at: ActiveValue::set(generated_at.into()),
+            updated_at: ActiveValue::set(generated_at.into()),
+            ..Default::default()
+        }
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
@@ -21,6 +21,13 @@ impl ActiveModelBehavior for ActiveModel {
 
 // implement your read-oriented logic here
 impl Model {
+    /// Whether the interval repeats after a number of days, months or
+    /// years, unlike `never`.
+    #[must_use]
+    pub fn has_period(&self) -> bool {
+        matches!(self.sqlite_modifier.as_str(), "days" | "months" | "years")
+    }
+
     #[must_use]
     pub fn next_interval_expiry(
         &self,
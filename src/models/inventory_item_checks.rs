@@ -1,5 +1,21 @@
-pub use super::_entities::inventory_item_checks::{ActiveModel, Entity, Model};
-use sea_orm::entity::prelude::*;
+use std::collections::HashMap;
+
+pub use super::_entities::inventory_item_checks::{ActiveModel, Column, Entity, Model};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::{Alias, Asterisk, Expr, Func, Order, Query, WindowStatement},
+    FromQueryResult, QuerySelect,
+};
+use sha2::{Digest, Sha256};
+
+use super::inventory_item_check_steps;
+
+/// Prefix of a captured signature, which is stored as a PNG data URL.
+pub const SIGNATURE_DATA_URL_PREFIX: &str = "data:image/png;base64,";
+/// Upper bound for the length of a stored signature data URL.
+pub const MAX_SIGNATURE_LENGTH: usize = 512 * 1024;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
 pub type InventoryItemChecks = Entity;
 
 #[async_trait::async_trait]
@@ -18,11 +34,161 @@ impl ActiveModelBehavior for ActiveModel {
     }
 }
 
+/// Decodes a captured signature data URL into the PNG image it holds.
+///
+/// Returns `None` when `data_url` is not a base64 encoded PNG image.
+#[must_use]
+pub fn decode_signature(data_url: &str) -> Option<Vec<u8>> {
+    if data_url.len() > MAX_SIGNATURE_LENGTH {
+        return None;
+    }
+    let png = STANDARD
+        .decode(data_url.strip_prefix(SIGNATURE_DATA_URL_PREFIX)?)
+        .ok()?;
+    png.starts_with(PNG_SIGNATURE).then_some(png)
+}
+
 // implement your read-oriented logic here
-impl Model {}
+impl Model {
+    /// Code printed on the check certificate. It is derived from the recorded
+    /// check, its step results and `secret`, so a certificate whose content
+    /// no longer matches the stored check can be told apart.
+    #[must_use]
+    pub fn verification_code(
+        &self,
+        secret: &str,
+        steps: &[inventory_item_check_steps::Model],
+    ) -> String {
+        let mut steps = steps.iter().collect::<Vec<_>>();
+        steps.sort_by_key(|step| step.executed_checklist_step_id);
+
+        let mut hasher = Sha256::new();
+        hasher.update(secret.as_bytes());
+        for part in [
+            self.id.to_string(),
+            self.inventory_item_id.to_string(),
+            self.executed_checklist_id.to_string(),
+            self.checked_at.to_rfc3339(),
+            self.checked_by.to_string(),
+            self.result_id.to_string(),
+            self.notes.clone().unwrap_or_default(),
+            self.signature.clone().unwrap_or_default(),
+        ] {
+            hasher.update([0]);
+            hasher.update(part.as_bytes());
+        }
+        for step in steps {
+            hasher.update([0]);
+            hasher.update(
+                format!(
+                    "{}:{}:{}",
+                    step.executed_checklist_step_id,
+                    step.result_id,
+                    step.notes.as_deref().unwrap_or_default()
+                )
+                .as_bytes(),
+            );
+        }
+
+        let digest = format!("{:X}", hasher.finalize());
+        digest.as_bytes()[..20]
+            .chunks(4)
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
 
 // implement your write-oriented logic here
 impl ActiveModel {}
 
 // implement your custom finders, selectors oriented logic here
-impl Entity {}
+impl Entity {
+    /// The latest `limit` checks of each of `item_ids` made before
+    /// `checked_before`, if given, newest first. The checks are numbered per
+    /// item with a window function, so one query serves any number of items.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn latest_by_item<C: ConnectionTrait>(
+        db: &C,
+        item_ids: &[i32],
+        limit: u64,
+        checked_before: Option<DateTimeWithTimeZone>,
+    ) -> Result<HashMap<i32, Vec<Model>>, DbErr> {
+        if item_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let position = Alias::new("position");
+        let mut ranked = Query::select()
+            .column(Asterisk)
+            .expr_window_as(
+                Func::cust(Alias::new("ROW_NUMBER")),
+                WindowStatement::partition_by(Column::InventoryItemId)
+                    .order_by(Column::CheckedAt, Order::Desc)
+                    .order_by(Column::Id, Order::Desc)
+                    .to_owned(),
+                position.clone(),
+            )
+            .from(Entity)
+            .and_where(Column::InventoryItemId.is_in(item_ids.iter().copied()))
+            .to_owned();
+        if let Some(checked_before) = checked_before {
+            ranked.and_where(Column::CheckedAt.lt(checked_before));
+        }
+        let select = Query::select()
+            .column(Asterisk)
+            .from_subquery(ranked, Alias::new("ranked"))
+            .and_where(Expr::col(position.clone()).lte(limit))
+            .order_by(position, Order::Asc)
+            .to_owned();
+
+        let mut by_item = HashMap::<i32, Vec<Model>>::new();
+        for check in Model::find_by_statement(db.get_database_backend().build(&select))
+            .all(db)
+            .await?
+        {
+            by_item
+                .entry(check.inventory_item_id)
+                .or_default()
+                .push(check);
+        }
+        Ok(by_item)
+    }
+
+    /// Number of checks of each of `item_ids` made from `checked_from`, if
+    /// given, until before `checked_before`. Items without checks are left
+    /// out.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn count_by_item<C: ConnectionTrait>(
+        db: &C,
+        item_ids: &[i32],
+        checked_from: Option<DateTimeWithTimeZone>,
+        checked_before: DateTimeWithTimeZone,
+    ) -> Result<HashMap<i32, u64>, DbErr> {
+        if item_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let mut select = Self::find()
+            .select_only()
+            .column(Column::InventoryItemId)
+            .column_as(Column::Id.count(), "count")
+            .filter(Column::InventoryItemId.is_in(item_ids.iter().copied()))
+            .filter(Column::CheckedAt.lt(checked_before))
+            .group_by(Column::InventoryItemId);
+        if let Some(checked_from) = checked_from {
+            select = select.filter(Column::CheckedAt.gte(checked_from));
+        }
+        Ok(select
+            .into_tuple::<(i32, i64)>()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|(item_id, count)| (item_id, count.unsigned_abs()))
+            .collect())
+    }
+}
@@ -25,6 +25,7 @@ impl ActiveModel {}
 
 impl Entity {}
 
+#[must_use]
 pub fn normalize_metadata_field_names(field_names: Vec<String>) -> Vec<String> {
     let mut seen = BTreeSet::new();
     let mut normalized = Vec::new();
@@ -49,6 +50,7 @@ mod tests {
     use super::normalize_metadata_field_names;
 
     #[test]
+    #[allow(clippy::manual_string_new)]
     fn trims_and_deduplicates_metadata_field_names() {
         assert_eq!(
             normalize_metadata_field_names(vec![
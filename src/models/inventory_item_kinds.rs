@@ -1,4 +1,4 @@
-pub use super::_entities::inventory_item_kinds::{ActiveModel, Entity, Model};
+pub use super::_entities::inventory_item_kinds::{ActiveModel, Column, Entity, Model};
 use sea_orm::entity::prelude::*;
 pub type InventoryItemKinds = Entity;
 
@@ -25,4 +25,16 @@ impl Model {}
 impl ActiveModel {}
 
 // implement your custom finders, selectors oriented logic here
-impl Entity {}
+impl Entity {
+    /// Item kinds of `tenant_id`.
+    #[must_use]
+    pub fn find_in_tenant(tenant_id: i32) -> Select<Self> {
+        Self::find().filter(Column::TenantId.eq(tenant_id))
+    }
+
+    /// The item kind with `id`, if it belongs to `tenant_id`.
+    #[must_use]
+    pub fn find_by_id_in_tenant(tenant_id: i32, id: i32) -> Select<Self> {
+        Self::find_by_id(id).filter(Column::TenantId.eq(tenant_id))
+    }
+}
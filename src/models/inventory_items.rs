@@ -1,28 +1,211 @@
-pub use super::_entities::inventory_items::{ActiveModel, Entity, Model};
-use sea_orm::entity::prelude::*;
+pub use super::_entities::inventory_items::{ActiveModel, Column, Entity, Model};
+use std::collections::HashMap;
+
+use loco_rs::model::{ModelError, ModelResult};
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::{Expr, Func, IntoCondition},
+};
+
+use super::{intervals, teams::ItemScope};
 pub type InventoryItems = Entity;
 
+/// Prefix of the scan codes printed on labels, followed by the item id.
+pub const SCAN_CODE_PREFIX: &str = "item-";
+
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
-    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    /// Also stores when the item is next due whenever its interval, last
+    /// check or expiry change, see [`Model::compute_next_due_at`].
+    async fn before_save<C>(self, db: &C, insert: bool) -> std::result::Result<Self, DbErr>
     where
         C: ConnectionTrait,
     {
-        if !insert && self.updated_at.is_unchanged() {
-            let mut this = self;
+        let mut this = self;
+        if !insert && this.updated_at.is_unchanged() {
             this.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
-            Ok(this)
-        } else {
-            Ok(self)
         }
+        if insert && this.created_at.is_not_set() {
+            this.created_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+        }
+        let due_changed = insert
+            || this.interval_id.is_set()
+            || this.last_checked_at.is_set()
+            || this.expiry.is_set()
+            || this.created_at.is_set();
+        if due_changed && !this.next_due_at.is_set() {
+            // a partial update, e.g. of the last check only, leaves the other
+            // fields the due date depends on to the stored item
+            let partial = this.interval_id.is_not_set()
+                || this.created_at.is_not_set()
+                || this.last_checked_at.is_not_set()
+                || this.expiry.is_not_set();
+            let stored = match this.id.try_as_ref() {
+                Some(&id) if !insert && partial => Entity::find_by_id(id).one(db).await?,
+                _ => None,
+            };
+            let stored = stored.as_ref();
+            let interval_id = or_stored(&this.interval_id, stored, |item| item.interval_id);
+            let created_at = or_stored(&this.created_at, stored, |item| item.created_at);
+            if let (Some(interval_id), Some(created_at)) = (interval_id, created_at) {
+                let interval = intervals::Entity::find_by_id(interval_id).one(db).await?;
+                this.next_due_at = sea_orm::ActiveValue::Set(next_due_at(
+                    created_at,
+                    or_stored(&this.last_checked_at, stored, |item| item.last_checked_at).flatten(),
+                    or_stored(&this.expiry, stored, |item| item.expiry).flatten(),
+                    interval.as_ref(),
+                ));
+            }
+        }
+        Ok(this)
+    }
+}
+
+/// The value of `field`, or the one of the `stored` item while the field is
+/// not set.
+fn or_stored<V>(
+    field: &sea_orm::ActiveValue<V>,
+    stored: Option<&Model>,
+    value: impl Fn(&Model) -> V,
+) -> Option<V>
+where
+    V: Clone + Into<sea_orm::Value>,
+{
+    field.try_as_ref().cloned().or_else(|| stored.map(value))
+}
+
+/// When an item created at `created_at` is next due: its next check after
+/// `last_checked_at` according to `interval`, or `expiry` if that comes
+/// first. `None` for intervals without a period and no expiry.
+fn next_due_at(
+    created_at: DateTimeWithTimeZone,
+    last_checked_at: Option<DateTimeWithTimeZone>,
+    expiry: Option<DateTimeWithTimeZone>,
+    interval: Option<&intervals::Model>,
+) -> Option<DateTimeWithTimeZone> {
+    let next_check = interval
+        .filter(|interval| interval.has_period())
+        .map(|interval| interval.next_interval_expiry(&created_at, &last_checked_at));
+    match (next_check, expiry) {
+        (Some(next_check), Some(expiry)) => Some(next_check.min(expiry)),
+        (next_check, expiry) => next_check.or(expiry),
     }
 }
 
 // implement your read-oriented logic here
-impl Model {}
+impl Model {
+    /// Returns when the next check is due according to the item's interval,
+    /// `None` for intervals without a period.
+    #[must_use]
+    pub fn next_check_at(
+        &self,
+        interval: Option<&intervals::Model>,
+    ) -> Option<DateTimeWithTimeZone> {
+        interval
+            .filter(|interval| interval.has_period())
+            .map(|interval| interval.next_interval_expiry(&self.created_at, &self.last_checked_at))
+    }
+
+    /// Returns when the item is next due: the next check according to its
+    /// interval, or its expiry if that comes first. Saving an item stores
+    /// this as `next_due_at`; computing it is only needed for items as they
+    /// were at another time.
+    #[must_use]
+    pub fn compute_next_due_at(
+        &self,
+        interval: Option<&intervals::Model>,
+    ) -> Option<DateTimeWithTimeZone> {
+        next_due_at(self.created_at, self.last_checked_at, self.expiry, interval)
+    }
+
+    /// Code identifying the item on printed labels, independent of its
+    /// serial number.
+    #[must_use]
+    pub fn scan_code(&self) -> String {
+        format!("{SCAN_CODE_PREFIX}{}", self.id)
+    }
+
+    /// finds an item of `tenant_id` within `scope` by its serial number
+    /// (case-insensitive) or by its [`Model::scan_code`]
+    ///
+    /// # Errors
+    ///
+    /// When no item matches the code or DB query error
+    pub async fn find_by_scan_code(
+        db: &DatabaseConnection,
+        tenant_id: i32,
+        scope: &ItemScope,
+        code: &str,
+    ) -> ModelResult<Self> {
+        let code = code.trim();
+        let by_serial = Entity::find_in_tenant(tenant_id)
+            .filter(scope.condition(Column::Id))
+            .filter(
+                Expr::expr(Func::lower(Expr::col(Column::SerialNumber))).eq(code.to_lowercase()),
+            )
+            .one(db)
+            .await?;
+        if let Some(item) = by_serial {
+            return Ok(item);
+        }
+
+        let id = code
+            .strip_prefix(SCAN_CODE_PREFIX)
+            .and_then(|id| id.parse::<i32>().ok())
+            .ok_or(ModelError::EntityNotFound)?;
+        Entity::find_by_id_in_tenant(tenant_id, id)
+            .filter(scope.condition(Column::Id))
+            .one(db)
+            .await?
+            .ok_or(ModelError::EntityNotFound)
+    }
+}
 
 // implement your write-oriented logic here
 impl ActiveModel {}
 
 // implement your custom finders, selectors oriented logic here
-impl Entity {}
+impl Entity {
+    /// Items of `tenant_id`.
+    #[must_use]
+    pub fn find_in_tenant(tenant_id: i32) -> Select<Self> {
+        Self::find().filter(Column::TenantId.eq(tenant_id))
+    }
+
+    /// The item with `id`, if it belongs to `tenant_id`.
+    #[must_use]
+    pub fn find_by_id_in_tenant(tenant_id: i32, id: i32) -> Select<Self> {
+        Self::find_by_id(id).filter(Column::TenantId.eq(tenant_id))
+    }
+
+    /// Recomputes the stored `next_due_at` of the items matching
+    /// `condition`, e.g. after their interval was changed. Returns the
+    /// number of items whose due date changed.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn refresh_next_due_at<C, F>(db: &C, condition: F) -> Result<usize, DbErr>
+    where
+        C: ConnectionTrait,
+        F: IntoCondition,
+    {
+        let intervals = intervals::Entity::find()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|interval| (interval.id, interval))
+            .collect::<HashMap<_, _>>();
+        let mut changed = 0;
+        for item in Self::find().filter(condition).all(db).await? {
+            let next_due_at = item.compute_next_due_at(intervals.get(&item.interval_id));
+            if next_due_at != item.next_due_at {
+                let mut item: ActiveModel = item.into();
+                item.next_due_at = sea_orm::ActiveValue::Set(next_due_at);
+                item.update(db).await?;
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+}
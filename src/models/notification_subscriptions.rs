@@ -0,0 +1,248 @@
+use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono_tz::Tz;
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub use super::_entities::notification_subscriptions::{self, ActiveModel, Column, Entity, Model};
+
+/// Where a subscription sends its notices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    /// Mailed to the subscribed user.
+    Email,
+    /// Posted to a webhook endpoint, signed like every other webhook.
+    Webhook,
+}
+
+impl NotificationChannel {
+    pub const ALL: [Self; 2] = [Self::Email, Self::Webhook];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Webhook => "webhook",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|channel| channel.as_str() == value.trim())
+    }
+}
+
+/// How often a subscription sends the items that became due.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationFrequency {
+    /// On the next run of the `notifications` task.
+    Immediate,
+    /// At most once per calendar day of the user.
+    Daily,
+    /// At most once every seven days.
+    Weekly,
+}
+
+impl NotificationFrequency {
+    pub const ALL: [Self; 3] = [Self::Immediate, Self::Daily, Self::Weekly];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Immediate => "immediate",
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|frequency| frequency.as_str() == value.trim())
+    }
+}
+
+/// The least urgent state an item must reach to be notified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationLevel {
+    /// Due within the next week, or overdue.
+    Warning,
+    /// Overdue only, e.g. for escalations to managers.
+    Critical,
+}
+
+impl NotificationLevel {
+    pub const ALL: [Self; 2] = [Self::Warning, Self::Critical];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.as_str() == value.trim())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    #[must_use]
+    pub fn channel(&self) -> NotificationChannel {
+        NotificationChannel::parse(&self.channel).unwrap_or(NotificationChannel::Email)
+    }
+
+    #[must_use]
+    pub fn frequency(&self) -> NotificationFrequency {
+        NotificationFrequency::parse(&self.frequency).unwrap_or(NotificationFrequency::Immediate)
+    }
+
+    #[must_use]
+    pub fn min_urgency(&self) -> NotificationLevel {
+        NotificationLevel::parse(&self.min_urgency).unwrap_or(NotificationLevel::Warning)
+    }
+
+    /// Whether `now` falls into the quiet period, given as whole hours in
+    /// `tz`. Periods may span midnight, e.g. from 22 to 6.
+    #[must_use]
+    pub fn is_quiet_at(&self, now: DateTime<Utc>, tz: Tz) -> bool {
+        let (Some(start), Some(end)) = (self.quiet_start_hour, self.quiet_end_hour) else {
+            return false;
+        };
+        let hour = i16::try_from(now.with_timezone(&tz).hour()).unwrap_or_default();
+        if start <= end {
+            (start..end).contains(&hour)
+        } else {
+            hour >= start || hour < end
+        }
+    }
+
+    /// Whether the frequency allows sending again at `now`.
+    #[must_use]
+    pub fn is_due_at(&self, now: DateTime<Utc>, tz: Tz) -> bool {
+        let Some(last_sent_at) = self.last_sent_at else {
+            return true;
+        };
+        match self.frequency() {
+            NotificationFrequency::Immediate => true,
+            NotificationFrequency::Daily => {
+                last_sent_at.with_timezone(&tz).date_naive() < now.with_timezone(&tz).date_naive()
+            }
+            NotificationFrequency::Weekly => now - last_sent_at.to_utc() >= Duration::days(7),
+        }
+    }
+
+    /// Whether an item of kind `kind_id` at `location` is covered by the
+    /// subscription. Locations match case-insensitively by substring.
+    #[must_use]
+    pub fn covers(&self, item_id: i32, kind_id: i32, location: Option<&str>) -> bool {
+        if self.inventory_item_id.is_some_and(|id| id != item_id) {
+            return false;
+        }
+        if self.inventory_item_kind_id.is_some_and(|id| id != kind_id) {
+            return false;
+        }
+        self.location.as_deref().is_none_or(|needle| {
+            location
+                .is_some_and(|location| location.to_lowercase().contains(&needle.to_lowercase()))
+        })
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn subscription() -> Model {
+        let now = Utc::now().into();
+        Model {
+            created_at: now,
+            updated_at: now,
+            id: 1,
+            location: None,
+            min_urgency: "warning".to_string(),
+            channel: "email".to_string(),
+            frequency: "daily".to_string(),
+            quiet_start_hour: Some(22),
+            quiet_end_hour: Some(6),
+            last_sent_at: None,
+            user_id: 1,
+            inventory_item_kind_id: None,
+            inventory_item_id: None,
+            webhook_endpoint_id: None,
+            responsible_only: false,
+            team_id: None,
+        }
+    }
+
+    #[test]
+    fn quiet_periods_span_midnight() {
+        let subscription = subscription();
+        let tz = chrono_tz::Europe::Berlin;
+        let at = |hour| Utc.with_ymd_and_hms(2026, 1, 15, hour, 30, 0).unwrap();
+        // 22:30 and 05:30 in Berlin
+        assert!(subscription.is_quiet_at(at(21), tz));
+        assert!(subscription.is_quiet_at(at(4), tz));
+        // 06:30 and 21:30 in Berlin
+        assert!(!subscription.is_quiet_at(at(5), tz));
+        assert!(!subscription.is_quiet_at(at(20), tz));
+    }
+
+    #[test]
+    fn daily_subscriptions_send_once_per_local_day() {
+        let tz = chrono_tz::Europe::Berlin;
+        let mut subscription = subscription();
+        subscription.last_sent_at =
+            Some(Utc.with_ymd_and_hms(2026, 1, 15, 7, 0, 0).unwrap().into());
+        assert!(!subscription.is_due_at(Utc.with_ymd_and_hms(2026, 1, 15, 22, 0, 0).unwrap(), tz));
+        // 00:30 on the next day in Berlin
+        assert!(subscription.is_due_at(Utc.with_ymd_and_hms(2026, 1, 15, 23, 30, 0).unwrap(), tz));
+    }
+
+    #[test]
+    fn covers_items_by_kind_and_location() {
+        let mut subscription = subscription();
+        subscription.inventory_item_kind_id = Some(3);
+        subscription.location = Some("store a".to_string());
+        assert!(subscription.covers(1, 3, Some("Store A, shelf 2")));
+        assert!(!subscription.covers(1, 4, Some("Store A")));
+        assert!(!subscription.covers(1, 3, None));
+        subscription.inventory_item_id = Some(2);
+        assert!(!subscription.covers(1, 3, Some("Store A")));
+    }
+}
@@ -0,0 +1,206 @@
+use chrono::Utc;
+use loco_rs::prelude::*;
+use sea_orm::{DerivePartialModel, FromQueryResult, QueryOrder, QuerySelect, Select};
+use serde::{Deserialize, Serialize};
+
+pub use super::_entities::report_jobs::{self, ActiveModel, Column, Entity, Model};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ReportJobStatus {
+    pub const ALL: [Self; 4] = [Self::Queued, Self::Running, Self::Completed, Self::Failed];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value)
+    }
+}
+
+/// A report job without the generated document, for listings and status
+/// polling.
+#[derive(Clone, Debug, DerivePartialModel, FromQueryResult, Serialize)]
+#[sea_orm(entity = "Entity")]
+pub struct ReportJobSummary {
+    pub pid: Uuid,
+    pub kind: String,
+    pub lang: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub title: Option<String>,
+    pub filename: Option<String>,
+    pub report_id: Option<String>,
+    pub started_at: Option<DateTimeWithTimeZone>,
+    pub finished_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    #[must_use]
+    pub fn status(&self) -> Option<ReportJobStatus> {
+        ReportJobStatus::parse(&self.status)
+    }
+
+    /// finds a job `user_id` queued in `tenant_id` by its public id
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn find_by_pid(
+        db: &DatabaseConnection,
+        tenant_id: i32,
+        user_id: i32,
+        pid: &str,
+    ) -> ModelResult<Option<Self>> {
+        let Ok(pid) = Uuid::parse_str(pid) else {
+            return Ok(None);
+        };
+        Ok(Entity::find_for_user(tenant_id, user_id)
+            .filter(Column::Pid.eq(pid))
+            .one(db)
+            .await?)
+    }
+
+    /// lists the most recent jobs `user_id` queued in `tenant_id`, newest
+    /// first
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn list_recent(
+        db: &DatabaseConnection,
+        tenant_id: i32,
+        user_id: i32,
+        limit: u64,
+    ) -> ModelResult<Vec<ReportJobSummary>> {
+        Ok(Entity::find_for_user(tenant_id, user_id)
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .into_partial_model::<ReportJobSummary>()
+            .all(db)
+            .await?)
+    }
+
+    /// marks the job as picked up by a worker
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn mark_running(self, db: &DatabaseConnection) -> ModelResult<Self> {
+        let mut job = self.into_active_model();
+        job.status = ActiveValue::set(ReportJobStatus::Running.as_str().to_string());
+        job.started_at = ActiveValue::set(Some(Utc::now().into()));
+        Ok(job.update(db).await?)
+    }
+
+    /// stores the generated document of the job
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn mark_completed(
+        self,
+        db: &DatabaseConnection,
+        title: String,
+        filename: String,
+        report_id: String,
+        content: Vec<u8>,
+    ) -> ModelResult<Self> {
+        let mut job = self.into_active_model();
+        job.status = ActiveValue::set(ReportJobStatus::Completed.as_str().to_string());
+        job.title = ActiveValue::set(Some(title));
+        job.filename = ActiveValue::set(Some(filename));
+        job.report_id = ActiveValue::set(Some(report_id));
+        job.content = ActiveValue::set(Some(content));
+        job.finished_at = ActiveValue::set(Some(Utc::now().into()));
+        Ok(job.update(db).await?)
+    }
+
+    /// records why the job could not be completed
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn mark_failed(self, db: &DatabaseConnection, error: String) -> ModelResult<Self> {
+        let mut job = self.into_active_model();
+        job.status = ActiveValue::set(ReportJobStatus::Failed.as_str().to_string());
+        job.error = ActiveValue::set(Some(error));
+        job.finished_at = ActiveValue::set(Some(Utc::now().into()));
+        Ok(job.update(db).await?)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Builds a queued job of `user_id` of `kind` with its serialized
+    /// `params`, rendered in `lang` and `timezone` from the data of
+    /// `tenant_id`.
+    #[must_use]
+    pub fn queue(
+        tenant_id: i32,
+        user_id: i32,
+        kind: &str,
+        params: String,
+        lang: String,
+        timezone: String,
+    ) -> Self {
+        Self {
+            tenant_id: ActiveValue::set(tenant_id),
+            user_id: ActiveValue::set(user_id),
+            pid: ActiveValue::set(Uuid::new_v4()),
+            kind: ActiveValue::set(kind.to_string()),
+            params: ActiveValue::set(params),
+            lang: ActiveValue::set(lang),
+            timezone: ActiveValue::set(timezone),
+            status: ActiveValue::set(ReportJobStatus::Queued.as_str().to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Jobs `user_id` queued in `tenant_id`.
+    #[must_use]
+    pub fn find_for_user(tenant_id: i32, user_id: i32) -> Select<Self> {
+        Self::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(Column::UserId.eq(user_id))
+    }
+}
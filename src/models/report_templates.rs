@@ -0,0 +1,91 @@
+use chrono::Utc;
+use loco_rs::prelude::*;
+use sea_orm::Select;
+
+pub use super::_entities::report_templates::{self, ActiveModel, Column, Entity, Model};
+use super::generated_reports::ReportKind;
+use crate::reports::CustomTemplate;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    /// finds the custom template of reports of `kind` of `tenant_id`
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn find_by_kind(
+        db: &DatabaseConnection,
+        tenant_id: i32,
+        kind: ReportKind,
+    ) -> ModelResult<Option<Self>> {
+        Ok(Entity::find_in_tenant(tenant_id)
+            .filter(Column::Kind.eq(kind.as_str()))
+            .one(db)
+            .await?)
+    }
+
+    #[must_use]
+    pub fn custom_template(&self) -> CustomTemplate {
+        CustomTemplate {
+            source: self.source.clone(),
+            logo: self.logo.clone(),
+        }
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Replaces the custom template of reports of `kind` of `tenant_id`.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn save_for_kind(
+        db: &DatabaseConnection,
+        tenant_id: i32,
+        kind: ReportKind,
+        template: CustomTemplate,
+    ) -> ModelResult<Model> {
+        if let Some(existing) = Model::find_by_kind(db, tenant_id, kind).await? {
+            let mut item = existing.into_active_model();
+            item.source = ActiveValue::set(template.source);
+            item.logo = ActiveValue::set(template.logo);
+            Ok(item.update(db).await?)
+        } else {
+            Ok(Self {
+                tenant_id: ActiveValue::set(tenant_id),
+                kind: ActiveValue::set(kind.as_str().to_string()),
+                source: ActiveValue::set(template.source),
+                logo: ActiveValue::set(template.logo),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?)
+        }
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Custom templates of `tenant_id`.
+    #[must_use]
+    pub fn find_in_tenant(tenant_id: i32) -> Select<Self> {
+        Self::find().filter(Column::TenantId.eq(tenant_id))
+    }
+}
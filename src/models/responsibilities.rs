@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{
+    entity::prelude::*,
+    sea_query::{Alias, Expr, Query, SelectStatement},
+    ActiveValue, Condition, IntoActiveModel, JoinType,
+};
+use serde::Serialize;
+
+pub use super::_entities::responsibilities::{self, ActiveModel, Column, Entity, Model};
+use super::{inventory_items, team_memberships, teams::TeamMembers};
+
+/// What an owner and deputy are assigned to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResponsibilityTarget {
+    Kind(i32),
+    Item(i32),
+}
+
+/// Who holds an owner or deputy role.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum Assignee {
+    User(i32),
+    Team(i32),
+}
+
+impl Assignee {
+    fn from_columns(user_id: Option<i32>, team_id: Option<i32>) -> Option<Self> {
+        user_id.map(Self::User).or(team_id.map(Self::Team))
+    }
+
+    fn user_id(self) -> Option<i32> {
+        match self {
+            Self::User(id) => Some(id),
+            Self::Team(_) => None,
+        }
+    }
+
+    fn team_id(self) -> Option<i32> {
+        match self {
+            Self::User(_) => None,
+            Self::Team(id) => Some(id),
+        }
+    }
+
+    /// Value of the assignee in forms, e.g. `user:3` or `team:1`.
+    #[must_use]
+    pub fn key(self) -> String {
+        match self {
+            Self::User(id) => format!("user:{id}"),
+            Self::Team(id) => format!("team:{id}"),
+        }
+    }
+
+    /// Parses a [`Assignee::key`].
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let (kind, id) = value.trim().split_once(':')?;
+        let id = id.parse().ok()?;
+        match kind {
+            "user" => Some(Self::User(id)),
+            "team" => Some(Self::Team(id)),
+            _ => None,
+        }
+    }
+
+    /// Whether `user_id` is this user or a member of this team.
+    #[must_use]
+    pub fn includes(self, user_id: i32, members: &TeamMembers) -> bool {
+        match self {
+            Self::User(id) => id == user_id,
+            Self::Team(id) => members
+                .get(&id)
+                .is_some_and(|members| members.contains(&user_id)),
+        }
+    }
+}
+
+/// Role of an assignee.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Owner,
+    Deputy,
+}
+
+impl Role {
+    /// Columns of the user and team holding the role.
+    fn columns(self) -> (Column, Column) {
+        match self {
+            Self::Owner => (Column::OwnerUserId, Column::OwnerTeamId),
+            Self::Deputy => (Column::DeputyUserId, Column::DeputyTeamId),
+        }
+    }
+}
+
+/// Owner and deputy of a kind or an item.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Assignment {
+    pub owner: Option<Assignee>,
+    pub deputy: Option<Assignee>,
+}
+
+impl Assignment {
+    /// `self` with every role it leaves open taken from `fallback`, e.g. an
+    /// item's assignment over the one of its kind.
+    #[must_use]
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            owner: self.owner.or(fallback.owner),
+            deputy: self.deputy.or(fallback.deputy),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.owner.is_none() && self.deputy.is_none()
+    }
+
+    /// The assignee reminders go to: the owner, or the deputy while the
+    /// owner is absent or when there is no owner. A team is absent when all
+    /// of its members are.
+    #[must_use]
+    pub fn acting(&self, is_absent: impl Fn(Assignee) -> bool) -> Option<Assignee> {
+        match (self.owner, self.deputy) {
+            (Some(owner), Some(deputy)) if is_absent(owner) => Some(deputy),
+            (owner, deputy) => owner.or(deputy),
+        }
+    }
+}
+
+impl From<&Model> for Assignment {
+    fn from(row: &Model) -> Self {
+        Self {
+            owner: Assignee::from_columns(row.owner_user_id, row.owner_team_id),
+            deputy: Assignee::from_columns(row.deputy_user_id, row.deputy_team_id),
+        }
+    }
+}
+
+/// Assignments of kinds and items, to resolve many items at once.
+#[derive(Debug, Default)]
+pub struct Assignments {
+    by_kind: HashMap<i32, Assignment>,
+    by_item: HashMap<i32, Assignment>,
+}
+
+impl Assignments {
+    #[must_use]
+    pub fn get(&self, target: ResponsibilityTarget) -> Assignment {
+        match target {
+            ResponsibilityTarget::Kind(id) => self.by_kind.get(&id),
+            ResponsibilityTarget::Item(id) => self.by_item.get(&id),
+        }
+        .copied()
+        .unwrap_or_default()
+    }
+
+    /// The item's own owner and deputy, each falling back to its kind's.
+    #[must_use]
+    pub fn for_item(&self, item: &inventory_items::Model) -> Assignment {
+        self.get(ResponsibilityTarget::Item(item.id))
+            .or(self.get(ResponsibilityTarget::Kind(item.inventory_item_kind_id)))
+    }
+}
+
+impl FromIterator<Model> for Assignments {
+    fn from_iter<I: IntoIterator<Item = Model>>(rows: I) -> Self {
+        let mut assignments = Self::default();
+        for row in rows {
+            if let Some(item_id) = row.inventory_item_id {
+                assignments.by_item.insert(item_id, Assignment::from(&row));
+            } else if let Some(kind_id) = row.inventory_item_kind_id {
+                assignments.by_kind.insert(kind_id, Assignment::from(&row));
+            }
+        }
+        assignments
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    fn set_assignment(&mut self, assignment: Assignment) {
+        self.owner_user_id = ActiveValue::set(assignment.owner.and_then(Assignee::user_id));
+        self.owner_team_id = ActiveValue::set(assignment.owner.and_then(Assignee::team_id));
+        self.deputy_user_id = ActiveValue::set(assignment.deputy.and_then(Assignee::user_id));
+        self.deputy_team_id = ActiveValue::set(assignment.deputy.and_then(Assignee::team_id));
+    }
+}
+
+fn target_condition(target: ResponsibilityTarget) -> Condition {
+    match target {
+        ResponsibilityTarget::Kind(id) => Condition::all().add(Column::InventoryItemKindId.eq(id)),
+        ResponsibilityTarget::Item(id) => Condition::all().add(Column::InventoryItemId.eq(id)),
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Subquery of the ids of the items where `user_id` or one of their
+    /// teams holds one of `roles`, directly or through the item's kind. Like
+    /// [`Assignments::for_item`], each role of the item falls back to the
+    /// kind's only while the item leaves it open.
+    #[must_use]
+    pub fn items_held_by(user_id: i32, roles: &[Role]) -> SelectStatement {
+        let item_row = Alias::new("item_row");
+        let kind_row = Alias::new("kind_row");
+        let teams = Query::select()
+            .column(team_memberships::Column::TeamId)
+            .from(team_memberships::Entity)
+            .and_where(team_memberships::Column::UserId.eq(user_id))
+            .to_owned();
+        let holds = |row: &Alias, user: Column, team: Column| {
+            Condition::any()
+                .add(Expr::col((row.clone(), user)).eq(user_id))
+                .add(Expr::col((row.clone(), team)).in_subquery(teams.clone()))
+        };
+        let role = |(user, team): (Column, Column)| {
+            Condition::any().add(holds(&item_row, user, team)).add(
+                Condition::all()
+                    .add(Expr::col((item_row.clone(), user)).is_null())
+                    .add(Expr::col((item_row.clone(), team)).is_null())
+                    .add(holds(&kind_row, user, team)),
+            )
+        };
+        Query::select()
+            .column((inventory_items::Entity, inventory_items::Column::Id))
+            .from(inventory_items::Entity)
+            .join_as(
+                JoinType::LeftJoin,
+                Entity,
+                item_row.clone(),
+                Expr::col((item_row.clone(), Column::InventoryItemId))
+                    .equals((inventory_items::Entity, inventory_items::Column::Id)),
+            )
+            .join_as(
+                JoinType::LeftJoin,
+                Entity,
+                kind_row.clone(),
+                Expr::col((kind_row.clone(), Column::InventoryItemKindId)).equals((
+                    inventory_items::Entity,
+                    inventory_items::Column::InventoryItemKindId,
+                )),
+            )
+            .cond_where(
+                roles
+                    .iter()
+                    .fold(Condition::any(), |any, held| any.add(role(held.columns()))),
+            )
+            .to_owned()
+    }
+
+    /// Loads the assignments of every kind and item.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn load_all<C: ConnectionTrait>(db: &C) -> Result<Assignments, DbErr> {
+        Ok(Self::find().all(db).await?.into_iter().collect())
+    }
+
+    /// Loads the assignments of one item and its kind.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn load_for_item<C: ConnectionTrait>(
+        db: &C,
+        item: &inventory_items::Model,
+    ) -> Result<Assignments, DbErr> {
+        Ok(Self::find()
+            .filter(
+                Condition::any()
+                    .add(target_condition(ResponsibilityTarget::Item(item.id)))
+                    .add(target_condition(ResponsibilityTarget::Kind(
+                        item.inventory_item_kind_id,
+                    ))),
+            )
+            .all(db)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    /// Loads the owner and deputy assigned to `target` itself, without
+    /// fallback.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn load_for<C: ConnectionTrait>(
+        db: &C,
+        target: ResponsibilityTarget,
+    ) -> Result<Assignment, DbErr> {
+        Ok(Self::find()
+            .filter(target_condition(target))
+            .one(db)
+            .await?
+            .as_ref()
+            .map(Assignment::from)
+            .unwrap_or_default())
+    }
+
+    /// Replaces the owner and deputy of `target`. An empty assignment
+    /// removes the row, so items fall back to their kind again.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn assign<C: ConnectionTrait>(
+        db: &C,
+        target: ResponsibilityTarget,
+        assignment: Assignment,
+    ) -> Result<(), DbErr> {
+        let existing = Self::find()
+            .filter(target_condition(target))
+            .one(db)
+            .await?;
+        match (existing, assignment.is_empty()) {
+            (None, true) => {}
+            (Some(row), true) => {
+                row.delete(db).await?;
+            }
+            (Some(row), false) => {
+                let mut row = row.into_active_model();
+                row.set_assignment(assignment);
+                row.update(db).await?;
+            }
+            (None, false) => {
+                let (kind_id, item_id) = match target {
+                    ResponsibilityTarget::Kind(id) => (Some(id), None),
+                    ResponsibilityTarget::Item(id) => (None, Some(id)),
+                };
+                let mut row = ActiveModel {
+                    inventory_item_kind_id: ActiveValue::set(kind_id),
+                    inventory_item_id: ActiveValue::set(item_id),
+                    ..Default::default()
+                };
+                row.set_assignment(assignment);
+                row.insert(db).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deputy_acts_while_the_owner_is_absent() {
+        let item = Assignment {
+            owner: Some(Assignee::User(1)),
+            deputy: None,
+        };
+        let kind = Assignment {
+            owner: Some(Assignee::User(3)),
+            deputy: Some(Assignee::Team(2)),
+        };
+        let effective = item.or(kind);
+        assert_eq!(effective.owner, Some(Assignee::User(1)));
+        assert_eq!(effective.deputy, Some(Assignee::Team(2)));
+        assert_eq!(effective.acting(|_| false), Some(Assignee::User(1)));
+        assert_eq!(
+            effective.acting(|assignee| assignee == Assignee::User(1)),
+            Some(Assignee::Team(2))
+        );
+        // without a deputy the owner stays responsible
+        assert_eq!(item.acting(|_| true), Some(Assignee::User(1)));
+    }
+
+    #[test]
+    fn parses_assignee_keys() {
+        assert_eq!(Assignee::parse("team:4"), Some(Assignee::Team(4)));
+        assert_eq!(
+            Assignee::parse(&Assignee::User(7).key()),
+            Some(Assignee::User(7))
+        );
+        assert_eq!(Assignee::parse("group:1"), None);
+        assert_eq!(Assignee::parse("user:x"), None);
+    }
+}
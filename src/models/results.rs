@@ -2,6 +2,9 @@ pub use super::_entities::results::{ActiveModel, Entity, Model};
 use sea_orm::entity::prelude::*;
 pub type Results = Entity;
 
+/// Code of the result that marks a check as passed.
+pub const RESULT_OK_CODE: &str = "RESULT_OK";
+
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
     async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
@@ -19,7 +22,12 @@ impl ActiveModelBehavior for ActiveModel {
 }
 
 // implement your read-oriented logic here
-impl Model {}
+impl Model {
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.code == RESULT_OK_CODE
+    }
+}
 
 // implement your write-oriented logic here
 impl ActiveModel {}
@@ -0,0 +1,113 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use loco_rs::prelude::*;
+use sea_orm::sea_query::{Expr, Query};
+
+pub use super::_entities::sent_notifications::{self, ActiveModel, Column, Entity, Model};
+use super::inventory_items;
+
+/// Identifies a notice: the item, its urgency and the due date it was sent
+/// for. A new due date after a check or an escalation is a new notice.
+pub type NoticeKey = (i32, String, DateTimeWithTimeZone);
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    #[must_use]
+    pub fn key(&self) -> NoticeKey {
+        (self.inventory_item_id, self.urgency.clone(), self.due_at)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Notices already sent by each of the subscriptions.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn keys_by_subscription(
+        db: &DatabaseConnection,
+        subscription_ids: &[i32],
+    ) -> ModelResult<HashMap<i32, HashSet<NoticeKey>>> {
+        let mut keys = HashMap::<i32, HashSet<NoticeKey>>::new();
+        for sent in Self::find()
+            .filter(Column::NotificationSubscriptionId.is_in(subscription_ids.iter().copied()))
+            .all(db)
+            .await?
+        {
+            if let Some(subscription_id) = sent.notification_subscription_id {
+                keys.entry(subscription_id).or_default().insert(sent.key());
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Notices already sent to each of the users as the assignee of the
+    /// items.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn keys_by_assignee(
+        db: &DatabaseConnection,
+        user_ids: &[i32],
+    ) -> ModelResult<HashMap<i32, HashSet<NoticeKey>>> {
+        let mut keys = HashMap::<i32, HashSet<NoticeKey>>::new();
+        for sent in Self::find()
+            .filter(Column::UserId.is_in(user_ids.iter().copied()))
+            .all(db)
+            .await?
+        {
+            if let Some(user_id) = sent.user_id {
+                keys.entry(user_id).or_default().insert(sent.key());
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Deletes the notices sent for a due date the item no longer has, after
+    /// a check or a change of its interval or expiry. Returns how many.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn prune_stale(db: &DatabaseConnection) -> ModelResult<u64> {
+        let current = Query::select()
+            .expr(Expr::val(1))
+            .from(inventory_items::Entity)
+            .and_where(
+                Expr::col((inventory_items::Entity, inventory_items::Column::Id))
+                    .equals((Entity, Column::InventoryItemId)),
+            )
+            .and_where(
+                Expr::col((inventory_items::Entity, inventory_items::Column::NextDueAt))
+                    .equals((Entity, Column::DueAt)),
+            )
+            .to_owned();
+        Ok(Self::delete_many()
+            .filter(Expr::exists(current).not())
+            .exec(db)
+            .await?
+            .rows_affected)
+    }
+}
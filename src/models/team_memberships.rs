@@ -0,0 +1,52 @@
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, ActiveValue};
+
+pub use super::_entities::team_memberships::{self, ActiveModel, Column, Entity, Model};
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Adds `user_id` to `team_id` unless they are a member already.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn add<C: ConnectionTrait>(db: &C, team_id: i32, user_id: i32) -> Result<(), DbErr> {
+        let existing = Self::find()
+            .filter(Column::TeamId.eq(team_id))
+            .filter(Column::UserId.eq(user_id))
+            .one(db)
+            .await?;
+        if existing.is_none() {
+            ActiveModel {
+                team_id: ActiveValue::set(team_id),
+                user_id: ActiveValue::set(user_id),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+        Ok(())
+    }
+}
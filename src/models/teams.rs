@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, sea_query::SelectStatement, Condition, QueryOrder};
+
+pub use super::_entities::teams::{self, ActiveModel, Column, Entity, Model};
+use super::{
+    responsibilities::{self, Role},
+    team_memberships,
+};
+
+/// Member user ids by team id, to resolve team assignments in bulk.
+pub type TeamMembers = HashMap<i32, Vec<i32>>;
+
+/// The items a user may see and inspect.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ItemScope {
+    /// The user is in no scoped team.
+    All,
+    /// Subquery of the ids of the items owned or deputized by the user or
+    /// one of their teams, see [`responsibilities::Entity::items_held_by`].
+    Only(Box<SelectStatement>),
+}
+
+impl ItemScope {
+    /// Limits `item_id`, a column holding item ids, to the items in scope.
+    #[must_use]
+    pub fn condition(&self, item_id: impl ColumnTrait) -> Condition {
+        match self {
+            Self::All => Condition::all(),
+            Self::Only(items) => Condition::all().add(item_id.in_subquery(*items.clone())),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// The team with `id`, if it belongs to `tenant_id`.
+    #[must_use]
+    pub fn find_by_id_in_tenant(tenant_id: i32, id: i32) -> Select<Self> {
+        Self::find_by_id(id).filter(Column::TenantId.eq(tenant_id))
+    }
+
+    /// Every team of `tenant_id`, by name.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn list<C: ConnectionTrait>(db: &C, tenant_id: i32) -> Result<Vec<Model>, DbErr> {
+        Self::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .order_by_asc(Column::Name)
+            .all(db)
+            .await
+    }
+
+    /// Member user ids of every team.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn members_by_team<C: ConnectionTrait>(db: &C) -> Result<TeamMembers, DbErr> {
+        let mut members = TeamMembers::new();
+        for membership in team_memberships::Entity::find()
+            .order_by_asc(team_memberships::Column::Id)
+            .all(db)
+            .await?
+        {
+            members
+                .entry(membership.team_id)
+                .or_default()
+                .push(membership.user_id);
+        }
+        Ok(members)
+    }
+
+    /// Which items of `tenant_id` the user may see: everything, unless they
+    /// are in a scoped team of that tenant.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn item_scope_for_user<C: ConnectionTrait>(
+        db: &C,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<ItemScope, DbErr> {
+        let scoped = Self::find()
+            .inner_join(team_memberships::Entity)
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(team_memberships::Column::UserId.eq(user_id))
+            .filter(Column::Scoped.eq(true))
+            .count(db)
+            .await?;
+        Ok(if scoped == 0 {
+            ItemScope::All
+        } else {
+            ItemScope::Only(Box::new(responsibilities::Entity::items_held_by(
+                user_id,
+                &[Role::Owner, Role::Deputy],
+            )))
+        })
+    }
+}
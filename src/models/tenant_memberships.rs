@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder};
+
+pub use super::_entities::tenant_memberships::{self, ActiveModel, Column, Entity, Model};
+use super::tenants::DEFAULT_TENANT_ID;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Adds `user_id` to `tenant_id` unless they are a member already. The
+    /// first member of a tenant becomes its admin.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn add<C: ConnectionTrait>(
+        db: &C,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<(), DbErr> {
+        if !Self::is_member(db, tenant_id, user_id).await? {
+            let has_admin = Self::find()
+                .filter(Column::TenantId.eq(tenant_id))
+                .filter(Column::Admin.eq(true))
+                .one(db)
+                .await?
+                .is_some();
+            ActiveModel {
+                tenant_id: ActiveValue::set(tenant_id),
+                user_id: ActiveValue::set(user_id),
+                admin: ActiveValue::set(!has_admin),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Whether `user_id` belongs to `tenant_id`.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn is_member<C: ConnectionTrait>(
+        db: &C,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<bool, DbErr> {
+        Ok(Self::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(Column::UserId.eq(user_id))
+            .one(db)
+            .await?
+            .is_some())
+    }
+
+    /// Whether `user_id` is an admin of `tenant_id`.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn is_admin<C: ConnectionTrait>(
+        db: &C,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<bool, DbErr> {
+        Ok(Self::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Admin.eq(true))
+            .one(db)
+            .await?
+            .is_some())
+    }
+
+    /// Whether `user_id` administers what all tenants share, like the
+    /// translations and the intervals, expiries and results: the admins of
+    /// the default tenant, which the installation started with.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn is_global_admin<C: ConnectionTrait>(db: &C, user_id: i32) -> Result<bool, DbErr> {
+        Self::is_admin(db, DEFAULT_TENANT_ID, user_id).await
+    }
+
+    /// Member user ids of every tenant.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn members_by_tenant<C: ConnectionTrait>(
+        db: &C,
+    ) -> Result<HashMap<i32, Vec<i32>>, DbErr> {
+        let mut members = HashMap::<i32, Vec<i32>>::new();
+        for membership in Self::find().order_by_asc(Column::Id).all(db).await? {
+            members
+                .entry(membership.tenant_id)
+                .or_default()
+                .push(membership.user_id);
+        }
+        Ok(members)
+    }
+}
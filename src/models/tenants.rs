@@ -0,0 +1,92 @@
+use chrono::Utc;
+use sea_orm::{entity::prelude::*, ActiveValue, QueryOrder, TransactionTrait};
+
+pub use super::_entities::tenants::{self, ActiveModel, Column, Entity, Model};
+use super::{tenant_memberships, users};
+
+/// Tenant created by the migration. It holds everything recorded before
+/// tenants existed and its admins are the global admins.
+pub const DEFAULT_TENANT_ID: i32 = 1;
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {}
+
+// implement your write-oriented logic here
+impl ActiveModel {}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Tenants `user_id` belongs to, by name.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn for_user<C: ConnectionTrait>(db: &C, user_id: i32) -> Result<Vec<Model>, DbErr> {
+        Self::find()
+            .inner_join(tenant_memberships::Entity)
+            .filter(tenant_memberships::Column::UserId.eq(user_id))
+            .order_by_asc(Column::Name)
+            .all(db)
+            .await
+    }
+
+    /// The tenant `user` works in: the one they switched to while they are
+    /// still a member, otherwise the first one they belong to.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn current_for_user<C: ConnectionTrait>(
+        db: &C,
+        user: &users::Model,
+    ) -> Result<Option<Model>, DbErr> {
+        let tenants = Self::for_user(db, user.id).await?;
+        let current = tenants
+            .iter()
+            .position(|tenant| Some(tenant.id) == user.current_tenant_id)
+            .or_else(|| {
+                tenants
+                    .iter()
+                    .position(|tenant| tenant.id == DEFAULT_TENANT_ID)
+            })
+            .unwrap_or_default();
+        Ok(tenants.into_iter().nth(current))
+    }
+
+    /// Creates a tenant with `user_id` as its first member and admin.
+    ///
+    /// # Errors
+    ///
+    /// DB query error, e.g. when the name is taken
+    pub async fn create_with_member<C>(db: &C, name: &str, user_id: i32) -> Result<Model, DbErr>
+    where
+        C: ConnectionTrait + TransactionTrait,
+    {
+        let txn = db.begin().await?;
+        let tenant = ActiveModel {
+            name: ActiveValue::set(name.to_string()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        tenant_memberships::Entity::add(&txn, tenant.id, user_id).await?;
+        txn.commit().await?;
+        Ok(tenant)
+    }
+}
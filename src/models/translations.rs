@@ -1,5 +1,5 @@
-pub use super::_entities::translations::{ActiveModel, Entity, Model};
-use sea_orm::entity::prelude::*;
+pub use super::_entities::translations::{ActiveModel, Column, Entity, Model};
+use sea_orm::{entity::prelude::*, ActiveValue, IntoActiveModel, QueryOrder};
 pub type Translations = Entity;
 
 #[async_trait::async_trait]
@@ -19,10 +19,69 @@ impl ActiveModelBehavior for ActiveModel {
 }
 
 // implement your read-oriented logic here
-impl Model {}
+impl Model {
+    /// finds the translation of `code` into `lang`, ignoring the case of the
+    /// language
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn find_by_code_and_lang(
+        db: &DatabaseConnection,
+        code: &str,
+        lang: &str,
+    ) -> Result<Option<Self>, DbErr> {
+        Ok(Entity::find()
+            .filter(Column::Code.eq(code))
+            .all(db)
+            .await?
+            .into_iter()
+            .find(|translation| translation.lang.eq_ignore_ascii_case(lang)))
+    }
+}
 
 // implement your write-oriented logic here
-impl ActiveModel {}
+impl ActiveModel {
+    /// Sets the translation of `code` into `lang`, replacing an existing one.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn upsert(
+        db: &DatabaseConnection,
+        code: String,
+        lang: String,
+        text: String,
+    ) -> Result<Model, DbErr> {
+        if let Some(existing) = Model::find_by_code_and_lang(db, &code, &lang).await? {
+            let mut translation = existing.into_active_model();
+            translation.text = ActiveValue::set(text);
+            translation.update(db).await
+        } else {
+            Self {
+                code: ActiveValue::set(code),
+                lang: ActiveValue::set(lang),
+                text: ActiveValue::set(text),
+                ..Default::default()
+            }
+            .insert(db)
+            .await
+        }
+    }
+}
 
 // implement your custom finders, selectors oriented logic here
-impl Entity {}
+impl Entity {
+    /// all translations ordered by code and language
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn list_ordered(db: &DatabaseConnection) -> Result<Vec<Model>, DbErr> {
+        Self::find()
+            .order_by_asc(Column::Code)
+            .order_by_asc(Column::Lang)
+            .all(db)
+            .await
+    }
+}
@@ -6,9 +6,13 @@ use serde_json::Map;
 use uuid::Uuid;
 
 pub use super::_entities::users::{self, ActiveModel, Entity, Model};
+use super::api_keys::hash_token;
 
 pub const MAGIC_LINK_LENGTH: i8 = 32;
 pub const MAGIC_LINK_EXPIRATION_MIN: i8 = 5;
+pub const API_KEY_PREFIX: &str = "lo-";
+/// Prefix of the secret in calendar feed URLs.
+pub const CALENDAR_TOKEN_PREFIX: &str = "cal-";
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct LoginParams {
@@ -50,7 +54,7 @@ impl ActiveModelBehavior for super::_entities::users::ActiveModel {
         if insert {
             let mut this = self;
             this.pid = ActiveValue::Set(Uuid::new_v4());
-            this.api_key = ActiveValue::Set(format!("lo-{}", Uuid::new_v4()));
+            this.api_key = ActiveValue::Set(format!("{API_KEY_PREFIX}{}", Uuid::new_v4()));
             Ok(this)
         } else {
             Ok(self)
@@ -203,6 +207,24 @@ impl Model {
         user.ok_or_else(|| ModelError::EntityNotFound)
     }
 
+    /// finds a user by the secret of their calendar feed, of which only the
+    /// hash is stored
+    ///
+    /// # Errors
+    ///
+    /// When could not find user by the given token or DB query error
+    pub async fn find_by_calendar_token(db: &DatabaseConnection, token: &str) -> ModelResult<Self> {
+        let user = users::Entity::find()
+            .filter(
+                model::query::condition()
+                    .eq(users::Column::CalendarTokenHash, hash_token(token))
+                    .build(),
+            )
+            .one(db)
+            .await?;
+        user.ok_or_else(|| ModelError::EntityNotFound)
+    }
+
     /// Verifies whether the provided plain password matches the hashed password
     ///
     /// # Errors
@@ -213,8 +235,15 @@ impl Model {
         hash::verify_password(password, &self.password)
     }
 
+    /// Whether the user is away on `today`, a date in the user's time zone.
+    /// Their deputies take over reminders meanwhile.
+    #[must_use]
+    pub fn is_absent_on(&self, today: chrono::NaiveDate) -> bool {
+        self.absent_until.is_some_and(|until| today <= until)
+    }
+
     /// Asynchronously creates a user with a password and saves it to the
-    /// database.
+    /// database. New users join the default tenant.
     ///
     /// # Errors
     ///
@@ -366,4 +395,73 @@ impl ActiveModel {
         self.magic_link_expiration = ActiveValue::set(None);
         self.update(db).await.map_err(ModelError::from)
     }
+
+    /// Stores the time zone dates are shown and entered in, e.g.
+    /// `Europe/Berlin`.
+    ///
+    /// # Errors
+    /// - Returns an error if database update fails
+    pub async fn set_timezone(
+        mut self,
+        db: &DatabaseConnection,
+        timezone: &str,
+    ) -> ModelResult<Model> {
+        self.timezone = ActiveValue::set(Some(timezone.to_string()));
+        self.update(db).await.map_err(ModelError::from)
+    }
+
+    /// Replaces the secret of the calendar feed, which invalidates every
+    /// subscription made with the old URL. Only its hash is stored, so the
+    /// returned plain text token is only available at this point.
+    ///
+    /// # Errors
+    /// - Returns an error if database update fails
+    pub async fn reset_calendar_token(
+        mut self,
+        db: &DatabaseConnection,
+    ) -> ModelResult<(Model, String)> {
+        let token = format!(
+            "{CALENDAR_TOKEN_PREFIX}{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+        self.calendar_token_hash = ActiveValue::set(Some(hash_token(&token)));
+        let user = self.update(db).await?;
+        Ok((user, token))
+    }
+
+    /// Stores the last day of an absence, or clears it with `None`.
+    ///
+    /// # Errors
+    /// - Returns an error if database update fails
+    pub async fn set_absent_until(
+        mut self,
+        db: &DatabaseConnection,
+        absent_until: Option<chrono::NaiveDate>,
+    ) -> ModelResult<Model> {
+        self.absent_until = ActiveValue::set(absent_until);
+        self.update(db).await.map_err(ModelError::from)
+    }
+
+    /// Stores the tenant the user works in.
+    ///
+    /// # Errors
+    /// - Returns an error if database update fails
+    pub async fn set_current_tenant(
+        mut self,
+        db: &DatabaseConnection,
+        tenant_id: i32,
+    ) -> ModelResult<Model> {
+        self.current_tenant_id = ActiveValue::set(Some(tenant_id));
+        self.update(db).await.map_err(ModelError::from)
+    }
+
+    /// Stores the preferred UI language, e.g. `de-DE`.
+    ///
+    /// # Errors
+    /// - Returns an error if database update fails
+    pub async fn set_locale(mut self, db: &DatabaseConnection, locale: &str) -> ModelResult<Model> {
+        self.locale = ActiveValue::set(Some(locale.to_string()));
+        self.update(db).await.map_err(ModelError::from)
+    }
 }
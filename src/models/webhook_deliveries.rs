@@ -0,0 +1,281 @@
+use chrono::{Duration, Utc};
+use loco_rs::prelude::*;
+use sea_orm::{sea_query::Expr, Condition, QueryOrder, QuerySelect, Select};
+use serde::{Deserialize, Serialize};
+
+pub use super::_entities::webhook_deliveries::{self, ActiveModel, Column, Entity, Model};
+use super::webhook_endpoints;
+
+/// Attempts after which a delivery is given up.
+pub const MAX_ATTEMPTS: i32 = 6;
+/// Wait before the first retry, quadrupled with every further attempt.
+const BASE_BACKOFF_SECONDS: i64 = 60;
+/// How long a claimed delivery is left to its worker before a retry may
+/// claim it again, e.g. after the worker crashed.
+const CLAIM_TIMEOUT_SECONDS: i64 = 5 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Waiting for the first attempt or a retry.
+    Pending,
+    /// Claimed by a worker that is sending it.
+    Sending,
+    Succeeded,
+    /// Given up after [`MAX_ATTEMPTS`] or because the endpoint is gone.
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub const ALL: [Self; 4] = [Self::Pending, Self::Sending, Self::Succeeded, Self::Failed];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Sending => "sending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value)
+    }
+}
+
+/// Wait before the attempt following `attempts` failed ones: 1, 4, 16, 64
+/// and 256 minutes.
+#[must_use]
+pub fn backoff(attempts: i32) -> Duration {
+    let exponent = u32::try_from(attempts.saturating_sub(1)).unwrap_or_default();
+    Duration::seconds(BASE_BACKOFF_SECONDS.saturating_mul(4_i64.saturating_pow(exponent)))
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    #[must_use]
+    pub fn status(&self) -> Option<DeliveryStatus> {
+        DeliveryStatus::parse(&self.status)
+    }
+
+    /// claims a delivery that is pending and due, or whose worker timed out,
+    /// for sending it. Returns `None` when another worker was faster or it
+    /// is not due.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn claim(self, db: &DatabaseConnection) -> ModelResult<Option<Self>> {
+        let now = Utc::now();
+        let claimable = Condition::any()
+            .add(
+                Condition::all()
+                    .add(Column::Status.eq(DeliveryStatus::Pending.as_str()))
+                    .add(
+                        Condition::any()
+                            .add(Column::NextAttemptAt.is_null())
+                            .add(Column::NextAttemptAt.lte(now)),
+                    ),
+            )
+            .add(
+                Condition::all()
+                    .add(Column::Status.eq(DeliveryStatus::Sending.as_str()))
+                    .add(Column::NextAttemptAt.lte(now)),
+            );
+        let claimed = Entity::update_many()
+            .col_expr(
+                Column::Status,
+                Expr::value(DeliveryStatus::Sending.as_str()),
+            )
+            .col_expr(
+                Column::NextAttemptAt,
+                Expr::value(now + Duration::seconds(CLAIM_TIMEOUT_SECONDS)),
+            )
+            .filter(Column::Id.eq(self.id))
+            .filter(claimable)
+            .exec(db)
+            .await?;
+        if claimed.rows_affected == 0 {
+            return Ok(None);
+        }
+        Ok(Entity::find_by_id(self.id).one(db).await?)
+    }
+
+    /// records an attempt the endpoint accepted
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn mark_succeeded(
+        self,
+        db: &DatabaseConnection,
+        response_status: u16,
+    ) -> ModelResult<Self> {
+        let attempts = self.attempts + 1;
+        let mut delivery = self.into_active_model();
+        delivery.status = ActiveValue::set(DeliveryStatus::Succeeded.as_str().to_string());
+        delivery.attempts = ActiveValue::set(attempts);
+        delivery.response_status = ActiveValue::set(Some(i32::from(response_status)));
+        delivery.error = ActiveValue::set(None);
+        delivery.next_attempt_at = ActiveValue::set(None);
+        delivery.delivered_at = ActiveValue::set(Some(Utc::now().into()));
+        Ok(delivery.update(db).await?)
+    }
+
+    /// records a failed attempt and schedules the next one, or gives up
+    /// after [`MAX_ATTEMPTS`]
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn mark_attempt_failed(
+        self,
+        db: &DatabaseConnection,
+        response_status: Option<u16>,
+        error: String,
+    ) -> ModelResult<Self> {
+        let attempts = self.attempts + 1;
+        let mut delivery = self.into_active_model();
+        delivery.attempts = ActiveValue::set(attempts);
+        delivery.response_status = ActiveValue::set(response_status.map(i32::from));
+        delivery.error = ActiveValue::set(Some(error));
+        if attempts >= MAX_ATTEMPTS {
+            delivery.status = ActiveValue::set(DeliveryStatus::Failed.as_str().to_string());
+            delivery.next_attempt_at = ActiveValue::set(None);
+        } else {
+            delivery.status = ActiveValue::set(DeliveryStatus::Pending.as_str().to_string());
+            delivery.next_attempt_at =
+                ActiveValue::set(Some((Utc::now() + backoff(attempts)).into()));
+        }
+        Ok(delivery.update(db).await?)
+    }
+
+    /// gives up a delivery that cannot be sent at all
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn mark_failed(self, db: &DatabaseConnection, error: String) -> ModelResult<Self> {
+        let mut delivery = self.into_active_model();
+        delivery.status = ActiveValue::set(DeliveryStatus::Failed.as_str().to_string());
+        delivery.error = ActiveValue::set(Some(error));
+        delivery.next_attempt_at = ActiveValue::set(None);
+        Ok(delivery.update(db).await?)
+    }
+
+    /// queues the delivery again, e.g. after the endpoint was fixed
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn reset(self, db: &DatabaseConnection) -> ModelResult<Self> {
+        let mut delivery = self.into_active_model();
+        delivery.status = ActiveValue::set(DeliveryStatus::Pending.as_str().to_string());
+        delivery.attempts = ActiveValue::set(0);
+        delivery.next_attempt_at = ActiveValue::set(None);
+        Ok(delivery.update(db).await?)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Builds a pending delivery of `payload` for `event` to `endpoint`.
+    #[must_use]
+    pub fn queue(
+        endpoint: &webhook_endpoints::Model,
+        pid: Uuid,
+        event: &str,
+        payload: String,
+    ) -> Self {
+        Self {
+            tenant_id: ActiveValue::set(endpoint.tenant_id),
+            pid: ActiveValue::set(pid),
+            event: ActiveValue::set(event.to_string()),
+            payload: ActiveValue::set(payload),
+            status: ActiveValue::set(DeliveryStatus::Pending.as_str().to_string()),
+            attempts: ActiveValue::set(0),
+            webhook_endpoint_id: ActiveValue::set(endpoint.id),
+            ..Default::default()
+        }
+    }
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Pending deliveries whose retry is due at `now` and claimed ones whose
+    /// worker timed out.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn find_due(
+        db: &DatabaseConnection,
+        now: DateTimeWithTimeZone,
+    ) -> ModelResult<Vec<Model>> {
+        Ok(Self::find()
+            .filter(Column::Status.is_in([
+                DeliveryStatus::Pending.as_str(),
+                DeliveryStatus::Sending.as_str(),
+            ]))
+            .filter(Column::NextAttemptAt.lte(now))
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await?)
+    }
+
+    /// The delivery with `id`, if it belongs to `tenant_id`.
+    #[must_use]
+    pub fn find_by_id_in_tenant(tenant_id: i32, id: i32) -> Select<Self> {
+        Self::find_by_id(id).filter(Column::TenantId.eq(tenant_id))
+    }
+
+    /// The most recent deliveries of `tenant_id`, newest first.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn list_recent(
+        db: &DatabaseConnection,
+        tenant_id: i32,
+        limit: u64,
+    ) -> ModelResult<Vec<Model>> {
+        Ok(Self::find()
+            .filter(Column::TenantId.eq(tenant_id))
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .all(db)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_off_exponentially() {
+        assert_eq!(backoff(1), Duration::minutes(1));
+        assert_eq!(backoff(2), Duration::minutes(4));
+        assert_eq!(backoff(5), Duration::minutes(256));
+    }
+}
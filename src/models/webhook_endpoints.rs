@@ -0,0 +1,158 @@
+use chrono::Utc;
+use loco_rs::prelude::*;
+use sea_orm::Select;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use super::_entities::webhook_endpoints::{self, ActiveModel, Column, Entity, Model};
+
+/// Prefix of generated signing secrets.
+pub const SECRET_PREFIX: &str = "whsec_";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    /// A check was recorded for an item, passed or not.
+    #[serde(rename = "check.submitted")]
+    CheckSubmitted,
+    #[serde(rename = "item.created")]
+    ItemCreated,
+    #[serde(rename = "item.updated")]
+    ItemUpdated,
+    /// The item was removed from the inventory.
+    #[serde(rename = "item.retired")]
+    ItemRetired,
+    /// The item is due within the next week.
+    #[serde(rename = "item.warning")]
+    ItemWarning,
+    /// The item is overdue or expired.
+    #[serde(rename = "item.critical")]
+    ItemCritical,
+}
+
+impl WebhookEvent {
+    pub const ALL: [Self; 6] = [
+        Self::CheckSubmitted,
+        Self::ItemCreated,
+        Self::ItemUpdated,
+        Self::ItemRetired,
+        Self::ItemWarning,
+        Self::ItemCritical,
+    ];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CheckSubmitted => "check.submitted",
+            Self::ItemCreated => "item.created",
+            Self::ItemUpdated => "item.updated",
+            Self::ItemRetired => "item.retired",
+            Self::ItemWarning => "item.warning",
+            Self::ItemCritical => "item.critical",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == value.trim())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(self, _db: &C, insert: bool) -> std::result::Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert && self.updated_at.is_unchanged() {
+            let mut this = self;
+            this.updated_at = sea_orm::ActiveValue::Set(Utc::now().into());
+            Ok(this)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+// implement your read-oriented logic here
+impl Model {
+    #[must_use]
+    pub fn events(&self) -> Vec<WebhookEvent> {
+        self.events
+            .split(',')
+            .filter_map(WebhookEvent::parse)
+            .collect()
+    }
+
+    #[must_use]
+    pub fn subscribes_to(&self, event: WebhookEvent) -> bool {
+        self.active && self.events().contains(&event)
+    }
+}
+
+// implement your write-oriented logic here
+impl ActiveModel {
+    /// Builds an active endpoint of `tenant_id` posting `events` to `url`,
+    /// signed with a newly generated secret.
+    #[must_use]
+    pub fn generate(tenant_id: i32, name: String, url: String, events: &[WebhookEvent]) -> Self {
+        Self {
+            tenant_id: ActiveValue::set(tenant_id),
+            name: ActiveValue::set(name),
+            url: ActiveValue::set(url),
+            secret: ActiveValue::set(format!(
+                "{SECRET_PREFIX}{}{}",
+                Uuid::new_v4().simple(),
+                Uuid::new_v4().simple()
+            )),
+            events: ActiveValue::set(join_events(events)),
+            active: ActiveValue::set(true),
+            ..Default::default()
+        }
+    }
+}
+
+/// Stored form of a list of events.
+#[must_use]
+pub fn join_events(events: &[WebhookEvent]) -> String {
+    events
+        .iter()
+        .map(|event| event.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// implement your custom finders, selectors oriented logic here
+impl Entity {
+    /// Endpoints of `tenant_id`.
+    #[must_use]
+    pub fn find_in_tenant(tenant_id: i32) -> Select<Self> {
+        Self::find().filter(Column::TenantId.eq(tenant_id))
+    }
+
+    /// The endpoint with `id`, if it belongs to `tenant_id`.
+    #[must_use]
+    pub fn find_by_id_in_tenant(tenant_id: i32, id: i32) -> Select<Self> {
+        Self::find_by_id(id).filter(Column::TenantId.eq(tenant_id))
+    }
+
+    /// Active endpoints of `tenant_id` subscribed to `event`.
+    ///
+    /// # Errors
+    ///
+    /// DB query error
+    pub async fn find_subscribed<C: ConnectionTrait>(
+        db: &C,
+        tenant_id: i32,
+        event: WebhookEvent,
+    ) -> ModelResult<Vec<Model>> {
+        Ok(Self::find_in_tenant(tenant_id)
+            .filter(Column::Active.eq(true))
+            .all(db)
+            .await?
+            .into_iter()
+            .filter(|endpoint| endpoint.subscribes_to(event))
+            .collect())
+    }
+}
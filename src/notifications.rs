@@ -0,0 +1,556 @@
+//! Notices of items that became due, sent per user subscription.
+//!
+//! Every run of the `notifications` task collects for each subscription the
+//! covered items at or above its urgency, drops those it already reported
+//! for their current due date, and sends the rest in one email or webhook
+//! unless its frequency or quiet period holds them back until a later run.
+//! Records of notices for a due date the item no longer has are deleted
+//! first.
+//!
+//! Subscriptions limited to the user's own items only cover the items whose
+//! owner they are, or whose deputy while the owner is absent, directly or
+//! through one of their teams. A team subscription notifies every member
+//! who is not absent, and its own items are the ones of the team.
+//!
+//! Without any subscription, the owner of a due item, or its deputy while
+//! the owner is absent, is reminded of it by email once per urgency and due
+//! date. An email subscription reaching them that covers the item takes
+//! over, with its urgency, frequency and quiet period.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use loco_rs::prelude::*;
+use sea_orm::{QueryOrder, QuerySelect};
+use serde_json::{json, Value};
+
+use crate::{
+    controllers::{
+        home::{calculate_urgency, UrgencyLevel},
+        inventory::load_metadata_value_maps_for_items,
+        reports::location_of,
+    },
+    i18n::Translator,
+    mailers::notification::NotificationMailer,
+    models::{
+        inventory_items,
+        notification_subscriptions::{self, NotificationChannel, NotificationLevel},
+        responsibilities::{self, Assignee},
+        sent_notifications::{self, NoticeKey},
+        teams::{self, TeamMembers},
+        tenant_memberships, users, webhook_endpoints,
+    },
+    timezone, webhooks,
+};
+
+/// Event of the webhooks sent by subscriptions with the webhook channel.
+pub const NOTIFICATION_EVENT: &str = "notification.due_items";
+/// Page where users manage their subscriptions, relative to the site.
+pub const MANAGE_PATH: &str = "/notifications/list";
+
+/// An item with a due date, i.e. an interval or an expiry.
+struct DueItem {
+    item: inventory_items::Model,
+    due_at: DateTimeWithTimeZone,
+    location: Option<String>,
+    /// Owner of the item, or its deputy while the owner is absent.
+    responsible: Option<Assignee>,
+}
+
+/// An item a subscription or reminder is about to report.
+struct Notice<'a> {
+    due: &'a DueItem,
+    level: NotificationLevel,
+}
+
+impl<'a> Notice<'a> {
+    /// The notice of `due` at its urgency on `today` in `tz`, `None` while
+    /// it is not due soon.
+    fn of(due: &'a DueItem, today: NaiveDate, tz: Tz) -> Option<Self> {
+        let level = match calculate_urgency(&due.due_at, today, tz) {
+            UrgencyLevel::Critical => NotificationLevel::Critical,
+            UrgencyLevel::Warning => NotificationLevel::Warning,
+            UrgencyLevel::Normal => return None,
+        };
+        Some(Self { due, level })
+    }
+
+    fn key(&self) -> NoticeKey {
+        (
+            self.due.item.id,
+            self.level.as_str().to_string(),
+            self.due.due_at,
+        )
+    }
+}
+
+/// Sends the pending notices of every subscription, as of `now`.
+///
+/// Returns the number of emails and webhooks sent. A subscription whose
+/// notice could not be sent is logged and tried again on the next run.
+///
+/// # Errors
+///
+/// DB query error
+pub async fn send_due(ctx: &AppContext, now: DateTime<Utc>) -> Result<usize> {
+    let subscriptions = notification_subscriptions::Entity::find()
+        .find_also_related(users::Entity)
+        .order_by_asc(notification_subscriptions::Column::Id)
+        .all(&ctx.db)
+        .await?;
+    sent_notifications::Entity::prune_stale(&ctx.db).await?;
+    let people = People::load(ctx, now).await?;
+    let due_items = load_due_items(ctx, &people).await?;
+    if due_items.is_empty() {
+        return Ok(0);
+    }
+
+    let subscription_ids = subscriptions
+        .iter()
+        .map(|(subscription, _)| subscription.id)
+        .collect::<Vec<_>>();
+    let mut sent_by_subscription =
+        sent_notifications::Entity::keys_by_subscription(&ctx.db, &subscription_ids).await?;
+
+    let mut sent = 0;
+    for (subscription, user) in &subscriptions {
+        let Some(user) = user else {
+            continue;
+        };
+        let translator = Translator::for_user(ctx, user);
+        let tz = translator.timezone();
+        if subscription.is_quiet_at(now, tz) || !subscription.is_due_at(now, tz) {
+            continue;
+        }
+
+        let today = now.with_timezone(&tz).date_naive();
+        let already_sent = sent_by_subscription
+            .remove(&subscription.id)
+            .unwrap_or_default();
+        let notices = due_items
+            .iter()
+            .filter(|due| people.includes(subscription, due))
+            .filter_map(|due| Notice::of(due, today, tz))
+            .filter(|notice| notice.level >= subscription.min_urgency())
+            .filter(|notice| !already_sent.contains(&notice.key()))
+            .collect::<Vec<_>>();
+        if notices.is_empty() {
+            continue;
+        }
+
+        let recipients = people.recipients(subscription, user);
+        if recipients.is_empty() {
+            continue;
+        }
+
+        if let Err(err) = deliver(ctx, subscription, user, &recipients, &translator, &notices).await
+        {
+            tracing::error!(
+                subscription_id = subscription.id,
+                error = %err,
+                "could not send notification"
+            );
+            continue;
+        }
+        record_sent(ctx, subscription.clone(), &notices, now).await?;
+        sent += 1;
+    }
+    sent += remind_assignees(ctx, &people, &due_items, &subscriptions, now).await?;
+    Ok(sent)
+}
+
+/// Reminds the present owner or deputy acting for each due item, unless an
+/// email subscription reaching them covers the item. Returns the number of
+/// emails sent.
+async fn remind_assignees(
+    ctx: &AppContext,
+    people: &People<'_>,
+    due_items: &[DueItem],
+    subscriptions: &[(notification_subscriptions::Model, Option<users::Model>)],
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let mut due_by_user = BTreeMap::<i32, Vec<&DueItem>>::new();
+    for due in due_items {
+        let Some(responsible) = due.responsible else {
+            continue;
+        };
+        for user_id in people.present_users(responsible) {
+            let subscribed = subscriptions.iter().any(|(subscription, _)| {
+                people.reaches(subscription, user_id) && people.includes(subscription, due)
+            });
+            if !subscribed && people.is_tenant_member(user_id, due.item.tenant_id) {
+                due_by_user.entry(user_id).or_default().push(due);
+            }
+        }
+    }
+
+    let user_ids = due_by_user.keys().copied().collect::<Vec<_>>();
+    let mut sent_by_user = sent_notifications::Entity::keys_by_assignee(&ctx.db, &user_ids).await?;
+
+    let mut sent = 0;
+    for (user_id, due_items) in due_by_user {
+        let Some(user) = people.users.get(&user_id) else {
+            continue;
+        };
+        let translator = Translator::for_user(ctx, user);
+        let tz = translator.timezone();
+        let today = now.with_timezone(&tz).date_naive();
+        let already_sent = sent_by_user.remove(&user_id).unwrap_or_default();
+        let notices = due_items
+            .into_iter()
+            .filter_map(|due| Notice::of(due, today, tz))
+            .filter(|notice| !already_sent.contains(&notice.key()))
+            .collect::<Vec<_>>();
+        if notices.is_empty() {
+            continue;
+        }
+
+        if let Err(err) = NotificationMailer::send_due_items(
+            ctx,
+            user,
+            email_locals(ctx, user, &translator, &notices),
+        )
+        .await
+        {
+            tracing::error!(user_id, error = %err, "could not send reminder");
+            continue;
+        }
+        sent_notifications::Entity::insert_many(sent_rows(&notices, None, Some(user_id)))
+            .exec(&ctx.db)
+            .await?;
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+/// Users, team and tenant members of a run, to tell who is absent and who
+/// may see which items.
+struct People<'a> {
+    ctx: &'a AppContext,
+    now: DateTime<Utc>,
+    users: HashMap<i32, users::Model>,
+    members: TeamMembers,
+    tenant_members: HashMap<i32, Vec<i32>>,
+    /// Ids of the items visible to the members of scoped teams, by tenant
+    /// and user id.
+    scoped_items: HashMap<(i32, i32), HashSet<i32>>,
+}
+
+impl<'a> People<'a> {
+    async fn load(ctx: &'a AppContext, now: DateTime<Utc>) -> Result<Self> {
+        let users = users::Entity::find()
+            .all(&ctx.db)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect();
+        let members = teams::Entity::members_by_team(&ctx.db).await?;
+        let tenant_members = tenant_memberships::Entity::members_by_tenant(&ctx.db).await?;
+        let scoped_items = load_scoped_items(ctx, &members).await?;
+        Ok(Self {
+            ctx,
+            now,
+            users,
+            members,
+            tenant_members,
+            scoped_items,
+        })
+    }
+
+    /// Whether the subscription reports `due`: the subscriber may see the
+    /// item, and the subscription covers it.
+    fn includes(&self, subscription: &notification_subscriptions::Model, due: &DueItem) -> bool {
+        self.is_tenant_member(subscription.user_id, due.item.tenant_id)
+            && self.is_in_scope(subscription.user_id, &due.item)
+            && (!subscription.responsible_only || self.is_responsible(subscription, due))
+            && subscription.covers(
+                due.item.id,
+                due.item.inventory_item_kind_id,
+                due.location.as_deref(),
+            )
+    }
+
+    /// Whether the subscription emails the user: its subscriber, or a member
+    /// of its team.
+    fn reaches(&self, subscription: &notification_subscriptions::Model, user_id: i32) -> bool {
+        subscription.channel() == NotificationChannel::Email
+            && match subscription.team_id {
+                Some(team_id) => self
+                    .members
+                    .get(&team_id)
+                    .is_some_and(|members| members.contains(&user_id)),
+                None => subscription.user_id == user_id,
+            }
+    }
+
+    /// The present users among `assignee`: the user, or the team's members.
+    fn present_users(&self, assignee: Assignee) -> Vec<i32> {
+        let user_ids = match assignee {
+            Assignee::User(user_id) => vec![user_id],
+            Assignee::Team(team_id) => self.members.get(&team_id).cloned().unwrap_or_default(),
+        };
+        user_ids
+            .into_iter()
+            .filter(|user_id| !self.is_absent(*user_id))
+            .collect()
+    }
+
+    /// Whether the user belongs to the tenant, and so is told about its items.
+    fn is_tenant_member(&self, user_id: i32, tenant_id: i32) -> bool {
+        self.tenant_members
+            .get(&tenant_id)
+            .is_some_and(|members| members.contains(&user_id))
+    }
+
+    /// Whether the item is in the user's item scope, see
+    /// [`teams::Entity::item_scope_for_user`].
+    fn is_in_scope(&self, user_id: i32, item: &inventory_items::Model) -> bool {
+        self.scoped_items
+            .get(&(item.tenant_id, user_id))
+            .is_none_or(|items| items.contains(&item.id))
+    }
+
+    /// Whether the user is absent today, in their time zone.
+    fn is_absent(&self, user_id: i32) -> bool {
+        self.users.get(&user_id).is_some_and(|user| {
+            let tz = timezone::user_timezone(&self.ctx.config, user);
+            user.is_absent_on(self.now.with_timezone(&tz).date_naive())
+        })
+    }
+
+    /// A team is absent when all of its members are.
+    fn is_assignee_absent(&self, assignee: Assignee) -> bool {
+        match assignee {
+            Assignee::User(user_id) => self.is_absent(user_id),
+            Assignee::Team(team_id) => self
+                .members
+                .get(&team_id)
+                .is_none_or(|members| members.iter().all(|user_id| self.is_absent(*user_id))),
+        }
+    }
+
+    /// Whether the item counts as the subscription's own: the team's for
+    /// team subscriptions, otherwise the user's or one of their teams'
+    /// while the user is present.
+    fn is_responsible(
+        &self,
+        subscription: &notification_subscriptions::Model,
+        due: &DueItem,
+    ) -> bool {
+        due.responsible
+            .is_some_and(|responsible| match subscription.team_id {
+                Some(team_id) => responsible == Assignee::Team(team_id),
+                None => {
+                    responsible.includes(subscription.user_id, &self.members)
+                        && !self.is_absent(subscription.user_id)
+                }
+            })
+    }
+
+    /// Who a subscription's email goes to: the subscriber, or every present
+    /// member of its team.
+    fn recipients<'b>(
+        &'b self,
+        subscription: &notification_subscriptions::Model,
+        user: &'b users::Model,
+    ) -> Vec<&'b users::Model> {
+        match subscription.team_id {
+            Some(team_id) => self
+                .members
+                .get(&team_id)
+                .into_iter()
+                .flatten()
+                .filter(|member_id| !self.is_absent(**member_id))
+                .filter_map(|member_id| self.users.get(member_id))
+                .collect(),
+            None => vec![user],
+        }
+    }
+}
+
+/// Ids of the items the members of scoped teams may see, by tenant and
+/// user id. Users in no scoped team of a tenant see all of its items.
+async fn load_scoped_items(
+    ctx: &AppContext,
+    members: &TeamMembers,
+) -> Result<HashMap<(i32, i32), HashSet<i32>>> {
+    let scoped_teams = teams::Entity::find()
+        .filter(teams::Column::Scoped.eq(true))
+        .all(&ctx.db)
+        .await?;
+    let mut scoped_items = HashMap::new();
+    for team in scoped_teams {
+        for &user_id in members.get(&team.id).into_iter().flatten() {
+            let key = (team.tenant_id, user_id);
+            if scoped_items.contains_key(&key) {
+                continue;
+            }
+            let scope =
+                teams::Entity::item_scope_for_user(&ctx.db, team.tenant_id, user_id).await?;
+            let items = inventory_items::Entity::find()
+                .select_only()
+                .column(inventory_items::Column::Id)
+                .filter(scope.condition(inventory_items::Column::Id))
+                .into_tuple::<i32>()
+                .all(&ctx.db)
+                .await?;
+            scoped_items.insert(key, items.into_iter().collect());
+        }
+    }
+    Ok(scoped_items)
+}
+
+async fn load_due_items(ctx: &AppContext, people: &People<'_>) -> Result<Vec<DueItem>> {
+    let items = inventory_items::Entity::find()
+        .filter(inventory_items::Column::NextDueAt.is_not_null())
+        .order_by_asc(inventory_items::Column::Id)
+        .all(&ctx.db)
+        .await?;
+    let item_ids = items.iter().map(|item| item.id).collect::<Vec<_>>();
+    let metadata_by_item_id = load_metadata_value_maps_for_items(&ctx.db, &item_ids).await?;
+    let assignments = responsibilities::Entity::load_all(&ctx.db).await?;
+
+    Ok(items
+        .into_iter()
+        .filter_map(|item| {
+            let due_at = item.next_due_at?;
+            Some(DueItem {
+                location: location_of(metadata_by_item_id.get(&item.id)),
+                responsible: assignments
+                    .for_item(&item)
+                    .acting(|assignee| people.is_assignee_absent(assignee)),
+                item,
+                due_at,
+            })
+        })
+        .collect())
+}
+
+async fn deliver(
+    ctx: &AppContext,
+    subscription: &notification_subscriptions::Model,
+    user: &users::Model,
+    recipients: &[&users::Model],
+    translator: &Translator,
+    notices: &[Notice<'_>],
+) -> Result<()> {
+    match subscription.channel() {
+        NotificationChannel::Email => {
+            for recipient in recipients {
+                let translator = if recipient.id == user.id {
+                    translator
+                } else {
+                    &Translator::for_user(ctx, recipient)
+                };
+                NotificationMailer::send_due_items(
+                    ctx,
+                    recipient,
+                    email_locals(ctx, recipient, translator, notices),
+                )
+                .await?;
+            }
+            Ok(())
+        }
+        NotificationChannel::Webhook => {
+            let endpoint_id = subscription
+                .webhook_endpoint_id
+                .ok_or_else(|| Error::string("webhook subscription without endpoint"))?;
+            let endpoint = webhook_endpoints::Entity::find_by_id(endpoint_id)
+                .one(&ctx.db)
+                .await?
+                .ok_or_else(|| Error::string("webhook subscription without endpoint"))?;
+            webhooks::queue(
+                ctx,
+                &endpoint,
+                NOTIFICATION_EVENT,
+                &webhook_data(user, notices),
+            )
+            .await?;
+            Ok(())
+        }
+    }
+}
+
+fn email_locals(
+    ctx: &AppContext,
+    user: &users::Model,
+    translator: &Translator,
+    notices: &[Notice<'_>],
+) -> Value {
+    let base_url = ctx.config.server.full_url();
+    let items = notices
+        .iter()
+        .map(|notice| {
+            json!({
+                "name": notice.due.item.name,
+                "serial_number": notice.due.item.serial_number,
+                "urgency": translator.t(&format!("notification-{}", notice.level.as_str())),
+                "due_label": translator.t("notification-due"),
+                "due": translator.format_date(&notice.due.due_at),
+                "url": format!("{base_url}/inventory/item/{}", notice.due.item.id),
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "subject": translator.t("notification-subject"),
+        "greeting": format!("{} {},", translator.t("notification-greeting"), user.name),
+        "intro": translator.t("notification-intro"),
+        "items": items,
+        "manage": translator.t("notification-manage"),
+        "manage_url": format!("{base_url}{MANAGE_PATH}"),
+    })
+}
+
+fn webhook_data(user: &users::Model, notices: &[Notice<'_>]) -> Value {
+    let items = notices
+        .iter()
+        .map(|notice| {
+            json!({
+                "item": notice.due.item,
+                "urgency": notice.level,
+                "due_at": notice.due.due_at,
+                "location": notice.due.location,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "user": { "pid": user.pid, "name": user.name, "email": user.email },
+        "items": items,
+    })
+}
+
+/// Rows recording `notices` as sent by the subscription or to the assignee.
+fn sent_rows(
+    notices: &[Notice<'_>],
+    subscription_id: Option<i32>,
+    user_id: Option<i32>,
+) -> Vec<sent_notifications::ActiveModel> {
+    notices
+        .iter()
+        .map(|notice| sent_notifications::ActiveModel {
+            urgency: ActiveValue::set(notice.level.as_str().to_string()),
+            due_at: ActiveValue::set(notice.due.due_at),
+            notification_subscription_id: ActiveValue::set(subscription_id),
+            user_id: ActiveValue::set(user_id),
+            inventory_item_id: ActiveValue::set(notice.due.item.id),
+            ..Default::default()
+        })
+        .collect()
+}
+
+async fn record_sent(
+    ctx: &AppContext,
+    subscription: notification_subscriptions::Model,
+    notices: &[Notice<'_>],
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let txn = ctx.db.begin().await?;
+    sent_notifications::Entity::insert_many(sent_rows(notices, Some(subscription.id), None))
+        .exec(&txn)
+        .await?;
+    let mut subscription = subscription.into_active_model();
+    subscription.last_sent_at = ActiveValue::set(Some(now.into()));
+    subscription.update(&txn).await?;
+    txn.commit().await?;
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use loco_rs::Result;
+use typst::foundations::{Array, Bytes, Dict, IntoValue};
+
+use super::{
+    compile_custom_template, insert_labels, insert_value, render_template_pdf, sample_datetime,
+    single_item_history::ReportField, CustomTemplate, TemplateDiagnostic,
+};
+use crate::i18n::Translator;
+
+const REPORT_TEMPLATE_PATH: &str = "assets/reports/check_certificate_main.typ";
+/// Source of the built-in main file, the starting point of custom templates.
+pub const BUILTIN_TEMPLATE: &str = include_str!("../../assets/reports/check_certificate_main.typ");
+const LABELS: &[&str] = &[
+    "generated_at",
+    "report_id",
+    "verify_at",
+    "inspected_item",
+    "inspection",
+    "checked_at",
+    "inspector",
+    "checklist",
+    "description",
+    "notes",
+    "overall_result",
+    "checklist_steps",
+    "step",
+    "result",
+    "no_step_results",
+    "date",
+    "signature",
+    "verification_code",
+    "verification_note",
+];
+
+#[derive(Clone, Debug)]
+pub struct CertificateStep {
+    pub position: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub result: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Certificate for a single recorded check, built from the checklist snapshot
+/// taken when the check was performed.
+#[derive(Clone, Debug)]
+pub struct CheckCertificate {
+    pub title: String,
+    pub generated_at: String,
+    pub report_id: String,
+    pub verify_url: String,
+    pub verification_code: String,
+    /// Name of the inspected item.
+    pub subject: String,
+    /// Item, kind, test standard and metadata of the checked item.
+    pub item: Vec<ReportField>,
+    pub checklist: String,
+    pub checklist_description: Option<String>,
+    pub checked_at: String,
+    pub inspector: Option<String>,
+    pub overall_result: Option<String>,
+    pub passed: bool,
+    pub notes: Option<String>,
+    pub steps: Vec<CertificateStep>,
+    /// Captured signature as PNG image, otherwise an empty signature line is
+    /// printed.
+    pub signature: Option<Vec<u8>>,
+}
+
+impl CheckCertificate {
+    /// Certificate with made-up data for previewing templates.
+    #[must_use]
+    pub fn sample(translator: &Translator) -> Self {
+        let date = sample_datetime();
+        Self {
+            title: translator.t("report-check_certificate-title"),
+            generated_at: translator.format_generated_at(date.to_utc()),
+            report_id: "RPT-0000-0000-0000-0000".to_string(),
+            verify_url: "https://example.com/verify/RPT-0000-0000-0000-0000".to_string(),
+            verification_code: "0000-0000-0000-0000-0000".to_string(),
+            subject: "Harness A".to_string(),
+            item: vec![
+                ReportField {
+                    label: translator.t("report-inventory_item"),
+                    value: Some("Harness A".to_string()),
+                },
+                ReportField {
+                    label: translator.t("report-test_standard"),
+                    value: Some("EN 358".to_string()),
+                },
+            ],
+            checklist: "Annual inspection".to_string(),
+            checklist_description: None,
+            checked_at: translator.format_datetime(date),
+            inspector: Some("Jane Doe".to_string()),
+            overall_result: Some(translator.code("RESULT_OK")),
+            passed: true,
+            notes: None,
+            steps: vec![CertificateStep {
+                position: 1,
+                name: "Visual inspection".to_string(),
+                description: None,
+                result: Some(translator.code("RESULT_OK")),
+                notes: None,
+            }],
+            signature: None,
+        }
+    }
+}
+
+/// Renders the check certificate as a PDF, with `template` instead of the
+/// built-in template if given.
+///
+/// # Errors
+///
+/// When the template fails to compile
+pub fn render_pdf(
+    certificate: &CheckCertificate,
+    translator: &Translator,
+    template: Option<&CustomTemplate>,
+) -> Result<Vec<u8>> {
+    render_template_pdf(
+        REPORT_TEMPLATE_PATH,
+        build_input(certificate, translator),
+        template,
+    )
+}
+
+/// Renders `template` with sample data.
+///
+/// # Errors
+///
+/// The Typst diagnostics when the template fails to compile
+pub fn preview_pdf(
+    template: &CustomTemplate,
+    translator: &Translator,
+) -> std::result::Result<Vec<u8>, Vec<TemplateDiagnostic>> {
+    compile_custom_template(
+        template,
+        build_input(&CheckCertificate::sample(translator), translator),
+    )
+}
+
+fn build_input(certificate: &CheckCertificate, translator: &Translator) -> Dict {
+    let mut dict = Dict::new();
+    insert_labels(&mut dict, "labels", translator, LABELS);
+    insert_value(&mut dict, "title", certificate.title.clone());
+    insert_value(&mut dict, "generated_at", certificate.generated_at.clone());
+    insert_value(&mut dict, "report_id", certificate.report_id.clone());
+    insert_value(&mut dict, "verify_url", certificate.verify_url.clone());
+    insert_value(
+        &mut dict,
+        "verification_code",
+        certificate.verification_code.clone(),
+    );
+    insert_value(
+        &mut dict,
+        "item",
+        certificate
+            .item
+            .iter()
+            .map(|field| {
+                let mut dict = Dict::new();
+                insert_value(&mut dict, "label", field.label.clone());
+                insert_value(&mut dict, "value", field.value.clone());
+                dict.into_value()
+            })
+            .collect::<Array>(),
+    );
+    insert_value(&mut dict, "checklist", certificate.checklist.clone());
+    insert_value(
+        &mut dict,
+        "checklist_description",
+        certificate.checklist_description.clone(),
+    );
+    insert_value(&mut dict, "checked_at", certificate.checked_at.clone());
+    insert_value(&mut dict, "inspector", certificate.inspector.clone());
+    insert_value(
+        &mut dict,
+        "overall_result",
+        certificate.overall_result.clone(),
+    );
+    insert_value(&mut dict, "passed", certificate.passed);
+    insert_value(&mut dict, "notes", certificate.notes.clone());
+    insert_value(
+        &mut dict,
+        "steps",
+        certificate
+            .steps
+            .iter()
+            .map(|step| step_dict(step).into_value())
+            .collect::<Array>(),
+    );
+    insert_value(
+        &mut dict,
+        "signature",
+        certificate.signature.clone().map(Bytes::new),
+    );
+    dict
+}
+
+fn step_dict(step: &CertificateStep) -> Dict {
+    let mut dict = Dict::new();
+    insert_value(&mut dict, "position", step.position);
+    insert_value(&mut dict, "name", step.name.clone());
+    insert_value(&mut dict, "description", step.description.clone());
+    insert_value(&mut dict, "result", step.result.clone());
+    insert_value(&mut dict, "notes", step.notes.clone());
+    dict
+}
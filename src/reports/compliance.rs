@@ -0,0 +1,338 @@
+use loco_rs::Result;
+use serde::{Deserialize, Serialize};
+use typst::foundations::{Array, Dict, IntoValue};
+
+use super::{
+    compile_custom_template, insert_labels, insert_value, render_template_pdf, sample_datetime,
+    single_item_history::ReportField, CustomTemplate, TemplateDiagnostic,
+};
+use crate::i18n::Translator;
+
+const REPORT_TEMPLATE_PATH: &str = "assets/reports/compliance_main.typ";
+/// Source of the built-in main file, the starting point of custom templates.
+pub const BUILTIN_TEMPLATE: &str = include_str!("../../assets/reports/compliance_main.typ");
+const LABELS: &[&str] = &[
+    "generated_at",
+    "report_id",
+    "verify_at",
+    "summary",
+    "by_item_kind",
+    "items",
+    "no_items",
+    "item",
+    "serial_number",
+    "item_kind",
+    "location",
+    "last_checked",
+    "result",
+    "next_due",
+    "status",
+    "total",
+    "compliant",
+    "overdue",
+    "failed",
+    "expired",
+    "compliance_rate",
+    "checks_in_period",
+];
+
+/// Compliance of one item at the report's reference date, in order of
+/// precedence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceStatus {
+    /// The item's expiry date has passed.
+    Expired,
+    /// The last check did not end with an OK result.
+    Failed,
+    /// The next check is past due.
+    Overdue,
+    Compliant,
+}
+
+impl ComplianceStatus {
+    pub const ALL: [Self; 4] = [Self::Expired, Self::Failed, Self::Overdue, Self::Compliant];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Expired => "expired",
+            Self::Failed => "failed",
+            Self::Overdue => "overdue",
+            Self::Compliant => "compliant",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ComplianceItem {
+    pub name: String,
+    pub serial_number: Option<String>,
+    pub item_kind: Option<String>,
+    pub location: Option<String>,
+    pub last_checked_at: Option<String>,
+    pub last_result: Option<String>,
+    pub checks_in_period: u64,
+    pub next_due: Option<String>,
+    pub status: ComplianceStatus,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ComplianceCounts {
+    pub total: u64,
+    pub compliant: u64,
+    pub overdue: u64,
+    pub failed: u64,
+    pub expired: u64,
+    pub checks_in_period: u64,
+}
+
+impl ComplianceCounts {
+    pub fn add(&mut self, item: &ComplianceItem) {
+        self.total += 1;
+        self.checks_in_period += item.checks_in_period;
+        match item.status {
+            ComplianceStatus::Compliant => self.compliant += 1,
+            ComplianceStatus::Overdue => self.overdue += 1,
+            ComplianceStatus::Failed => self.failed += 1,
+            ComplianceStatus::Expired => self.expired += 1,
+        }
+    }
+
+    /// Share of compliant items in percent, rounded to one decimal.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn compliance_rate(&self) -> Option<f64> {
+        (self.total > 0)
+            .then(|| (self.compliant as f64 * 1000.0 / self.total as f64).round() / 10.0)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ComplianceKindSummary {
+    pub item_kind: String,
+    pub counts: ComplianceCounts,
+}
+
+#[derive(Clone, Debug)]
+pub struct ComplianceReport {
+    pub title: String,
+    pub generated_at: String,
+    pub report_id: String,
+    pub verify_url: String,
+    /// Human readable description of the applied filters.
+    pub filters: Vec<ReportField>,
+    pub summary: ComplianceCounts,
+    pub kinds: Vec<ComplianceKindSummary>,
+    pub items: Vec<ComplianceItem>,
+}
+
+impl ComplianceReport {
+    /// Report with made-up data for previewing templates.
+    #[must_use]
+    pub fn sample(translator: &Translator) -> Self {
+        let date = sample_datetime();
+        let items = vec![
+            ComplianceItem {
+                name: "Harness A".to_string(),
+                serial_number: Some("SN-12345".to_string()),
+                item_kind: Some("Safety Harness".to_string()),
+                location: Some("Station 1".to_string()),
+                last_checked_at: Some(translator.format_datetime(date)),
+                last_result: Some(translator.code("RESULT_OK")),
+                checks_in_period: 1,
+                next_due: Some(translator.format_date(&date)),
+                status: ComplianceStatus::Compliant,
+            },
+            ComplianceItem {
+                name: "Harness B".to_string(),
+                serial_number: None,
+                item_kind: Some("Safety Harness".to_string()),
+                location: Some("Station 2".to_string()),
+                last_checked_at: Some(translator.format_datetime(date)),
+                last_result: Some(translator.code("RESULT_FAIL")),
+                checks_in_period: 1,
+                next_due: Some(translator.format_date(&date)),
+                status: ComplianceStatus::Failed,
+            },
+        ];
+        let mut summary = ComplianceCounts::default();
+        for item in &items {
+            summary.add(item);
+        }
+
+        Self {
+            title: translator.t("report-compliance-title"),
+            generated_at: translator.format_generated_at(date.to_utc()),
+            report_id: "RPT-0000-0000-0000-0000".to_string(),
+            verify_url: "https://example.com/verify/RPT-0000-0000-0000-0000".to_string(),
+            filters: vec![ReportField {
+                label: translator.t("report-location"),
+                value: Some("Station".to_string()),
+            }],
+            kinds: vec![ComplianceKindSummary {
+                item_kind: "Safety Harness".to_string(),
+                counts: summary.clone(),
+            }],
+            summary,
+            items,
+        }
+    }
+}
+
+/// Renders the compliance report as a PDF, with `template` instead of the
+/// built-in template if given.
+///
+/// # Errors
+///
+/// When the template fails to compile
+pub fn render_pdf(
+    report: &ComplianceReport,
+    translator: &Translator,
+    template: Option<&CustomTemplate>,
+) -> Result<Vec<u8>> {
+    render_template_pdf(
+        REPORT_TEMPLATE_PATH,
+        build_input(report, translator),
+        template,
+    )
+}
+
+/// Renders `template` with sample data.
+///
+/// # Errors
+///
+/// The Typst diagnostics when the template fails to compile
+pub fn preview_pdf(
+    template: &CustomTemplate,
+    translator: &Translator,
+) -> std::result::Result<Vec<u8>, Vec<TemplateDiagnostic>> {
+    compile_custom_template(
+        template,
+        build_input(&ComplianceReport::sample(translator), translator),
+    )
+}
+
+fn build_input(report: &ComplianceReport, translator: &Translator) -> Dict {
+    let mut dict = Dict::new();
+    insert_labels(&mut dict, "labels", translator, LABELS);
+    insert_value(&mut dict, "title", report.title.clone());
+    insert_value(&mut dict, "generated_at", report.generated_at.clone());
+    insert_value(&mut dict, "report_id", report.report_id.clone());
+    insert_value(&mut dict, "verify_url", report.verify_url.clone());
+    insert_value(
+        &mut dict,
+        "filters",
+        report
+            .filters
+            .iter()
+            .map(|field| {
+                let mut dict = Dict::new();
+                insert_value(&mut dict, "label", field.label.clone());
+                insert_value(&mut dict, "value", field.value.clone());
+                dict.into_value()
+            })
+            .collect::<Array>(),
+    );
+    insert_value(&mut dict, "summary", counts_dict(&report.summary));
+    insert_value(
+        &mut dict,
+        "kinds",
+        report
+            .kinds
+            .iter()
+            .map(|kind| {
+                let mut dict = counts_dict(&kind.counts);
+                insert_value(&mut dict, "item_kind", kind.item_kind.clone());
+                dict.into_value()
+            })
+            .collect::<Array>(),
+    );
+    insert_value(
+        &mut dict,
+        "items",
+        report
+            .items
+            .iter()
+            .map(|item| item_dict(item).into_value())
+            .collect::<Array>(),
+    );
+    dict
+}
+
+fn counts_dict(counts: &ComplianceCounts) -> Dict {
+    let mut dict = Dict::new();
+    insert_value(&mut dict, "total", counts.total.to_string());
+    insert_value(&mut dict, "compliant", counts.compliant.to_string());
+    insert_value(&mut dict, "overdue", counts.overdue.to_string());
+    insert_value(&mut dict, "failed", counts.failed.to_string());
+    insert_value(&mut dict, "expired", counts.expired.to_string());
+    insert_value(
+        &mut dict,
+        "checks_in_period",
+        counts.checks_in_period.to_string(),
+    );
+    insert_value(
+        &mut dict,
+        "compliance_rate",
+        counts.compliance_rate().map(|rate| format!("{rate:.1} %")),
+    );
+    dict
+}
+
+fn item_dict(item: &ComplianceItem) -> Dict {
+    let mut dict = Dict::new();
+    insert_value(&mut dict, "name", item.name.clone());
+    insert_value(&mut dict, "serial_number", item.serial_number.clone());
+    insert_value(&mut dict, "item_kind", item.item_kind.clone());
+    insert_value(&mut dict, "location", item.location.clone());
+    insert_value(&mut dict, "last_checked_at", item.last_checked_at.clone());
+    insert_value(&mut dict, "last_result", item.last_result.clone());
+    insert_value(
+        &mut dict,
+        "checks_in_period",
+        item.checks_in_period.to_string(),
+    );
+    insert_value(&mut dict, "next_due", item.next_due.clone());
+    insert_value(&mut dict, "status", item.status.as_str());
+    dict
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(status: ComplianceStatus, checks_in_period: u64) -> ComplianceItem {
+        ComplianceItem {
+            name: "Harness".to_string(),
+            serial_number: None,
+            item_kind: None,
+            location: None,
+            last_checked_at: None,
+            last_result: None,
+            checks_in_period,
+            next_due: None,
+            status,
+        }
+    }
+
+    #[test]
+    fn counts_statuses_and_compliance_rate() {
+        let mut counts = ComplianceCounts::default();
+        assert_eq!(counts.compliance_rate(), None);
+
+        for status in [
+            ComplianceStatus::Compliant,
+            ComplianceStatus::Compliant,
+            ComplianceStatus::Overdue,
+        ] {
+            counts.add(&item(status, 2));
+        }
+
+        assert_eq!(counts.total, 3);
+        assert_eq!(counts.compliant, 2);
+        assert_eq!(counts.overdue, 1);
+        assert_eq!(counts.checks_in_period, 6);
+        assert_eq!(counts.compliance_rate(), Some(66.7));
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt::Write as _;
+
+use datamatrix::{DataMatrix, SymbolList};
+use loco_rs::{Error, Result};
+use qrcode::QrCode;
+use serde::{Deserialize, Serialize};
+use typst::foundations::{Array, Bytes, Dict, IntoValue};
+
+use super::{insert_labels, insert_value, render_template_pdf};
+use crate::i18n::Translator;
+
+const LABELS_TEMPLATE_PATH: &str = "assets/reports/labels_main.typ";
+const CAPTIONS: &[&str] = &["label_serial_number", "label_next_due"];
+
+/// Label sheet layouts, named after the Avery A4 products they match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelLayout {
+    /// 21 labels per sheet, 63.5 × 38.1 mm.
+    #[default]
+    AveryL7160,
+    /// 14 labels per sheet, 99.1 × 38.1 mm.
+    AveryL7163,
+    /// 8 labels per sheet, 99.1 × 67.7 mm.
+    AveryL7165,
+    /// 65 labels per sheet, 38.1 × 21.2 mm.
+    AveryL7651,
+}
+
+/// Position and size of the labels on an A4 sheet, in millimetres.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LabelGeometry {
+    pub columns: u32,
+    pub rows: u32,
+    pub width: f64,
+    pub height: f64,
+    pub margin_left: f64,
+    pub margin_top: f64,
+    pub pitch_x: f64,
+    pub pitch_y: f64,
+}
+
+impl LabelGeometry {
+    #[must_use]
+    pub fn labels_per_sheet(&self) -> u32 {
+        self.columns * self.rows
+    }
+}
+
+impl LabelLayout {
+    pub const ALL: [Self; 4] = [
+        Self::AveryL7160,
+        Self::AveryL7163,
+        Self::AveryL7165,
+        Self::AveryL7651,
+    ];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AveryL7160 => "avery_l7160",
+            Self::AveryL7163 => "avery_l7163",
+            Self::AveryL7165 => "avery_l7165",
+            Self::AveryL7651 => "avery_l7651",
+        }
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::AveryL7160 => "Avery L7160 (3 × 7, 63.5 × 38.1 mm)",
+            Self::AveryL7163 => "Avery L7163 (2 × 7, 99.1 × 38.1 mm)",
+            Self::AveryL7165 => "Avery L7165 (2 × 4, 99.1 × 67.7 mm)",
+            Self::AveryL7651 => "Avery L7651 (5 × 13, 38.1 × 21.2 mm)",
+        }
+    }
+
+    #[must_use]
+    pub fn geometry(self) -> LabelGeometry {
+        match self {
+            Self::AveryL7160 => LabelGeometry {
+                columns: 3,
+                rows: 7,
+                width: 63.5,
+                height: 38.1,
+                margin_left: 7.25,
+                margin_top: 15.15,
+                pitch_x: 66.04,
+                pitch_y: 38.1,
+            },
+            Self::AveryL7163 => LabelGeometry {
+                columns: 2,
+                rows: 7,
+                width: 99.1,
+                height: 38.1,
+                margin_left: 4.65,
+                margin_top: 15.15,
+                pitch_x: 101.6,
+                pitch_y: 38.1,
+            },
+            Self::AveryL7165 => LabelGeometry {
+                columns: 2,
+                rows: 4,
+                width: 99.1,
+                height: 67.7,
+                margin_left: 4.65,
+                margin_top: 13.1,
+                pitch_x: 101.6,
+                pitch_y: 67.7,
+            },
+            Self::AveryL7651 => LabelGeometry {
+                columns: 5,
+                rows: 13,
+                width: 38.1,
+                height: 21.2,
+                margin_left: 4.75,
+                margin_top: 10.7,
+                pitch_x: 40.64,
+                pitch_y: 21.2,
+            },
+        }
+    }
+}
+
+/// The 2D symbology printed on each label.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelCode {
+    #[default]
+    Qr,
+    DataMatrix,
+}
+
+impl LabelCode {
+    pub const ALL: [Self; 2] = [Self::Qr, Self::DataMatrix];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Qr => "qr",
+            Self::DataMatrix => "data_matrix",
+        }
+    }
+
+    /// Encodes `data` and returns the symbol as an SVG document.
+    ///
+    /// # Errors
+    ///
+    /// When `data` does not fit into the largest symbol size
+    pub fn to_svg(self, data: &str) -> Result<String> {
+        match self {
+            Self::Qr => {
+                let code = QrCode::new(data.as_bytes()).map_err(|error| {
+                    Error::BadRequest(format!("cannot encode QR code: {error}"))
+                })?;
+                let width = code.width();
+                let dark = code
+                    .to_colors()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, color)| *color == qrcode::Color::Dark)
+                    .map(|(index, _)| (index % width, index / width))
+                    .collect::<Vec<_>>();
+                Ok(modules_svg(width, width, 4, &dark))
+            }
+            Self::DataMatrix => {
+                let code =
+                    DataMatrix::encode_str(data, SymbolList::default()).map_err(|error| {
+                        Error::BadRequest(format!("cannot encode DataMatrix code: {error:?}"))
+                    })?;
+                let bitmap = code.bitmap();
+                let dark = bitmap.pixels().collect::<Vec<_>>();
+                Ok(modules_svg(bitmap.width(), bitmap.height(), 1, &dark))
+            }
+        }
+    }
+}
+
+/// What the code on each label encodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelContent {
+    /// The item's mobile check page, so any phone camera can open it.
+    #[default]
+    Url,
+    /// The serial number, for handheld scanners typing into the search box.
+    /// Items without a serial number fall back to their URL.
+    Serial,
+}
+
+impl LabelContent {
+    pub const ALL: [Self; 2] = [Self::Url, Self::Serial];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Url => "url",
+            Self::Serial => "serial",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LabelItem {
+    pub name: String,
+    pub serial_number: Option<String>,
+    pub next_due: Option<String>,
+    /// Data encoded in the label's code.
+    pub code_data: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct LabelSheet {
+    pub layout: LabelLayout,
+    pub code: LabelCode,
+    /// Number of labels to leave empty at the start of the first sheet, so
+    /// partially used sheets can be fed again.
+    pub skip: u32,
+    pub labels: Vec<LabelItem>,
+}
+
+/// Renders the label sheet as a PDF.
+///
+/// # Errors
+///
+/// When a code cannot be encoded or the template fails to compile
+pub fn render_pdf(sheet: &LabelSheet, translator: &Translator) -> Result<Vec<u8>> {
+    render_template_pdf(LABELS_TEMPLATE_PATH, build_input(sheet, translator)?, None)
+}
+
+fn modules_svg(width: usize, height: usize, quiet_zone: usize, dark: &[(usize, usize)]) -> String {
+    let mut path = String::new();
+    for (x, y) in dark {
+        let _ = write!(path, "M{} {}h1v1h-1z", x + quiet_zone, y + quiet_zone);
+    }
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" shape-rendering="crispEdges"><rect width="100%" height="100%" fill="#fff"/><path d="{path}" fill="#000"/></svg>"##,
+        width + 2 * quiet_zone,
+        height + 2 * quiet_zone,
+    )
+}
+
+fn build_input(sheet: &LabelSheet, translator: &Translator) -> Result<Dict> {
+    let geometry = sheet.layout.geometry();
+    let mut layout = Dict::new();
+    insert_value(&mut layout, "columns", i64::from(geometry.columns));
+    insert_value(&mut layout, "rows", i64::from(geometry.rows));
+    insert_value(&mut layout, "width", geometry.width);
+    insert_value(&mut layout, "height", geometry.height);
+    insert_value(&mut layout, "margin_left", geometry.margin_left);
+    insert_value(&mut layout, "margin_top", geometry.margin_top);
+    insert_value(&mut layout, "pitch_x", geometry.pitch_x);
+    insert_value(&mut layout, "pitch_y", geometry.pitch_y);
+
+    let labels = sheet
+        .labels
+        .iter()
+        .map(|label| label_dict(label, sheet.code).map(IntoValue::into_value))
+        .collect::<Result<Array>>()?;
+
+    let mut dict = Dict::new();
+    insert_labels(&mut dict, "captions", translator, CAPTIONS);
+    insert_value(&mut dict, "layout", layout);
+    insert_value(&mut dict, "skip", i64::from(sheet.skip));
+    insert_value(&mut dict, "labels", labels);
+    Ok(dict)
+}
+
+fn label_dict(label: &LabelItem, code: LabelCode) -> Result<Dict> {
+    let mut dict = Dict::new();
+    insert_value(&mut dict, "name", label.name.clone());
+    insert_value(&mut dict, "serial_number", label.serial_number.clone());
+    insert_value(&mut dict, "next_due", label.next_due.clone());
+    insert_value(
+        &mut dict,
+        "code",
+        Bytes::from_string(code.to_svg(&label.code_data)?),
+    );
+    Ok(dict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A4_WIDTH: f64 = 210.0;
+    const A4_HEIGHT: f64 = 297.0;
+
+    #[test]
+    fn layouts_fit_on_a4() {
+        for layout in LabelLayout::ALL {
+            let geometry = layout.geometry();
+            let right = geometry.margin_left
+                + f64::from(geometry.columns - 1) * geometry.pitch_x
+                + geometry.width;
+            let bottom = geometry.margin_top
+                + f64::from(geometry.rows - 1) * geometry.pitch_y
+                + geometry.height;
+            assert!(right <= A4_WIDTH, "{layout:?} is too wide");
+            assert!(bottom <= A4_HEIGHT, "{layout:?} is too tall");
+            assert!(
+                geometry.pitch_x >= geometry.width,
+                "{layout:?} labels overlap"
+            );
+        }
+    }
+
+    #[test]
+    fn encodes_both_symbologies_as_svg() {
+        for code in LabelCode::ALL {
+            let svg = code.to_svg("https://example.com/inventory/item/1").unwrap();
+            assert!(svg.starts_with("<svg"), "{code:?}");
+            assert!(svg.contains("h1v1h-1z"), "{code:?}");
+        }
+    }
+}
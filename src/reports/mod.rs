@@ -1 +1,288 @@
+pub mod check_certificate;
+pub mod compliance;
+pub mod labels;
 pub mod single_item_history;
+
+use std::{path::PathBuf, sync::LazyLock};
+
+use crate::{i18n::Translator, models::generated_reports::ReportKind};
+use chrono::{DateTime, FixedOffset};
+use loco_rs::{Error, Result};
+use serde::Serialize;
+use typst::{
+    diag::{FileError, FileResult, SourceDiagnostic},
+    foundations::{Bytes, Datetime, Dict, IntoValue},
+    layout::PagedDocument,
+    syntax::{FileId, Source, VirtualPath},
+    text::{Font, FontBook},
+    utils::LazyHash,
+    Library, World,
+};
+use typst_as_lib::{typst_kit_options::TypstKitFontOptions, TypstEngine, TypstWorld};
+
+/// Directory of the built-in templates, the only files a custom template may
+/// read besides its logo.
+const TEMPLATE_DIR: &str = "assets/reports";
+/// Virtual path of a custom main file. It lives next to the built-in
+/// templates so their relative imports keep working.
+const CUSTOM_MAIN_PATH: &str = "/assets/reports/custom_main.typ";
+/// Virtual path of the logo uploaded with a custom template, also passed to
+/// the template as `logo`.
+pub const CUSTOM_LOGO_PATH: &str = "/custom/logo";
+
+static REPORT_ENGINE: LazyLock<TypstEngine> = LazyLock::new(|| {
+    TypstEngine::builder()
+        .with_file_system_resolver(PathBuf::from(env!("CARGO_MANIFEST_DIR")))
+        .search_fonts_with(
+            TypstKitFontOptions::default()
+                .include_system_fonts(true)
+                .include_embedded_fonts(true),
+        )
+        .build()
+});
+
+/// Main file uploaded by an admin that replaces the built-in one of a report,
+/// with an optional logo image.
+#[derive(Clone, Debug, Default)]
+pub struct CustomTemplate {
+    pub source: String,
+    pub logo: Option<Vec<u8>>,
+}
+
+/// An error or warning reported by Typst while compiling a template.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TemplateDiagnostic {
+    pub message: String,
+    /// Path of the file the problem is located in, `None` for the custom main
+    /// file.
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub hints: Vec<String>,
+}
+
+/// The engine's world with the main file replaced by a custom template.
+struct CustomTemplateWorld<'a> {
+    base: TypstWorld<'a>,
+    main: Source,
+    logo: Option<Bytes>,
+}
+
+impl CustomTemplateWorld<'_> {
+    fn logo_id() -> FileId {
+        FileId::new(None, VirtualPath::new(CUSTOM_LOGO_PATH))
+    }
+
+    /// Rejects every file outside of [`TEMPLATE_DIR`], so an uploaded
+    /// template cannot read the configuration or the database, and packages,
+    /// which would be downloaded.
+    fn check_access(id: FileId) -> FileResult<()> {
+        let path = id.vpath().as_rootless_path();
+        if id.package().is_none() && path.starts_with(TEMPLATE_DIR) {
+            Ok(())
+        } else {
+            Err(FileError::AccessDenied)
+        }
+    }
+
+    fn diagnostic(&self, diagnostic: &SourceDiagnostic) -> TemplateDiagnostic {
+        let location = diagnostic.span.id().and_then(|id| {
+            let source = self.source(id).ok()?;
+            let range = source.range(diagnostic.span)?;
+            let (line, column) = source.lines().byte_to_line_column(range.start)?;
+            let file =
+                (id != self.main.id()).then(|| id.vpath().as_rootless_path().display().to_string());
+            Some((file, line + 1, column + 1))
+        });
+        let (file, line, column) = match location {
+            Some((file, line, column)) => (file, Some(line), Some(column)),
+            None => (None, None, None),
+        };
+        TemplateDiagnostic {
+            message: diagnostic.message.to_string(),
+            file,
+            line,
+            column,
+            hints: diagnostic.hints.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl World for CustomTemplateWorld<'_> {
+    fn library(&self) -> &LazyHash<Library> {
+        self.base.library()
+    }
+
+    fn book(&self) -> &LazyHash<FontBook> {
+        self.base.book()
+    }
+
+    fn main(&self) -> FileId {
+        self.main.id()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        if id == self.main.id() {
+            Ok(self.main.clone())
+        } else {
+            Self::check_access(id)?;
+            self.base.source(id)
+        }
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        if id == Self::logo_id() {
+            self.logo
+                .clone()
+                .ok_or_else(|| FileError::NotFound(CUSTOM_LOGO_PATH.into()))
+        } else {
+            Self::check_access(id)?;
+            self.base.file(id)
+        }
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.base.font(index)
+    }
+
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        self.base.today(offset)
+    }
+}
+
+/// Compiles `template` with `input` available as `sys.inputs` and exports
+/// the result as PDF. Errors are returned as Typst diagnostics so they can
+/// be shown to the template author.
+///
+/// # Errors
+///
+/// When the template fails to compile
+pub fn compile_custom_template(
+    template: &CustomTemplate,
+    mut input: Dict,
+) -> std::result::Result<Vec<u8>, Vec<TemplateDiagnostic>> {
+    if template.logo.is_some() {
+        insert_value(&mut input, "logo", CUSTOM_LOGO_PATH);
+    }
+    let main_id = FileId::new(None, VirtualPath::new(CUSTOM_MAIN_PATH));
+    let base = REPORT_ENGINE
+        .world_builder(main_id)
+        .with_inputs(input)
+        .build()
+        .map_err(|error| vec![plain_diagnostic(format!("{error:?}"))])?;
+    let world = CustomTemplateWorld {
+        base,
+        main: Source::new(main_id, template.source.clone()),
+        logo: template.logo.clone().map(Bytes::new),
+    };
+
+    let warned = typst::compile::<PagedDocument>(&world);
+    typst::comemo::evict(0);
+    for warning in &warned.warnings {
+        tracing::debug!(?warning, "typst custom template warning");
+    }
+    let document = warned.output.map_err(|errors| {
+        errors
+            .iter()
+            .map(|error| world.diagnostic(error))
+            .collect::<Vec<_>>()
+    })?;
+
+    typst_pdf::pdf(&document, &typst_pdf::PdfOptions::default())
+        .map_err(|errors| errors.iter().map(|error| world.diagnostic(error)).collect())
+}
+
+fn plain_diagnostic(message: String) -> TemplateDiagnostic {
+    TemplateDiagnostic {
+        message,
+        file: None,
+        line: None,
+        column: None,
+        hints: Vec::new(),
+    }
+}
+
+/// Compiles the Typst template at `template_path` with `input` available as
+/// `sys.inputs` and exports the result as PDF. A `custom` template is used
+/// instead when given, falling back to the built-in one if it fails to
+/// compile.
+pub(crate) fn render_template_pdf(
+    template_path: &str,
+    input: Dict,
+    custom: Option<&CustomTemplate>,
+) -> Result<Vec<u8>> {
+    if let Some(custom) = custom {
+        match compile_custom_template(custom, input.clone()) {
+            Ok(pdf) => return Ok(pdf),
+            Err(diagnostics) => {
+                tracing::warn!(
+                    ?diagnostics,
+                    template_path,
+                    "custom report template failed, using the built-in template"
+                );
+            }
+        }
+    }
+
+    let warned = REPORT_ENGINE.compile_with_input::<_, _, PagedDocument>(template_path, input);
+
+    for warning in warned.warnings {
+        tracing::warn!(?warning, template_path, "typst report warning");
+    }
+
+    let document = warned
+        .output
+        .map_err(|error| Error::string(&format!("{error:?}")))?;
+
+    typst_pdf::pdf(&document, &typst_pdf::PdfOptions::default())
+        .map_err(|error| Error::string(&format!("{error:?}")))
+}
+
+/// Source of the built-in main file of reports of `kind`.
+#[must_use]
+pub fn builtin_template(kind: ReportKind) -> &'static str {
+    match kind {
+        ReportKind::ItemHistory => single_item_history::BUILTIN_TEMPLATE,
+        ReportKind::Compliance => compliance::BUILTIN_TEMPLATE,
+        ReportKind::CheckCertificate => check_certificate::BUILTIN_TEMPLATE,
+    }
+}
+
+/// Renders `template` as a report of `kind` with sample data.
+///
+/// # Errors
+///
+/// The Typst diagnostics when the template fails to compile
+pub fn preview_pdf(
+    kind: ReportKind,
+    template: &CustomTemplate,
+    translator: &Translator,
+) -> std::result::Result<Vec<u8>, Vec<TemplateDiagnostic>> {
+    match kind {
+        ReportKind::ItemHistory => single_item_history::preview_pdf(template, translator),
+        ReportKind::Compliance => compliance::preview_pdf(template, translator),
+        ReportKind::CheckCertificate => check_certificate::preview_pdf(template, translator),
+    }
+}
+
+/// Fixed date used in the sample data of template previews.
+pub(crate) fn sample_datetime() -> DateTime<FixedOffset> {
+    DateTime::from_timestamp(1_735_725_600, 0)
+        .unwrap_or_default()
+        .fixed_offset()
+}
+
+pub(crate) fn insert_value<T: IntoValue>(dict: &mut Dict, key: &str, value: T) {
+    dict.insert(key.into(), value.into_value());
+}
+
+/// Adds the language of `translator` as `lang` and the translation of
+/// `report-<name>` for each of `names` as dictionary `key` to `dict`.
+pub(crate) fn insert_labels(dict: &mut Dict, key: &str, translator: &Translator, names: &[&str]) {
+    insert_value(dict, "lang", translator.locale().language.as_str());
+    let mut labels = Dict::new();
+    for name in names {
+        insert_value(&mut labels, name, translator.t(&format!("report-{name}")));
+    }
+    insert_value(dict, key, labels);
+}
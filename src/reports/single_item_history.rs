@@ -1,25 +1,37 @@
-use std::{path::PathBuf, sync::LazyLock};
+use loco_rs::Result;
+use typst::foundations::{Array, Dict, IntoValue};
 
-use chrono::{DateTime, FixedOffset, Utc};
-use loco_rs::{Error, Result};
-use typst::{
-    foundations::{Array, Dict, IntoValue},
-    layout::PagedDocument,
+use super::{
+    compile_custom_template, insert_labels, insert_value, render_template_pdf, sample_datetime,
+    CustomTemplate, TemplateDiagnostic,
 };
-use typst_as_lib::{typst_kit_options::TypstKitFontOptions, TypstEngine};
+use crate::i18n::Translator;
 
 const REPORT_TEMPLATE_PATH: &str = "assets/reports/single_item_history_main.typ";
-
-static REPORT_ENGINE: LazyLock<TypstEngine> = LazyLock::new(|| {
-    TypstEngine::builder()
-        .with_file_system_resolver(PathBuf::from(env!("CARGO_MANIFEST_DIR")))
-        .search_fonts_with(
-            TypstKitFontOptions::default()
-                .include_system_fonts(true)
-                .include_embedded_fonts(true),
-        )
-        .build()
-});
+/// Source of the built-in main file, the starting point of custom templates.
+pub const BUILTIN_TEMPLATE: &str =
+    include_str!("../../assets/reports/single_item_history_main.typ");
+const LABELS: &[&str] = &[
+    "generated_at",
+    "report_id",
+    "verify_at",
+    "item_details",
+    "inventory_item",
+    "serial_number",
+    "item_kind",
+    "checklist",
+    "interval",
+    "last_checked",
+    "created_at",
+    "updated_at",
+    "expiry",
+    "check_history",
+    "no_checks",
+    "checked_at",
+    "checked_by",
+    "overall",
+    "notes",
+];
 
 #[derive(Clone, Debug)]
 pub struct ReportField {
@@ -62,81 +74,111 @@ pub struct SingleItemHistoryReport {
     pub title: String,
     pub generated_at: String,
     pub report_id: String,
+    pub verify_url: String,
     pub item: ReportItem,
     pub metadata: Vec<ReportField>,
     pub checks: Vec<ReportCheck>,
 }
 
-pub fn render_pdf(report: &SingleItemHistoryReport) -> Result<Vec<u8>> {
-    let warned = REPORT_ENGINE
-        .compile_with_input::<_, _, PagedDocument>(REPORT_TEMPLATE_PATH, build_input(report));
-
-    for warning in warned.warnings {
-        tracing::warn!(?warning, "typst report warning");
+impl SingleItemHistoryReport {
+    /// Report with made-up data for previewing templates.
+    #[must_use]
+    pub fn sample(translator: &Translator) -> Self {
+        let date = sample_datetime();
+        Self {
+            title: translator.t("report-item_history-title"),
+            generated_at: translator.format_generated_at(date.to_utc()),
+            report_id: "RPT-0000-0000-0000-0000".to_string(),
+            verify_url: "https://example.com/verify/RPT-0000-0000-0000-0000".to_string(),
+            item: ReportItem {
+                name: "Harness A".to_string(),
+                serial_number: Some("SN-12345".to_string()),
+                item_kind: Some("Safety Harness".to_string()),
+                checklist: Some("Annual inspection".to_string()),
+                interval: Some(translator.code("INTERVAL_YEARLY")),
+                created_at: translator.format_datetime(date),
+                updated_at: translator.format_datetime(date),
+                last_checked_at: Some(translator.format_datetime(date)),
+                expiry: None,
+            },
+            metadata: vec![ReportField {
+                label: "Location".to_string(),
+                value: Some("Station 1".to_string()),
+            }],
+            checks: vec![ReportCheck {
+                checked_at: translator.format_datetime(date),
+                checked_by: Some("Jane Doe".to_string()),
+                overall_result: Some(translator.code("RESULT_OK")),
+                notes: None,
+                steps: vec![ReportStep {
+                    position: 1,
+                    name: "Visual inspection".to_string(),
+                    result: Some(translator.code("RESULT_OK")),
+                    notes: None,
+                }],
+            }],
+        }
     }
-
-    let document = warned
-        .output
-        .map_err(|error| Error::string(&format!("{error:?}")))?;
-
-    typst_pdf::pdf(&document, &Default::default())
-        .map_err(|error| Error::string(&format!("{error:?}")))
 }
 
-pub fn format_timestamp(value: DateTime<FixedOffset>) -> String {
-    value.format("%Y-%m-%d %H:%M").to_string()
+/// Renders the item history report as a PDF, with `template` instead of the
+/// built-in template if given.
+///
+/// # Errors
+///
+/// When the template fails to compile
+pub fn render_pdf(
+    report: &SingleItemHistoryReport,
+    translator: &Translator,
+    template: Option<&CustomTemplate>,
+) -> Result<Vec<u8>> {
+    render_template_pdf(
+        REPORT_TEMPLATE_PATH,
+        build_input(report, translator),
+        template,
+    )
 }
 
-pub fn format_generated_at(value: DateTime<Utc>) -> String {
-    value.format("%Y-%m-%d %H:%M UTC").to_string()
+/// Renders `template` with sample data.
+///
+/// # Errors
+///
+/// The Typst diagnostics when the template fails to compile
+pub fn preview_pdf(
+    template: &CustomTemplate,
+    translator: &Translator,
+) -> std::result::Result<Vec<u8>, Vec<TemplateDiagnostic>> {
+    compile_custom_template(
+        template,
+        build_input(&SingleItemHistoryReport::sample(translator), translator),
+    )
 }
 
-pub fn humanize_code(code: &str) -> String {
-    let trimmed = code
-        .strip_prefix("RESULT_")
-        .or_else(|| code.strip_prefix("INTERVAL_"))
-        .unwrap_or(code);
-
-    trimmed
-        .split('_')
-        .filter(|part| !part.is_empty())
-        .map(|part| {
-            let mut chars = part.chars();
-            let Some(first) = chars.next() else {
-                return String::new();
-            };
-            let rest = chars.as_str().to_ascii_lowercase();
-            format!("{}{}", first.to_ascii_uppercase(), rest)
-        })
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
-fn build_input(report: &SingleItemHistoryReport) -> Dict {
+fn build_input(report: &SingleItemHistoryReport, translator: &Translator) -> Dict {
     let mut dict = Dict::new();
+    insert_labels(&mut dict, "labels", translator, LABELS);
     insert_value(&mut dict, "title", report.title.clone());
     insert_value(&mut dict, "generated_at", report.generated_at.clone());
     insert_value(&mut dict, "report_id", report.report_id.clone());
+    insert_value(&mut dict, "verify_url", report.verify_url.clone());
     insert_value(&mut dict, "item", item_dict(&report.item));
     insert_value(
         &mut dict,
         "metadata",
-        Array::from_iter(
-            report
-                .metadata
-                .iter()
-                .map(|field| field_dict(field).into_value()),
-        ),
+        report
+            .metadata
+            .iter()
+            .map(|field| field_dict(field).into_value())
+            .collect::<Array>(),
     );
     insert_value(
         &mut dict,
         "checks",
-        Array::from_iter(
-            report
-                .checks
-                .iter()
-                .map(|check| check_dict(check).into_value()),
-        ),
+        report
+            .checks
+            .iter()
+            .map(|check| check_dict(check).into_value())
+            .collect::<Array>(),
     );
     dict
 }
@@ -180,11 +222,11 @@ fn check_dict(check: &ReportCheck) -> Dict {
     insert_value(
         &mut dict,
         "steps",
-        Array::from_iter(check.steps.iter().map(|step| step_dict(step).into_value())),
+        check
+            .steps
+            .iter()
+            .map(|step| step_dict(step).into_value())
+            .collect::<Array>(),
     );
     dict
 }
-
-fn insert_value<T: IntoValue>(dict: &mut Dict, key: &str, value: T) {
-    dict.insert(key.into(), value.into_value());
-}
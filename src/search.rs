@@ -0,0 +1,496 @@
+//! Full-text search over inventory items, their metadata values and the
+//! notes of their checks.
+//!
+//! Every item and every check is one document in the `search_index` table,
+//! an `FTS5` table on `SQLite` and a table with a weighted `tsvector` on
+//! Postgres. The write paths keep the documents in sync through
+//! [`index_item`], [`index_check`], [`index_kind_items`] and [`remove`];
+//! [`reindex_all`] rebuilds the whole index, e.g. from the `search_reindex`
+//! task.
+use std::collections::HashMap;
+
+use loco_rs::prelude::*;
+use sea_orm::{
+    sea_query::{Alias, Expr, Order, Query, SelectStatement},
+    ConnectionTrait, DbBackend, FromQueryResult, QueryOrder,
+};
+use serde::Serialize;
+
+use crate::models::{
+    _entities::{
+        inventory_item_check_steps, inventory_item_checks, inventory_item_metadata_values,
+    },
+    inventory_item_kinds, inventory_items,
+    teams::ItemScope,
+};
+
+const TABLE: &str = "search_index";
+/// Most hits returned per entity type.
+pub const MAX_HITS: u64 = 20;
+/// Most words of a query that are searched for.
+const MAX_TERMS: usize = 8;
+/// Private use characters marking matches in highlighted text until it is
+/// escaped, so indexed text cannot inject markup.
+const MARK_START: char = '\u{e000}';
+const MARK_END: char = '\u{e001}';
+
+/// Kind of a search document. Results are grouped by it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchEntity {
+    Item,
+    Check,
+}
+
+impl SearchEntity {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Item => "item",
+            Self::Check => "check",
+        }
+    }
+
+    /// Row id of the document of the entity with `id`, unique across kinds.
+    fn key(self, id: i32) -> i64 {
+        let kind = match self {
+            Self::Item => 0,
+            Self::Check => 1,
+        };
+        i64::from(id) * 2 + kind
+    }
+}
+
+struct Document {
+    entity: SearchEntity,
+    entity_id: i32,
+    tenant_id: i32,
+    item_id: i32,
+    title: String,
+    body: String,
+}
+
+fn col(name: &str) -> Alias {
+    Alias::new(name)
+}
+
+/// Joins the non-empty `parts` into one line each, without match markers.
+fn document_text<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .replace([MARK_START, MARK_END], "")
+}
+
+async fn write_document<C: ConnectionTrait>(db: &C, document: Document) -> Result<()> {
+    let key = document.entity.key(document.entity_id);
+    delete_key(db, key).await?;
+    let insert = Query::insert()
+        .into_table(col(TABLE))
+        .columns([
+            col("rowid"),
+            col("entity"),
+            col("entity_id"),
+            col("tenant_id"),
+            col("item_id"),
+            col("title"),
+            col("body"),
+        ])
+        .values_panic([
+            key.into(),
+            document.entity.as_str().into(),
+            document.entity_id.into(),
+            document.tenant_id.into(),
+            document.item_id.into(),
+            document.title.replace([MARK_START, MARK_END], "").into(),
+            document.body.into(),
+        ])
+        .to_owned();
+    db.execute(db.get_database_backend().build(&insert)).await?;
+    Ok(())
+}
+
+async fn delete_key<C: ConnectionTrait>(db: &C, key: i64) -> Result<()> {
+    let delete = Query::delete()
+        .from_table(col(TABLE))
+        .and_where(Expr::col(col("rowid")).eq(key))
+        .to_owned();
+    db.execute(db.get_database_backend().build(&delete)).await?;
+    Ok(())
+}
+
+/// Removes the document of the `entity` with `id`, e.g. after deleting it.
+///
+/// # Errors
+///
+/// When the database fails.
+pub async fn remove<C: ConnectionTrait>(db: &C, entity: SearchEntity, id: i32) -> Result<()> {
+    delete_key(db, entity.key(id)).await
+}
+
+/// Check documents of `checks` of `item`: the check notes followed by the
+/// notes of every step.
+async fn write_check_documents<C: ConnectionTrait>(
+    db: &C,
+    item: &inventory_items::Model,
+    checks: Vec<inventory_item_checks::Model>,
+) -> Result<()> {
+    let check_ids = checks.iter().map(|check| check.id).collect::<Vec<_>>();
+    let mut step_notes = HashMap::<i32, Vec<String>>::new();
+    for step in inventory_item_check_steps::Entity::find()
+        .filter(inventory_item_check_steps::Column::InventoryItemCheckId.is_in(check_ids))
+        .order_by_asc(inventory_item_check_steps::Column::Id)
+        .all(db)
+        .await?
+    {
+        if let Some(notes) = step.notes {
+            step_notes
+                .entry(step.inventory_item_check_id)
+                .or_default()
+                .push(notes);
+        }
+    }
+    for check in checks {
+        let steps = step_notes.remove(&check.id).unwrap_or_default();
+        let body = document_text(
+            check
+                .notes
+                .as_deref()
+                .into_iter()
+                .chain(steps.iter().map(String::as_str)),
+        );
+        write_document(
+            db,
+            Document {
+                entity: SearchEntity::Check,
+                entity_id: check.id,
+                tenant_id: item.tenant_id,
+                item_id: item.id,
+                title: item.name.clone(),
+                body,
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Rebuilds the document of the item with `item_id` from its name, serial
+/// number, kind and metadata values, and those of its checks, which carry
+/// the item name. Does nothing for unknown items.
+///
+/// # Errors
+///
+/// When the database fails.
+pub async fn index_item<C: ConnectionTrait>(db: &C, item_id: i32) -> Result<()> {
+    let Some(item) = inventory_items::Entity::find_by_id(item_id).one(db).await? else {
+        return Ok(());
+    };
+    let kind = inventory_item_kinds::Entity::find_by_id(item.inventory_item_kind_id)
+        .one(db)
+        .await?;
+    let values = inventory_item_metadata_values::Entity::find()
+        .filter(inventory_item_metadata_values::Column::InventoryItemId.eq(item.id))
+        .order_by_asc(inventory_item_metadata_values::Column::InventoryItemKindMetadataFieldId)
+        .all(db)
+        .await?;
+    let body = document_text(
+        item.serial_number
+            .as_deref()
+            .into_iter()
+            .chain(kind.as_ref().map(|kind| kind.name.as_str()))
+            .chain(values.iter().map(|value| value.value.as_str())),
+    );
+    write_document(
+        db,
+        Document {
+            entity: SearchEntity::Item,
+            entity_id: item.id,
+            tenant_id: item.tenant_id,
+            item_id: item.id,
+            title: item.name.clone(),
+            body,
+        },
+    )
+    .await?;
+
+    let checks = inventory_item_checks::Entity::find()
+        .filter(inventory_item_checks::Column::InventoryItemId.eq(item.id))
+        .all(db)
+        .await?;
+    write_check_documents(db, &item, checks).await
+}
+
+/// Rebuilds the document of the check with `check_id`. Does nothing for
+/// unknown checks.
+///
+/// # Errors
+///
+/// When the database fails.
+pub async fn index_check<C: ConnectionTrait>(db: &C, check_id: i32) -> Result<()> {
+    let Some(check) = inventory_item_checks::Entity::find_by_id(check_id)
+        .one(db)
+        .await?
+    else {
+        return Ok(());
+    };
+    let Some(item) = inventory_items::Entity::find_by_id(check.inventory_item_id)
+        .one(db)
+        .await?
+    else {
+        return Ok(());
+    };
+    write_check_documents(db, &item, vec![check]).await
+}
+
+/// Rebuilds the documents of every item of the kind with `kind_id`, whose
+/// name and metadata fields are part of them.
+///
+/// # Errors
+///
+/// When the database fails.
+pub async fn index_kind_items<C: ConnectionTrait>(db: &C, kind_id: i32) -> Result<()> {
+    let item_ids = inventory_items::Entity::find()
+        .filter(inventory_items::Column::InventoryItemKindId.eq(kind_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|item| item.id);
+    for item_id in item_ids {
+        index_item(db, item_id).await?;
+    }
+    Ok(())
+}
+
+/// Drops every document and indexes all items and checks again. Returns
+/// the number of items indexed.
+///
+/// # Errors
+///
+/// When the database fails.
+pub async fn reindex_all<C: ConnectionTrait>(db: &C) -> Result<usize> {
+    let delete = Query::delete().from_table(col(TABLE)).to_owned();
+    db.execute(db.get_database_backend().build(&delete)).await?;
+    let items = inventory_items::Entity::find()
+        .order_by_asc(inventory_items::Column::Id)
+        .all(db)
+        .await?;
+    for item in &items {
+        index_item(db, item.id).await?;
+    }
+    Ok(items.len())
+}
+
+/// Whether the index holds no documents yet, e.g. right after migrating.
+///
+/// # Errors
+///
+/// When the database fails.
+pub async fn is_empty<C: ConnectionTrait>(db: &C) -> Result<bool> {
+    let select = Query::select()
+        .expr(Expr::col(col("rowid")))
+        .from(col(TABLE))
+        .limit(1)
+        .to_owned();
+    Ok(db
+        .query_one(db.get_database_backend().build(&select))
+        .await?
+        .is_none())
+}
+
+/// Words of a search query, without any operators of the search syntax.
+fn terms(query: &str) -> Vec<&str> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .take(MAX_TERMS)
+        .collect()
+}
+
+/// One found item or check.
+#[derive(Clone, Debug, Serialize)]
+pub struct SearchHit {
+    /// Id of the item or check.
+    pub id: i32,
+    pub item_id: i32,
+    /// Item name as HTML, matches wrapped in `<mark>`.
+    pub title: String,
+    /// Excerpt of the serial number, kind and metadata of an item or the
+    /// notes of a check as HTML, matches wrapped in `<mark>`.
+    pub snippet: String,
+}
+
+/// Hits of a query grouped by entity type, best matches first.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SearchResults {
+    pub items: Vec<SearchHit>,
+    pub checks: Vec<SearchHit>,
+}
+
+impl SearchResults {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.checks.is_empty()
+    }
+}
+
+#[derive(FromQueryResult)]
+struct HitRow {
+    entity_id: i32,
+    item_id: i32,
+    title: String,
+    snippet: String,
+}
+
+/// Escapes `text` as HTML and turns the match markers into `<mark>` tags.
+fn highlighted_html(text: &str) -> String {
+    tera::escape_html(text)
+        .replace(MARK_START, "<mark>")
+        .replace(MARK_END, "</mark>")
+}
+
+/// Ranked select of the documents of `entity` matching all `terms` as
+/// prefixes.
+fn hit_select(backend: DbBackend, entity: SearchEntity, terms: &[&str]) -> SelectStatement {
+    let start = MARK_START.to_string();
+    let end = MARK_END.to_string();
+    let mut select = Query::select();
+    select
+        .column(col("entity_id"))
+        .column(col("item_id"))
+        .from(col(TABLE))
+        .and_where(Expr::col(col("entity")).eq(entity.as_str()))
+        .limit(MAX_HITS);
+    if backend == DbBackend::Postgres {
+        let query = terms
+            .iter()
+            .map(|term| format!("{term}:*"))
+            .collect::<Vec<_>>()
+            .join(" & ");
+        let headline = |column: &str, options: String| {
+            Expr::cust_with_values(
+                format!("ts_headline('simple', {column}, to_tsquery('simple', $1), $2)"),
+                [query.clone(), options],
+            )
+        };
+        select
+            .expr_as(
+                headline(
+                    "title",
+                    format!("StartSel={start},StopSel={end},HighlightAll=true"),
+                ),
+                col("title"),
+            )
+            .expr_as(
+                headline(
+                    "body",
+                    format!("StartSel={start},StopSel={end},MaxWords=20,MinWords=8"),
+                ),
+                col("snippet"),
+            )
+            .and_where(Expr::cust_with_values(
+                "document @@ to_tsquery('simple', $1)",
+                [query.clone()],
+            ))
+            .order_by_expr(
+                Expr::cust_with_values("ts_rank(document, to_tsquery('simple', $1))", [query]),
+                Order::Desc,
+            );
+    } else {
+        let query = terms
+            .iter()
+            .map(|term| format!("\"{term}\"*"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        select
+            .expr_as(
+                Expr::cust_with_values("highlight(search_index, 4, ?, ?)", [&start, &end]),
+                col("title"),
+            )
+            .expr_as(
+                Expr::cust_with_values("snippet(search_index, 5, ?, ?, '…', 16)", [&start, &end]),
+                col("snippet"),
+            )
+            .and_where(Expr::cust_with_values("search_index MATCH ?", [query]))
+            // title matches weigh ten times as much as body matches
+            .order_by_expr(
+                Expr::cust("bm25(search_index, 0.0, 0.0, 0.0, 0.0, 10.0, 1.0)"),
+                Order::Asc,
+            );
+    }
+    select
+}
+
+/// Items and checks of `tenant_id` matching every word of `query`, each
+/// also as the start of a longer word, limited to the items in `scope` and
+/// their checks.
+///
+/// # Errors
+///
+/// When the database fails.
+pub async fn search<C: ConnectionTrait>(
+    db: &C,
+    tenant_id: i32,
+    query: &str,
+    scope: &ItemScope,
+) -> Result<SearchResults> {
+    let terms = terms(query);
+    let mut results = SearchResults::default();
+    if terms.is_empty() {
+        return Ok(results);
+    }
+    let backend = db.get_database_backend();
+    for entity in [SearchEntity::Item, SearchEntity::Check] {
+        let mut select = hit_select(backend, entity, &terms);
+        select.and_where(Expr::col(col("tenant_id")).eq(tenant_id));
+        if let ItemScope::Only(items) = scope {
+            select.and_where(Expr::col(col("item_id")).in_subquery(*items.clone()));
+        }
+        let hits = HitRow::find_by_statement(backend.build(&select))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|row| SearchHit {
+                id: row.entity_id,
+                item_id: row.item_id,
+                title: highlighted_html(&row.title),
+                snippet: highlighted_html(&row.snippet),
+            })
+            .collect();
+        match entity {
+            SearchEntity::Item => results.items = hits,
+            SearchEntity::Check => results.checks = hits,
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_queries_into_plain_words() {
+        assert_eq!(
+            terms("SN-1234 \"cracked\" OR housing*"),
+            ["SN", "1234", "cracked", "OR", "housing"]
+        );
+        assert!(terms(" -* ").is_empty());
+    }
+
+    #[test]
+    fn escapes_highlighted_text() {
+        assert_eq!(
+            highlighted_html("<b>\u{e000}Rope\u{e001} & more"),
+            "&lt;b&gt;<mark>Rope</mark> &amp; more"
+        );
+    }
+
+    #[test]
+    fn keys_documents_by_entity_and_id() {
+        assert_ne!(SearchEntity::Item.key(3), SearchEntity::Check.key(3));
+        assert_ne!(SearchEntity::Check.key(1), SearchEntity::Item.key(2));
+    }
+}
@@ -0,0 +1,168 @@
+//! Sessions of the HTML pages.
+//!
+//! The GUI keeps the JWT of `/api/auth/login` in local storage, which the
+//! browser does not send when following links and submitting forms. Logging
+//! in therefore also sets the [`SESSION_COOKIE`], which identifies the user
+//! and the tenant they work in. Its value is
+//! `<user pid>.<tenant id>.<expires>.<signature>`, the signature being an
+//! HMAC-SHA256 keyed with the JWT secret over these fields and the user's
+//! password hash. So a cookie only authenticates the user it was issued to,
+//! expires together with their JWT and is revoked by a password change.
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap},
+};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use loco_rs::{app::AppContext, Error, Result};
+use sha2::Sha256;
+
+use crate::{i18n, models::users};
+
+/// Cookie identifying the user and tenant of the HTML pages.
+pub const SESSION_COOKIE: &str = "session";
+
+/// Fields of a session cookie.
+#[derive(Debug, PartialEq, Eq)]
+struct SessionClaims<'c> {
+    pid: &'c str,
+    tenant_id: i32,
+    expires: i64,
+    signature: &'c str,
+}
+
+impl<'c> SessionClaims<'c> {
+    fn parse(value: &'c str) -> Option<Self> {
+        let mut parts = value.splitn(4, '.');
+        Some(Self {
+            pid: parts.next()?,
+            tenant_id: parts.next()?.parse().ok()?,
+            expires: parts.next()?.parse().ok()?,
+            signature: parts.next()?,
+        })
+    }
+}
+
+/// HMAC-SHA256 over the session fields and the user's password hash.
+///
+/// # Panics
+///
+/// Never, HMAC accepts keys of any length
+fn session_mac(
+    secret: &str,
+    pid: &str,
+    password: &str,
+    tenant_id: i32,
+    expires: i64,
+) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{SESSION_COOKIE}:{pid}:{tenant_id}:{expires}:{password}").as_bytes());
+    mac
+}
+
+/// Whether `claims` were signed with `secret` for the user with `password`.
+fn verify(secret: &str, password: &str, claims: &SessionClaims<'_>) -> bool {
+    hex::decode(claims.signature).is_ok_and(|signature| {
+        session_mac(
+            secret,
+            claims.pid,
+            password,
+            claims.tenant_id,
+            claims.expires,
+        )
+        .verify_slice(&signature)
+        .is_ok()
+    })
+}
+
+/// `Set-Cookie` value signing in `user` to `tenant_id` for `max_age`
+/// seconds, signed with `secret`.
+#[must_use]
+pub fn session_cookie(secret: &str, user: &users::Model, tenant_id: i32, max_age: u64) -> String {
+    let pid = user.pid.to_string();
+    let expires = Utc::now()
+        .timestamp()
+        .saturating_add(i64::try_from(max_age).unwrap_or(i64::MAX));
+    let signature = session_mac(secret, &pid, &user.password, tenant_id, expires)
+        .finalize()
+        .into_bytes();
+    format!(
+        "{SESSION_COOKIE}={pid}.{tenant_id}.{expires}.{}; Path=/; Max-Age={max_age}; SameSite=Lax; HttpOnly",
+        hex::encode(signature)
+    )
+}
+
+/// `Set-Cookie` value ending the session.
+#[must_use]
+pub fn clear_session_cookie() -> String {
+    format!("{SESSION_COOKIE}=; Path=/; Max-Age=0; SameSite=Lax; HttpOnly")
+}
+
+/// User and tenant id of the [`SESSION_COOKIE`], if it is valid and not
+/// expired.
+pub async fn cookie_session(ctx: &AppContext, headers: &HeaderMap) -> Option<(users::Model, i32)> {
+    let claims = SessionClaims::parse(i18n::cookie(headers, SESSION_COOKIE)?)?;
+    if claims.expires <= Utc::now().timestamp() {
+        return None;
+    }
+    let secret = &ctx.config.get_jwt_config().ok()?.secret;
+    let user = users::Model::find_by_pid(&ctx.db, claims.pid).await.ok()?;
+    verify(secret, &user.password, &claims).then_some((user, claims.tenant_id))
+}
+
+/// User authenticated by the locale middleware with a bearer token or the
+/// session cookie. Rejects anonymous requests with 401.
+#[derive(Clone, Debug)]
+pub struct RequestUser(pub users::Model);
+
+impl FromRequestParts<AppContext> for RequestUser {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _ctx: &AppContext) -> Result<Self> {
+        parts
+            .extensions
+            .get::<users::Model>()
+            .cloned()
+            .map(Self)
+            .ok_or_else(|| Error::Unauthorized("sign in required".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed(secret: &str, pid: &str, password: &str, tenant_id: i32, expires: i64) -> String {
+        let signature = session_mac(secret, pid, password, tenant_id, expires)
+            .finalize()
+            .into_bytes();
+        format!("{pid}.{tenant_id}.{expires}.{}", hex::encode(signature))
+    }
+
+    #[test]
+    fn verifies_signed_sessions() {
+        let value = signed("secret", "pid-1", "hash-1", 7, 1_700_000_000);
+        let claims = SessionClaims::parse(&value).unwrap();
+        assert_eq!(claims.pid, "pid-1");
+        assert_eq!(claims.tenant_id, 7);
+        assert_eq!(claims.expires, 1_700_000_000);
+        assert!(verify("secret", "hash-1", &claims));
+        assert!(!verify("other", "hash-1", &claims));
+        assert!(!verify("secret", "changed password", &claims));
+
+        let forged = value.replacen(".7.", ".8.", 1);
+        assert!(!verify(
+            "secret",
+            "hash-1",
+            &SessionClaims::parse(&forged).unwrap()
+        ));
+        let other_user = value.replacen("pid-1", "pid-2", 1);
+        assert!(!verify(
+            "secret",
+            "hash-1",
+            &SessionClaims::parse(&other_user).unwrap()
+        ));
+        assert_eq!(SessionClaims::parse("pid.7.soon.00"), None);
+    }
+}
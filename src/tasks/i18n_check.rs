@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use loco_rs::prelude::*;
+
+use crate::i18n::{self, DEFAULT_LOCALE, I18N_DIR};
+
+/// Reports the Fluent keys each locale in `assets/i18n` is missing or has in
+/// addition to the default locale. Fails when keys are missing, so it can
+/// run in CI; `locale:fr-FR` limits the check to one locale.
+pub struct I18nCheck;
+
+#[async_trait]
+impl Task for I18nCheck {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "i18n_check".to_string(),
+            detail: format!("Report missing and extra translation keys against {DEFAULT_LOCALE}"),
+        }
+    }
+
+    async fn run(&self, _app_context: &AppContext, vars: &task::Vars) -> Result<()> {
+        let only = vars.cli_arg("locale").ok();
+        let diffs = i18n::diff_locales(Path::new(I18N_DIR))?
+            .into_iter()
+            .filter(|diff| only.is_none_or(|locale| *locale == diff.locale))
+            .collect::<Vec<_>>();
+
+        let mut missing = 0;
+        for diff in &diffs {
+            println!(
+                "{}: {} missing, {} extra",
+                diff.locale,
+                diff.missing.len(),
+                diff.extra.len()
+            );
+            for key in &diff.missing {
+                println!("  missing {key}");
+            }
+            for key in &diff.extra {
+                println!("  extra   {key}");
+            }
+            missing += diff.missing.len();
+        }
+
+        if missing > 0 {
+            return Err(Error::string(&format!(
+                "{missing} translation keys are missing"
+            )));
+        }
+        Ok(())
+    }
+}
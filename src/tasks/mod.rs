@@ -1 +1,4 @@
-
+pub mod i18n_check;
+pub mod notifications;
+pub mod search_reindex;
+pub mod webhooks;
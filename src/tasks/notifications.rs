@@ -0,0 +1,24 @@
+use chrono::Utc;
+use loco_rs::prelude::*;
+
+use crate::notifications;
+
+/// Sends the notices of every subscription whose frequency and quiet period
+/// allow it. Meant to run every few minutes from the scheduler.
+pub struct Notifications;
+
+#[async_trait]
+impl Task for Notifications {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "notifications".to_string(),
+            detail: "Send due item notices to subscribed users".to_string(),
+        }
+    }
+
+    async fn run(&self, app_context: &AppContext, _vars: &task::Vars) -> Result<()> {
+        let sent = notifications::send_due(app_context, Utc::now()).await?;
+        println!("{sent} notifications sent");
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use loco_rs::prelude::*;
+
+use crate::search;
+
+/// Rebuilds the full-text search index from the items and checks, e.g.
+/// after restoring a backup.
+pub struct SearchReindex;
+
+#[async_trait]
+impl Task for SearchReindex {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "search_reindex".to_string(),
+            detail: "Rebuild the full-text search index".to_string(),
+        }
+    }
+
+    async fn run(&self, app_context: &AppContext, _vars: &task::Vars) -> Result<()> {
+        let indexed = search::reindex_all(&app_context.db).await?;
+        println!("{indexed} items indexed");
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use loco_rs::prelude::*;
+
+use crate::webhooks;
+
+/// Sends `item.warning` and `item.critical` webhooks for items that became
+/// due and queues failed deliveries whose retry is due. Meant to run every
+/// few minutes from the scheduler.
+pub struct Webhooks;
+
+#[async_trait]
+impl Task for Webhooks {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "webhooks".to_string(),
+            detail: "Send due item webhooks and retry failed deliveries".to_string(),
+        }
+    }
+
+    async fn run(&self, app_context: &AppContext, _vars: &task::Vars) -> Result<()> {
+        let sent = webhooks::scan_due_items(app_context).await?;
+        let retried = webhooks::retry_due(app_context).await?;
+        println!("{sent} due item deliveries queued, {retried} deliveries retried");
+        Ok(())
+    }
+}
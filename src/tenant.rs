@@ -0,0 +1,143 @@
+//! Tenants (organizations) separating the equipment, checklists and users of
+//! independent departments.
+//!
+//! The tenant of a request is negotiated by the locale middleware: the
+//! current tenant of a user authenticated with a bearer token, or the one
+//! signed into the [`SESSION_COOKIE`](crate::session::SESSION_COOKIE) when
+//! the user logged in or switched. Anonymous requests have no tenant.
+use std::future::Future;
+
+use axum::{extract::FromRequestParts, http::request::Parts, http::StatusCode};
+use loco_rs::{app::AppContext, controller::ErrorDetail, Error, Result};
+
+pub use crate::models::tenants::DEFAULT_TENANT_ID;
+use crate::{
+    initializers::app_cache::AppData,
+    models::{
+        teams::{self, ItemScope},
+        tenant_memberships, tenants, users,
+    },
+    session::RequestUser,
+};
+
+tokio::task_local! {
+    static CURRENT_TENANT: tenants::Model;
+}
+
+/// Tenant of a request by `user`: `requested` by their session if they are
+/// still a member of it, otherwise their current tenant. `None` when the
+/// user belongs to no tenant at all.
+pub async fn negotiate_tenant(
+    ctx: &AppContext,
+    user: &users::Model,
+    requested: Option<i32>,
+) -> Option<tenants::Model> {
+    if let Some(id) = requested {
+        if tenant_memberships::Entity::is_member(&ctx.db, id, user.id)
+            .await
+            .ok()?
+        {
+            return ctx.get_tenants().ok()?.remove(&id);
+        }
+    }
+    tenants::Entity::current_for_user(&ctx.db, user)
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Runs `future` with `tenant` as the [`current_tenant`].
+pub async fn scope_tenant<F: Future>(tenant: tenants::Model, future: F) -> F::Output {
+    CURRENT_TENANT.scope(tenant, future).await
+}
+
+/// Tenant negotiated for the request being handled, if any.
+#[must_use]
+pub fn current_tenant() -> Option<tenants::Model> {
+    CURRENT_TENANT.try_with(Clone::clone).ok()
+}
+
+fn forbidden(description: &str) -> Error {
+    Error::CustomError(
+        StatusCode::FORBIDDEN,
+        ErrorDetail::new("forbidden", description),
+    )
+}
+
+/// Id of the tenant negotiated by the locale middleware. Rejects anonymous
+/// requests with 401 and users without a tenant with 403.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestTenant(pub i32);
+
+impl FromRequestParts<AppContext> for RequestTenant {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _ctx: &AppContext) -> Result<Self> {
+        if let Some(tenant) = parts.extensions.get::<tenants::Model>() {
+            return Ok(Self(tenant.id));
+        }
+        if parts.extensions.get::<users::Model>().is_none() {
+            return Err(Error::Unauthorized("sign in required".to_string()));
+        }
+        Err(forbidden("not a member of any tenant"))
+    }
+}
+
+/// Id of the tenant negotiated by the locale middleware, for handlers only
+/// its admins may use. Rejects anonymous requests with 401 and other users
+/// with 403.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestAdmin(pub i32);
+
+impl FromRequestParts<AppContext> for RequestAdmin {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, ctx: &AppContext) -> Result<Self> {
+        let RequestTenant(tenant_id) = RequestTenant::from_request_parts(parts, ctx).await?;
+        let is_admin = match parts.extensions.get::<users::Model>() {
+            Some(user) => tenant_memberships::Entity::is_admin(&ctx.db, tenant_id, user.id).await?,
+            None => false,
+        };
+        if !is_admin {
+            return Err(forbidden("only admins of the tenant may do this"));
+        }
+        Ok(Self(tenant_id))
+    }
+}
+
+/// User authenticated by the locale middleware, for handlers changing what
+/// all tenants share, see [`tenant_memberships::Entity::is_global_admin`].
+/// Rejects anonymous requests with 401 and other users with 403.
+#[derive(Clone, Debug)]
+pub struct RequestGlobalAdmin(pub users::Model);
+
+impl FromRequestParts<AppContext> for RequestGlobalAdmin {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, ctx: &AppContext) -> Result<Self> {
+        let RequestUser(user) = RequestUser::from_request_parts(parts, ctx).await?;
+        if !tenant_memberships::Entity::is_global_admin(&ctx.db, user.id).await? {
+            return Err(forbidden("only global admins may do this"));
+        }
+        Ok(Self(user))
+    }
+}
+
+/// Items of the request's tenant the user authenticated by the locale
+/// middleware may see, limited to those of their teams while they are in a
+/// scoped team of that tenant. Rejects anonymous requests with 401 and users
+/// without a tenant with 403.
+#[derive(Clone, Debug)]
+pub struct RequestItemScope(pub ItemScope);
+
+impl FromRequestParts<AppContext> for RequestItemScope {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, ctx: &AppContext) -> Result<Self> {
+        let RequestUser(user) = RequestUser::from_request_parts(parts, ctx).await?;
+        let RequestTenant(tenant_id) = RequestTenant::from_request_parts(parts, ctx).await?;
+        Ok(Self(
+            teams::Entity::item_scope_for_user(&ctx.db, tenant_id, user.id).await?,
+        ))
+    }
+}
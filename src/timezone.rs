@@ -0,0 +1,177 @@
+//! Site and per-user time zones used to parse date inputs, to find day
+//! boundaries and to render timestamps.
+use std::{convert::Infallible, future::Future};
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap},
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use loco_rs::{app::AppContext, config::Config};
+
+use crate::{i18n, models::users};
+
+/// Cookie remembering the time zone of the browser or the logged in user.
+pub const TZ_COOKIE: &str = "tz";
+/// Key of the site time zone in the `settings` section of the config.
+const SITE_TIMEZONE_SETTING: &str = "timezone";
+/// How long the time zone cookie is kept, one year.
+const TZ_COOKIE_MAX_AGE: u32 = 365 * 24 * 60 * 60;
+
+tokio::task_local! {
+    static CURRENT_TIMEZONE: Tz;
+}
+
+/// Parses an IANA time zone name such as `Europe/Berlin`.
+#[must_use]
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse::<Tz>().ok()
+}
+
+/// Site time zone configured as `settings.timezone`, UTC when unset or
+/// unknown.
+#[must_use]
+pub fn site_timezone(config: &Config) -> Tz {
+    let Some(name) = config
+        .settings
+        .as_ref()
+        .and_then(|settings| settings.get(SITE_TIMEZONE_SETTING))
+        .and_then(serde_json::Value::as_str)
+    else {
+        return Tz::UTC;
+    };
+    parse_timezone(name).unwrap_or_else(|| {
+        tracing::warn!(timezone = name, "unknown site time zone, using UTC");
+        Tz::UTC
+    })
+}
+
+/// Time zone of `user`, the site time zone unless they picked one.
+#[must_use]
+pub fn user_timezone(config: &Config, user: &users::Model) -> Tz {
+    user.timezone
+        .as_deref()
+        .and_then(parse_timezone)
+        .unwrap_or_else(|| site_timezone(config))
+}
+
+/// `Set-Cookie` value remembering `timezone` for later requests.
+#[must_use]
+pub fn timezone_cookie(timezone: Tz) -> String {
+    format!(
+        "{TZ_COOKIE}={}; Path=/; Max-Age={TZ_COOKIE_MAX_AGE}; SameSite=Lax",
+        timezone.name()
+    )
+}
+
+/// Chooses the time zone of a request from the [`TZ_COOKIE`] cookie, the
+/// user's preference and the site time zone, in that order.
+#[must_use]
+pub fn negotiate_timezone(preference: Option<&str>, headers: &HeaderMap, site: Tz) -> Tz {
+    i18n::cookie(headers, TZ_COOKIE)
+        .into_iter()
+        .chain(preference)
+        .find_map(parse_timezone)
+        .unwrap_or(site)
+}
+
+/// Runs `future` with `timezone` as the [`current_timezone`].
+pub async fn scope_timezone<F: Future>(timezone: Tz, future: F) -> F::Output {
+    CURRENT_TIMEZONE.scope(timezone, future).await
+}
+
+/// Time zone negotiated for the request being handled, or UTC outside of a
+/// request.
+#[must_use]
+pub fn current_timezone() -> Tz {
+    CURRENT_TIMEZONE
+        .try_with(|timezone| *timezone)
+        .unwrap_or(Tz::UTC)
+}
+
+/// First instant of `date` in `timezone`. Days starting in a DST gap begin
+/// at the end of the gap.
+#[must_use]
+pub fn start_of_day(date: NaiveDate, timezone: Tz) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    (0..=2)
+        .find_map(|hour| {
+            timezone
+                .from_local_datetime(&(midnight + chrono::Duration::hours(hour)))
+                .earliest()
+        })
+        .map_or_else(|| midnight.and_utc(), |start| start.to_utc())
+}
+
+/// The current date in `timezone`.
+#[must_use]
+pub fn today(timezone: Tz) -> NaiveDate {
+    Utc::now().with_timezone(&timezone).date_naive()
+}
+
+/// Time zone negotiated by the locale middleware, or the site time zone.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestTimezone(pub Tz);
+
+impl FromRequestParts<AppContext> for RequestTimezone {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        ctx: &AppContext,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self(parts.extensions.get::<Tz>().copied().unwrap_or_else(
+            || negotiate_timezone(None, &parts.headers, site_timezone(&ctx.config)),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{header, HeaderValue};
+
+    use super::*;
+
+    #[test]
+    fn negotiates_timezone() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(negotiate_timezone(None, &headers, Tz::UTC), Tz::UTC);
+        assert_eq!(
+            negotiate_timezone(Some("Europe/Berlin"), &headers, Tz::UTC),
+            Tz::Europe__Berlin
+        );
+        assert_eq!(
+            negotiate_timezone(Some("Mars/Olympus"), &headers, Tz::Europe__Vienna),
+            Tz::Europe__Vienna
+        );
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("lang=de-DE; tz=America/New_York"),
+        );
+        assert_eq!(
+            negotiate_timezone(Some("Europe/Berlin"), &headers, Tz::UTC),
+            Tz::America__New_York
+        );
+    }
+
+    #[test]
+    fn finds_local_day_boundaries() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 16).unwrap();
+        assert_eq!(
+            start_of_day(date, Tz::Europe__Berlin).to_rfc3339(),
+            "2025-08-15T22:00:00+00:00"
+        );
+        assert_eq!(
+            start_of_day(date, Tz::UTC).to_rfc3339(),
+            "2025-08-16T00:00:00+00:00"
+        );
+        // Santiago skips from midnight to 01:00 when DST starts
+        let gap = NaiveDate::from_ymd_opt(2024, 9, 8).unwrap();
+        assert_eq!(
+            start_of_day(gap, Tz::America__Santiago).to_rfc3339(),
+            "2024-09-08T04:00:00+00:00"
+        );
+    }
+}
@@ -0,0 +1,412 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset};
+use loco_rs::model::query::{PageResponse, PaginationQuery};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::{
+    api_keys::{self, ApiScope},
+    checklist_steps, checklists, executed_checklist_steps, expiries, intervals,
+    inventory_item_check_steps, inventory_item_checks, inventory_item_kinds, inventory_items,
+    notification_subscriptions::{
+        self, NotificationChannel, NotificationFrequency, NotificationLevel,
+    },
+    results,
+};
+use crate::search::{SearchHit, SearchResults};
+
+/// Error body returned by every API endpoint (mirrors loco's `ErrorDetail`).
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: Option<String>,
+    pub description: Option<String>,
+    #[schema(value_type = Option<Object>)]
+    pub errors: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct PageView<T> {
+    pub items: Vec<T>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+impl<T> PageView<T> {
+    #[must_use]
+    pub fn new<M>(
+        response: PageResponse<M>,
+        pagination: &PaginationQuery,
+        f: impl FnMut(M) -> T,
+    ) -> Self {
+        Self {
+            items: response.page.into_iter().map(f).collect(),
+            page: pagination.page,
+            page_size: pagination.page_size,
+            total_pages: response.total_pages,
+            total_items: response.total_items,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ItemResponse {
+    pub id: i32,
+    pub name: String,
+    pub serial_number: Option<String>,
+    pub inventory_item_kind_id: i32,
+    pub checklist_id: i32,
+    pub interval_id: i32,
+    pub expiry: Option<DateTime<FixedOffset>>,
+    pub last_checked_at: Option<DateTime<FixedOffset>>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    /// Metadata values keyed by the item kind's metadata field names.
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl ItemResponse {
+    #[must_use]
+    pub fn new(item: inventory_items::Model, metadata: BTreeMap<String, String>) -> Self {
+        Self {
+            id: item.id,
+            name: item.name,
+            serial_number: item.serial_number,
+            inventory_item_kind_id: item.inventory_item_kind_id,
+            checklist_id: item.checklist_id,
+            interval_id: item.interval_id,
+            expiry: item.expiry,
+            last_checked_at: item.last_checked_at,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+            metadata,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ItemKindResponse {
+    pub id: i32,
+    pub name: String,
+    pub test_standard: String,
+    pub default_checklist_id: i32,
+    pub default_interval_id: i32,
+    pub default_expiry_id: i32,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub metadata_fields: Vec<String>,
+}
+
+impl ItemKindResponse {
+    #[must_use]
+    pub fn new(kind: inventory_item_kinds::Model, metadata_fields: Vec<String>) -> Self {
+        Self {
+            id: kind.id,
+            name: kind.name,
+            test_standard: kind.test_standard,
+            default_checklist_id: kind.default_checklist_id,
+            default_interval_id: kind.default_interval_id,
+            default_expiry_id: kind.default_expiry_id,
+            created_at: kind.created_at,
+            updated_at: kind.updated_at,
+            metadata_fields,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ChecklistStepResponse {
+    pub id: i32,
+    pub position: i32,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl From<checklist_steps::Model> for ChecklistStepResponse {
+    fn from(step: checklist_steps::Model) -> Self {
+        Self {
+            id: step.id,
+            position: step.position,
+            name: step.name,
+            description: step.description,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ChecklistResponse {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub steps: Vec<ChecklistStepResponse>,
+}
+
+impl ChecklistResponse {
+    #[must_use]
+    pub fn new(checklist: checklists::Model, steps: Vec<checklist_steps::Model>) -> Self {
+        Self {
+            id: checklist.id,
+            name: checklist.name,
+            description: checklist.description,
+            created_at: checklist.created_at,
+            updated_at: checklist.updated_at,
+            steps: steps.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// An interval or expiry period.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct PeriodResponse {
+    pub id: i32,
+    pub code: String,
+    /// One of `days`, `months`, `years` or `never`.
+    pub sqlite_modifier: String,
+    pub sqlite_num_of_modifier: i32,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl From<intervals::Model> for PeriodResponse {
+    fn from(interval: intervals::Model) -> Self {
+        Self {
+            id: interval.id,
+            code: interval.code,
+            sqlite_modifier: interval.sqlite_modifier,
+            sqlite_num_of_modifier: interval.sqlite_num_of_modifier,
+            created_at: interval.created_at,
+            updated_at: interval.updated_at,
+        }
+    }
+}
+
+impl From<expiries::Model> for PeriodResponse {
+    fn from(expiry: expiries::Model) -> Self {
+        Self {
+            id: expiry.id,
+            code: expiry.code,
+            sqlite_modifier: expiry.sqlite_modifier,
+            sqlite_num_of_modifier: expiry.sqlite_num_of_modifier,
+            created_at: expiry.created_at,
+            updated_at: expiry.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ResultResponse {
+    pub id: i32,
+    pub code: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl From<results::Model> for ResultResponse {
+    fn from(result: results::Model) -> Self {
+        Self {
+            id: result.id,
+            code: result.code,
+            created_at: result.created_at,
+            updated_at: result.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CheckStepResponse {
+    pub position: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub result_id: Option<i32>,
+    pub notes: Option<String>,
+}
+
+impl CheckStepResponse {
+    #[must_use]
+    pub fn new(
+        executed_step: executed_checklist_steps::Model,
+        result: Option<&inventory_item_check_steps::Model>,
+    ) -> Self {
+        Self {
+            position: executed_step.position,
+            name: executed_step.name,
+            description: executed_step.description,
+            result_id: result.map(|step| step.result_id),
+            notes: result.and_then(|step| step.notes.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CheckResponse {
+    pub id: i32,
+    pub inventory_item_id: i32,
+    pub executed_checklist_id: i32,
+    pub checked_by: i32,
+    pub checked_at: DateTime<FixedOffset>,
+    pub finished: bool,
+    pub result_id: i32,
+    pub notes: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub steps: Vec<CheckStepResponse>,
+}
+
+impl CheckResponse {
+    #[must_use]
+    pub fn new(check: inventory_item_checks::Model, steps: Vec<CheckStepResponse>) -> Self {
+        Self {
+            id: check.id,
+            inventory_item_id: check.inventory_item_id,
+            executed_checklist_id: check.executed_checklist_id,
+            checked_by: check.checked_by,
+            checked_at: check.checked_at,
+            finished: check.finished,
+            result_id: check.result_id,
+            notes: check.notes,
+            created_at: check.created_at,
+            updated_at: check.updated_at,
+            steps,
+        }
+    }
+}
+
+/// An API key as listed to its owner. The token itself is never returned
+/// after creation, only its first characters.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: i32,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<ApiScope>,
+    pub expires_at: Option<DateTime<FixedOffset>>,
+    pub last_used_at: Option<DateTime<FixedOffset>>,
+    pub revoked_at: Option<DateTime<FixedOffset>>,
+    pub active: bool,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<api_keys::Model> for ApiKeyResponse {
+    fn from(key: api_keys::Model) -> Self {
+        Self {
+            scopes: key.scopes(),
+            active: key.is_active(),
+            id: key.id,
+            name: key.name,
+            key_prefix: key.key_prefix,
+            expires_at: key.expires_at,
+            last_used_at: key.last_used_at,
+            revoked_at: key.revoked_at,
+            created_at: key.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreatedApiKeyResponse {
+    #[serde(flatten)]
+    pub key: ApiKeyResponse,
+    /// The plain text key. It is shown only once and cannot be recovered.
+    pub token: String,
+}
+
+/// Ids of the items the user is responsible for.
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct MyItemsResponse {
+    pub owner_of: Vec<i32>,
+    pub deputy_of: Vec<i32>,
+}
+
+/// A notification subscription as listed to its owner.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct NotificationSubscriptionResponse {
+    pub id: i32,
+    pub inventory_item_kind_id: Option<i32>,
+    pub location: Option<String>,
+    pub inventory_item_id: Option<i32>,
+    pub min_urgency: NotificationLevel,
+    pub channel: NotificationChannel,
+    pub webhook_endpoint_id: Option<i32>,
+    pub frequency: NotificationFrequency,
+    pub quiet_start_hour: Option<i16>,
+    pub quiet_end_hour: Option<i16>,
+    pub responsible_only: bool,
+    pub team_id: Option<i32>,
+    pub last_sent_at: Option<DateTime<FixedOffset>>,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<notification_subscriptions::Model> for NotificationSubscriptionResponse {
+    fn from(subscription: notification_subscriptions::Model) -> Self {
+        Self {
+            min_urgency: subscription.min_urgency(),
+            channel: subscription.channel(),
+            frequency: subscription.frequency(),
+            id: subscription.id,
+            inventory_item_kind_id: subscription.inventory_item_kind_id,
+            location: subscription.location,
+            inventory_item_id: subscription.inventory_item_id,
+            webhook_endpoint_id: subscription.webhook_endpoint_id,
+            quiet_start_hour: subscription.quiet_start_hour,
+            quiet_end_hour: subscription.quiet_end_hour,
+            responsible_only: subscription.responsible_only,
+            team_id: subscription.team_id,
+            last_sent_at: subscription.last_sent_at,
+            created_at: subscription.created_at,
+        }
+    }
+}
+
+/// A tenant the user belongs to.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct TenantResponse {
+    pub id: i32,
+    pub name: String,
+    /// Whether the user currently works in this tenant.
+    pub current: bool,
+    /// Names of the members, sorted.
+    pub members: Vec<String>,
+}
+
+/// An item or check found by a full-text search.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct SearchHitResponse {
+    /// Id of the item or check.
+    pub id: i32,
+    pub item_id: i32,
+    /// Item name as HTML, matches wrapped in `<mark>`.
+    pub title: String,
+    /// Excerpt of the matching text as HTML, matches wrapped in `<mark>`.
+    pub snippet: String,
+}
+
+impl From<SearchHit> for SearchHitResponse {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            id: hit.id,
+            item_id: hit.item_id,
+            title: hit.title,
+            snippet: hit.snippet,
+        }
+    }
+}
+
+/// Search hits grouped by entity type, best matches first.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct SearchResponse {
+    pub items: Vec<SearchHitResponse>,
+    pub checks: Vec<SearchHitResponse>,
+}
+
+impl From<SearchResults> for SearchResponse {
+    fn from(results: SearchResults) -> Self {
+        Self {
+            items: results.items.into_iter().map(Into::into).collect(),
+            checks: results.checks.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::models::_entities::users;
+use crate::{calendar::feed_path, models::_entities::users};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct LoginResponse {
@@ -39,3 +39,39 @@ impl CurrentResponse {
         }
     }
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AbsenceResponse {
+    /// Last day the user is away, in their time zone.
+    pub absent_until: Option<chrono::NaiveDate>,
+}
+
+impl AbsenceResponse {
+    #[must_use]
+    pub fn new(user: &users::Model) -> Self {
+        Self {
+            absent_until: user.absent_until,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CalendarResponse {
+    /// Whether the user created a calendar feed.
+    pub active: bool,
+    /// Subscription URL of the feed. Only a hash of its token is stored, so
+    /// the URL is only returned when it is created.
+    pub url: Option<String>,
+}
+
+impl CalendarResponse {
+    /// Response for `user`, with the URL of the plain `token` if it was just
+    /// created.
+    #[must_use]
+    pub fn new(user: &users::Model, base_url: &str, token: Option<&str>) -> Self {
+        Self {
+            active: user.calendar_token_hash.is_some(),
+            url: token.map(|token| format!("{base_url}{}", feed_path(token))),
+        }
+    }
+}
@@ -0,0 +1,435 @@
+//! Outgoing webhooks: events are stored as one delivery per subscribed
+//! endpoint and posted by the [`WebhookWorker`], retrying with backoff.
+//!
+//! Every request carries the event in `X-Webhook-Event`, the delivery id in
+//! `X-Webhook-Delivery` and `X-Webhook-Signature: t=<unix time>,v1=<hex>`,
+//! an HMAC-SHA256 of `<unix time>.<body>` keyed with the endpoint secret.
+//!
+//! Endpoints must not point at the server's own network: loopback, private,
+//! link-local and unspecified addresses are rejected when an endpoint is
+//! saved and again after resolving its host when sending.
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use loco_rs::{config::Config, prelude::*};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::{
+    controllers::home::{calculate_urgency, UrgencyLevel},
+    models::{
+        inventory_item_checks, inventory_items, results, webhook_deliveries,
+        webhook_endpoints::{self, WebhookEvent},
+        webhook_item_states,
+    },
+    timezone,
+    workers::webhook::{WebhookWorker, WebhookWorkerArgs},
+};
+
+pub const EVENT_HEADER: &str = "x-webhook-event";
+pub const DELIVERY_HEADER: &str = "x-webhook-delivery";
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+/// Endpoints that take longer than this to answer count as failed.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Key of the setting that lets endpoints use private and loopback
+/// addresses, only meant for tests with a local receiver.
+const ALLOW_PRIVATE_TARGETS_SETTING: &str = "webhooks_allow_private_targets";
+
+/// Client for endpoints on the public internet.
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    client_builder()
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .unwrap_or_default()
+});
+/// Client for any endpoint, see [`ALLOW_PRIVATE_TARGETS_SETTING`].
+static UNRESTRICTED_CLIENT: LazyLock<reqwest::Client> =
+    LazyLock::new(|| client_builder().build().unwrap_or_default());
+
+/// Redirects are not followed, they could lead to a private address.
+fn client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .user_agent(concat!("bestbefors-webhooks/", env!("CARGO_PKG_VERSION")))
+}
+
+/// Whether `ip` is reachable on the public internet rather than a loopback,
+/// private, link-local, unspecified or multicast address.
+#[must_use]
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            // 100.64.0.0/10, shared address space of carrier-grade NAT
+            let shared = ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64;
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || shared)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ip(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Whether the config allows endpoints on private and loopback addresses.
+#[must_use]
+pub fn allows_private_targets(config: &Config) -> bool {
+    config
+        .settings
+        .as_ref()
+        .and_then(|settings| settings.get(ALLOW_PRIVATE_TARGETS_SETTING))
+        .and_then(Value::as_bool)
+        .unwrap_or_default()
+}
+
+/// Checks that `url` is an http(s) URL whose host is not a local or private
+/// address, unless `allow_private`. Host names are only checked for
+/// `localhost` here, their addresses are checked when sending.
+///
+/// # Errors
+///
+/// Describing why the URL is rejected
+pub fn check_target(url: &str, allow_private: bool) -> std::result::Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| format!("invalid webhook URL: {url}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("invalid webhook URL: {url}"));
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| format!("invalid webhook URL: {url}"))?;
+    if allow_private {
+        return Ok(());
+    }
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let is_public = if let Ok(ip) = host.parse::<IpAddr>() {
+        is_public_ip(ip)
+    } else {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        host != "localhost" && !host.ends_with(".localhost")
+    };
+    if is_public {
+        Ok(())
+    } else {
+        Err(format!(
+            "webhook URL must not point at a local address: {url}"
+        ))
+    }
+}
+
+/// Resolves the hosts of webhook requests, failing when one of the
+/// addresses is not public so a host name cannot lead into the server's own
+/// network.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .collect::<Vec<_>>();
+            if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+                return Err(format!(
+                    "{} resolves to the local address {}",
+                    name.as_str(),
+                    addr.ip()
+                )
+                .into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Hex HMAC-SHA256 of `<timestamp>.<body>` keyed with `secret`.
+///
+/// # Panics
+///
+/// Never, HMAC accepts keys of any length
+#[must_use]
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Stores a delivery of `data` for every endpoint of `tenant_id` subscribed
+/// to `event` and queues them for the worker.
+///
+/// # Errors
+///
+/// DB query error or when a delivery could not be queued
+pub async fn dispatch(
+    ctx: &AppContext,
+    tenant_id: i32,
+    event: WebhookEvent,
+    data: Value,
+) -> Result<usize> {
+    let endpoints = webhook_endpoints::Entity::find_subscribed(&ctx.db, tenant_id, event).await?;
+    for endpoint in &endpoints {
+        queue(ctx, endpoint, event.as_str(), &data).await?;
+    }
+    Ok(endpoints.len())
+}
+
+/// Stores a delivery of `data` as `event` for one endpoint, whatever events
+/// it subscribed to, and queues it for the worker.
+///
+/// # Errors
+///
+/// DB query error or when the delivery could not be queued
+pub async fn queue(
+    ctx: &AppContext,
+    endpoint: &webhook_endpoints::Model,
+    event: &str,
+    data: &Value,
+) -> Result<webhook_deliveries::Model> {
+    let pid = Uuid::new_v4();
+    let payload = json!({
+        "id": pid,
+        "event": event,
+        "created_at": Utc::now(),
+        "data": data,
+    });
+    let delivery =
+        webhook_deliveries::ActiveModel::queue(endpoint, pid, event, payload.to_string())
+            .insert(&ctx.db)
+            .await?;
+    WebhookWorker::perform_later(
+        ctx,
+        WebhookWorkerArgs {
+            delivery_id: delivery.id,
+        },
+    )
+    .await?;
+    Ok(delivery)
+}
+
+/// [`dispatch`]es `event` on behalf of a request, which must not fail
+/// because a webhook could not be queued.
+pub async fn notify(ctx: &AppContext, tenant_id: i32, event: WebhookEvent, data: Value) {
+    if let Err(err) = dispatch(ctx, tenant_id, event, data).await {
+        tracing::error!(event = event.as_str(), error = %err, "could not queue webhook");
+    }
+}
+
+/// Event data describing an item.
+#[must_use]
+pub fn item_data(item: &inventory_items::Model) -> Value {
+    json!({ "item": item })
+}
+
+/// Event data describing a check of `item`, with whether it passed.
+#[must_use]
+pub fn check_data(
+    item: &inventory_items::Model,
+    check: &inventory_item_checks::Model,
+    result: Option<&results::Model>,
+) -> Value {
+    json!({
+        "item": item,
+        "check": check,
+        "result": result.map(|result| result.code.clone()),
+        "passed": result.is_some_and(results::Model::is_ok),
+    })
+}
+
+/// Posts a pending delivery once and records the outcome. Only the status
+/// code of the response is kept, the body may come from any system the
+/// endpoint URL leads to.
+///
+/// # Errors
+///
+/// DB query error
+pub async fn deliver(ctx: &AppContext, delivery: webhook_deliveries::Model) -> Result<()> {
+    // a delivery can be queued more than once, e.g. by a retry and a
+    // redelivery, only the worker that claims it sends it
+    let Some(delivery) = delivery.claim(&ctx.db).await? else {
+        return Ok(());
+    };
+    let endpoint = webhook_endpoints::Entity::find_by_id(delivery.webhook_endpoint_id)
+        .one(&ctx.db)
+        .await?;
+    let Some(endpoint) = endpoint.filter(|endpoint| endpoint.active) else {
+        delivery
+            .mark_failed(&ctx.db, "endpoint is disabled".to_string())
+            .await?;
+        return Ok(());
+    };
+    let allow_private = allows_private_targets(&ctx.config);
+    if let Err(error) = check_target(&endpoint.url, allow_private) {
+        delivery.mark_failed(&ctx.db, error).await?;
+        return Ok(());
+    }
+    let client = if allow_private {
+        &UNRESTRICTED_CLIENT
+    } else {
+        &CLIENT
+    };
+
+    let timestamp = Utc::now().timestamp();
+    let signature = sign(&endpoint.secret, timestamp, &delivery.payload);
+    let sent = client
+        .post(&endpoint.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &delivery.event)
+        .header(DELIVERY_HEADER, delivery.pid.to_string())
+        .header(SIGNATURE_HEADER, format!("t={timestamp},v1={signature}"))
+        .body(delivery.payload.clone())
+        .send()
+        .await;
+
+    match sent {
+        Ok(response) if response.status().is_success() => {
+            delivery
+                .mark_succeeded(&ctx.db, response.status().as_u16())
+                .await?;
+        }
+        Ok(response) => {
+            let status = response.status().as_u16();
+            delivery
+                .mark_attempt_failed(&ctx.db, Some(status), format!("HTTP {status}"))
+                .await?;
+        }
+        Err(err) => {
+            delivery
+                .mark_attempt_failed(&ctx.db, None, err.to_string())
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Queues the pending deliveries whose retry is due.
+///
+/// # Errors
+///
+/// DB query error or when a delivery could not be queued
+pub async fn retry_due(ctx: &AppContext) -> Result<usize> {
+    let due = webhook_deliveries::Entity::find_due(&ctx.db, Utc::now().into()).await?;
+    for delivery in &due {
+        WebhookWorker::perform_later(
+            ctx,
+            WebhookWorkerArgs {
+                delivery_id: delivery.id,
+            },
+        )
+        .await?;
+    }
+    Ok(due.len())
+}
+
+/// Sends `item.warning` and `item.critical` for items whose urgency rose
+/// since the last scan, by calendar day in the site time zone.
+///
+/// # Errors
+///
+/// DB query error or when a delivery could not be queued
+pub async fn scan_due_items(ctx: &AppContext) -> Result<usize> {
+    let tz = timezone::site_timezone(&ctx.config);
+    let today = timezone::today(tz);
+    let mut states = webhook_item_states::Entity::find()
+        .all(&ctx.db)
+        .await?
+        .into_iter()
+        .map(|state| (state.inventory_item_id, state))
+        .collect::<HashMap<_, _>>();
+
+    let mut sent = 0;
+    for item in inventory_items::Entity::find().all(&ctx.db).await? {
+        let urgency = item.next_due_at.map_or(UrgencyLevel::Normal, |due| {
+            calculate_urgency(&due, today, tz)
+        });
+        let previous = states.remove(&item.id);
+        if previous
+            .as_ref()
+            .is_some_and(|state| state.urgency == urgency.to_string())
+        {
+            continue;
+        }
+        let escalated = match previous.as_ref().map(|state| state.urgency.as_str()) {
+            Some("CRITICAL") => false,
+            Some("WARNING") => urgency == UrgencyLevel::Critical,
+            _ => urgency != UrgencyLevel::Normal,
+        };
+
+        let mut state = previous.map_or_else(
+            || webhook_item_states::ActiveModel {
+                inventory_item_id: ActiveValue::set(item.id),
+                ..Default::default()
+            },
+            IntoActiveModel::into_active_model,
+        );
+        state.urgency = ActiveValue::set(urgency.to_string());
+        state.save(&ctx.db).await?;
+
+        if escalated {
+            let event = if urgency == UrgencyLevel::Critical {
+                WebhookEvent::ItemCritical
+            } else {
+                WebhookEvent::ItemWarning
+            };
+            sent += dispatch(ctx, item.tenant_id, event, item_data(&item)).await?;
+        }
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_local_targets() {
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://localhost:8080/hook",
+            "http://api.localhost/hook",
+            "http://10.0.0.5/hook",
+            "http://172.16.1.1/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+            "ftp://example.com/hook",
+        ] {
+            assert!(check_target(url, false).is_err(), "{url}");
+        }
+        assert!(check_target("http://127.0.0.1/hook", true).is_ok());
+        assert!(check_target("https://example.com/hook", false).is_ok());
+        assert!(check_target("https://93.184.215.14/hook", false).is_ok());
+        assert!(check_target("ftp://example.com/hook", true).is_err());
+    }
+
+    #[test]
+    fn signs_timestamp_and_body() {
+        // echo -n '1700000000.{"a":1}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            sign("secret", 1_700_000_000, r#"{"a":1}"#),
+            "49f24e537407743fa4a0242bb63b94b9a47ee99cbbe071ccd8a22550ae411686"
+        );
+    }
+}
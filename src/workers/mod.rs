@@ -1 +1,3 @@
 pub mod downloader;
+pub mod report;
+pub mod webhook;
@@ -0,0 +1,74 @@
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controllers::reports::{generate_report, ReportRequest},
+    i18n::{match_locale, Translator, DEFAULT_LOCALE},
+    models::{report_jobs, teams},
+    timezone::{parse_timezone, site_timezone},
+};
+
+/// Renders queued report jobs and stores the PDF with the job for later
+/// download.
+pub struct ReportWorker {
+    pub ctx: AppContext,
+}
+
+#[derive(Deserialize, Debug, Serialize)]
+pub struct ReportWorkerArgs {
+    pub job_id: i32,
+}
+
+#[async_trait]
+impl BackgroundWorker<ReportWorkerArgs> for ReportWorker {
+    fn build(ctx: &AppContext) -> Self {
+        Self { ctx: ctx.clone() }
+    }
+
+    async fn perform(&self, args: ReportWorkerArgs) -> Result<()> {
+        let Some(job) = report_jobs::Entity::find_by_id(args.job_id)
+            .one(&self.ctx.db)
+            .await?
+        else {
+            tracing::warn!(job_id = args.job_id, "report job no longer exists");
+            return Ok(());
+        };
+        let job = job.mark_running(&self.ctx.db).await?;
+
+        let translator =
+            Translator::for_context(&self.ctx, match_locale(&job.lang).unwrap_or(DEFAULT_LOCALE))
+                .with_timezone(
+                    parse_timezone(&job.timezone)
+                        .unwrap_or_else(|| site_timezone(&self.ctx.config)),
+                );
+        let generated = match serde_json::from_str::<ReportRequest>(&job.params) {
+            Ok(request) => {
+                let scope =
+                    teams::Entity::item_scope_for_user(&self.ctx.db, job.tenant_id, job.user_id)
+                        .await?;
+                generate_report(&self.ctx, job.tenant_id, &scope, &request, &translator).await
+            }
+            Err(err) => Err(Error::string(&format!("invalid report job params: {err}"))),
+        };
+
+        // a failed report is recorded with the job, retrying would fail the
+        // same way
+        match generated {
+            Ok(report) => {
+                job.mark_completed(
+                    &self.ctx.db,
+                    report.title,
+                    report.filename,
+                    report.report_id,
+                    report.content,
+                )
+                .await?;
+            }
+            Err(err) => {
+                tracing::error!(job_id = args.job_id, error = %err, "report job failed");
+                job.mark_failed(&self.ctx.db, err.to_string()).await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{models::webhook_deliveries, webhooks};
+
+/// Posts a webhook delivery to its endpoint. Failed attempts are scheduled
+/// for a retry and picked up again by the `webhooks` task.
+pub struct WebhookWorker {
+    pub ctx: AppContext,
+}
+
+#[derive(Deserialize, Debug, Serialize)]
+pub struct WebhookWorkerArgs {
+    pub delivery_id: i32,
+}
+
+#[async_trait]
+impl BackgroundWorker<WebhookWorkerArgs> for WebhookWorker {
+    fn build(ctx: &AppContext) -> Self {
+        Self { ctx: ctx.clone() }
+    }
+
+    async fn perform(&self, args: WebhookWorkerArgs) -> Result<()> {
+        let Some(delivery) = webhook_deliveries::Entity::find_by_id(args.delivery_id)
+            .one(&self.ctx.db)
+            .await?
+        else {
+            tracing::warn!(
+                delivery_id = args.delivery_id,
+                "webhook delivery no longer exists"
+            );
+            return Ok(());
+        };
+        webhooks::deliver(&self.ctx, delivery).await
+    }
+}
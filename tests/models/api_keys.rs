@@ -0,0 +1,49 @@
+use bestbefors::{
+    app::App,
+    models::api_keys::{self, ApiScope},
+};
+use chrono::{Duration, Utc};
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, ActiveValue, IntoActiveModel};
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn finds_only_active_keys_by_token() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let (key, token) = api_keys::ActiveModel::generate(
+        1,
+        "Scanner".to_string(),
+        &[ApiScope::SubmitChecks],
+        Some((Utc::now() + Duration::days(1)).into()),
+    );
+    let key = key.insert(db).await.unwrap();
+
+    let found = api_keys::Model::find_active_by_token(db, &token)
+        .await
+        .unwrap();
+    assert_eq!(found.id, key.id);
+    assert!(found.has_scope(ApiScope::SubmitChecks));
+    assert!(!found.has_scope(ApiScope::Read));
+    assert!(api_keys::Model::find_active_by_token(db, "bbk_unknown")
+        .await
+        .is_err());
+
+    let mut expired = key.clone().into_active_model();
+    expired.expires_at = ActiveValue::set(Some((Utc::now() - Duration::minutes(1)).into()));
+    let expired = expired.update(db).await.unwrap();
+    assert!(api_keys::Model::find_active_by_token(db, &token)
+        .await
+        .is_err());
+
+    let mut revoked = expired.into_active_model();
+    revoked.expires_at = ActiveValue::set(None);
+    revoked.revoked_at = ActiveValue::set(Some(Utc::now().into()));
+    revoked.update(db).await.unwrap();
+    assert!(api_keys::Model::find_active_by_token(db, &token)
+        .await
+        .is_err());
+}
@@ -1,4 +1,8 @@
-use bestbefors::app::App;
+use bestbefors::{
+    app::App,
+    models::{intervals, inventory_items},
+};
+use chrono::{DateTime, FixedOffset};
 use loco_rs::testing::prelude::*;
 use serial_test::serial;
 
@@ -29,3 +33,52 @@ async fn test_model() {
     // snapshot the result:
     // assert_debug_snapshot!(item);
 }
+
+fn timestamp(value: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(value).unwrap()
+}
+
+fn yearly_interval(sqlite_modifier: &str) -> intervals::Model {
+    intervals::Model {
+        created_at: timestamp("2025-01-01T00:00:00Z"),
+        updated_at: timestamp("2025-01-01T00:00:00Z"),
+        id: 4,
+        code: "INTERVAL_YEARLY".to_string(),
+        sqlite_modifier: sqlite_modifier.to_string(),
+        sqlite_num_of_modifier: 1,
+    }
+}
+
+#[test]
+fn next_due_at_uses_interval_or_earlier_expiry() {
+    let mut item = inventory_items::Model {
+        created_at: timestamp("2025-01-01T00:00:00Z"),
+        updated_at: timestamp("2025-01-01T00:00:00Z"),
+        tenant_id: 1,
+        id: 1,
+        name: "Harness".to_string(),
+        serial_number: None,
+        last_checked_at: Some(timestamp("2025-06-01T00:00:00Z")),
+        expiry: None,
+        inventory_item_kind_id: 1,
+        checklist_id: 1,
+        interval_id: 4,
+        next_due_at: None,
+    };
+    let interval = yearly_interval("years");
+
+    assert_eq!(
+        item.compute_next_due_at(Some(&interval)),
+        Some(timestamp("2026-06-01T00:00:00Z"))
+    );
+
+    item.expiry = Some(timestamp("2026-03-31T00:00:00Z"));
+    assert_eq!(item.expiry, item.compute_next_due_at(Some(&interval)));
+
+    item.expiry = None;
+    assert_eq!(
+        item.compute_next_due_at(Some(&yearly_interval("never"))),
+        None
+    );
+    assert_eq!(item.compute_next_due_at(None), None);
+}
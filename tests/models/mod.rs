@@ -1,5 +1,7 @@
 mod users;
 
+mod api_keys;
+
 mod checklist_steps;
 mod checklists;
 mod executed_checklist_steps;
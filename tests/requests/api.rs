@@ -0,0 +1,394 @@
+use std::collections::BTreeSet;
+
+use axum_test::TestServer;
+use bestbefors::{
+    app::App,
+    controllers::api::{self, openapi::ApiDoc},
+    initializers::app_cache::AppCacheInitializer,
+    models::api_keys,
+};
+use loco_rs::{app::Hooks, boot::BootResult, testing::prelude::*};
+use sea_orm::EntityTrait;
+use serde_json::{json, Value};
+use serial_test::serial;
+use utoipa::OpenApi;
+
+use super::prepare_data;
+
+/// Plain text `users.api_key` of the seeded user 1.
+const USER1_LEGACY_API_KEY: &str = "lo-95ec80d7-cb60-4b70-9b4b-9ef74cb88758";
+
+async fn boot_with_seed() -> (BootResult, TestServer) {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    (boot, server)
+}
+
+#[tokio::test]
+#[serial]
+async fn rejects_requests_without_valid_token() {
+    let (_boot, server) = boot_with_seed().await;
+
+    let response = server.get("/api/v1/items").await;
+    assert_eq!(response.status_code(), 401);
+
+    let (auth_key, auth_value) =
+        prepare_data::auth_header("lo-00000000-0000-0000-0000-000000000000");
+    let response = server
+        .get("/api/v1/items")
+        .add_header(auth_key, auth_value)
+        .await;
+    assert_eq!(response.status_code(), 401);
+    let body: Value = response.json();
+    assert_eq!(body["error"], "unauthorized");
+
+    // the plain text users.api_key is no credential
+    let (auth_key, auth_value) = prepare_data::auth_header(USER1_LEGACY_API_KEY);
+    let response = server
+        .get("/api/v1/items")
+        .add_header(auth_key, auth_value)
+        .await;
+    assert_eq!(response.status_code(), 401);
+}
+
+#[tokio::test]
+#[serial]
+async fn accepts_jwt_and_lists_with_pagination() {
+    let (boot, server) = boot_with_seed().await;
+    let user = prepare_data::init_user_login(&server, &boot.app_context).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+
+    let response = server
+        .get("/api/v1/results")
+        .add_query_param("page_size", 2)
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let body: Value = response.json();
+    assert_eq!(body["page"], 1);
+    assert_eq!(body["page_size"], 2);
+    assert_eq!(body["items"].as_array().unwrap().len(), 2);
+    assert!(body["total_items"].as_u64().unwrap() >= 3);
+
+    let response = server
+        .get("/api/v1/results")
+        .add_query_param("page", "abc")
+        .add_header(auth_key, auth_value)
+        .await;
+    assert_eq!(response.status_code(), 400);
+}
+
+#[tokio::test]
+#[serial]
+async fn only_global_admins_change_shared_lookup_tables() {
+    let (boot, server) = boot_with_seed().await;
+    let user = prepare_data::init_user_login(&server, &boot.app_context).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+    let period = json!({
+        "code": "INTERVAL_WEEKLY",
+        "sqlite_modifier": "days",
+        "sqlite_num_of_modifier": 7
+    });
+
+    for table in ["intervals", "expiries"] {
+        let response = server
+            .post(&format!("/api/v1/{table}"))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&period)
+            .await;
+        assert_eq!(response.status_code(), 403, "{table}");
+        let response = server
+            .put(&format!("/api/v1/{table}/1"))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&period)
+            .await;
+        assert_eq!(response.status_code(), 403, "{table}");
+        let response = server
+            .delete(&format!("/api/v1/{table}/1"))
+            .add_header(auth_key.clone(), auth_value.clone())
+            .await;
+        assert_eq!(response.status_code(), 403, "{table}");
+    }
+    let response = server
+        .post("/api/v1/results")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "code": "RESULT_SKIPPED" }))
+        .await;
+    assert_eq!(response.status_code(), 403);
+    let response = server
+        .delete("/api/v1/results/1")
+        .add_header(auth_key.clone(), auth_value)
+        .await;
+    assert_eq!(response.status_code(), 403);
+
+    // user1 administers the default tenant and thereby the shared tables
+    let token = prepare_data::login_user1(&server).await;
+    let (_, admin_value) = prepare_data::auth_header(&token);
+    let response = server
+        .post("/api/v1/intervals")
+        .add_header(auth_key.clone(), admin_value.clone())
+        .json(&period)
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let response = server
+        .post("/api/v1/results")
+        .add_header(auth_key, admin_value)
+        .json(&json!({ "code": "RESULT_SKIPPED" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+}
+
+#[tokio::test]
+#[serial]
+async fn manages_item_kinds_items_and_checks_with_api_key() {
+    let (boot, server) = boot_with_seed().await;
+    let token = prepare_data::user1_api_key(&boot.app_context).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&token);
+
+    let response = server
+        .post("/api/v1/item_kinds")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "name": "Harness",
+            "test_standard": "EN 358",
+            "default_checklist_id": 2,
+            "default_interval_id": 4,
+            "default_expiry_id": 1,
+            "metadata_fields": ["Manufacturer"]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let kind: Value = response.json();
+    assert_eq!(kind["metadata_fields"], json!(["Manufacturer"]));
+    let kind_id = kind["id"].as_i64().unwrap();
+
+    let response = server
+        .post("/api/v1/items")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "name": "Harness A",
+            "serial_number": "SN-42",
+            "inventory_item_kind_id": kind_id,
+            "metadata": { "Manufacturer": "AustriAlpin" }
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let item: Value = response.json();
+    assert_eq!(item["checklist_id"], 2);
+    assert_eq!(item["metadata"]["Manufacturer"], "AustriAlpin");
+    let item_id = item["id"].as_i64().unwrap();
+
+    let response = server
+        .put(&format!("/api/v1/items/{item_id}"))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "name": "Harness B",
+            "inventory_item_kind_id": kind_id,
+            "expiry": "2030-01-31",
+            "metadata": { "manufacturer": "Petzl" }
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let item: Value = response.json();
+    assert_eq!(item["name"], "Harness B");
+    assert_eq!(item["serial_number"], Value::Null);
+    assert_eq!(item["metadata"]["Manufacturer"], "Petzl");
+
+    let response = server
+        .get("/api/v1/items")
+        .add_query_param("kind_id", kind_id)
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    let items: Value = response.json();
+    assert_eq!(items["total_items"], 1);
+
+    let response = server
+        .post("/api/v1/checks")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "inventory_item_id": item_id,
+            "checked_by": 1,
+            "result_id": 1,
+            "notes": "All good",
+            "steps": [
+                { "checklist_step_id": 2, "result_id": 1 },
+                { "checklist_step_id": 3, "result_id": 1, "notes": "Buckles fine" }
+            ]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let check: Value = response.json();
+    assert_eq!(check["steps"].as_array().unwrap().len(), 2);
+    assert_eq!(check["steps"][1]["notes"], "Buckles fine");
+    let check_id = check["id"].as_i64().unwrap();
+
+    let response = server
+        .delete(&format!("/api/v1/items/{item_id}"))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server
+        .delete(&format!("/api/v1/checks/{check_id}"))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+
+    let response = server
+        .get(&format!("/api/v1/items/{item_id}"))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    let item: Value = response.json();
+    assert_eq!(item["last_checked_at"], Value::Null);
+
+    let response = server
+        .delete(&format!("/api/v1/items/{item_id}"))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+
+    let response = server
+        .get(&format!("/api/v1/items/{item_id}"))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 404);
+
+    let response = server
+        .delete(&format!("/api/v1/item_kinds/{kind_id}"))
+        .add_header(auth_key, auth_value)
+        .await;
+    assert_eq!(response.status_code(), 200);
+}
+
+#[tokio::test]
+#[serial]
+async fn serves_openapi_spec_without_auth() {
+    let (_boot, server) = boot_with_seed().await;
+
+    let response = server.get("/api/v1/openapi.json").await;
+    assert_eq!(response.status_code(), 200);
+    let spec: Value = response.json();
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+    assert!(spec["components"]["schemas"]["PerformCheckPayload"].is_object());
+    assert!(spec["components"]["schemas"]["AddChecklistParams"].is_object());
+}
+
+#[tokio::test]
+#[serial]
+async fn openapi_spec_matches_registered_api_routes() {
+    let boot = boot_test::<App>().await.unwrap();
+
+    let spec_path = format!("{}{}", api::PREFIX, api::openapi::SPEC_PATH);
+    let registered = App::routes(&boot.app_context)
+        .collect()
+        .into_iter()
+        .filter(|route| route.uri.starts_with(api::PREFIX) && route.uri != spec_path)
+        .flat_map(|route| {
+            let path = route.uri[api::PREFIX.len()..]
+                .trim_end_matches('/')
+                .to_string();
+            route
+                .actions
+                .into_iter()
+                .map(move |method| format!("{} {path}", method.as_str().to_lowercase()))
+        })
+        .collect::<BTreeSet<_>>();
+
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let documented = spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, item)| {
+            item.as_object()
+                .unwrap()
+                .keys()
+                .filter(|key| matches!(key.as_str(), "get" | "post" | "put" | "patch" | "delete"))
+                .map(move |method| format!("{method} {path}"))
+        })
+        .collect::<BTreeSet<_>>();
+
+    assert!(
+        !registered.is_empty(),
+        "expected routes under {}",
+        api::PREFIX
+    );
+    assert_eq!(registered, documented);
+}
+
+#[tokio::test]
+#[serial]
+async fn scoped_api_keys_are_enforced_and_revocable() {
+    let (boot, server) = boot_with_seed().await;
+    let user = prepare_data::init_user_login(&server, &boot.app_context).await;
+    let (auth_key, session_value) = prepare_data::auth_header(&user.token);
+
+    let response = server
+        .post("/api/v1/api_keys")
+        .add_header(auth_key.clone(), session_value.clone())
+        .json(&json!({ "name": "Scanner", "scopes": ["read"] }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let created: Value = response.json();
+    let token = created["token"].as_str().unwrap().to_string();
+    let key_id = created["id"].as_i64().unwrap();
+    assert!(token.starts_with(created["key_prefix"].as_str().unwrap()));
+    assert_eq!(created["scopes"], json!(["read"]));
+
+    let stored = api_keys::Entity::find_by_id(i32::try_from(key_id).unwrap())
+        .one(&boot.app_context.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(stored.key_hash, token);
+    assert_eq!(stored.key_hash, api_keys::hash_token(&token));
+    assert!(stored.last_used_at.is_none());
+
+    let (_, key_value) = prepare_data::auth_header(&token);
+    let response = server
+        .get("/api/v1/items")
+        .add_header(auth_key.clone(), key_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+
+    let response = server
+        .post("/api/v1/results")
+        .add_header(auth_key.clone(), key_value.clone())
+        .json(&json!({ "code": "RESULT_SKIPPED" }))
+        .await;
+    assert_eq!(response.status_code(), 403);
+
+    let response = server
+        .get("/api/v1/api_keys")
+        .add_header(auth_key.clone(), key_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 403);
+
+    let response = server
+        .get("/api/v1/api_keys")
+        .add_header(auth_key.clone(), session_value.clone())
+        .await;
+    let keys: Value = response.json();
+    assert_eq!(keys["total_items"], 1);
+    assert!(keys["items"][0]["last_used_at"].is_string());
+    assert!(keys["items"][0].get("token").is_none());
+
+    let response = server
+        .delete(&format!("/api/v1/api_keys/{key_id}"))
+        .add_header(auth_key.clone(), session_value)
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let revoked: Value = response.json();
+    assert_eq!(revoked["active"], false);
+
+    let response = server
+        .get("/api/v1/items")
+        .add_header(auth_key, key_value)
+        .await;
+    assert_eq!(response.status_code(), 401);
+}
@@ -0,0 +1,166 @@
+use axum::http::header;
+use bestbefors::{
+    app::App,
+    initializers::app_cache::AppCacheInitializer,
+    models::{inventory_item_kind_metadata_fields, inventory_item_metadata_values, users},
+};
+use chrono::{TimeZone, Utc};
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, ActiveValue, EntityTrait, IntoActiveModel};
+use serde_json::Value;
+use serial_test::serial;
+
+use super::prepare_data;
+
+/// `DTSTART` of the event with `uid` in `ics`.
+fn event_date<'a>(ics: &'a str, uid: &str) -> Option<&'a str> {
+    let event = &ics[ics.find(&format!("UID:{uid}\r\n"))?..];
+    let start = event.find("DTSTART;VALUE=DATE:")? + "DTSTART;VALUE=DATE:".len();
+    Some(&event[start..start + 8])
+}
+
+#[tokio::test]
+#[serial]
+async fn serves_due_dates_as_calendar_feed() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let db = &boot.app_context.db;
+
+    let now = Utc::now();
+    let kind = prepare_data::create_kind(&boot.app_context, 1, "Harness").await;
+    let location_field = inventory_item_kind_metadata_fields::ActiveModel {
+        id: ActiveValue::not_set(),
+        name: ActiveValue::set("Location".to_string()),
+        position: ActiveValue::set(1),
+        inventory_item_kind_id: ActiveValue::set(kind.id),
+        created_at: ActiveValue::set(now.into()),
+        updated_at: ActiveValue::set(now.into()),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+    let last_checked_at = Utc.with_ymd_and_hms(2025, 3, 1, 10, 0, 0).unwrap();
+    let item = prepare_data::create_serial_item(
+        &boot.app_context,
+        &kind,
+        "Harness A",
+        "SN-42",
+        Some(last_checked_at),
+        Some(Utc.with_ymd_and_hms(2030, 1, 31, 0, 0, 0).unwrap()),
+    )
+    .await;
+    inventory_item_metadata_values::ActiveModel {
+        id: ActiveValue::not_set(),
+        value: ActiveValue::set("Store A".to_string()),
+        inventory_item_id: ActiveValue::set(item.id),
+        inventory_item_kind_metadata_field_id: ActiveValue::set(location_field.id),
+        created_at: ActiveValue::set(now.into()),
+        updated_at: ActiveValue::set(now.into()),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, &boot.app_context).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+
+    let response = server
+        .get("/api/auth/calendar")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.json::<Value>()["active"], false);
+    assert_eq!(response.json::<Value>()["url"], Value::Null);
+    let response = server
+        .post("/api/auth/calendar")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let url = response.json::<Value>()["url"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(url.contains("/calendar/cal-"));
+    let path = &url[url.find("/calendar/").unwrap()..];
+
+    let response = server.get(path).await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response
+        .header(header::CONTENT_TYPE)
+        .to_str()
+        .unwrap()
+        .starts_with("text/calendar"));
+    let ics = response.text();
+    let inspection_uid = format!("item-{}-inspection@bestbefors", item.id);
+    let expiry_uid = format!("item-{}-expiry@bestbefors", item.id);
+    assert_eq!(event_date(&ics, &inspection_uid), Some("20260301"));
+    assert_eq!(event_date(&ics, &expiry_uid), Some("20300131"));
+    assert!(ics.contains("SUMMARY:Inspection due: Harness A\r\n"));
+    assert!(ics.contains("SUMMARY:Expires: Harness A\r\n"));
+    assert!(ics.contains("Location: Store A"));
+
+    let ics = server
+        .get(path)
+        .add_query_param("location", "store b")
+        .await
+        .text();
+    assert_eq!(event_date(&ics, &expiry_uid), None);
+    let ics = server
+        .get(path)
+        .add_query_param("kind_id", kind.id)
+        .add_query_param("location", "store a")
+        .await
+        .text();
+    assert_eq!(event_date(&ics, &expiry_uid), Some("20300131"));
+    assert_eq!(
+        server
+            .get(path)
+            .add_query_param("kind_id", "abc")
+            .await
+            .status_code(),
+        400
+    );
+
+    // a later check moves the next inspection
+    let mut checked = item.into_active_model();
+    checked.last_checked_at = ActiveValue::set(Some(
+        Utc.with_ymd_and_hms(2026, 4, 15, 9, 0, 0).unwrap().into(),
+    ));
+    checked.update(db).await.unwrap();
+    let ics = server.get(path).await.text();
+    assert_eq!(event_date(&ics, &inspection_uid), Some("20270415"));
+
+    // members of a scoped team only subscribe to their items
+    let rope = prepare_data::create_kind(&boot.app_context, 1, "Rope").await;
+    let rope = prepare_data::create_item(&boot.app_context, &rope, "Rope A", now, None, None).await;
+    prepare_data::scope_user_to_item(&boot.app_context, user.user.id, &rope).await;
+    let ics = server.get(path).await.text();
+    assert_eq!(event_date(&ics, &inspection_uid), None);
+    assert!(ics.contains("SUMMARY:Inspection due: Rope A\r\n"));
+
+    // only the hash of the token is stored, so the URL is not shown again
+    let response = server
+        .get("/api/auth/calendar")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await
+        .json::<Value>();
+    assert_eq!(response["active"], true);
+    assert_eq!(response["url"], Value::Null);
+    let token = &path["/calendar/".len()..path.len() - ".ics".len()];
+    let stored = users::Entity::find_by_id(user.user.id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(stored.calendar_token_hash.as_deref(), Some(token));
+
+    let response = server
+        .post("/api/auth/calendar")
+        .add_header(auth_key, auth_value)
+        .await;
+    assert_ne!(response.json::<Value>()["url"].as_str().unwrap(), url);
+    assert_eq!(server.get(path).await.status_code(), 404);
+}
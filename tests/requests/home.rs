@@ -0,0 +1,180 @@
+use bestbefors::{
+    app::App,
+    initializers::app_cache::AppCacheInitializer,
+    models::{inventory_item_checks, inventory_items},
+};
+use chrono::{Days, Utc};
+use loco_rs::{app::AppContext, testing::prelude::*};
+use sea_orm::{ActiveModelTrait, ActiveValue, EntityTrait};
+use serde_json::json;
+use serial_test::serial;
+
+use super::prepare_data;
+
+async fn reload(ctx: &AppContext, item: &inventory_items::Model) -> inventory_items::Model {
+    inventory_items::Entity::find_by_id(item.id)
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+/// Ids of the dashboard rows in the order shown.
+fn dashboard_order(page: &str, items: &[&inventory_items::Model]) -> Vec<i32> {
+    let mut rows = items
+        .iter()
+        .map(|item| {
+            let position = page.find(&format!("data-item-id=\"{}\"", item.id)).unwrap();
+            (position, item.id)
+        })
+        .collect::<Vec<_>>();
+    rows.sort_unstable();
+    rows.into_iter().map(|(_, id)| id).collect()
+}
+
+#[tokio::test]
+#[serial]
+async fn dashboard_sorts_by_stored_due_dates() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let now = Utc::now();
+    let kind = prepare_data::create_kind(ctx, 1, "Rope").await;
+    // yearly checks: overdue, due in five days, expiring in three, next year
+    let overdue =
+        prepare_data::create_item(ctx, &kind, "Overdue", now - Days::new(400), None, None).await;
+    let soon =
+        prepare_data::create_item(ctx, &kind, "Soon", now - Days::new(360), None, None).await;
+    let expiring =
+        prepare_data::create_item(ctx, &kind, "Expiring", now, None, Some(now + Days::new(3)))
+            .await;
+    let fresh = prepare_data::create_item(ctx, &kind, "Fresh", now, None, None).await;
+    let items = [&overdue, &soon, &expiring, &fresh];
+
+    assert_eq!(expiring.next_due_at, expiring.expiry);
+    assert!(fresh.next_due_at.unwrap() > (now + Days::new(364)).fixed_offset());
+    assert!(overdue.next_due_at.unwrap() < now.fixed_offset());
+
+    let server = prepare_data::test_server(&boot);
+    let token = prepare_data::login_user1(&server).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&token);
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let page = server.get("/").await.text();
+    assert_eq!(
+        dashboard_order(&page, &items),
+        [overdue.id, expiring.id, soon.id, fresh.id]
+    );
+    assert!(page.contains(&format!(
+        "data-item-id=\"{}\" class=\"table-danger\"",
+        overdue.id
+    )));
+    assert!(page.contains(&format!(
+        "data-item-id=\"{}\" class=\"table-warning\"",
+        soon.id
+    )));
+
+    // a check moves the item's due date a year ahead
+    for item in [&overdue, &fresh, &fresh, &fresh, &fresh] {
+        let response = server
+            .post("/api/v1/checks")
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&json!({
+                "inventory_item_id": item.id,
+                "checked_by": 1,
+                "result_id": 1,
+                "steps": [
+                    { "checklist_step_id": 2, "result_id": 1 },
+                    { "checklist_step_id": 3, "result_id": 1 }
+                ]
+            }))
+            .await;
+        assert_eq!(response.status_code(), 200);
+    }
+    let checked = reload(ctx, &overdue).await;
+    assert!(checked.next_due_at.unwrap() > (now + Days::new(364)).fixed_offset());
+    let page = server.get("/").await.text();
+    assert_eq!(
+        dashboard_order(&page, &items),
+        [expiring.id, soon.id, overdue.id, fresh.id]
+    );
+
+    // only the latest checks are loaded
+    let latest =
+        inventory_item_checks::Entity::latest_by_item(&ctx.db, &[overdue.id, fresh.id], 3, None)
+            .await
+            .unwrap();
+    assert_eq!(latest[&overdue.id].len(), 1);
+    let fresh_checks = &latest[&fresh.id];
+    assert_eq!(fresh_checks.len(), 3);
+    assert!(fresh_checks
+        .windows(2)
+        .all(|pair| (pair[0].checked_at, pair[0].id) > (pair[1].checked_at, pair[1].id)));
+    let newest = inventory_item_checks::Entity::find()
+        .all(&ctx.db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|check| check.id)
+        .max()
+        .unwrap();
+    assert_eq!(fresh_checks[0].id, newest);
+    let earlier = inventory_item_checks::Entity::latest_by_item(
+        &ctx.db,
+        &[overdue.id, fresh.id],
+        3,
+        Some(latest[&overdue.id][0].checked_at),
+    )
+    .await
+    .unwrap();
+    assert!(!earlier.contains_key(&overdue.id));
+
+    // a partial update takes the other fields from the stored item
+    let mut partial = inventory_items::ActiveModel {
+        id: ActiveValue::unchanged(soon.id),
+        ..Default::default()
+    };
+    partial.last_checked_at = ActiveValue::set(Some(now.into()));
+    partial.update(&ctx.db).await.unwrap();
+    let partially = reload(ctx, &soon).await;
+    assert!(partially.next_due_at.unwrap() > (now + Days::new(364)).fixed_offset());
+    partial = inventory_items::ActiveModel {
+        id: ActiveValue::unchanged(soon.id),
+        last_checked_at: ActiveValue::set(soon.last_checked_at),
+        ..Default::default()
+    };
+    partial.update(&ctx.db).await.unwrap();
+    assert_eq!(reload(ctx, &soon).await.next_due_at, soon.next_due_at);
+
+    // changing the interval recomputes the due dates of its items
+    let response = server
+        .put("/api/v1/intervals/4")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "code": "INTERVAL_YEARLY",
+            "sqlite_modifier": "months",
+            "sqlite_num_of_modifier": 1
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(reload(ctx, &soon).await.next_due_at.unwrap() < now.fixed_offset());
+    assert!(
+        reload(ctx, &checked).await.next_due_at.unwrap() < (now + Days::new(32)).fixed_offset()
+    );
+
+    // editing the item does too
+    let response = server
+        .put(&format!("/api/v1/items/{}", expiring.id))
+        .add_header(auth_key, auth_value)
+        .json(&json!({
+            "name": "Expiring",
+            "inventory_item_kind_id": kind.id,
+            "interval_id": 4,
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let edited = reload(ctx, &expiring).await;
+    assert_eq!(edited.expiry, None);
+    assert!(edited.next_due_at.unwrap() > (now + Days::new(27)).fixed_offset());
+}
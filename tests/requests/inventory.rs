@@ -1,38 +1,33 @@
 use axum::http::header;
-use axum_test::TestServer;
 use bestbefors::{
     app::App,
+    initializers::app_cache::AppCacheInitializer,
     models::{
-        _entities::checklist_steps::Column as ChecklistStepsColumn, checklist_steps,
-        executed_checklist_steps, executed_checklists, inventory_item_check_steps,
+        _entities::{
+            checklist_steps::Column as ChecklistStepsColumn,
+            inventory_item_kind_metadata_fields::Column as MetadataFieldsColumn,
+        },
+        checklist_steps, executed_checklist_steps, executed_checklists, inventory_item_check_steps,
         inventory_item_checks, inventory_item_kind_metadata_fields, inventory_item_kinds,
         inventory_item_metadata_values, inventory_items,
     },
 };
-use loco_rs::testing::prelude::*;
+use loco_rs::{app::AppContext, testing::prelude::*};
 use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter};
+use serde_json::{json, Value};
 use serial_test::serial;
 
+use super::prepare_data;
+
 #[tokio::test]
 #[serial]
 async fn downloads_single_item_history_pdf() {
     let boot = boot_test::<App>().await.unwrap();
     seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
 
     let now = chrono::Utc::now();
-    let kind = inventory_item_kinds::ActiveModel {
-        id: ActiveValue::not_set(),
-        name: ActiveValue::set("Harness".to_string()),
-        test_standard: ActiveValue::set("EN 358".to_string()),
-        default_checklist_id: ActiveValue::set(2),
-        default_interval_id: ActiveValue::set(4),
-        default_expiry_id: ActiveValue::set(1),
-        created_at: ActiveValue::set(now.into()),
-        updated_at: ActiveValue::set(now.into()),
-    }
-    .insert(&boot.app_context.db)
-    .await
-    .unwrap();
+    let kind = prepare_data::create_kind(ctx, 1, "Harness").await;
 
     let metadata_field = inventory_item_kind_metadata_fields::ActiveModel {
         id: ActiveValue::not_set(),
@@ -42,25 +37,13 @@ async fn downloads_single_item_history_pdf() {
         created_at: ActiveValue::set(now.into()),
         updated_at: ActiveValue::set(now.into()),
     }
-    .insert(&boot.app_context.db)
+    .insert(&ctx.db)
     .await
     .unwrap();
 
-    let item = inventory_items::ActiveModel {
-        id: ActiveValue::not_set(),
-        name: ActiveValue::set("Harness A".to_string()),
-        serial_number: ActiveValue::set(Some("SN-42".to_string())),
-        last_checked_at: ActiveValue::set(Some(now.into())),
-        expiry: ActiveValue::set(Some(now.into())),
-        inventory_item_kind_id: ActiveValue::set(kind.id),
-        checklist_id: ActiveValue::set(2),
-        interval_id: ActiveValue::set(4),
-        created_at: ActiveValue::set(now.into()),
-        updated_at: ActiveValue::set(now.into()),
-    }
-    .insert(&boot.app_context.db)
-    .await
-    .unwrap();
+    let item =
+        prepare_data::create_serial_item(ctx, &kind, "Harness A", "SN-42", Some(now), Some(now))
+            .await;
 
     inventory_item_metadata_values::ActiveModel {
         id: ActiveValue::not_set(),
@@ -70,7 +53,7 @@ async fn downloads_single_item_history_pdf() {
         created_at: ActiveValue::set(now.into()),
         updated_at: ActiveValue::set(now.into()),
     }
-    .insert(&boot.app_context.db)
+    .insert(&ctx.db)
     .await
     .unwrap();
 
@@ -81,13 +64,13 @@ async fn downloads_single_item_history_pdf() {
         created_at: ActiveValue::set(now.into()),
         updated_at: ActiveValue::set(now.into()),
     }
-    .insert(&boot.app_context.db)
+    .insert(&ctx.db)
     .await
     .unwrap();
 
     let template_steps = checklist_steps::Entity::find()
         .filter(ChecklistStepsColumn::ChecklistId.eq(2))
-        .all(&boot.app_context.db)
+        .all(&ctx.db)
         .await
         .unwrap();
     assert!(
@@ -106,7 +89,7 @@ async fn downloads_single_item_history_pdf() {
             created_at: ActiveValue::set(now.into()),
             updated_at: ActiveValue::set(now.into()),
         }
-        .insert(&boot.app_context.db)
+        .insert(&ctx.db)
         .await
         .unwrap();
         executed_steps.push(executed_step);
@@ -121,10 +104,11 @@ async fn downloads_single_item_history_pdf() {
         executed_checklist_id: ActiveValue::set(executed_checklist.id),
         checked_by: ActiveValue::set(1),
         result_id: ActiveValue::set(1),
+        signature: ActiveValue::set(None),
         created_at: ActiveValue::set(now.into()),
         updated_at: ActiveValue::set(now.into()),
     }
-    .insert(&boot.app_context.db)
+    .insert(&ctx.db)
     .await
     .unwrap();
 
@@ -138,15 +122,13 @@ async fn downloads_single_item_history_pdf() {
             created_at: ActiveValue::set(now.into()),
             updated_at: ActiveValue::set(now.into()),
         }
-        .insert(&boot.app_context.db)
+        .insert(&ctx.db)
         .await
         .unwrap();
     }
 
-    let server = TestServer::builder()
-        .mock_transport()
-        .build(boot.router.clone().unwrap())
-        .unwrap();
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
     let response = server
         .get(&format!("/inventory/item/{}/report.pdf", item.id))
         .await;
@@ -172,3 +154,292 @@ async fn downloads_single_item_history_pdf() {
         "expected response body to start with PDF signature"
     );
 }
+
+/// 1x1 pixel PNG as captured by the signature pad.
+const SIGNATURE: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+#[tokio::test]
+#[serial]
+async fn downloads_signed_check_certificate() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+
+    let kind = prepare_data::create_kind(&boot.app_context, 1, "Harness").await;
+    let item = prepare_data::create_serial_item(
+        &boot.app_context,
+        &kind,
+        "Harness A",
+        "SN-42",
+        None,
+        None,
+    )
+    .await;
+
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+    let check_payload = |signature: &str| {
+        json!({
+            "checked_by": 1,
+            "result_id": 1,
+            "steps": [
+                { "checklist_step_id": 2, "result_id": 1 },
+                { "checklist_step_id": 3, "result_id": 1, "notes": "Stitching ok" }
+            ],
+            "signature": signature
+        })
+    };
+
+    let response = server
+        .post(&format!("/inventory/item/{}/check", item.id))
+        .json(&check_payload("data:image/png;base64,bm90IGEgcG5n"))
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server
+        .post(&format!("/inventory/item/{}/check", item.id))
+        .json(&check_payload(SIGNATURE))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let check_id = response.json::<Value>()["check_id"].as_i64().unwrap();
+
+    let response = server
+        .get(&format!(
+            "/inventory/item/{}/checks/{check_id}/certificate.pdf",
+            item.id
+        ))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(
+        response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok()),
+        Some("application/pdf")
+    );
+    assert!(response.as_bytes().starts_with(b"%PDF-"));
+
+    let response = server
+        .get(&format!(
+            "/inventory/item/{}/checks/{check_id}/certificate.pdf",
+            item.id + 1
+        ))
+        .await;
+    assert_eq!(response.status_code(), 404);
+
+    let check = inventory_item_checks::Entity::find_by_id(i32::try_from(check_id).unwrap())
+        .one(&boot.app_context.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(check.signature.as_deref(), Some(SIGNATURE));
+    let steps = inventory_item_check_steps::Entity::find()
+        .all(&boot.app_context.db)
+        .await
+        .unwrap();
+    let code = check.verification_code("secret", &steps);
+    assert_eq!(code.len(), 24);
+    assert_eq!(code, check.verification_code("secret", &steps));
+    assert_ne!(code, check.verification_code("other secret", &steps));
+    let tampered = inventory_item_checks::Model {
+        notes: Some("Edited afterwards".to_string()),
+        ..check.clone()
+    };
+    assert_ne!(code, tampered.verification_code("secret", &steps));
+}
+
+async fn create_list_item(
+    ctx: &AppContext,
+    kind: &inventory_item_kinds::Model,
+    name: &str,
+    last_checked_days_ago: u64,
+    expired: bool,
+) -> inventory_items::Model {
+    let now = chrono::Utc::now();
+    prepare_data::create_serial_item(
+        ctx,
+        kind,
+        name,
+        &name.replace(' ', "-").to_uppercase(),
+        Some(now - chrono::Days::new(last_checked_days_ago)),
+        expired.then(|| now - chrono::Days::new(1)),
+    )
+    .await
+}
+
+async fn set_list_metadata(ctx: &AppContext, kind_id: i32, item_id: i32, values: &[(&str, &str)]) {
+    let now = chrono::Utc::now();
+    for (position, (name, value)) in (1..).zip(values) {
+        let existing = inventory_item_kind_metadata_fields::Entity::find()
+            .filter(MetadataFieldsColumn::InventoryItemKindId.eq(kind_id))
+            .filter(MetadataFieldsColumn::Name.eq(*name))
+            .one(&ctx.db)
+            .await
+            .unwrap();
+        let field = match existing {
+            Some(field) => field,
+            None => inventory_item_kind_metadata_fields::ActiveModel {
+                id: ActiveValue::not_set(),
+                name: ActiveValue::set((*name).to_string()),
+                position: ActiveValue::set(position),
+                inventory_item_kind_id: ActiveValue::set(kind_id),
+                created_at: ActiveValue::set(now.into()),
+                updated_at: ActiveValue::set(now.into()),
+            }
+            .insert(&ctx.db)
+            .await
+            .unwrap(),
+        };
+        inventory_item_metadata_values::ActiveModel {
+            id: ActiveValue::not_set(),
+            value: ActiveValue::set((*value).to_string()),
+            inventory_item_id: ActiveValue::set(item_id),
+            inventory_item_kind_metadata_field_id: ActiveValue::set(field.id),
+            created_at: ActiveValue::set(now.into()),
+            updated_at: ActiveValue::set(now.into()),
+        }
+        .insert(&ctx.db)
+        .await
+        .unwrap();
+    }
+}
+
+/// Names of the items listed on a page of the inventory list.
+fn listed(page: &str, names: &[&str]) -> Vec<String> {
+    names
+        .iter()
+        .filter(|name| page.contains(&format!("<td>{name}</td>")))
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[tokio::test]
+#[serial]
+async fn filters_sorts_and_paginates_the_inventory_list() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let now = chrono::Utc::now();
+    let rope = prepare_data::create_kind(ctx, 1, "Rope").await;
+    let helmet = prepare_data::create_kind(ctx, 1, "Helmet").await;
+
+    let alpha = create_list_item(ctx, &rope, "Rope Alpha", 0, false).await;
+    let beta = create_list_item(ctx, &rope, "Rope Beta", 400, false).await;
+    create_list_item(ctx, &helmet, "Helmet Gamma", 0, true).await;
+    let delta = create_list_item(ctx, &helmet, "Helmet Delta", 0, false).await;
+    create_list_item(ctx, &rope, "Rope Epsilon", 360, false).await;
+    set_list_metadata(
+        ctx,
+        rope.id,
+        alpha.id,
+        &[("Location", "Hall 1"), ("Color", "red")],
+    )
+    .await;
+    set_list_metadata(ctx, rope.id, beta.id, &[("Location", "Tower")]).await;
+
+    // the latest check of Delta failed
+    let executed_checklist = executed_checklists::ActiveModel {
+        id: ActiveValue::not_set(),
+        name: ActiveValue::set("Helmet check".to_string()),
+        description: ActiveValue::set(None),
+        created_at: ActiveValue::set(now.into()),
+        updated_at: ActiveValue::set(now.into()),
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+    for (days_ago, result_id) in [(30, 1), (0, 2)] {
+        inventory_item_checks::ActiveModel {
+            id: ActiveValue::not_set(),
+            finished: ActiveValue::set(true),
+            checked_at: ActiveValue::set((now - chrono::Days::new(days_ago)).into()),
+            notes: ActiveValue::set(None),
+            inventory_item_id: ActiveValue::set(delta.id),
+            executed_checklist_id: ActiveValue::set(executed_checklist.id),
+            checked_by: ActiveValue::set(1),
+            result_id: ActiveValue::set(result_id),
+            signature: ActiveValue::set(None),
+            created_at: ActiveValue::set(now.into()),
+            updated_at: ActiveValue::set(now.into()),
+        }
+        .insert(&ctx.db)
+        .await
+        .unwrap();
+    }
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+    let names = [
+        "Rope Alpha",
+        "Rope Beta",
+        "Helmet Gamma",
+        "Helmet Delta",
+        "Rope Epsilon",
+    ];
+    let cases = [
+        (
+            "",
+            vec![
+                "Rope Alpha",
+                "Rope Beta",
+                "Helmet Gamma",
+                "Helmet Delta",
+                "Rope Epsilon",
+            ],
+        ),
+        ("status=expired", vec!["Helmet Gamma"]),
+        ("status=failed", vec!["Helmet Delta"]),
+        ("status=overdue", vec!["Rope Beta"]),
+        ("status=compliant", vec!["Rope Alpha", "Rope Epsilon"]),
+        ("urgency=critical", vec!["Rope Beta", "Helmet Gamma"]),
+        ("urgency=warning", vec!["Rope Epsilon"]),
+        ("urgency=normal", vec!["Rope Alpha", "Helmet Delta"]),
+        ("location=hall", vec!["Rope Alpha"]),
+        ("field=color&value=RED", vec!["Rope Alpha"]),
+        (
+            &*format!("kind_id={}", helmet.id),
+            vec!["Helmet Gamma", "Helmet Delta"],
+        ),
+        ("q=helm&urgency=normal", vec!["Helmet Delta"]),
+        ("q=50%25", vec![]),
+    ];
+    for (query, expected) in cases {
+        let page = server.get(&format!("/inventory/list?{query}")).await.text();
+        assert_eq!(listed(&page, &names), expected, "{query}");
+    }
+
+    // an exact serial number opens the item
+    let response = server.get("/inventory/list?q=rope-alpha").await;
+    assert_eq!(response.status_code(), 303);
+    assert_eq!(
+        response.header(header::LOCATION),
+        format!("/inventory/item/{}", alpha.id).as_str()
+    );
+
+    let page = server
+        .get("/inventory/list?sort=name&dir=desc&page_size=2")
+        .await
+        .text();
+    assert_eq!(listed(&page, &names), ["Rope Beta", "Rope Epsilon"]);
+    assert!(page.find("Rope Epsilon").unwrap() < page.find("Rope Beta").unwrap());
+    assert!(page.contains("list?sort=name&amp;dir=desc&amp;page=2&amp;page_size=2\" rel=\"next\""));
+    let page = server
+        .get("/inventory/list?sort=name&dir=desc&page=3&page_size=2")
+        .await
+        .text();
+    assert_eq!(listed(&page, &names), ["Helmet Delta"]);
+    let page = server
+        .get("/inventory/list?sort=kind&page_size=2")
+        .await
+        .text();
+    assert_eq!(listed(&page, &names), ["Helmet Gamma", "Helmet Delta"]);
+
+    for query in ["status=late", "sort=color", "page=x"] {
+        let response = server.get(&format!("/inventory/list?{query}")).await;
+        assert_eq!(response.status_code(), 400, "{query}");
+    }
+}
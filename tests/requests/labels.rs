@@ -0,0 +1,86 @@
+use axum::http::header;
+use bestbefors::{app::App, initializers::app_cache::AppCacheInitializer};
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+
+use super::prepare_data;
+
+#[tokio::test]
+#[serial]
+async fn downloads_label_sheet_for_item_kind() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let now = chrono::Utc::now();
+    let kind = prepare_data::create_kind(ctx, 1, "Harness").await;
+    let empty_kind = prepare_data::create_kind(ctx, 1, "Helmet").await;
+    prepare_data::create_serial_item(ctx, &kind, "Harness A", "SN-1", None, None).await;
+    prepare_data::create_item(ctx, &kind, "Harness B", now, None, None).await;
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+
+    let response = server.get("/labels/new").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains("avery_l7160"));
+
+    for (code, content, layout) in [
+        ("qr", "url", "avery_l7160"),
+        ("data_matrix", "serial", "avery_l7651"),
+    ] {
+        let response = server
+            .get("/labels/sheet.pdf")
+            .add_query_param("kind_id", kind.id)
+            .add_query_param("code", code)
+            .add_query_param("content", content)
+            .add_query_param("layout", layout)
+            .add_query_param("skip", 3)
+            .await;
+
+        assert_eq!(response.status_code(), 200, "{code} {content} {layout}");
+        assert_eq!(
+            response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok()),
+            Some("application/pdf")
+        );
+        assert!(response.as_bytes().starts_with(b"%PDF-"));
+    }
+
+    let response = server
+        .get("/labels/sheet.pdf")
+        .add_query_param("kind_id", empty_kind.id)
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server
+        .get("/labels/sheet.pdf")
+        .add_query_param("layout", "avery_l7165")
+        .add_query_param("skip", 8)
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server
+        .get("/labels/sheet.pdf")
+        .add_query_param("ids", "1,abc")
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    // members of a scoped team only print the labels of their items
+    let rope = prepare_data::create_kind(ctx, 1, "Rope").await;
+    let rope = prepare_data::create_item(ctx, &rope, "Rope A", now, None, None).await;
+    prepare_data::scope_user_to_item(ctx, 1, &rope).await;
+    let response = server
+        .get("/labels/sheet.pdf")
+        .add_query_param("kind_id", kind.id)
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let response = server
+        .get("/labels/sheet.pdf")
+        .add_query_param("ids", rope.id)
+        .await;
+    assert_eq!(response.status_code(), 200);
+}
@@ -0,0 +1,120 @@
+use axum::http::{header, HeaderValue};
+use bestbefors::{app::App, models::users};
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+
+use super::prepare_data;
+
+fn html_lang(text: &str) -> &str {
+    let start = text.find("<html lang=\"").unwrap() + "<html lang=\"".len();
+    let end = start + text[start..].find('"').unwrap();
+    &text[start..end]
+}
+
+#[tokio::test]
+#[serial]
+async fn negotiates_the_locale_of_every_page() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+
+    let response = server.get("/inventory/list").await;
+    assert_eq!(html_lang(&response.text()), "en-US");
+    assert!(response.text().contains(r#"hreflang="de-DE""#));
+    assert!(response.text().contains("/static/flags/de.svg"));
+
+    let response = server
+        .get("/inventory/list")
+        .add_header(
+            header::ACCEPT_LANGUAGE,
+            HeaderValue::from_static("de-AT, en;q=0.5"),
+        )
+        .await;
+    assert_eq!(html_lang(&response.text()), "de-DE");
+    assert!(response.text().contains("Inventar"));
+
+    let response = server
+        .get("/locale/de-DE")
+        .add_query_param("redirect", "/inventory/list")
+        .await;
+    assert_eq!(response.status_code(), 303);
+    assert_eq!(response.header(header::LOCATION), "/inventory/list");
+    let cookie = response.header(header::SET_COOKIE);
+    assert!(cookie.to_str().unwrap().starts_with("lang=de-DE;"));
+
+    let response = server
+        .get("/inventory/list")
+        .add_header(header::COOKIE, HeaderValue::from_static("lang=de-DE"))
+        .add_header(header::ACCEPT_LANGUAGE, HeaderValue::from_static("en-US"))
+        .await;
+    assert_eq!(html_lang(&response.text()), "de-DE");
+
+    let response = server
+        .get("/inventory/list")
+        .add_query_param("lang", "en-US")
+        .add_header(header::COOKIE, HeaderValue::from_static("lang=de-DE"))
+        .await;
+    assert_eq!(html_lang(&response.text()), "en-US");
+
+    for redirect in ["//example.com", "/\\example.com", "https://example.com"] {
+        let response = server
+            .get("/locale/de-DE")
+            .add_query_param("redirect", redirect)
+            .await;
+        assert_eq!(response.header(header::LOCATION), "/", "{redirect}");
+    }
+    assert_eq!(server.get("/locale/xx").await.status_code(), 404);
+}
+
+#[tokio::test]
+#[serial]
+async fn stores_the_language_preference_of_users() {
+    let boot = boot_test::<App>().await.unwrap();
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, &boot.app_context).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+
+    let response = server
+        .post("/api/auth/locale")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&serde_json::json!({ "locale": "klingon" }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server
+        .post("/api/auth/locale")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&serde_json::json!({ "locale": "de" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response
+        .header(header::SET_COOKIE)
+        .to_str()
+        .unwrap()
+        .starts_with("lang=de-DE;"));
+    let stored = users::Model::find_by_pid(&boot.app_context.db, &user.user.pid.to_string())
+        .await
+        .unwrap();
+    assert_eq!(stored.locale.as_deref(), Some("de-DE"));
+
+    let response = server
+        .get("/inventory/list")
+        .add_header(auth_key, auth_value)
+        .await;
+    assert_eq!(html_lang(&response.text()), "de-DE");
+
+    let response = server
+        .post("/api/auth/login")
+        .json(&serde_json::json!({
+            "email": stored.email,
+            "password": "1234",
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response
+        .header(header::SET_COOKIE)
+        .to_str()
+        .unwrap()
+        .starts_with("lang=de-DE;"));
+}
@@ -1,3 +1,20 @@
+mod api;
 mod auth;
+mod calendar;
+mod home;
 mod inventory;
+mod labels;
+mod locale;
+mod notifications;
 mod prepare_data;
+mod report_templates;
+mod reports;
+mod responsibilities;
+mod scan;
+mod search;
+mod teams;
+mod tenants;
+mod timezone;
+mod translations;
+mod verify;
+mod webhooks;
@@ -0,0 +1,264 @@
+use bestbefors::{
+    app::App,
+    initializers::app_cache::AppCacheInitializer,
+    models::{
+        inventory_item_kind_metadata_fields, inventory_item_kinds, inventory_item_metadata_values,
+        inventory_items, sent_notifications, webhook_deliveries,
+        webhook_endpoints::{self, WebhookEvent},
+    },
+    notifications::{self, NOTIFICATION_EVENT},
+    timezone,
+};
+use chrono::{Duration, TimeZone, Timelike, Utc};
+use loco_rs::{app::AppContext, testing::prelude::*};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, IntoActiveModel, PaginatorTrait,
+    QueryFilter,
+};
+use serde_json::{json, Value};
+use serial_test::serial;
+
+use super::prepare_data;
+
+/// Creates an item checked just now with its location.
+async fn create_item(
+    ctx: &AppContext,
+    kind: &inventory_item_kinds::Model,
+    location_field_id: i32,
+    name: &str,
+    location: &str,
+    expiry: chrono::DateTime<Utc>,
+) -> inventory_items::Model {
+    let now = Utc::now();
+    let item = prepare_data::create_item(ctx, kind, name, now, Some(now), Some(expiry)).await;
+    inventory_item_metadata_values::ActiveModel {
+        id: ActiveValue::not_set(),
+        value: ActiveValue::set(location.to_string()),
+        inventory_item_id: ActiveValue::set(item.id),
+        inventory_item_kind_metadata_field_id: ActiveValue::set(location_field_id),
+        created_at: ActiveValue::set(now.into()),
+        updated_at: ActiveValue::set(now.into()),
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+    item
+}
+
+#[tokio::test]
+#[serial]
+async fn sends_subscribed_items_once_per_due_date() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let now = Utc::now();
+    let kind = prepare_data::create_kind(ctx, 1, "Rope").await;
+    let location_field = inventory_item_kind_metadata_fields::ActiveModel {
+        id: ActiveValue::not_set(),
+        name: ActiveValue::set("Location".to_string()),
+        position: ActiveValue::set(1),
+        inventory_item_kind_id: ActiveValue::set(kind.id),
+        created_at: ActiveValue::set(now.into()),
+        updated_at: ActiveValue::set(now.into()),
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+    let overdue = create_item(
+        ctx,
+        &kind,
+        location_field.id,
+        "Rope A",
+        "Store A",
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+    )
+    .await;
+    create_item(
+        ctx,
+        &kind,
+        location_field.id,
+        "Rope B",
+        "Store B",
+        now + Duration::days(3),
+    )
+    .await;
+    let endpoint = webhook_endpoints::ActiveModel::generate(
+        1,
+        "Pager".to_string(),
+        "http://127.0.0.1:9/hook".to_string(),
+        &[WebhookEvent::ItemCritical],
+    )
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, ctx).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+
+    let response = server
+        .post("/api/v1/notifications/subscriptions")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "min_urgency": "critical",
+            "channel": "webhook",
+            "frequency": "daily",
+        }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let response = server
+        .post("/api/v1/notifications/subscriptions")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "min_urgency": "warning",
+            "channel": "email",
+            "frequency": "immediate",
+            "quiet_start_hour": 22,
+        }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server
+        .post("/api/v1/notifications/subscriptions")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "inventory_item_kind_id": kind.id,
+            "location": "store a",
+            "min_urgency": "warning",
+            "channel": "email",
+            "frequency": "immediate",
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let email_subscription = response.json::<Value>();
+    let response = server
+        .post("/api/v1/notifications/subscriptions")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "min_urgency": "critical",
+            "channel": "webhook",
+            "webhook_endpoint_id": endpoint.id,
+            "frequency": "daily",
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let webhook_subscription = response.json::<Value>();
+    assert_eq!(webhook_subscription["channel"], "webhook");
+
+    // only the overdue item is at the location and critical
+    assert_eq!(notifications::send_due(ctx, now).await.unwrap(), 2);
+    let mails = prepare_data::notification_mails(ctx);
+    assert_eq!(mails.len(), 1);
+    assert!(mails[0].contains("Rope A"));
+    assert!(!mails[0].contains("Rope B"));
+    let delivery = webhook_deliveries::Entity::find()
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery.event, NOTIFICATION_EVENT);
+    assert_eq!(delivery.webhook_endpoint_id, endpoint.id);
+    let payload = serde_json::from_str::<Value>(&delivery.payload).unwrap();
+    let items = payload["data"]["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["item"]["id"], overdue.id);
+    assert_eq!(items[0]["urgency"], "critical");
+
+    // nothing new to report
+    assert_eq!(notifications::send_due(ctx, now).await.unwrap(), 0);
+
+    // covering every location reports the other item, after the quiet hour
+    let hour = now
+        .with_timezone(&timezone::site_timezone(&ctx.config))
+        .hour();
+    let response = server
+        .put(&format!(
+            "/api/v1/notifications/subscriptions/{}",
+            email_subscription["id"]
+        ))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "min_urgency": "warning",
+            "channel": "email",
+            "frequency": "immediate",
+            "quiet_start_hour": hour,
+            "quiet_end_hour": (hour + 1) % 24,
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(notifications::send_due(ctx, now).await.unwrap(), 0);
+    let later = now + Duration::hours(1);
+    assert_eq!(notifications::send_due(ctx, later).await.unwrap(), 1);
+    let mails = prepare_data::notification_mails(ctx);
+    assert_eq!(mails.len(), 2);
+    assert!(mails[1].contains("Rope B"));
+    assert!(!mails[1].contains("Rope A"));
+
+    let response = server
+        .delete(&format!(
+            "/api/v1/notifications/subscriptions/{}",
+            webhook_subscription["id"]
+        ))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let response = server
+        .get("/api/v1/notifications/subscriptions")
+        .add_header(auth_key, auth_value)
+        .await;
+    assert_eq!(response.json::<Value>()["total_items"], 1);
+    assert_eq!(server.get("/notifications/list").await.status_code(), 200);
+
+    // a new due date makes the notices sent for the old one obsolete
+    let sent_for = |item_id: i32| {
+        sent_notifications::Entity::find()
+            .filter(sent_notifications::Column::InventoryItemId.eq(item_id))
+            .count(&ctx.db)
+    };
+    assert_eq!(sent_for(overdue.id).await.unwrap(), 1);
+    let mut item = overdue.into_active_model();
+    item.expiry = ActiveValue::set(Some(
+        Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap().into(),
+    ));
+    let overdue = item.update(&ctx.db).await.unwrap();
+    assert_eq!(notifications::send_due(ctx, later).await.unwrap(), 1);
+    assert_eq!(sent_for(overdue.id).await.unwrap(), 1);
+    let mails = prepare_data::notification_mails(ctx);
+    assert!(mails[2].contains("Rope A"));
+}
+
+#[tokio::test]
+#[serial]
+async fn members_of_scoped_teams_are_only_told_about_their_items() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let kind = prepare_data::create_kind(ctx, 1, "Rope").await;
+    let own = prepare_data::create_expired_item(ctx, &kind, "Rope A").await;
+    prepare_data::create_expired_item(ctx, &kind, "Rope B").await;
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, ctx).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+    let response = server
+        .post("/api/v1/notifications/subscriptions")
+        .add_header(auth_key, auth_value)
+        .json(&json!({
+            "min_urgency": "critical",
+            "channel": "email",
+            "frequency": "immediate",
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    prepare_data::scope_user_to_item(ctx, user.user.id, &own).await;
+
+    assert_eq!(notifications::send_due(ctx, Utc::now()).await.unwrap(), 1);
+    let mails = prepare_data::notification_mails(ctx);
+    assert_eq!(mails.len(), 1);
+    assert!(mails[0].contains("Rope A"));
+    assert!(!mails[0].contains("Rope B"));
+}
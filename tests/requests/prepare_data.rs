@@ -1,9 +1,23 @@
 use axum::http::{HeaderName, HeaderValue};
-use bestbefors::{models::users, views::auth::LoginResponse};
-use loco_rs::{app::AppContext, TestServer};
+use bestbefors::{
+    initializers::app_cache,
+    models::{
+        api_keys::{self, ApiScope},
+        inventory_item_kinds, inventory_items,
+        responsibilities::{self, Assignee, Assignment, ResponsibilityTarget},
+        team_memberships, teams, tenant_memberships, tenants, users,
+    },
+    notifications,
+    views::auth::LoginResponse,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use loco_rs::{app::AppContext, boot::BootResult, TestServer};
+use sea_orm::{ActiveModelTrait, ActiveValue};
 
 const USER_EMAIL: &str = "test@loco.com";
 const USER_PASSWORD: &str = "1234";
+const USER1_EMAIL: &str = "user1@example.com";
+const USER1_PASSWORD: &str = "12341234";
 
 pub struct LoggedInUser {
     pub user: users::Model,
@@ -32,6 +46,13 @@ pub async fn init_user_login(request: &TestServer, ctx: &AppContext) -> LoggedIn
 
     request.post("/api/auth/verify").json(&verify_payload).await;
 
+    // Registering joins no tenant, so the test user becomes a plain member
+    // of the default tenant, whose admin is user1.
+    tenant_memberships::Entity::add(&ctx.db, tenants::DEFAULT_TENANT_ID, user.id)
+        .await
+        .unwrap();
+    app_cache::refresh_users_cache(ctx).await.unwrap();
+
     let response = request
         .post("/api/auth/login")
         .json(&serde_json::json!({
@@ -50,8 +71,170 @@ pub async fn init_user_login(request: &TestServer, ctx: &AppContext) -> LoggedIn
     }
 }
 
+/// Test server keeping the cookies it receives, like the session cookie set
+/// by logging in.
+pub fn test_server(boot: &BootResult) -> TestServer {
+    TestServer::builder()
+        .mock_transport()
+        .save_cookies()
+        .build(boot.router.clone().unwrap())
+        .unwrap()
+}
+
+/// Logs in the seeded user 1, starting the session of the HTML pages, and
+/// returns their JWT.
+pub async fn login_user1(request: &TestServer) -> String {
+    let response = request
+        .post("/api/auth/login")
+        .json(&serde_json::json!({
+            "email": USER1_EMAIL,
+            "password": USER1_PASSWORD
+        }))
+        .await;
+    let login_response: LoginResponse = serde_json::from_str(&response.text()).unwrap();
+    login_response.token
+}
+
 pub fn auth_header(token: &str) -> (HeaderName, HeaderValue) {
     let auth_header_value = HeaderValue::from_str(&format!("Bearer {}", &token)).unwrap();
 
     (HeaderName::from_static("authorization"), auth_header_value)
 }
+
+/// Creates a key with every scope for the seeded user 1 and returns its
+/// token.
+pub async fn user1_api_key(ctx: &AppContext) -> String {
+    let (key, token) =
+        api_keys::ActiveModel::generate(1, "Tests".to_string(), &[ApiScope::Write], None);
+    key.insert(&ctx.db).await.unwrap();
+    token
+}
+
+/// Creates a kind of the tenant with the seeded checklist, yearly interval
+/// and expiry.
+pub async fn create_kind(
+    ctx: &AppContext,
+    tenant_id: i32,
+    name: &str,
+) -> inventory_item_kinds::Model {
+    let now = Utc::now();
+    inventory_item_kinds::ActiveModel {
+        id: ActiveValue::not_set(),
+        name: ActiveValue::set(name.to_string()),
+        test_standard: ActiveValue::set("EN 1891".to_string()),
+        default_checklist_id: ActiveValue::set(2),
+        default_interval_id: ActiveValue::set(4),
+        default_expiry_id: ActiveValue::set(1),
+        created_at: ActiveValue::set(now.into()),
+        updated_at: ActiveValue::set(now.into()),
+        tenant_id: ActiveValue::set(tenant_id),
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap()
+}
+
+/// An item of `kind` with the seeded checklist and yearly interval.
+fn item_model(
+    kind: &inventory_item_kinds::Model,
+    name: &str,
+    created_at: DateTime<Utc>,
+    last_checked_at: Option<DateTime<Utc>>,
+    expiry: Option<DateTime<Utc>>,
+) -> inventory_items::ActiveModel {
+    inventory_items::ActiveModel {
+        id: ActiveValue::not_set(),
+        name: ActiveValue::set(name.to_string()),
+        serial_number: ActiveValue::set(None),
+        last_checked_at: ActiveValue::set(last_checked_at.map(Into::into)),
+        expiry: ActiveValue::set(expiry.map(Into::into)),
+        inventory_item_kind_id: ActiveValue::set(kind.id),
+        checklist_id: ActiveValue::set(2),
+        interval_id: ActiveValue::set(4),
+        created_at: ActiveValue::set(created_at.into()),
+        updated_at: ActiveValue::set(created_at.into()),
+        tenant_id: ActiveValue::set(kind.tenant_id),
+        next_due_at: ActiveValue::not_set(),
+    }
+}
+
+/// Creates an item of `kind` with the seeded checklist and yearly interval.
+pub async fn create_item(
+    ctx: &AppContext,
+    kind: &inventory_item_kinds::Model,
+    name: &str,
+    created_at: DateTime<Utc>,
+    last_checked_at: Option<DateTime<Utc>>,
+    expiry: Option<DateTime<Utc>>,
+) -> inventory_items::Model {
+    item_model(kind, name, created_at, last_checked_at, expiry)
+        .insert(&ctx.db)
+        .await
+        .unwrap()
+}
+
+/// Creates an item like [`create_item`] with a serial number, created just
+/// now.
+pub async fn create_serial_item(
+    ctx: &AppContext,
+    kind: &inventory_item_kinds::Model,
+    name: &str,
+    serial_number: &str,
+    last_checked_at: Option<DateTime<Utc>>,
+    expiry: Option<DateTime<Utc>>,
+) -> inventory_items::Model {
+    let mut item = item_model(kind, name, Utc::now(), last_checked_at, expiry);
+    item.serial_number = ActiveValue::set(Some(serial_number.to_string()));
+    item.insert(&ctx.db).await.unwrap()
+}
+
+/// Creates an item of `kind` checked just now that expired in 2024, so it is
+/// critical.
+pub async fn create_expired_item(
+    ctx: &AppContext,
+    kind: &inventory_item_kinds::Model,
+    name: &str,
+) -> inventory_items::Model {
+    let now = Utc::now();
+    let expiry = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    create_item(ctx, kind, name, now, Some(now), Some(expiry)).await
+}
+
+/// Puts the user into a new scoped team of the items' tenant that owns
+/// `item`, so that it is the only item they see.
+pub async fn scope_user_to_item(ctx: &AppContext, user_id: i32, item: &inventory_items::Model) {
+    let team = teams::ActiveModel {
+        name: ActiveValue::set(format!("Team of {}", item.name)),
+        scoped: ActiveValue::set(true),
+        tenant_id: ActiveValue::set(item.tenant_id),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+    team_memberships::Entity::add(&ctx.db, team.id, user_id)
+        .await
+        .unwrap();
+    responsibilities::Entity::assign(
+        &ctx.db,
+        ResponsibilityTarget::Item(item.id),
+        Assignment {
+            owner: Some(Assignee::Team(team.id)),
+            deputy: None,
+        },
+    )
+    .await
+    .unwrap();
+}
+
+/// Notification emails sent so far, in order.
+pub fn notification_mails(ctx: &AppContext) -> Vec<String> {
+    ctx.mailer
+        .as_ref()
+        .unwrap()
+        .deliveries()
+        .messages
+        .into_iter()
+        .filter(|message| message.contains(notifications::MANAGE_PATH))
+        .collect()
+}
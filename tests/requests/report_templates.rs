@@ -0,0 +1,141 @@
+use axum_test::multipart::{MultipartForm, Part};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bestbefors::{
+    app::App,
+    initializers::app_cache::AppCacheInitializer,
+    models::{generated_reports::ReportKind, report_templates},
+    reports::CustomTemplate,
+};
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+
+use super::prepare_data;
+
+/// 1x1 pixel PNG.
+const LOGO: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+const BRANDED_TEMPLATE: &str = r#"#import sys: inputs
+#import "compliance.typ": render_compliance_report
+
+#image(inputs.logo, width: 2cm)
+ACME Inc., Main Street 1
+#render_compliance_report(inputs)
+"#;
+
+fn template_form(action: &str, source: &str) -> MultipartForm {
+    MultipartForm::new()
+        .add_text("action", action)
+        .add_text("source", source)
+}
+
+#[tokio::test]
+#[serial]
+async fn custom_report_templates_are_validated_and_fall_back() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+    let member = prepare_data::test_server(&boot);
+    let response = member.get("/report_templates").await;
+    assert_eq!(response.status_code(), 401);
+    prepare_data::init_user_login(&member, &boot.app_context).await;
+    let response = member.get("/report_templates").await;
+    assert_eq!(response.status_code(), 403);
+    let response = member.post("/report_templates/compliance/reset").await;
+    assert_eq!(response.status_code(), 403);
+
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+
+    let response = server.get("/report_templates").await;
+    assert_eq!(response.status_code(), 200);
+    let response = server.get("/report_templates/compliance").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains("render_compliance_report"));
+    let response = server.get("/report_templates/unknown").await;
+    assert_eq!(response.status_code(), 404);
+
+    let response = server
+        .post("/report_templates/compliance")
+        .multipart(template_form(
+            "save",
+            "#import sys: inputs\n#inputs.missing_field",
+        ))
+        .await;
+    assert_eq!(response.status_code(), 422);
+    assert!(response.text().contains("missing_field"));
+    assert!(response.text().contains("main:2:"));
+    assert!(
+        report_templates::Model::find_by_kind(&boot.app_context.db, 1, ReportKind::Compliance)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // only the built-in templates and the logo can be read
+    for path in ["/config/test.yaml", "../../config/test.yaml", "/Cargo.toml"] {
+        let response = server
+            .post("/report_templates/compliance")
+            .multipart(template_form("preview", &format!("#read(\"{path}\")")))
+            .await;
+        assert_eq!(response.status_code(), 422, "{path}");
+        assert!(response.text().contains("access denied"), "{path}");
+    }
+
+    let logo = STANDARD.decode(LOGO).unwrap();
+    let response = server
+        .post("/report_templates/compliance")
+        .multipart(
+            template_form("preview", BRANDED_TEMPLATE)
+                .add_part("logo", Part::bytes(logo.clone()).file_name("logo.png")),
+        )
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.as_bytes().starts_with(b"%PDF-"));
+
+    let response = server
+        .post("/report_templates/compliance")
+        .multipart(
+            template_form("save", BRANDED_TEMPLATE)
+                .add_part("logo", Part::bytes(logo.clone()).file_name("logo.png")),
+        )
+        .await;
+    assert_eq!(response.status_code(), 303);
+    let stored =
+        report_templates::Model::find_by_kind(&boot.app_context.db, 1, ReportKind::Compliance)
+            .await
+            .unwrap()
+            .unwrap();
+    assert_eq!(stored.source, BRANDED_TEMPLATE);
+    assert_eq!(stored.logo.as_deref(), Some(logo.as_slice()));
+
+    let response = server.get("/reports/compliance.pdf").await;
+    assert_eq!(response.status_code(), 200);
+
+    // a template that stops compiling, e.g. after an update of the report
+    // data, must not break report generation
+    report_templates::ActiveModel::save_for_kind(
+        &boot.app_context.db,
+        1,
+        ReportKind::Compliance,
+        CustomTemplate {
+            source: "#import sys: inputs\n#inputs.missing_field".to_string(),
+            logo: None,
+        },
+    )
+    .await
+    .unwrap();
+    let response = server.get("/reports/compliance.pdf").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.as_bytes().starts_with(b"%PDF-"));
+
+    let response = server.post("/report_templates/compliance/reset").await;
+    assert_eq!(response.status_code(), 303);
+    assert!(
+        report_templates::Model::find_by_kind(&boot.app_context.db, 1, ReportKind::Compliance)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
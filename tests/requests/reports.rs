@@ -0,0 +1,347 @@
+use axum::http::header;
+use axum_test::TestServer;
+use bestbefors::{
+    app::App,
+    controllers::reports::{build_compliance_report, ComplianceFilter},
+    i18n::Translator,
+    initializers::app_cache::AppCacheInitializer,
+    models::{generated_reports::ReportIdentity, inventory_items, teams::ItemScope},
+    reports::compliance::ComplianceStatus,
+    tenant,
+};
+use chrono::{Duration, Utc};
+use loco_rs::{
+    boot::{run_app, BootResult, StartMode},
+    testing::prelude::*,
+};
+use sea_orm::{ActiveModelTrait, ActiveValue, EntityTrait, IntoActiveModel};
+use serde_json::{json, Value};
+use serial_test::serial;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use unic_langid::langid;
+
+use super::prepare_data;
+
+async fn create_kind(server: &TestServer, token: &str) -> i64 {
+    let (auth_key, auth_value) = prepare_data::auth_header(token);
+    let response = server
+        .post("/api/v1/item_kinds")
+        .add_header(auth_key, auth_value)
+        .json(&json!({
+            "name": "Harness",
+            "test_standard": "EN 358",
+            "default_checklist_id": 2,
+            "default_interval_id": 4,
+            "default_expiry_id": 8,
+            "metadata_fields": ["Location"]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let kind: Value = response.json();
+    kind["id"].as_i64().unwrap()
+}
+
+async fn create_item(
+    server: &TestServer,
+    token: &str,
+    kind_id: i64,
+    name: &str,
+    location: &str,
+) -> i32 {
+    let (auth_key, auth_value) = prepare_data::auth_header(token);
+    let response = server
+        .post("/api/v1/items")
+        .add_header(auth_key, auth_value)
+        .json(&json!({
+            "name": name,
+            "inventory_item_kind_id": kind_id,
+            "metadata": { "Location": location }
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let item: Value = response.json();
+    i32::try_from(item["id"].as_i64().unwrap()).unwrap()
+}
+
+async fn check_item(server: &TestServer, item_id: i32, result_id: i32) {
+    let response = server
+        .post(&format!("/inventory/item/{item_id}/check"))
+        .json(&json!({
+            "checked_by": 1,
+            "result_id": result_id,
+            "steps": [
+                { "checklist_step_id": 2, "result_id": result_id },
+                { "checklist_step_id": 3, "result_id": result_id }
+            ]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+}
+
+/// Test server on a database connection that counts the queries it runs.
+async fn counting_server(boot: &BootResult) -> (TestServer, Arc<AtomicUsize>) {
+    let queries = Arc::new(AtomicUsize::new(0));
+    let counter = queries.clone();
+    let mut ctx = boot.app_context.clone();
+    ctx.db.set_metric_callback(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    let boot = run_app::<App>(&StartMode::ServerOnly, ctx).await.unwrap();
+    (prepare_data::test_server(&boot), queries)
+}
+
+/// Downloads the history report of `item_id` and counts the queries it runs.
+async fn item_history_queries(server: &TestServer, queries: &AtomicUsize, item_id: i32) -> usize {
+    queries.store(0, Ordering::SeqCst);
+    let response = server
+        .get(&format!("/inventory/item/{item_id}/report.pdf"))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.as_bytes().starts_with(b"%PDF"));
+    queries.load(Ordering::SeqCst)
+}
+
+#[tokio::test]
+#[serial]
+async fn compliance_report_flags_overdue_and_failed_items() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+
+    let token = prepare_data::user1_api_key(&boot.app_context).await;
+    let kind_id = create_kind(&server, &token).await;
+
+    let passed = create_item(&server, &token, kind_id, "Harness A", "Station 1").await;
+    let failed = create_item(&server, &token, kind_id, "Harness B", "Station 2").await;
+    let overdue = create_item(&server, &token, kind_id, "Harness C", "Station 2").await;
+    check_item(&server, passed, 1).await;
+    check_item(&server, failed, 2).await;
+
+    let mut old_item = inventory_items::Entity::find_by_id(overdue)
+        .one(&boot.app_context.db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into_active_model();
+    old_item.created_at = ActiveValue::set((Utc::now() - Duration::days(800)).into());
+    old_item.update(&boot.app_context.db).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+
+    let report = build_compliance_report(
+        &boot.app_context,
+        tenant::DEFAULT_TENANT_ID,
+        &ItemScope::All,
+        &ComplianceFilter::default(),
+        &ReportIdentity::new(&boot.app_context),
+        &Translator::default(),
+    )
+    .await
+    .unwrap();
+    let statuses = report
+        .items
+        .iter()
+        .map(|item| (item.name.as_str(), item.status))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        vec![
+            ("Harness A", ComplianceStatus::Compliant),
+            ("Harness B", ComplianceStatus::Failed),
+            ("Harness C", ComplianceStatus::Overdue),
+        ]
+    );
+    assert_eq!(report.summary.total, 3);
+    assert_eq!(report.summary.checks_in_period, 2);
+    assert_eq!(report.kinds.len(), 1);
+    assert_eq!(report.items[0].location.as_deref(), Some("Station 1"));
+
+    let report = build_compliance_report(
+        &boot.app_context,
+        tenant::DEFAULT_TENANT_ID,
+        &ItemScope::All,
+        &ComplianceFilter {
+            location: Some("station 2".to_string()),
+            from: Some((Utc::now() + Duration::days(1)).date_naive()),
+            ..ComplianceFilter::default()
+        },
+        &ReportIdentity::new(&boot.app_context),
+        &Translator::new(langid!("de-DE")),
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.summary.total, 2);
+    assert_eq!(report.summary.failed, 1);
+    assert_eq!(report.summary.checks_in_period, 0);
+    assert_eq!(report.title, "Prüfstatusbericht");
+    assert_eq!(report.filters[0].label, "Standort");
+    assert_eq!(report.items[0].last_result.as_deref(), Some("Nicht OK"));
+
+    let response = server
+        .get("/reports/compliance.pdf")
+        .add_query_param("kind_id", kind_id)
+        .add_query_param("from", "2020-01-01")
+        .add_query_param("to", "")
+        .add_query_param("lang", "de")
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(
+        response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok()),
+        Some("application/pdf")
+    );
+    assert!(response.as_bytes().starts_with(b"%PDF-"));
+
+    let response = server
+        .get("/reports/compliance.pdf")
+        .add_query_param("from", "2025-02-01")
+        .add_query_param("to", "2025-01-01")
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server.get("/reports/compliance").await;
+    assert_eq!(response.status_code(), 200);
+}
+
+#[tokio::test]
+#[serial]
+async fn queued_report_jobs_are_downloadable_when_completed() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+
+    // the test config runs workers in the foreground, so the job is done
+    // once the request returns
+    let response = server
+        .post("/reports/jobs")
+        .add_query_param("lang", "de")
+        .form(&[("kind", "compliance"), ("location", "Station")])
+        .await;
+    assert_eq!(response.status_code(), 303);
+
+    let response = server.get("/reports/jobs/status").await;
+    assert_eq!(response.status_code(), 200);
+    let jobs: Value = response.json();
+    let job = &jobs[0];
+    assert_eq!(job["kind"], "compliance");
+    assert_eq!(job["lang"], "de-DE");
+    assert_eq!(job["status"], "completed");
+    assert_eq!(job["title"], "Prüfstatusbericht");
+    assert!(job["report_id"].as_str().unwrap().starts_with("RPT-"));
+
+    let response = server
+        .get(&format!(
+            "/reports/jobs/{}/download",
+            job["pid"].as_str().unwrap()
+        ))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.as_bytes().starts_with(b"%PDF-"));
+
+    // jobs belong to the user who queued them
+    let download = format!("/reports/jobs/{}/download", job["pid"].as_str().unwrap());
+    let anonymous = prepare_data::test_server(&boot);
+    assert_eq!(
+        anonymous.get("/reports/jobs/status").await.status_code(),
+        401
+    );
+    assert_eq!(anonymous.get(&download).await.status_code(), 401);
+    let other = prepare_data::test_server(&boot);
+    prepare_data::init_user_login(&other, &boot.app_context).await;
+    let jobs: Value = other.get("/reports/jobs/status").await.json();
+    assert_eq!(jobs, json!([]));
+    assert_eq!(other.get(&download).await.status_code(), 404);
+
+    let response = server
+        .post("/reports/jobs")
+        .form(&[("kind", "item_history"), ("item_id", "999")])
+        .await;
+    assert_eq!(response.status_code(), 303);
+    let jobs: Value = server.get("/reports/jobs/status").await.json();
+    assert_eq!(jobs[0]["status"], "failed");
+    let response = server
+        .get(&format!(
+            "/reports/jobs/{}/download",
+            jobs[0]["pid"].as_str().unwrap()
+        ))
+        .await;
+    assert_eq!(response.status_code(), 404);
+
+    let response = server
+        .post("/reports/jobs")
+        .form(&[("kind", "unknown")])
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server.get("/reports/jobs").await;
+    assert_eq!(response.status_code(), 200);
+}
+
+#[tokio::test]
+#[serial]
+async fn item_history_loads_checks_with_a_fixed_number_of_queries() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+    let (server, queries) = counting_server(&boot).await;
+    prepare_data::login_user1(&server).await;
+
+    let token = prepare_data::user1_api_key(&boot.app_context).await;
+    let kind_id = create_kind(&server, &token).await;
+    let item_id = create_item(&server, &token, kind_id, "Harness A", "Station 1").await;
+    check_item(&server, item_id, 1).await;
+    let single_check_queries = item_history_queries(&server, &queries, item_id).await;
+    assert!(single_check_queries > 0);
+
+    for _ in 0..11 {
+        check_item(&server, item_id, 2).await;
+    }
+    assert_eq!(
+        item_history_queries(&server, &queries, item_id).await,
+        single_check_queries
+    );
+
+    // the details page, built by the same loader, shows all 12 checks page
+    // by page, each with its 2 steps and their results
+    let pages = [
+        server
+            .get(&format!("/inventory/item/{item_id}"))
+            .await
+            .text(),
+        server
+            .get(&format!("/inventory/item/{item_id}"))
+            .add_query_param("page", 2)
+            .await
+            .text(),
+    ];
+    assert!(pages[0].contains("12 checks"));
+    assert!(pages[0].contains("?page=2"));
+    assert!(pages[1].contains("Page 2 of 2"));
+    let count = |needle: &str| {
+        pages
+            .iter()
+            .map(|page| page.matches(needle).count())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(count("certificate.pdf"), [10, 2]);
+    assert_eq!(count("Visuell OK?"), [10, 2]);
+    assert_eq!(count("Funktion OK?"), [10, 2]);
+    // the failed checks and both of their steps
+    assert_eq!(count("Not OK"), [30, 3]);
+}
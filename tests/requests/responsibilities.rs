@@ -0,0 +1,167 @@
+use bestbefors::{
+    app::App, initializers::app_cache::AppCacheInitializer, models::users, notifications, timezone,
+};
+use chrono::Utc;
+use loco_rs::testing::prelude::*;
+use sea_orm::{EntityTrait, IntoActiveModel};
+use serde_json::{json, Value};
+use serial_test::serial;
+
+use super::prepare_data;
+
+#[tokio::test]
+#[serial]
+async fn routes_reminders_to_owners_and_deputies() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let now = Utc::now();
+    let kind = prepare_data::create_kind(ctx, 1, "Harness").await;
+    let own = prepare_data::create_expired_item(ctx, &kind, "Harness A").await;
+    let deputized = prepare_data::create_expired_item(ctx, &kind, "Harness B").await;
+
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, ctx).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    // only tenant admins assign kinds; the seeded user1 owns the kind, the
+    // logged in user is its deputy
+    let admin = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&admin).await;
+    let response = server
+        .post(&format!("/responsibilities/kind/{}", kind.id))
+        .form(&[("owner", "user:1"), ("deputy", "")])
+        .await;
+    assert_eq!(response.status_code(), 403);
+    let response = admin
+        .post(&format!("/responsibilities/kind/{}", kind.id))
+        .form(&[
+            ("owner", format!("user:{}", user.user.id)),
+            ("deputy", format!("user:{}", user.user.id)),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let response = admin
+        .post(&format!("/responsibilities/kind/{}", kind.id))
+        .form(&[("owner", "user:999"), ("deputy", "")])
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let response = admin
+        .post(&format!("/responsibilities/kind/{}", kind.id))
+        .form(&[
+            ("owner", "user:1".to_string()),
+            ("deputy", format!("user:{}", user.user.id)),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 303);
+    // and owns one item itself
+    let response = server
+        .post(&format!("/responsibilities/item/{}", own.id))
+        .form(&[
+            ("owner", format!("user:{}", user.user.id)),
+            ("deputy", String::new()),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 303);
+
+    let response = server
+        .get("/api/v1/responsibilities/mine")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let mine = response.json::<Value>();
+    assert_eq!(mine["owner_of"], json!([own.id]));
+    assert_eq!(mine["deputy_of"], json!([own.id, deputized.id]));
+
+    // the dashboard lists the items of the signed in user
+    let page = server.get("/").add_query_param("mine", true).await.text();
+    assert!(page.contains("Harness A"));
+    assert!(page.contains("Harness B"));
+    let page = admin.get("/").add_query_param("mine", true).await.text();
+    assert!(!page.contains("Harness A"));
+    assert!(page.contains("Harness B"));
+
+    let page = server
+        .get(&format!("/inventory/item/{}", deputized.id))
+        .await
+        .text();
+    assert!(page.contains("user1"));
+    assert!(page.contains("from kind"));
+    let response = server
+        .get(&format!("/inventory/item_kinds/{}", kind.id))
+        .await;
+    assert_eq!(response.status_code(), 200);
+
+    let response = server
+        .post("/api/v1/notifications/subscriptions")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "min_urgency": "critical",
+            "channel": "email",
+            "frequency": "immediate",
+            "responsible_only": true,
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.json::<Value>()["responsible_only"], true);
+
+    // user1 is present, so only the own item is reported to the deputy and
+    // user1 is reminded of the other one without subscribing
+    assert_eq!(notifications::send_due(ctx, now).await.unwrap(), 2);
+    let mails = prepare_data::notification_mails(ctx);
+    assert!(mails[0].contains("Harness A"));
+    assert!(!mails[0].contains("Harness B"));
+    assert!(mails[1].contains("user1@example.com"));
+    assert!(mails[1].contains("Harness B"));
+    assert!(!mails[1].contains("Harness A"));
+    // reminders are not repeated
+    assert_eq!(notifications::send_due(ctx, now).await.unwrap(), 0);
+
+    // while user1 is away the deputy takes over
+    let owner = users::Entity::find_by_id(1)
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    let today = now
+        .with_timezone(&timezone::user_timezone(&ctx.config, &owner))
+        .date_naive();
+    owner
+        .into_active_model()
+        .set_absent_until(&ctx.db, Some(today))
+        .await
+        .unwrap();
+    assert_eq!(notifications::send_due(ctx, now).await.unwrap(), 1);
+    let mails = prepare_data::notification_mails(ctx);
+    assert_eq!(mails.len(), 3);
+    assert!(mails[2].contains("Harness B"));
+
+    // users record their own absence
+    let response = server
+        .post("/api/auth/absence")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "absent_until": "2030-05-31" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let response = server
+        .get("/api/auth/absence")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.json::<Value>()["absent_until"], "2030-05-31");
+
+    // an empty form falls back to the kind's owner
+    let response = server
+        .post(&format!("/responsibilities/item/{}", own.id))
+        .form(&[("owner", ""), ("deputy", "")])
+        .await;
+    assert_eq!(response.status_code(), 303);
+    let mine = server
+        .get("/api/v1/responsibilities/mine")
+        .add_header(auth_key, auth_value)
+        .await
+        .json::<Value>();
+    assert_eq!(mine["owner_of"], json!([]));
+    assert_eq!(mine["deputy_of"], json!([own.id, deputized.id]));
+}
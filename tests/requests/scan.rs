@@ -0,0 +1,54 @@
+use bestbefors::{app::App, initializers::app_cache::AppCacheInitializer};
+use loco_rs::testing::prelude::*;
+use serial_test::serial;
+
+use super::prepare_data;
+
+#[tokio::test]
+#[serial]
+async fn opens_check_page_by_serial_or_label_code() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let now = chrono::Utc::now();
+    let kind = prepare_data::create_kind(ctx, 1, "Harness").await;
+    let item = prepare_data::create_serial_item(ctx, &kind, "Harness A", "SN-42", None, None).await;
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+
+    let response = server.get("/scan").add_query_param("saved", "Rope").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains("Rope"));
+
+    for code in ["sn-42".to_string(), item.scan_code()] {
+        let response = server.get(&format!("/scan/{code}")).await;
+        assert_eq!(response.status_code(), 200, "{code}");
+        let body = response.text();
+        assert!(body.contains("Harness A"), "{code}");
+        assert!(
+            body.contains(&format!("/inventory/item/{}/check", item.id)),
+            "{code}"
+        );
+    }
+
+    let response = server.get("/scan/SN-404").await;
+    assert_eq!(response.status_code(), 404);
+    assert!(response.text().contains("SN-404"));
+
+    let response = server.get("/scan/item-abc").await;
+    assert_eq!(response.status_code(), 404);
+
+    // members of a scoped team only find their items
+    let rope = prepare_data::create_kind(ctx, 1, "Rope").await;
+    let rope = prepare_data::create_item(ctx, &rope, "Rope A", now, None, None).await;
+    prepare_data::scope_user_to_item(ctx, 1, &rope).await;
+    for code in ["sn-42".to_string(), item.scan_code()] {
+        let response = server.get(&format!("/scan/{code}")).await;
+        assert_eq!(response.status_code(), 404, "{code}");
+    }
+    let response = server.get(&format!("/scan/{}", rope.scan_code())).await;
+    assert_eq!(response.status_code(), 200);
+}
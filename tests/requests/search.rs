@@ -0,0 +1,155 @@
+use bestbefors::{app::App, initializers::app_cache::AppCacheInitializer, search};
+use loco_rs::testing::prelude::*;
+use serde_json::{json, Value};
+use serial_test::serial;
+
+use super::prepare_data;
+
+#[tokio::test]
+#[serial]
+async fn finds_items_by_metadata_and_checks_by_notes() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, ctx).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+
+    let kind: Value = server
+        .post("/api/v1/item_kinds")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "name": "Helmet",
+            "test_standard": "EN 397",
+            "default_checklist_id": 2,
+            "default_interval_id": 4,
+            "default_expiry_id": 1,
+            "metadata_fields": ["Manufacturer", "Lot"]
+        }))
+        .await
+        .json();
+    let mut item_ids = Vec::new();
+    for (name, serial, manufacturer) in [
+        ("Helmet A", "H-100", "Petzl"),
+        ("Helmet B", "H-200", "Kask"),
+    ] {
+        let item: Value = server
+            .post("/api/v1/items")
+            .add_header(auth_key.clone(), auth_value.clone())
+            .json(&json!({
+                "name": name,
+                "serial_number": serial,
+                "inventory_item_kind_id": kind["id"],
+                "metadata": { "Manufacturer": manufacturer, "Lot": "L<42>" }
+            }))
+            .await
+            .json();
+        item_ids.push(item["id"].as_i64().unwrap());
+    }
+    let check: Value = server
+        .post("/api/v1/checks")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "inventory_item_id": item_ids[1],
+            "checked_by": 1,
+            "result_id": 1,
+            "notes": "Inspected",
+            "steps": [
+                { "checklist_step_id": 2, "result_id": 1 },
+                { "checklist_step_id": 3, "result_id": 1, "notes": "Cracked housing near the visor" }
+            ]
+        }))
+        .await
+        .json();
+
+    let find = |q: &str| {
+        server
+            .get("/api/v1/search")
+            .add_query_param("q", q)
+            .add_header(auth_key.clone(), auth_value.clone())
+    };
+
+    // metadata values and abbreviated words, markup in the text is escaped
+    let found: Value = find("petz").await.json();
+    assert_eq!(found["items"].as_array().unwrap().len(), 1);
+    assert_eq!(found["items"][0]["id"], item_ids[0]);
+    assert!(found["items"][0]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("<mark>Petzl</mark>"));
+    assert_eq!(found["checks"], json!([]));
+    let found: Value = find("l 42").await.json();
+    assert_eq!(found["items"].as_array().unwrap().len(), 2);
+    assert!(found["items"][0]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("&lt;<mark>42</mark>&gt;"));
+
+    // check notes, including those of single steps
+    let found: Value = find("cracked housing").await.json();
+    assert_eq!(found["items"], json!([]));
+    assert_eq!(found["checks"][0]["id"], check["id"]);
+    assert_eq!(found["checks"][0]["item_id"], item_ids[1]);
+    assert_eq!(found["checks"][0]["title"], "Helmet B");
+
+    // name matches rank first
+    let found: Value = find("helmet").await.json();
+    assert_eq!(found["items"].as_array().unwrap().len(), 2);
+    assert!(found["items"][0]["title"]
+        .as_str()
+        .unwrap()
+        .starts_with("<mark>Helmet</mark>"));
+
+    // edits replace the indexed text
+    let response = server
+        .put(&format!("/api/v1/items/{}", item_ids[0]))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "name": "Helmet A",
+            "serial_number": "H-100",
+            "inventory_item_kind_id": kind["id"],
+            "metadata": { "Manufacturer": "Edelrid", "Lot": "L<42>" }
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let found: Value = find("petzl").await.json();
+    assert_eq!(found["items"], json!([]));
+    let found: Value = find("edelrid").await.json();
+    assert_eq!(found["items"][0]["id"], item_ids[0]);
+
+    let response = server
+        .delete(&format!("/api/v1/checks/{}", check["id"]))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let found: Value = find("cracked").await.json();
+    assert_eq!(found["checks"], json!([]));
+
+    let response = server
+        .delete(&format!("/api/v1/items/{}", item_ids[1]))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let found: Value = find("kask").await.json();
+    assert_eq!(found["items"], json!([]));
+
+    // the HTML page groups the hits and opens exact serial numbers
+    let page = server.get("/search").add_query_param("q", "edel").await;
+    assert_eq!(page.status_code(), 200);
+    assert!(page.text().contains("<mark>Edelrid</mark>"));
+    let response = server.get("/search").add_query_param("q", "h-100").await;
+    assert_eq!(response.status_code(), 303);
+    assert_eq!(
+        response.header("location"),
+        format!("/inventory/item/{}", item_ids[0])
+    );
+    let page = server.get("/search").add_query_param("q", "nothing").await;
+    assert!(page.text().contains("Nothing found."));
+
+    // a rebuilt index finds the same
+    assert_eq!(search::reindex_all(&ctx.db).await.unwrap(), 1);
+    let found: Value = find("edelrid").await.json();
+    assert_eq!(found["items"][0]["id"], item_ids[0]);
+}
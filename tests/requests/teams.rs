@@ -0,0 +1,234 @@
+use bestbefors::{
+    app::App,
+    initializers::app_cache::AppCacheInitializer,
+    models::{
+        team_memberships,
+        teams::{self, ItemScope},
+        tenants,
+    },
+    notifications,
+};
+use chrono::Utc;
+use loco_rs::{app::AppContext, testing::prelude::*};
+use sea_orm::{ActiveModelTrait, ActiveValue};
+use serde_json::{json, Value};
+use serial_test::serial;
+
+use super::prepare_data;
+
+async fn team_id(ctx: &AppContext, name: &str) -> i32 {
+    teams::Entity::list(&ctx.db, 1)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|team| team.name == name)
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+#[serial]
+async fn teams_own_items_receive_notifications_and_scope_the_api() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let now = Utc::now();
+    let kind = prepare_data::create_kind(ctx, 1, "Rope").await;
+    let own = prepare_data::create_expired_item(ctx, &kind, "Rope A").await;
+    let other = prepare_data::create_expired_item(ctx, &kind, "Rope B").await;
+
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, ctx).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    // only admins of the tenant manage its teams
+    assert_eq!(server.get("/teams/list").await.status_code(), 403);
+    let response = server.post("/teams").form(&[("name", "Rescue")]).await;
+    assert_eq!(response.status_code(), 403);
+    let admin = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&admin).await;
+
+    let response = admin.post("/teams").form(&[("name", " ")]).await;
+    assert_eq!(response.status_code(), 400);
+    let response = admin
+        .post("/teams")
+        .form(&[("name", "Rescue"), ("scoped", "on")])
+        .await;
+    assert_eq!(response.status_code(), 303);
+    let response = admin.post("/teams").form(&[("name", "Workshop")]).await;
+    assert_eq!(response.status_code(), 303);
+    let rescue = team_id(ctx, "Rescue").await;
+    let workshop = team_id(ctx, "Workshop").await;
+
+    let response = admin
+        .post(&format!("/teams/{rescue}/members"))
+        .form(&[("user_id", "999")])
+        .await;
+    assert_eq!(response.status_code(), 400);
+    // the item is handed to the team while its members still see everything
+    let response = server
+        .post(&format!("/responsibilities/item/{}", own.id))
+        .form(&[
+            ("owner", format!("team:{rescue}")),
+            ("deputy", String::new()),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 303);
+    for user_id in [user.user.id, 1] {
+        let response = admin
+            .post(&format!("/teams/{rescue}/members"))
+            .form(&[("user_id", user_id.to_string())])
+            .await;
+        assert_eq!(response.status_code(), 303);
+    }
+    let page = admin.get("/teams/list").await.text();
+    assert!(page.contains("Rescue"));
+    assert!(page.contains("user1"));
+
+    let page = server.get(&format!("/inventory/item/{}", own.id)).await;
+    assert!(page.text().contains("Rescue"));
+
+    let mine = server
+        .get("/api/v1/responsibilities/mine")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await
+        .json::<Value>();
+    assert_eq!(mine["owner_of"], json!([own.id]));
+
+    // members of a scoped team only reach the items of their teams
+    let items = server
+        .get("/api/v1/items")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await
+        .json::<Value>();
+    assert_eq!(items["total_items"], 1);
+    assert_eq!(items["items"][0]["id"], own.id);
+    let response = server
+        .get(&format!("/api/v1/items/{}", other.id))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 404);
+    let response = server
+        .delete(&format!("/api/v1/items/{}", other.id))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 404);
+
+    // and the HTML pages show them only these items as well
+    let response = server
+        .post(&format!("/responsibilities/item/{}", other.id))
+        .form(&[
+            ("owner", format!("team:{rescue}")),
+            ("deputy", String::new()),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 404);
+    let page = server.get("/inventory/list").await.text();
+    assert!(page.contains("Rope A"));
+    assert!(!page.contains("Rope B"));
+    let page = server.get("/").await.text();
+    assert!(page.contains("Rope A"));
+    assert!(!page.contains("Rope B"));
+    for path in [
+        format!("/inventory/item/{}", other.id),
+        format!("/inventory/item/{}/check", other.id),
+        format!("/inventory/item/{}/report.pdf", other.id),
+    ] {
+        assert_eq!(server.get(&path).await.status_code(), 404, "{path}");
+    }
+    let response = server.get(&format!("/inventory/item/{}", own.id)).await;
+    assert_eq!(response.status_code(), 200);
+
+    // team subscriptions need a membership and go to every member
+    let mut subscription = json!({
+        "min_urgency": "critical",
+        "channel": "email",
+        "frequency": "immediate",
+        "responsible_only": true,
+        "team_id": workshop,
+    });
+    let response = server
+        .post("/api/v1/notifications/subscriptions")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&subscription)
+        .await;
+    assert_eq!(response.status_code(), 400);
+    subscription["team_id"] = json!(rescue);
+    let response = server
+        .post("/api/v1/notifications/subscriptions")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&subscription)
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.json::<Value>()["team_id"], rescue);
+
+    assert_eq!(notifications::send_due(ctx, now).await.unwrap(), 1);
+    let mails = ctx
+        .mailer
+        .as_ref()
+        .unwrap()
+        .deliveries()
+        .messages
+        .into_iter()
+        .filter(|message| message.contains(notifications::MANAGE_PATH))
+        .collect::<Vec<_>>();
+    assert_eq!(mails.len(), 2);
+    assert!(mails.iter().all(|mail| mail.contains("Rope A")));
+    assert!(mails.iter().all(|mail| !mail.contains("Rope B")));
+    assert!(mails.iter().any(|mail| mail.contains("user1@example.com")));
+
+    // without the scoped team everything is visible again
+    let response = admin
+        .post(&format!("/teams/{rescue}/members/{}/remove", user.user.id))
+        .await;
+    assert_eq!(response.status_code(), 303);
+    let response = server
+        .get(&format!("/api/v1/items/{}", other.id))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 200);
+
+    let response = admin.delete(&format!("/teams/{rescue}")).await;
+    assert_eq!(response.status_code(), 200);
+    let mine = server
+        .get("/api/v1/responsibilities/mine")
+        .add_header(auth_key, auth_value)
+        .await
+        .json::<Value>();
+    assert_eq!(mine["owner_of"], json!([]));
+}
+
+#[tokio::test]
+#[serial]
+async fn scoped_teams_only_limit_the_items_of_their_tenant() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let brigade = tenants::Entity::create_with_member(&ctx.db, "Fire brigade", 1)
+        .await
+        .unwrap();
+    let team = teams::ActiveModel {
+        name: ActiveValue::set("Rescue".to_string()),
+        scoped: ActiveValue::set(true),
+        tenant_id: ActiveValue::set(brigade.id),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+    team_memberships::Entity::add(&ctx.db, team.id, 1)
+        .await
+        .unwrap();
+
+    let scope = teams::Entity::item_scope_for_user(&ctx.db, brigade.id, 1)
+        .await
+        .unwrap();
+    assert!(matches!(scope, ItemScope::Only(_)));
+    let scope = teams::Entity::item_scope_for_user(&ctx.db, tenants::DEFAULT_TENANT_ID, 1)
+        .await
+        .unwrap();
+    assert_eq!(scope, ItemScope::All);
+}
@@ -0,0 +1,223 @@
+use axum::http::{header, HeaderValue};
+use bestbefors::{
+    app::App,
+    initializers::app_cache::AppCacheInitializer,
+    models::{inventory_items, tenants},
+};
+use chrono::Utc;
+use loco_rs::{app::AppContext, testing::prelude::*};
+use serde_json::{json, Value};
+use serial_test::serial;
+
+use super::prepare_data;
+
+/// Creates an item of its own kind in the tenant.
+async fn create_item(ctx: &AppContext, tenant_id: i32, name: &str) -> inventory_items::Model {
+    let kind = prepare_data::create_kind(ctx, tenant_id, &format!("{name} kind")).await;
+    prepare_data::create_item(ctx, &kind, name, Utc::now(), None, None).await
+}
+
+/// The `name=value` pair of the session cookie set by `response`.
+fn session_cookie(response: &axum_test::TestResponse) -> String {
+    let set_cookie = response
+        .iter_headers_by_name(header::SET_COOKIE)
+        .map(|value| value.to_str().unwrap().to_string())
+        .find(|value| value.starts_with("session="))
+        .unwrap();
+    set_cookie.split(';').next().unwrap().to_string()
+}
+
+#[tokio::test]
+#[serial]
+async fn tenants_isolate_inventory_and_switch_per_user() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, ctx).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let tenants = server
+        .get("/api/v1/tenants")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await
+        .json::<Value>();
+    assert_eq!(tenants[0]["name"], "Default");
+    assert_eq!(tenants[0]["current"], true);
+
+    let response = server
+        .post("/api/v1/tenants")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "name": "Default" }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let response = server
+        .post("/api/v1/tenants")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "name": "Fire brigade" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let brigade = response.json::<Value>()["id"].as_i64().unwrap();
+    let brigade = i32::try_from(brigade).unwrap();
+
+    let response = server
+        .post(&format!("/api/v1/tenants/{brigade}/members"))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "email": "nobody@example.com" }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let response = server
+        .post(&format!("/api/v1/tenants/{brigade}/members"))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "email": "user1@example.com" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(
+        response.json::<Value>()["members"],
+        json!(["loco", "user1"])
+    );
+
+    // a plain member of the default tenant cannot invite anyone to it
+    let response = server
+        .post(&format!(
+            "/api/v1/tenants/{}/members",
+            tenants::DEFAULT_TENANT_ID
+        ))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "email": "user2@example.com" }))
+        .await;
+    assert_eq!(response.status_code(), 403);
+
+    let shared = create_item(ctx, tenants::DEFAULT_TENANT_ID, "Default rope").await;
+    let own = create_item(ctx, brigade, "Brigade rope").await;
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    // still in the default tenant
+    let items = server
+        .get("/api/v1/items")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await
+        .json::<Value>();
+    assert_eq!(items["total_items"], 1);
+    assert_eq!(items["items"][0]["id"], shared.id);
+    let response = server
+        .get(&format!("/api/v1/items/{}", own.id))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 404);
+
+    // only tenants of the user can be switched to
+    let response = server
+        .post("/api/auth/tenant")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "tenant_id": 999 }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let response = server
+        .post("/api/auth/tenant")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "tenant_id": brigade }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let cookie = session_cookie(&response);
+
+    let items = server
+        .get("/api/v1/items")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await
+        .json::<Value>();
+    assert_eq!(items["total_items"], 1);
+    assert_eq!(items["items"][0]["id"], own.id);
+    let response = server
+        .get(&format!("/api/v1/items/{}", shared.id))
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 404);
+
+    // the HTML pages follow the session
+    let page = server.get("/inventory/list").await.text();
+    assert!(page.contains("Brigade rope"));
+    assert!(!page.contains("Default rope"));
+    assert!(page.contains("Fire brigade"));
+    let response = server.get(&format!("/inventory/item/{}", shared.id)).await;
+    assert_eq!(response.status_code(), 404);
+
+    // sessions are bound to their user and tenant
+    let anonymous = prepare_data::test_server(&boot);
+    let response = anonymous.get("/inventory/list").await;
+    assert_eq!(response.status_code(), 401);
+    let with_cookie = |cookie: &str| {
+        anonymous
+            .get("/inventory/list")
+            .add_header(header::COOKIE, HeaderValue::from_str(cookie).unwrap())
+    };
+    let page = with_cookie(&cookie).await.text();
+    assert!(page.contains("Brigade rope"));
+    let pid = user.user.pid.to_string();
+    let other_user = cookie.replacen(&pid, "11111111-1111-1111-1111-111111111111", 1);
+    assert_eq!(with_cookie(&other_user).await.status_code(), 401);
+    let other_tenant = cookie.replacen(&format!(".{brigade}."), ".1.", 1);
+    assert_eq!(with_cookie(&other_tenant).await.status_code(), 401);
+
+    let response = server.post("/api/auth/logout").await;
+    assert_eq!(response.status_code(), 200);
+    let response = server.get("/inventory/list").await;
+    assert_eq!(response.status_code(), 401);
+}
+
+#[tokio::test]
+#[serial]
+async fn registered_users_join_no_tenant_until_they_create_one() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    let response = server
+        .post("/api/auth/register")
+        .json(&json!({ "name": "newbie", "email": "newbie@example.com", "password": "1234" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let response = server
+        .post("/api/auth/login")
+        .json(&json!({ "email": "newbie@example.com", "password": "1234" }))
+        .await;
+    let token = response.json::<Value>()["token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let (auth_key, auth_value) = prepare_data::auth_header(&token);
+
+    let tenants = server
+        .get("/api/v1/tenants")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await
+        .json::<Value>();
+    assert_eq!(tenants, json!([]));
+    let response = server
+        .get("/api/v1/items")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await;
+    assert_eq!(response.status_code(), 403);
+
+    let response = server
+        .post("/api/v1/tenants")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "name": "Newbie's club" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let tenants = server
+        .get("/api/v1/tenants")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .await
+        .json::<Value>();
+    assert_eq!(tenants[0]["name"], "Newbie's club");
+    assert_eq!(tenants[0]["members"], json!(["newbie"]));
+    let response = server
+        .get("/api/v1/items")
+        .add_header(auth_key, auth_value)
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.json::<Value>()["items"], json!([]));
+}
@@ -0,0 +1,119 @@
+use axum::http::{header, HeaderValue};
+use bestbefors::{app::App, initializers::app_cache::AppCacheInitializer, models::users};
+use chrono::DateTime;
+use loco_rs::testing::prelude::*;
+use serde_json::{json, Value};
+use serial_test::serial;
+
+use super::prepare_data;
+
+/// Page text with the slashes Tera escapes in time zone names and dates
+/// restored.
+fn page_text(text: &str) -> String {
+    text.replace("&#x2F;", "/")
+}
+
+#[tokio::test]
+#[serial]
+async fn parses_dates_in_the_time_zone_of_the_user() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, &boot.app_context).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+
+    let response = server
+        .post("/api/auth/timezone")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "timezone": "Mars/Olympus" }))
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server
+        .post("/api/auth/timezone")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({ "timezone": "Europe/Berlin" }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response
+        .header(header::SET_COOKIE)
+        .to_str()
+        .unwrap()
+        .starts_with("tz=Europe/Berlin;"));
+    let stored = users::Model::find_by_pid(&boot.app_context.db, &user.user.pid.to_string())
+        .await
+        .unwrap();
+    assert_eq!(stored.timezone.as_deref(), Some("Europe/Berlin"));
+
+    let response = server
+        .post("/api/v1/item_kinds")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "name": "Harness",
+            "test_standard": "EN 358",
+            "default_checklist_id": 2,
+            "default_interval_id": 4,
+            "default_expiry_id": 1,
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let kind: Value = response.json();
+
+    let response = server
+        .post("/api/v1/items")
+        .add_header(auth_key.clone(), auth_value.clone())
+        .json(&json!({
+            "name": "Harness A",
+            "inventory_item_kind_id": kind["id"],
+            "expiry": "2026-03-10",
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let item: Value = response.json();
+    let expiry = DateTime::parse_from_rfc3339(item["expiry"].as_str().unwrap()).unwrap();
+    assert_eq!(expiry.to_utc().to_rfc3339(), "2026-03-09T23:00:00+00:00");
+
+    let response = server
+        .get("/inventory/list")
+        .add_header(auth_key, auth_value)
+        .await;
+    let text = page_text(&response.text());
+    assert!(text.contains("Europe/Berlin"));
+    assert!(text.contains("03/10/2026"));
+}
+
+#[tokio::test]
+#[serial]
+async fn remembers_the_time_zone_of_the_browser() {
+    let boot = boot_test::<App>().await.unwrap();
+    let server = prepare_data::test_server(&boot);
+
+    let response = server.get("/locale/preferences").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains(r#"<option value="UTC" selected>"#));
+
+    let response = server
+        .post("/locale/timezone")
+        .add_query_param("redirect", "/locale/preferences")
+        .form(&[("timezone", "America/New_York")])
+        .await;
+    assert_eq!(response.status_code(), 303);
+    assert_eq!(response.header(header::LOCATION), "/locale/preferences");
+    assert!(response
+        .header(header::SET_COOKIE)
+        .to_str()
+        .unwrap()
+        .starts_with("tz=America/New_York;"));
+
+    let response = server
+        .get("/locale/preferences")
+        .add_header(
+            header::COOKIE,
+            HeaderValue::from_static("tz=America/New_York"),
+        )
+        .await;
+    assert!(page_text(&response.text()).contains(r#"<option value="America/New_York" selected>"#));
+}
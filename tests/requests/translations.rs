@@ -0,0 +1,119 @@
+use bestbefors::{
+    app::App,
+    i18n::{Translator, DEFAULT_LOCALE},
+    initializers::app_cache::AppCacheInitializer,
+    models::translations,
+};
+use loco_rs::testing::prelude::*;
+use sea_orm::EntityTrait;
+use serial_test::serial;
+
+use super::prepare_data;
+
+#[tokio::test]
+#[serial]
+async fn edited_translations_are_used_for_lookup_codes() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    AppCacheInitializer::reload_cached(&boot.app_context)
+        .await
+        .unwrap();
+    let server = prepare_data::test_server(&boot);
+
+    // translations are shared by all tenants, only global admins change them
+    let form = [
+        ("code", "INTERVAL_YEARLY"),
+        ("lang", "en-US"),
+        ("text", "Every year"),
+    ];
+    let response = server.post("/translations").form(&form).await;
+    assert_eq!(response.status_code(), 401);
+    prepare_data::init_user_login(&server, &boot.app_context).await;
+    let response = server.post("/translations").form(&form).await;
+    assert_eq!(response.status_code(), 403);
+    let existing = translations::Entity::find()
+        .one(&boot.app_context.db)
+        .await
+        .unwrap()
+        .unwrap();
+    let response = server
+        .delete(&format!("/translations/{}", existing.id))
+        .await;
+    assert_eq!(response.status_code(), 403);
+    prepare_data::login_user1(&server).await;
+
+    let response = server.get("/intervals/list").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains("Yearly"));
+
+    let response = server
+        .post("/translations")
+        .form(&[
+            ("code", "INTERVAL_YEARLY"),
+            ("lang", "en-US"),
+            ("text", "Every year"),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 303);
+    let response = server
+        .post("/translations")
+        .form(&[
+            ("code", "INTERVAL_YEARLY"),
+            ("lang", "EN-us"),
+            ("text", "Annually"),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 303);
+
+    let translation = translations::Model::find_by_code_and_lang(
+        &boot.app_context.db,
+        "INTERVAL_YEARLY",
+        "en-US",
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(translation.text, "Annually");
+    assert!(server
+        .get("/intervals/list")
+        .await
+        .text()
+        .contains("Annually"));
+    assert_eq!(
+        Translator::for_context(&boot.app_context, DEFAULT_LOCALE).code("INTERVAL_YEARLY"),
+        "Annually"
+    );
+
+    let response = server
+        .post(&format!("/translations/{}", translation.id))
+        .form(&[("text", "Once a year")])
+        .await;
+    assert_eq!(response.status_code(), 303);
+    assert!(server
+        .get("/intervals/list")
+        .await
+        .text()
+        .contains("Once a year"));
+
+    let response = server
+        .delete(&format!("/translations/{}", translation.id))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(
+        Translator::for_context(&boot.app_context, DEFAULT_LOCALE).code("INTERVAL_YEARLY"),
+        "Yearly"
+    );
+
+    let response = server
+        .post("/translations")
+        .form(&[
+            ("code", "RESULT_DAMAGED"),
+            ("lang", "not a language"),
+            ("text", "x"),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let response = server.get("/translations/list").await;
+    assert_eq!(response.status_code(), 200);
+}
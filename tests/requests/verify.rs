@@ -0,0 +1,81 @@
+use bestbefors::{app::App, models::generated_reports};
+use chrono::Utc;
+use loco_rs::testing::prelude::*;
+use sea_orm::EntityTrait;
+use serde_json::Value;
+use serial_test::serial;
+
+use super::prepare_data;
+
+#[tokio::test]
+#[serial]
+async fn stores_generated_reports_for_verification() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+
+    let kind = prepare_data::create_kind(&boot.app_context, 1, "Harness").await;
+    let item = prepare_data::create_item(
+        &boot.app_context,
+        &kind,
+        "Harness A",
+        Utc::now(),
+        None,
+        None,
+    )
+    .await;
+
+    let server = prepare_data::test_server(&boot);
+    prepare_data::login_user1(&server).await;
+    let response = server
+        .get(&format!("/inventory/item/{}/report.pdf", item.id))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let pdf = response.as_bytes().to_vec();
+
+    let reports = generated_reports::Entity::find()
+        .all(&boot.app_context.db)
+        .await
+        .unwrap();
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert!(report
+        .report_id
+        .starts_with(generated_reports::REPORT_ID_PREFIX));
+    assert_eq!(
+        report.kind(),
+        Some(generated_reports::ReportKind::ItemHistory)
+    );
+    assert_eq!(report.subject.as_deref(), Some("Harness A"));
+    assert!(report.matches(&pdf));
+
+    let response = server
+        .get(&format!("/verify/{}", report.report_id.to_lowercase()))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains(&report.content_hash));
+
+    let response = server
+        .post(&format!("/verify/{}", report.report_id))
+        .bytes(pdf.clone().into())
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.json::<Value>()["authentic"], true);
+
+    let mut tampered = pdf;
+    tampered.extend_from_slice(b"\n% edited");
+    let response = server
+        .post(&format!("/verify/{}", report.report_id))
+        .bytes(tampered.into())
+        .await;
+    assert_eq!(response.status_code(), 200);
+    assert_eq!(response.json::<Value>()["authentic"], false);
+
+    let response = server.get("/verify/RPT-0000-0000-0000-0000").await;
+    assert_eq!(response.status_code(), 404);
+
+    let response = server
+        .get("/verify")
+        .add_query_param("report_id", " rpt-0000 ")
+        .await;
+    assert_eq!(response.status_code(), 303);
+}
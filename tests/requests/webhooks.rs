@@ -0,0 +1,237 @@
+use std::sync::{
+    atomic::{AtomicU16, Ordering},
+    Arc, Mutex,
+};
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use bestbefors::{
+    app::App,
+    initializers::app_cache::AppCacheInitializer,
+    models::{webhook_deliveries, webhook_endpoints},
+    webhooks::{self, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER},
+};
+use chrono::{TimeZone, Utc};
+use loco_rs::testing::prelude::*;
+use sea_orm::EntityTrait;
+use serde_json::{json, Value};
+use serial_test::serial;
+
+use super::prepare_data;
+
+/// A request received by the [`Receiver`].
+struct Received {
+    headers: HeaderMap,
+    body: String,
+}
+
+/// Stand-in for a system subscribed to the webhooks, answering with
+/// `status` and recording every request.
+#[derive(Clone)]
+struct Receiver {
+    status: Arc<AtomicU16>,
+    requests: Arc<Mutex<Vec<Received>>>,
+}
+
+impl Receiver {
+    /// Listens on a free local port and returns the URL to post to.
+    async fn start() -> (Self, String) {
+        let receiver = Self {
+            status: Arc::new(AtomicU16::new(200)),
+            requests: Arc::new(Mutex::new(Vec::new())),
+        };
+        let router = Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State(receiver): State<Self>, headers: HeaderMap, body: String| async move {
+                        receiver
+                            .requests
+                            .lock()
+                            .unwrap()
+                            .push(Received { headers, body });
+                        StatusCode::from_u16(receiver.status.load(Ordering::SeqCst)).unwrap()
+                    },
+                ),
+            )
+            .with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (receiver, url)
+    }
+
+    fn respond_with(&self, status: u16) {
+        self.status.store(status, Ordering::SeqCst);
+    }
+
+    fn take(&self) -> Vec<Received> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
+
+fn header<'a>(request: &'a Received, name: &str) -> &'a str {
+    request.headers.get(name).unwrap().to_str().unwrap()
+}
+
+/// Checks the signature header of `request` against `secret`.
+fn assert_signed(request: &Received, secret: &str) {
+    let signature = header(request, SIGNATURE_HEADER);
+    let (timestamp, digest) = signature
+        .strip_prefix("t=")
+        .and_then(|rest| rest.split_once(",v1="))
+        .unwrap();
+    assert_eq!(
+        webhooks::sign(secret, timestamp.parse().unwrap(), &request.body),
+        digest
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn delivers_signed_events_and_retries_failures() {
+    let boot = boot_test::<App>().await.unwrap();
+    seed::<App>(&boot.app_context).await.unwrap();
+    let ctx = &boot.app_context;
+    let (receiver, url) = Receiver::start().await;
+
+    let now = Utc::now();
+    let kind = prepare_data::create_kind(ctx, 1, "Helmet").await;
+    let last_checked_at = Utc.with_ymd_and_hms(2020, 1, 1, 10, 0, 0).unwrap();
+    let item =
+        prepare_data::create_item(ctx, &kind, "Helmet A", now, Some(last_checked_at), None).await;
+    AppCacheInitializer::reload_cached(ctx).await.unwrap();
+
+    let server = prepare_data::test_server(&boot);
+    let user = prepare_data::init_user_login(&server, ctx).await;
+    let (auth_key, auth_value) = prepare_data::auth_header(&user.token);
+
+    // only tenant admins manage webhooks
+    let response = server.get("/webhooks/list").await;
+    assert_eq!(response.status_code(), 403);
+    let response = server
+        .post("/webhooks")
+        .form(&[("name", "Fleet"), ("url", url.as_str())])
+        .await;
+    assert_eq!(response.status_code(), 403);
+    prepare_data::login_user1(&server).await;
+
+    let response = server
+        .post("/webhooks")
+        .form(&[
+            ("name", "Fleet"),
+            ("url", "ftp://example.com/hook"),
+            ("events", "check.submitted"),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 400);
+    let response = server
+        .post("/webhooks")
+        .form(&[
+            ("name", "Fleet"),
+            ("url", url.as_str()),
+            ("events", "check.submitted"),
+            ("events", "item.critical"),
+        ])
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let endpoint = webhook_endpoints::Entity::find()
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(endpoint
+        .secret
+        .starts_with(webhook_endpoints::SECRET_PREFIX));
+    assert!(!endpoint.subscribes_to(webhook_endpoints::WebhookEvent::ItemCreated));
+    // the secret is shown once
+    assert!(response.text().contains(&endpoint.secret));
+    let page = server.get("/webhooks/list").await.text();
+    assert!(!page.contains(&endpoint.secret));
+    assert!(page.contains(&endpoint.secret[endpoint.secret.len() - 4..]));
+
+    // the overdue item is reported once; the failed delivery waits for a retry
+    receiver.respond_with(500);
+    assert_eq!(webhooks::scan_due_items(ctx).await.unwrap(), 1);
+    let requests = receiver.take();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(header(&requests[0], EVENT_HEADER), "item.critical");
+    assert_signed(&requests[0], &endpoint.secret);
+    let payload = serde_json::from_str::<Value>(&requests[0].body).unwrap();
+    assert_eq!(payload["data"]["item"]["id"], item.id);
+
+    let delivery = webhook_deliveries::Entity::find()
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        header(&requests[0], DELIVERY_HEADER),
+        delivery.pid.to_string()
+    );
+    assert_eq!(delivery.status, "pending");
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.response_status, Some(500));
+    assert_eq!(delivery.error.as_deref(), Some("HTTP 500"));
+    assert!(delivery.next_attempt_at.is_some_and(|at| at > now));
+    assert_eq!(webhooks::scan_due_items(ctx).await.unwrap(), 0);
+    assert_eq!(webhooks::retry_due(ctx).await.unwrap(), 0);
+
+    receiver.respond_with(204);
+    let response = server
+        .post(&format!("/webhooks/deliveries/{}/redeliver", delivery.id))
+        .await;
+    assert_eq!(response.status_code(), 303);
+    assert_eq!(receiver.take().len(), 1);
+    let delivery = webhook_deliveries::Entity::find_by_id(delivery.id)
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery.status, "succeeded");
+    assert_eq!(delivery.response_status, Some(204));
+
+    // a delivery queued twice is sent once
+    webhooks::deliver(ctx, delivery.clone()).await.unwrap();
+    assert!(receiver.take().is_empty());
+    assert_eq!(webhooks::retry_due(ctx).await.unwrap(), 0);
+
+    let response = server
+        .post("/api/v1/checks")
+        .add_header(auth_key, auth_value)
+        .json(&json!({
+            "inventory_item_id": item.id,
+            "checked_by": 1,
+            "result_id": 1,
+            "steps": [
+                { "checklist_step_id": 2, "result_id": 1 },
+                { "checklist_step_id": 3, "result_id": 1 }
+            ]
+        }))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let requests = receiver.take();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(header(&requests[0], EVENT_HEADER), "check.submitted");
+    assert_signed(&requests[0], &endpoint.secret);
+    let payload = serde_json::from_str::<Value>(&requests[0].body).unwrap();
+    assert_eq!(payload["event"], "check.submitted");
+    assert_eq!(payload["data"]["item"]["id"], item.id);
+    assert_eq!(payload["data"]["passed"], true);
+
+    let response = server.get("/webhooks/list").await;
+    assert_eq!(response.status_code(), 200);
+    assert!(response.text().contains("check.submitted"));
+
+    let response = server.delete(&format!("/webhooks/{}", endpoint.id)).await;
+    assert_eq!(response.status_code(), 200);
+    assert!(webhook_deliveries::Entity::find()
+        .all(&ctx.db)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use bestbefors::{
+    app::App,
+    i18n::{self, I18N_DIR},
+};
+use loco_rs::{boot::run_task, task, testing::prelude::*};
+use serial_test::serial;
+
+#[test]
+fn every_locale_translates_all_keys() {
+    for diff in i18n::diff_locales(Path::new(I18N_DIR)).unwrap() {
+        assert!(
+            diff.missing.is_empty(),
+            "{} is missing translations, run `cargo loco task i18n_check`: {:?}",
+            diff.locale,
+            diff.missing
+        );
+    }
+}
+
+#[test]
+fn reports_missing_and_extra_keys() {
+    let dir = std::env::temp_dir().join(format!("bestbefors-i18n-{}", std::process::id()));
+    for (locale, source) in [
+        (
+            "en-US",
+            "hello = Hello\ngreeting = Hi\n    .title = Greeting\n-brand = B\n",
+        ),
+        ("fr-FR", "hello = Bonjour\nbye = Au revoir\n"),
+    ] {
+        std::fs::create_dir_all(dir.join(locale)).unwrap();
+        std::fs::write(dir.join(locale).join("main.ftl"), source).unwrap();
+    }
+
+    let diffs = i18n::diff_locales(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].locale, "fr-FR");
+    assert_eq!(
+        diffs[0].missing,
+        [
+            "main.ftl: -brand",
+            "main.ftl: greeting",
+            "main.ftl: greeting.title"
+        ]
+    );
+    assert_eq!(diffs[0].extra, ["main.ftl: bye"]);
+}
+
+#[tokio::test]
+#[serial]
+async fn i18n_check_task_passes() {
+    let boot = boot_test::<App>().await.unwrap();
+    run_task::<App>(
+        &boot.app_context,
+        Some(&"i18n_check".to_string()),
+        &task::Vars::default(),
+    )
+    .await
+    .unwrap();
+}
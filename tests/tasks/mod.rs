@@ -1 +1 @@
-
+mod i18n_check;